@@ -0,0 +1,38 @@
+//! Compares `Ordinal::one_hot_batch` against encoding each value of the batch with `one_hot`.
+//!
+//! Run with `cargo bench --bench one_hot`; timings are printed per batch.
+extern crate spaces;
+
+use spaces::discrete::Ordinal;
+use std::{hint::black_box, time::Instant};
+
+const ITERS: u32 = 200;
+
+fn time<T, F: FnMut() -> T>(mut f: F) -> f64 {
+    let start = Instant::now();
+
+    for _ in 0..ITERS {
+        black_box(f());
+    }
+
+    start.elapsed().as_secs_f64() * 1e6 / f64::from(ITERS)
+}
+
+fn main() {
+    for &(size, n) in &[(4, 10_000), (18, 10_000), (1000, 1000)] {
+        let space = Ordinal::new(size);
+        let vals: Vec<usize> = (0..n).map(|i| (i * 7919) % size).collect();
+
+        let batch = time(|| space.one_hot_batch(&vals).unwrap());
+        let looped = time(|| {
+            vals.iter()
+                .flat_map(|&v| space.one_hot(v).unwrap())
+                .collect::<Vec<f64>>()
+        });
+
+        println!(
+            "size {:>4}, {:>5} values: batch {:>9.1}us, looped {:>9.1}us ({:.1}x)",
+            size, n, batch, looped, looped / batch
+        );
+    }
+}
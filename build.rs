@@ -0,0 +1,28 @@
+//! Generates the C header of the `ffi` module into `OUT_DIR` when the feature is enabled; the
+//! checked-in copy at `include/spaces.h` is tested against it.
+#[cfg(feature = "ffi")]
+extern crate cbindgen;
+
+#[cfg(feature = "ffi")]
+fn main() {
+    use std::{env, path::Path};
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("cbindgen.toml is invalid");
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+
+    // Only the interface itself is parsed; the rest of the crate never crosses the boundary.
+    cbindgen::Builder::new()
+        .with_src(Path::new(&crate_dir).join("src/ffi.rs"))
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the C header.")
+        .write_to_file(Path::new(&out_dir).join("spaces.h"));
+}
+
+#[cfg(not(feature = "ffi"))]
+fn main() {}
@@ -0,0 +1,82 @@
+#ifndef SPACES_H
+#define SPACES_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a call through the C interface.
+typedef enum {
+  SPACES_STATUS_OK = 0,
+  // A required pointer argument was null.
+  SPACES_STATUS_NULL_POINTER = 1,
+  // The length of a value did not match the dimensionality of the space.
+  SPACES_STATUS_LENGTH_MISMATCH = 2,
+  // The space admits no uniform distribution, e.g. as it is unbounded or empty.
+  SPACES_STATUS_UNSAMPLEABLE = 3,
+  // The call panicked; the panic was caught at the boundary.
+  SPACES_STATUS_PANICKED = 4,
+} SpacesStatus;
+
+// Space parsed from a JSON description, opaque to C.
+typedef struct OpaqueSpace OpaqueSpace;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parse a space from a nul-terminated JSON description, returning null if `json` is null or does
+// not describe a valid space.
+//
+// # Safety
+//
+// `json` must be null or point to a nul-terminated string. The returned handle must be released
+// with `spaces_free`, exactly once.
+OpaqueSpace *spaces_parse(const char *json);
+
+// Release a space returned by `spaces_parse`; null is ignored.
+//
+// # Safety
+//
+// `space` must be null or a handle returned by `spaces_parse` that has not yet been released.
+void spaces_free(OpaqueSpace *space);
+
+// Write the number of entries in a value of the space to `out`.
+//
+// # Safety
+//
+// `space` must be null or a live handle, and `out` must be null or valid for writes.
+SpacesStatus spaces_dim(const OpaqueSpace *space, uint64_t *out);
+
+// Write the number of values in the space to `out` and return true if it is finite and fits in
+// a `u64`; otherwise, or if either pointer is null, return false and leave `out` untouched.
+//
+// # Safety
+//
+// `space` must be null or a live handle, and `out` must be null or valid for writes.
+bool spaces_card_finite(const OpaqueSpace *space, uint64_t *out);
+
+// Return true if the `len` entries at `ptr` are a value of the space; false if not, or if the
+// arguments are invalid.
+//
+// # Safety
+//
+// `space` must be null or a live handle, and `ptr` must be null or valid for `len` reads.
+bool spaces_contains_f64(const OpaqueSpace *space, const double *ptr, uintptr_t len);
+
+// Draw a value uniformly at random, from a generator seeded with `seed`, and write its `len`
+// entries to `out`; nothing is written unless the status is `Ok`.
+//
+// # Safety
+//
+// `space` must be null or a live handle, and `out` must be null or valid for `len` writes.
+SpacesStatus spaces_sample_f64(const OpaqueSpace *space, uint64_t seed, double *out, uintptr_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SPACES_H */
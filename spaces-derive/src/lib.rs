@@ -0,0 +1,292 @@
+//! Derive macros for the `spaces` crate.
+//!
+//! These are re-exported by `spaces` under its `derive` feature, and should be used from there.
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    GenericArgument,
+    Ident,
+    PathArguments,
+    Result,
+    Type,
+    Visibility,
+};
+
+// How a field of the deriving struct contributes to the composite space.
+enum Role {
+    // The field is a space in its own right.
+    Space,
+    // The field is a `Vec` or array of spaces with the given element type, each of which
+    // contributes a component.
+    Flatten(Box<Type>),
+}
+
+struct SpaceField {
+    ident: Ident,
+    vis: Visibility,
+    ty: Type,
+    role: Role,
+}
+
+/// Derive `Space`, `Sample` and `BoundedSpace` for a struct whose fields are spaces.
+///
+/// See the documentation of the re-export in `spaces` for details.
+#[proc_macro_derive(Space, attributes(space))]
+pub fn derive_space(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input).unwrap_or_else(compile_errors).into()
+}
+
+// Like `Error::into_compile_error`, which expands to `::core::compile_error!` and therefore cannot
+// be used from crates on the 2015 edition.
+fn compile_errors(errors: Error) -> TokenStream2 {
+    errors.into_iter().map(|e| {
+        let msg = e.to_string();
+
+        quote_spanned!(e.span()=> compile_error!(#msg);)
+    }).collect()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`#[derive(Space)]` does not support generic structs",
+        ));
+    }
+
+    let named = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref named) => named,
+            _ => return Err(Error::new_spanned(
+                &input.ident,
+                "`#[derive(Space)]` requires a struct with named fields",
+            )),
+        },
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            "`#[derive(Space)]` can only be applied to structs",
+        )),
+    };
+
+    let mut fields = vec![];
+
+    for field in named.named.iter() {
+        if let Some(role) = parse_role(field)? {
+            fields.push(SpaceField {
+                ident: field.ident.clone().expect("Named fields must have an identifier."),
+                vis: field.vis.clone(),
+                ty: field.ty.clone(),
+                role,
+            });
+        }
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let value = format_ident!("{}Value", name);
+    let value_doc = format!("Values of the composite space [`{}`].", name);
+
+    let value_fields = fields.iter().map(|f| {
+        let (ident, vis) = (&f.ident, &f.vis);
+
+        match f.role {
+            Role::Space => {
+                let ty = &f.ty;
+
+                quote_spanned!(ty.span()=> #vis #ident: <#ty as ::spaces::Space>::Value)
+            },
+            Role::Flatten(ref elem) => quote_spanned!(elem.span()=>
+                #vis #ident: ::std::vec::Vec<<#elem as ::spaces::Space>::Value>
+            ),
+        }
+    });
+
+    let dims = fields.iter().map(|f| {
+        let ident = &f.ident;
+
+        match f.role {
+            Role::Space => quote!(::spaces::Space::dim(&self.#ident)),
+            Role::Flatten(_) => quote!(self.#ident.iter().fold(
+                ::spaces::Dim::Finite(0),
+                |acc, d| acc + ::spaces::Space::dim(d)
+            )),
+        }
+    });
+
+    let cards = fields.iter().map(|f| {
+        let ident = &f.ident;
+
+        match f.role {
+            Role::Space => quote!(::spaces::Space::card(&self.#ident)),
+            Role::Flatten(_) => quote!(::spaces::Card::product(
+                self.#ident.iter().map(::spaces::Space::card)
+            )),
+        }
+    });
+
+    let samples = field_values(&fields, |ident, role| match role {
+        Role::Space => quote!(::spaces::Sample::sample(&self.#ident, rng)),
+        Role::Flatten(_) => quote!(
+            self.#ident.iter().map(|d| ::spaces::Sample::sample(d, rng)).collect()
+        ),
+    });
+
+    let try_samples = field_values(&fields, |ident, role| match role {
+        Role::Space => quote!(::spaces::Sample::try_sample(&self.#ident, rng)?),
+        Role::Flatten(_) => quote!(self.#ident
+            .iter()
+            .map(|d| ::spaces::Sample::try_sample(d, rng))
+            .collect::<::std::result::Result<_, ::spaces::SampleError>>()?
+        ),
+    });
+
+    let bound = |method: Ident| field_values(&fields, move |ident, role| match role {
+        Role::Space => quote!(::spaces::BoundedSpace::#method(&self.#ident)?),
+        Role::Flatten(_) => quote!(self.#ident
+            .iter()
+            .map(::spaces::BoundedSpace::#method)
+            .collect::<::std::option::Option<_>>()?
+        ),
+    });
+    let infs = bound(format_ident!("inf"));
+    let sups = bound(format_ident!("sup"));
+
+    let contains = fields.iter().map(|f| {
+        let ident = &f.ident;
+
+        match f.role {
+            Role::Space => quote!(::spaces::BoundedSpace::contains(&self.#ident, val.#ident)),
+            Role::Flatten(_) => quote!({
+                let vals = val.#ident;
+
+                vals.len() == self.#ident.len()
+                    && self.#ident.iter().zip(vals).all(|(d, v)| {
+                        ::spaces::BoundedSpace::contains(d, v)
+                    })
+            }),
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #value_doc]
+        #[derive(Clone, Debug, PartialEq, PartialOrd)]
+        #vis struct #value {
+            #(#value_fields,)*
+        }
+
+        impl ::spaces::Space for #name {
+            type Value = #value;
+
+            fn dim(&self) -> ::spaces::Dim { ::spaces::Dim::Finite(0) #(+ #dims)* }
+
+            fn card(&self) -> ::spaces::Card { ::spaces::Card::product(vec![#(#cards),*]) }
+        }
+
+        impl ::spaces::Sample for #name {
+            fn sample<R: ::spaces::__private::Rng + ?Sized>(&self, rng: &mut R) -> #value {
+                #value { #samples }
+            }
+
+            fn try_sample<R: ::spaces::__private::Rng + ?Sized>(
+                &self,
+                rng: &mut R,
+            ) -> ::std::result::Result<#value, ::spaces::SampleError> {
+                ::std::result::Result::Ok(#value { #try_samples })
+            }
+        }
+
+        impl ::spaces::BoundedSpace for #name {
+            fn inf(&self) -> ::std::option::Option<#value> {
+                ::std::option::Option::Some(#value { #infs })
+            }
+
+            fn sup(&self) -> ::std::option::Option<#value> {
+                ::std::option::Option::Some(#value { #sups })
+            }
+
+            fn contains(&self, val: #value) -> bool { true #(&& #contains)* }
+        }
+    })
+}
+
+// Builds the field initialisers of a value struct, one per space field.
+fn field_values<F>(fields: &[SpaceField], value: F) -> TokenStream2
+where F: Fn(&Ident, &Role) -> TokenStream2 {
+    fields.iter().map(|f| {
+        let ident = &f.ident;
+        let value = value(ident, &f.role);
+
+        quote!(#ident: #value,)
+    }).collect()
+}
+
+// Returns the role of a field, or `None` if it is marked `#[space(skip)]`.
+fn parse_role(field: &syn::Field) -> Result<Option<Role>> {
+    let (mut skip, mut flatten) = (false, false);
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("space")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+            } else if meta.path.is_ident("flatten") {
+                flatten = true;
+            } else {
+                return Err(meta.error("unknown space attribute, expected `skip` or `flatten`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    match (skip, flatten) {
+        (true, true) => Err(Error::new_spanned(
+            field,
+            "a field cannot be both `#[space(skip)]` and `#[space(flatten)]`",
+        )),
+        (true, false) => Ok(None),
+        (false, true) => {
+            element_type(&field.ty).map(|elem| Some(Role::Flatten(Box::new(elem.clone()))))
+        },
+        (false, false) => Ok(Some(Role::Space)),
+    }
+}
+
+// Returns the element type of a `Vec<S>` or `[S; N]` field.
+fn element_type(ty: &Type) -> Result<&Type> {
+    let elem = match *ty {
+        Type::Array(ref array) => Some(&*array.elem),
+        Type::Path(ref path) if path.qself.is_none() => {
+            path.path.segments.last().filter(|s| s.ident == "Vec").and_then(|s| {
+                match s.arguments {
+                    PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+                        match args.args[0] {
+                            GenericArgument::Type(ref elem) => Some(elem),
+                            _ => None,
+                        }
+                    },
+                    _ => None,
+                }
+            })
+        },
+        _ => None,
+    };
+
+    elem.ok_or_else(|| Error::new_spanned(
+        ty,
+        "`#[space(flatten)]` requires a field of type `Vec<S>` or `[S; N]`",
+    ))
+}
@@ -0,0 +1,113 @@
+use crate::{Interval, Surjection};
+use std::fmt;
+
+/// Affine transformation, `x -> scale * x + shift`, over real values.
+///
+/// This is the value-level counterpart of the arithmetic operators defined on `Interval` and
+/// `ProductSpace<Interval>`:
+/// ```
+/// use spaces::{AffineMap, Interval, Surjection};
+///
+/// let (space, map) = Interval::unit().affine(AffineMap::new(2.0, -1.0));
+///
+/// assert_eq!(space, Interval::bounded(-1.0, 1.0));
+/// assert_eq!(map.map_onto(0.25), -0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AffineMap {
+    pub scale: f64,
+    pub shift: f64,
+}
+
+impl AffineMap {
+    pub fn new(scale: f64, shift: f64) -> AffineMap { AffineMap { scale, shift, } }
+
+    pub fn identity() -> AffineMap { AffineMap::new(1.0, 0.0) }
+
+    pub fn scaling(scale: f64) -> AffineMap { AffineMap::new(scale, 0.0) }
+
+    pub fn shifting(shift: f64) -> AffineMap { AffineMap::new(1.0, shift) }
+
+    /// Return the map equivalent to applying `self` followed by `other`.
+    pub fn then(self, other: AffineMap) -> AffineMap {
+        AffineMap::new(other.scale * self.scale, other.scale * self.shift + other.shift)
+    }
+
+    #[inline]
+    pub fn apply(&self, val: f64) -> f64 { self.scale * val + self.shift }
+
+    /// Return the image of `interval` under the map.
+    ///
+    /// Scaling by a negative number swaps the bounds (and their kinds), and scaling by zero
+    /// collapses the interval onto the single point `shift`.
+    pub fn apply_interval(&self, interval: Interval) -> Interval {
+        if self.scale == 0.0 {
+            return Interval::bounded(self.shift, self.shift);
+        }
+
+        let lb = interval.lb.map(|x| self.apply(x));
+        let ub = interval.ub.map(|x| self.apply(x));
+        let (lb_kind, ub_kind) = (interval.lb_kind, interval.ub_kind);
+
+        if self.scale < 0.0 {
+            Interval::new(ub, lb).with_kinds(ub_kind, lb_kind)
+        } else {
+            Interval::new(lb, ub).with_kinds(lb_kind, ub_kind)
+        }
+    }
+}
+
+impl Surjection<f64, f64> for AffineMap {
+    fn map_onto(&self, val: f64) -> f64 { self.apply(val) }
+}
+
+impl Surjection<Vec<f64>, Vec<f64>> for AffineMap {
+    fn map_onto(&self, val: Vec<f64>) -> Vec<f64> {
+        val.into_iter().map(|x| self.apply(x)).collect()
+    }
+}
+
+impl fmt::Display for AffineMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "x -> {} * x + {}", self.scale, self.shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let m = AffineMap::new(2.0, -1.0);
+
+        assert_eq!(m.map_onto(0.0), -1.0);
+        assert_eq!(m.map_onto(1.0), 1.0);
+        assert_eq!(m.map_onto(vec![0.0, 0.5, 1.0]), vec![-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_then() {
+        let m = AffineMap::scaling(2.0).then(AffineMap::shifting(-1.0));
+
+        assert_eq!(m, AffineMap::new(2.0, -1.0));
+        assert_eq!(AffineMap::identity().then(m), m);
+    }
+
+    #[test]
+    fn test_apply_interval() {
+        assert_eq!(
+            AffineMap::new(-2.0, 0.0).apply_interval(Interval::bounded(0.0, 1.0)),
+            Interval::bounded(-2.0, 0.0)
+        );
+        assert_eq!(
+            AffineMap::new(-1.0, 0.0).apply_interval(Interval::left_bounded(1.0)),
+            Interval::right_bounded(-1.0)
+        );
+        assert_eq!(
+            AffineMap::new(0.0, 3.0).apply_interval(Interval::unbounded()),
+            Interval::bounded(3.0, 3.0)
+        );
+    }
+}
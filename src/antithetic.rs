@@ -0,0 +1,268 @@
+use crate::{prelude::*, Interval, PairSpace, ProductSpace, SampleError, TwoSpace};
+
+/// Trait for spaces that can draw antithetic pairs of values, for variance reduction in Monte
+/// Carlo estimates.
+///
+/// Each pair consists of a uniform draw `u` and its mirror `lo + hi - u` about the centre of the
+/// space, in every dimension. Both are distributed uniformly over the space, but are negatively
+/// correlated, such that averages of monotone functions over the pooled values vary less than
+/// over as many independent draws.
+/// ```
+/// extern crate rand;
+///
+/// use spaces::{real::Interval, AntitheticSample, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(-2.0, 4.0)]);
+/// let (u, v) = space.sample_antithetic(&mut rand::thread_rng());
+///
+/// assert!((u[0] + v[0] - 1.0).abs() < 1e-12);
+/// assert!((u[1] + v[1] - 2.0).abs() < 1e-12);
+/// ```
+pub trait AntitheticSample: Sample {
+    /// Draw a value uniformly at random together with its mirror, or return an error if the
+    /// space admits no uniform distribution.
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::Value, Self::Value), SampleError>;
+
+    /// Draw a value uniformly at random together with its mirror.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the space admits no uniform distribution; see `try_sample_antithetic`.
+    fn sample_antithetic<R: Rng + ?Sized>(&self, rng: &mut R) -> (Self::Value, Self::Value) {
+        self.try_sample_antithetic(rng)
+            .unwrap_or_else(|e| panic!("Cannot sample from space: {}.", e))
+    }
+
+    /// Draw `n` antithetic pairs, returned as `2n` values in which each draw is directly followed
+    /// by its mirror.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is positive and the space admits no uniform distribution.
+    fn sample_antithetic_n<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<Self::Value> {
+        let mut vals = Vec::with_capacity(2 * n);
+
+        for _ in 0..n {
+            let (u, v) = self.sample_antithetic(rng);
+
+            vals.push(u);
+            vals.push(v);
+        }
+
+        vals
+    }
+}
+
+/// Mirrors are taken about the smallest and largest representable values, and clamped onto the
+/// interval to absorb rounding; the mirror is formed such that no intermediate overflows.
+impl AntitheticSample for Interval<f64> {
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(f64, f64), SampleError> {
+        let u = self.try_sample(rng)?;
+        let (lo, hi) = self.tight_bounds();
+        let (lo, hi) = (lo.unwrap(), hi.unwrap());
+        let mirror = if (hi - lo).is_finite() { lo + (hi - u) } else { (lo + hi) - u };
+
+        Ok((u, self.map_onto(mirror)))
+    }
+}
+
+/// Mirrors are exact.
+impl AntitheticSample for Interval<i64> {
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(i64, i64), SampleError> {
+        let u = self.try_sample(rng)?;
+        let (lo, hi) = self.tight_bounds();
+
+        Ok((u, (lo.unwrap() + hi.unwrap() - u as i128) as i64))
+    }
+}
+
+/// Components are mirrored independently, from a single joint draw.
+impl<D: AntitheticSample> AntitheticSample for ProductSpace<D> {
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Vec<D::Value>, Vec<D::Value>), SampleError> {
+        self.iter().map(|d| d.try_sample_antithetic(rng)).collect::<Result<Vec<_>, _>>()
+            .map(|pairs| pairs.into_iter().unzip())
+    }
+}
+
+/// Components are mirrored independently, from a single joint draw.
+impl<D: AntitheticSample> AntitheticSample for TwoSpace<D> {
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<([D::Value; 2], [D::Value; 2]), SampleError> {
+        let (u0, v0) = self[0].try_sample_antithetic(rng)?;
+        let (u1, v1) = self[1].try_sample_antithetic(rng)?;
+
+        Ok(([u0, u1], [v0, v1]))
+    }
+}
+
+/// Components are mirrored independently, from a single joint draw.
+impl<D1: AntitheticSample, D2: AntitheticSample> AntitheticSample for PairSpace<D1, D2> {
+    fn try_sample_antithetic<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<((D1::Value, D2::Value), (D1::Value, D2::Value)), SampleError> {
+        let (u0, v0) = self.0.try_sample_antithetic(rng)?;
+        let (u1, v1) = self.1.try_sample_antithetic(rng)?;
+
+        Ok(((u0, u1), (v0, v1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BoundKind;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    fn variance(xs: &[f64]) -> f64 {
+        let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+
+        xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64
+    }
+
+    #[test]
+    fn test_mirror_f64() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for &(lb, ub) in [(0.0, 1.0), (-3.0, 7.5), (0.1, 0.3), (1e15, 1e15 + 3.0)].iter() {
+            let d = Interval::bounded(lb, ub);
+
+            for _ in 0..1000 {
+                let (u, v) = d.sample_antithetic(&mut rng);
+
+                assert!(d.contains(u) && d.contains(v), "({}, {}) not in {}", u, v, d);
+                assert!((u + v - (lb + ub)).abs() <= 4.0 * f64::EPSILON * ub.abs().max(lb.abs()));
+            }
+        }
+
+        let d = Interval::open(0.1, 0.7);
+
+        for _ in 0..1000 {
+            let (u, v) = d.sample_antithetic(&mut rng);
+
+            assert!(d.contains(u) && d.contains(v), "({}, {}) not in {}", u, v, d);
+        }
+
+        assert_eq!(Interval::bounded(2.0, 2.0).sample_antithetic(&mut rng), (2.0, 2.0));
+
+        for &(lb, ub) in [(-f64::MAX, f64::MAX), (1e308, f64::MAX), (-f64::MAX, 1e308)].iter() {
+            let d = Interval::bounded(lb, ub);
+
+            for _ in 0..1000 {
+                let (u, v) = d.sample_antithetic(&mut rng);
+
+                assert!(d.contains(u) && d.contains(v), "({}, {}) not in {}", u, v, d);
+                assert!((u / 2.0 + v / 2.0 - (lb / 2.0 + ub / 2.0)).abs() <= 1e-15 * f64::MAX);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirror_i64() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let d = Interval::bounded(-5i64, 10);
+        let open = Interval::bounded(0i64, 10).with_kinds(BoundKind::Open, BoundKind::Closed);
+        let full = Interval::bounded(i64::MIN, i64::MAX);
+
+        for _ in 0..1000 {
+            let (u, v) = d.sample_antithetic(&mut rng);
+
+            assert_eq!(u + v, 5);
+
+            let (u, v) = open.sample_antithetic(&mut rng);
+
+            assert_eq!(u + v, 11);
+            assert!(open.contains(u) && open.contains(v));
+
+            let (u, v) = full.sample_antithetic(&mut rng);
+
+            assert_eq!(u as i128 + v as i128, -1);
+        }
+    }
+
+    #[test]
+    fn test_products() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let d = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(-4.0, 2.0)]);
+        let vals = d.sample_antithetic_n(&mut rng, 100);
+
+        assert_eq!(vals.len(), 200);
+
+        for pair in vals.chunks(2) {
+            assert!(d.contains(&pair[0]) && d.contains(&pair[1]));
+            assert!((pair[0][0] + pair[1][0] - 1.0).abs() < 1e-12);
+            assert!((pair[0][1] + pair[1][1] + 2.0).abs() < 1e-12);
+        }
+
+        let (u, v) = TwoSpace::new([Interval::bounded(0i64, 4), Interval::bounded(1i64, 2)])
+            .sample_antithetic(&mut rng);
+
+        assert_eq!([u[0] + v[0], u[1] + v[1]], [4, 3]);
+
+        let (u, v) = PairSpace::new(Interval::bounded(0i64, 4), Interval::bounded(0.0, 2.0))
+            .sample_antithetic(&mut rng);
+
+        assert_eq!(u.0 + v.0, 4);
+        assert!((u.1 + v.1 - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_variance_reduction() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let d = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(0.0, 2.0)]);
+        let f = |x: &Vec<f64>| (x[0] + x[1]).exp();
+        let n = 50;
+
+        let (antithetic, iid): (Vec<f64>, Vec<f64>) = (0..1000).map(|_| {
+            let a = d.sample_antithetic_n(&mut rng, n).iter().map(f).sum::<f64>();
+            let b = (0..2 * n).map(|_| f(&d.sample(&mut rng))).sum::<f64>();
+
+            (a / (2 * n) as f64, b / (2 * n) as f64)
+        }).unzip();
+
+        assert!(
+            variance(&antithetic) < 0.5 * variance(&iid),
+            "{} >= {}", variance(&antithetic), variance(&iid)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(
+            Interval::left_bounded(0.0).try_sample_antithetic(&mut rng),
+            Err(SampleError::Unbounded)
+        );
+        assert_eq!(
+            Interval::bounded(0.0, f64::INFINITY).try_sample_antithetic(&mut rng),
+            Err(SampleError::NoDistribution)
+        );
+        assert_eq!(
+            ProductSpace::new(vec![Interval::unit(), Interval::open(0.0, 0.0)])
+                .try_sample_antithetic(&mut rng),
+            Err(SampleError::Empty)
+        );
+        assert!(Interval::<f64>::unit().sample_antithetic_n(&mut rng, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_unbounded() {
+        Interval::<i64>::unbounded().sample_antithetic(&mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{Equipartition, Interval, LogPartition, ProductSpace, Quantised, Reward};
+
+/// Trait for types that can be compared up to an absolute tolerance on their floating-point
+/// parameters.
+///
+/// Spaces deserialised from lossy formats (e.g. JSON written with limited precision) may compare
+/// unequal to the originals under `PartialEq`, which remains exact. `approx_eq` should be used
+/// wherever such spaces are compared.
+/// ```
+/// use spaces::{real::Interval, ApproxEq};
+///
+/// let a = Interval::bounded(0.1 + 0.2, 1.0);
+/// let b = Interval::bounded(0.3, 1.0);
+///
+/// assert!(a != b);
+/// assert!(a.approx_eq(&b, 1e-12));
+/// assert!(!a.approx_eq(&Interval::left_bounded(0.3), 1e-12));
+/// ```
+pub trait ApproxEq {
+    /// Returns true iff `self` and `other` are equal, up to a difference of at most `eps` in each
+    /// floating-point parameter.
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool;
+}
+
+/// Equal infinities compare approximately equal, whereas `NaN` never does.
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, eps: f64) -> bool {
+        self == other || (self - other).abs() <= eps
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_eq(&self, other: &Option<T>, eps: f64) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.approx_eq(b, eps),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &[T], eps: f64) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other.iter()).all(|(a, b)| a.approx_eq(b, eps))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Vec<T>, eps: f64) -> bool { self[..].approx_eq(&other[..], eps) }
+}
+
+/// The kinds of each bound must match exactly.
+impl ApproxEq for Interval<f64> {
+    fn approx_eq(&self, other: &Interval<f64>, eps: f64) -> bool {
+        self.lb.approx_eq(&other.lb, eps) && self.ub.approx_eq(&other.ub, eps)
+            && self.lb_kind == other.lb_kind && self.ub_kind == other.ub_kind
+    }
+}
+
+/// The number of partitions must match exactly.
+impl ApproxEq for Equipartition {
+    fn approx_eq(&self, other: &Equipartition, eps: f64) -> bool {
+        self.n_partitions() == other.n_partitions()
+            && self.lb.approx_eq(&other.lb, eps) && self.ub.approx_eq(&other.ub, eps)
+    }
+}
+
+/// The number of partitions must match exactly.
+impl ApproxEq for LogPartition {
+    fn approx_eq(&self, other: &LogPartition, eps: f64) -> bool {
+        self.n_partitions() == other.n_partitions()
+            && self.lb().approx_eq(&other.lb(), eps) && self.ub().approx_eq(&other.ub(), eps)
+    }
+}
+
+impl ApproxEq for Quantised {
+    fn approx_eq(&self, other: &Quantised, eps: f64) -> bool {
+        self.lb.approx_eq(&other.lb, eps) && self.ub.approx_eq(&other.ub, eps)
+            && self.step.approx_eq(&other.step, eps)
+    }
+}
+
+/// Only the intervals are compared; the normaliser statistics are ignored.
+impl ApproxEq for Reward {
+    fn approx_eq(&self, other: &Reward, eps: f64) -> bool {
+        self.interval().approx_eq(other.interval(), eps)
+    }
+}
+
+/// Covers both boxes (`ProductSpace<Interval>`) and grids (`ProductSpace<Equipartition>`).
+impl<D: ApproxEq> ApproxEq for ProductSpace<D> {
+    fn approx_eq(&self, other: &ProductSpace<D>, eps: f64) -> bool {
+        self.iter().as_slice().approx_eq(other.iter().as_slice(), eps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floats() {
+        assert!(0.3.approx_eq(&(0.1 + 0.2), 1e-12));
+        assert!(!0.3.approx_eq(&0.31, 1e-3));
+        assert!(f64::INFINITY.approx_eq(&f64::INFINITY, 0.0));
+        assert!(!f64::INFINITY.approx_eq(&f64::NEG_INFINITY, 1e300));
+        assert!(!f64::NAN.approx_eq(&f64::NAN, 1.0));
+        assert!(vec![1.0, 2.0].approx_eq(&vec![1.0, 2.0 + 1e-10], 1e-9));
+        assert!(!vec![1.0, 2.0].approx_eq(&vec![1.0], 1e-9));
+    }
+
+    #[test]
+    fn test_spaces() {
+        assert!(!Interval::open(0.0, 1.0).approx_eq(&Interval::bounded(0.0, 1.0), 1.0));
+        assert!(!Equipartition::new(0.0, 1.0, 4).approx_eq(&Equipartition::new(0.0, 1.0, 5), 0.1));
+        assert!(!Quantised::new(0.0, 1.0, 0.1).approx_eq(&Quantised::new(0.0, 1.0, 0.2), 1e-3));
+
+        let grid = ProductSpace::new(vec![Equipartition::new(0.0, 0.3, 3); 2]);
+        let other = ProductSpace::new(vec![Equipartition::new(0.0, 0.1 + 0.2, 3); 2]);
+
+        assert!(grid != other);
+        assert!(grid.approx_eq(&other, 1e-12));
+        assert!(!grid.approx_eq(&ProductSpace::new(vec![Equipartition::new(0.0, 0.3, 3)]), 1.0));
+    }
+
+    #[cfg(feature = "serialize")]
+    mod serialisation {
+        extern crate serde_json;
+
+        use self::serde_json::Value;
+        use serde::{de::DeserializeOwned, Serialize};
+        use super::*;
+
+        // Round-trips `space` through JSON with every float written to six decimal places, as
+        // produced by many non-Rust serialisers.
+        fn lossy_round_trip<S: Serialize + DeserializeOwned>(space: &S) -> S {
+            fn truncate(value: &mut Value) {
+                match value {
+                    Value::Number(n) if n.is_f64() => {
+                        let x: f64 = format!("{:.6}", n.as_f64().unwrap()).parse().unwrap();
+
+                        *value = x.into();
+                    },
+                    Value::Array(vs) => vs.iter_mut().for_each(truncate),
+                    Value::Object(map) => map.values_mut().for_each(truncate),
+                    _ => {},
+                }
+            }
+
+            let mut json = serde_json::to_value(space).unwrap();
+
+            truncate(&mut json);
+
+            serde_json::from_value(json).unwrap()
+        }
+
+        #[test]
+        fn test_lossy_round_trip() {
+            let interval = Interval::bounded(0.1, 1.0 / 3.0);
+            let restored = lossy_round_trip(&interval);
+
+            assert_ne!(restored, interval);
+            assert!(restored.approx_eq(&interval, 1e-6));
+
+            let bx = ProductSpace::new(vec![Interval::bounded(-2.0f64.sqrt(), 0.7); 3]);
+            let restored = lossy_round_trip(&bx);
+
+            assert_ne!(restored, bx);
+            assert!(restored.approx_eq(&bx, 1e-6));
+            assert!(!restored.approx_eq(&bx, 1e-9));
+
+            let grid = ProductSpace::new(vec![Equipartition::new(0.0, 2.0 / 3.0, 4); 2]);
+            let restored = lossy_round_trip(&grid);
+
+            assert!(restored.approx_eq(&grid, 1e-6));
+
+            let quantised = Quantised::new(0.0, 1.0, 1.0 / 7.0);
+            let restored = lossy_round_trip(&quantised);
+
+            assert_ne!(restored, quantised);
+            assert!(restored.approx_eq(&quantised, 1e-6));
+        }
+    }
+}
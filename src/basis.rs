@@ -0,0 +1,519 @@
+//! Fixed basis feature projections over bounded spaces, e.g. for linear function approximation.
+//!
+//! Each basis is constructed against a bounded box (or interval) and maps values of that space
+//! onto feature vectors of length `n_features()`. Inputs are first clamped to the bounds of the
+//! space and normalised onto the unit hypercube, so out-of-bound inputs produce the features of
+//! the nearest point of the space.
+//! ```
+//! use spaces::{basis::Fourier, real::Interval, ProductSpace, Surjection};
+//!
+//! let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]);
+//! let basis = Fourier::new(&space, 3).unwrap();
+//! let features: Vec<f64> = basis.map_onto(vec![0.0, 0.5]);
+//!
+//! assert_eq!(features.len(), basis.n_features());
+//! assert_eq!(basis.n_features(), 16);
+//! ```
+use crate::{prelude::*, Interval, ProductSpace, SizeLimitError};
+use std::{error::Error, f64::consts::PI, fmt};
+
+/// Error type for bases that cannot be constructed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BasisError {
+    /// The interval at `index` is unbounded or empty, so cannot be normalised.
+    Unbounded { index: usize },
+    /// A tile coding was requested with zero tilings or zero tiles per dimension.
+    NoTiles,
+    /// The basis would have too many features.
+    SizeLimit(SizeLimitError),
+}
+
+impl From<SizeLimitError> for BasisError {
+    fn from(e: SizeLimitError) -> BasisError { BasisError::SizeLimit(e) }
+}
+
+impl fmt::Display for BasisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BasisError::Unbounded { index } =>
+                write!(f, "interval {} of the space is unbounded or empty", index),
+            BasisError::NoTiles =>
+                write!(f, "tile coding requires at least one tiling and one tile per dimension"),
+            BasisError::SizeLimit(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for BasisError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BasisError::SizeLimit(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Bounds of a box, used to normalise inputs onto the unit hypercube.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+struct Normaliser(Vec<(f64, f64)>);
+
+impl Normaliser {
+    fn new(space: &ProductSpace<Interval>) -> Result<Normaliser, BasisError> {
+        space.iter().enumerate().map(|(index, d)| match (d.inf(), d.sup()) {
+            (Some(lb), Some(ub)) if lb.is_finite() && ub.is_finite() && !d.is_empty() =>
+                Ok((lb, ub)),
+            _ => Err(BasisError::Unbounded { index, }),
+        }).collect::<Result<_, _>>().map(Normaliser)
+    }
+
+    fn dim(&self) -> usize { self.0.len() }
+
+    // Returns the box from which the normaliser was constructed.
+    #[cfg(feature = "serialize")]
+    fn to_space(&self) -> ProductSpace<Interval> {
+        self.0.iter().map(|&(lb, ub)| Interval::bounded(lb, ub)).collect()
+    }
+
+    fn normalise(&self, val: &[f64]) -> Vec<f64> {
+        assert_eq!(val.len(), self.0.len(), "Value does not match the dimension of the space.");
+
+        self.0.iter().zip(val).map(|(&(lb, ub), &x)| {
+            // Halving keeps the width of boxes with extreme bounds finite, and is otherwise exact.
+            let (lb, ub, x) = (lb / 2.0, ub / 2.0, clip!(lb, x, ub) / 2.0);
+
+            if ub > lb { (x - lb) / (ub - lb) } else { 0.0 }
+        }).collect()
+    }
+}
+
+// Deserialized bases are rebuilt by their constructors, from the box of their normaliser; any
+// derived fields, such as the number of features, are recomputed.
+macro_rules! impl_basis_repr {
+    ($type:ident, $repr:ident, $name:tt; $($param:ident),+) => {
+        #[cfg(feature = "serialize")]
+        #[derive(Deserialize)]
+        #[serde(rename = $name)]
+        struct $repr {
+            $($param: usize,)+
+            bounds: Normaliser,
+        }
+
+        #[cfg(feature = "serialize")]
+        impl std::convert::TryFrom<$repr> for $type {
+            type Error = BasisError;
+
+            fn try_from(repr: $repr) -> Result<$type, BasisError> {
+                <$type>::new(&repr.bounds.to_space(), $(repr.$param),+)
+            }
+        }
+    };
+}
+
+/// Returns `base^dim`, subject to `MATERIALISE_LIMIT`.
+fn n_terms(base: usize, dim: usize) -> Result<usize, BasisError> {
+    let size = (0..dim).fold(1usize, |acc, _| acc.saturating_mul(base));
+
+    Ok(SizeLimitError::check(size)?)
+}
+
+/// Calls `f` with each multi-index in `{0, ..., base - 1}^dim`, in lexicographic order.
+fn for_each_index<F: FnMut(&[usize])>(base: usize, dim: usize, mut f: F) {
+    let mut idx = vec![0; dim];
+
+    loop {
+        f(&idx);
+
+        match idx.iter().rposition(|&i| i + 1 < base) {
+            Some(j) => {
+                idx[j] += 1;
+                idx[j + 1..].iter_mut().for_each(|i| *i = 0);
+            },
+            None => return,
+        }
+    }
+}
+
+macro_rules! impl_scalar_basis {
+    ($type:ty; $($param:ident),+) => {
+        impl $type {
+            /// Construct the basis over a single interval.
+            pub fn from_interval(
+                interval: Interval,
+                $($param: usize),+
+            ) -> Result<$type, BasisError> {
+                <$type>::new(&ProductSpace::new(vec![interval]), $($param),+)
+            }
+
+            /// Write the features of `val` into `out`, reusing its allocation.
+            ///
+            /// `out` is resized to `n_features()`, and its previous contents are discarded.
+            pub fn map_into(&self, val: &[f64], out: &mut Vec<f64>) {
+                out.clear();
+                out.reserve(self.n_features());
+
+                self.features_into(val, out);
+            }
+
+            fn features(&self, val: &[f64]) -> Vec<f64> {
+                let mut out = vec![];
+
+                self.map_into(val, &mut out);
+
+                out
+            }
+        }
+
+        impl Surjection<Vec<f64>, Vec<f64>> for $type {
+            fn map_onto(&self, val: Vec<f64>) -> Vec<f64> { self.features(&val) }
+        }
+
+        /// # Panics
+        ///
+        /// Panics if the basis was not constructed over a 1-dimensional space.
+        impl Surjection<f64, Vec<f64>> for $type {
+            fn map_onto(&self, val: f64) -> Vec<f64> { self.features(&[val]) }
+        }
+    };
+}
+
+/// Full polynomial basis of Chebyshev polynomials of the first kind.
+///
+/// Inputs are rescaled onto `[-1, 1]`, and the features are the products `T_c1(x1) ... T_cd(xd)`
+/// for every multi-index with `0 <= ci <= degree`, giving `(degree + 1)^d` features that all lie
+/// in `[-1, 1]`. The Chebyshev polynomials span the same space as the monomials of the same
+/// degree, but are far better conditioned.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "PolynomialRepr"))]
+pub struct Polynomial {
+    degree: usize,
+    bounds: Normaliser,
+    n_features: usize,
+}
+
+impl Polynomial {
+    /// Construct the basis, or return an error if any interval of `space` is unbounded, or the
+    /// basis would have too many features.
+    pub fn new(space: &ProductSpace<Interval>, degree: usize) -> Result<Polynomial, BasisError> {
+        let bounds = Normaliser::new(space)?;
+        let n_features = n_terms(degree + 1, bounds.dim())?;
+
+        Ok(Polynomial { degree, bounds, n_features, })
+    }
+
+    #[inline]
+    pub fn degree(&self) -> usize { self.degree }
+
+    #[inline]
+    pub fn n_features(&self) -> usize { self.n_features }
+
+    fn features_into(&self, val: &[f64], out: &mut Vec<f64>) {
+        // Chebyshev polynomials of each input, via T_{k+1}(x) = 2x T_k(x) - T_{k-1}(x).
+        let terms: Vec<Vec<f64>> = self.bounds.normalise(val).into_iter().map(|x| {
+            let x = 2.0 * x - 1.0;
+            let mut ts = vec![1.0, x];
+
+            for k in 1..self.degree {
+                ts.push(2.0 * x * ts[k] - ts[k - 1]);
+            }
+
+            ts.truncate(self.degree + 1);
+            ts
+        }).collect();
+
+        for_each_index(self.degree + 1, self.bounds.dim(), |c| {
+            out.push(c.iter().zip(terms.iter()).map(|(&k, ts)| ts[k]).product());
+        });
+    }
+}
+
+impl_scalar_basis!(Polynomial; degree);
+impl_basis_repr!(Polynomial, PolynomialRepr, "Polynomial"; degree);
+
+/// Fourier cosine basis.
+///
+/// With inputs normalised onto `[0, 1]`, the features are `cos(pi c . x)` for every multi-index
+/// with `0 <= ci <= order`, giving `(order + 1)^d` features that all lie in `[-1, 1]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "FourierRepr"))]
+pub struct Fourier {
+    order: usize,
+    bounds: Normaliser,
+    n_features: usize,
+}
+
+impl Fourier {
+    /// Construct the basis, or return an error if any interval of `space` is unbounded, or the
+    /// basis would have too many features.
+    pub fn new(space: &ProductSpace<Interval>, order: usize) -> Result<Fourier, BasisError> {
+        let bounds = Normaliser::new(space)?;
+        let n_features = n_terms(order + 1, bounds.dim())?;
+
+        Ok(Fourier { order, bounds, n_features, })
+    }
+
+    #[inline]
+    pub fn order(&self) -> usize { self.order }
+
+    #[inline]
+    pub fn n_features(&self) -> usize { self.n_features }
+
+    fn features_into(&self, val: &[f64], out: &mut Vec<f64>) {
+        let x = self.bounds.normalise(val);
+
+        for_each_index(self.order + 1, self.bounds.dim(), |c| {
+            let dot: f64 = c.iter().zip(x.iter()).map(|(&k, &xi)| k as f64 * xi).sum();
+
+            out.push((PI * dot).cos());
+        });
+    }
+}
+
+impl_scalar_basis!(Fourier; order);
+impl_basis_repr!(Fourier, FourierRepr, "Fourier"; order);
+
+/// Tile coding over a box.
+///
+/// Each of the `tilings` tilings partitions the box into a uniform grid of `tiles_per_dim` tiles
+/// along each dimension, offset from the other tilings by a fraction of a tile width. Offsets
+/// are asymmetric (tiling `t` is displaced by `t (2j + 1) / tilings` tile widths along dimension
+/// `j`, modulo one), and each tiling includes one extra tile per dimension to cover the
+/// displacement, giving `tilings * (tiles_per_dim + 1)^d` binary features. Exactly `tilings` of
+/// these, one per tiling, are active for any input.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "TileRepr"))]
+pub struct Tile {
+    tilings: usize,
+    tiles_per_dim: usize,
+    bounds: Normaliser,
+    tiles_per_tiling: usize,
+}
+
+impl Tile {
+    /// Construct the tile coding, or return an error if `tilings` or `tiles_per_dim` is zero,
+    /// any interval of `space` is unbounded, or the coding would have too many features.
+    pub fn new(
+        space: &ProductSpace<Interval>,
+        tilings: usize,
+        tiles_per_dim: usize,
+    ) -> Result<Tile, BasisError> {
+        if tilings == 0 || tiles_per_dim == 0 { return Err(BasisError::NoTiles); }
+
+        let bounds = Normaliser::new(space)?;
+        let tiles_per_tiling = n_terms(tiles_per_dim + 1, bounds.dim())?;
+
+        SizeLimitError::check(tiles_per_tiling.saturating_mul(tilings))?;
+
+        Ok(Tile { tilings, tiles_per_dim, bounds, tiles_per_tiling, })
+    }
+
+    #[inline]
+    pub fn tilings(&self) -> usize { self.tilings }
+
+    #[inline]
+    pub fn tiles_per_dim(&self) -> usize { self.tiles_per_dim }
+
+    #[inline]
+    pub fn n_features(&self) -> usize { self.tilings * self.tiles_per_tiling }
+
+    /// Returns the indices of the active feature in each tiling, in order of tiling.
+    pub fn active(&self, val: &[f64]) -> Vec<usize> {
+        let x = self.bounds.normalise(val);
+        let n = self.tiles_per_dim as f64;
+
+        (0..self.tilings).map(|t| {
+            let tile = x.iter().enumerate().fold(0, |acc, (j, &xj)| {
+                let offset = ((t * (2 * j + 1)) % self.tilings) as f64 / self.tilings as f64;
+                let coord = ((xj * n + offset).floor() as usize).min(self.tiles_per_dim);
+
+                acc * (self.tiles_per_dim + 1) + coord
+            });
+
+            t * self.tiles_per_tiling + tile
+        }).collect()
+    }
+
+    fn features_into(&self, val: &[f64], out: &mut Vec<f64>) {
+        out.resize(self.n_features(), 0.0);
+
+        for i in self.active(val) {
+            out[i] = 1.0;
+        }
+    }
+}
+
+impl_scalar_basis!(Tile; tilings, tiles_per_dim);
+impl_basis_repr!(Tile, TileRepr, "Tile"; tilings, tiles_per_dim);
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    fn space(d: usize) -> ProductSpace<Interval> {
+        ProductSpace::new((0..d).map(|i| Interval::bounded(-(i as f64), 1.0 + i as f64)).collect())
+    }
+
+    #[test]
+    fn test_n_features() {
+        for d in 1..4 {
+            for n in 0..4usize {
+                let n_terms = (n + 1).pow(d as u32);
+                let n_tiles = (n + 1) * 5usize.pow(d as u32);
+                let val = vec![0.5; d];
+
+                let polynomial = Polynomial::new(&space(d), n).unwrap();
+                let fourier = Fourier::new(&space(d), n).unwrap();
+                let tile = Tile::new(&space(d), n + 1, 4).unwrap();
+
+                assert_eq!(polynomial.n_features(), n_terms);
+                assert_eq!(polynomial.map_onto(val.clone()).len(), n_terms);
+                assert_eq!(fourier.n_features(), n_terms);
+                assert_eq!(fourier.map_onto(val.clone()).len(), n_terms);
+                assert_eq!(tile.n_features(), n_tiles);
+                assert_eq!(tile.map_onto(val).len(), n_tiles);
+            }
+        }
+
+        assert!(Fourier::new(&space(30), 3).is_err());
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let basis = Polynomial::from_interval(Interval::bounded(0.0, 2.0), 3).unwrap();
+        let x = 0.25;
+        let features: Vec<f64> = basis.map_onto(1.25);
+
+        // 1.25 is normalised onto 0.625, and rescaled onto 0.25.
+        assert_eq!(features, vec![1.0, x, 2.0 * x * x - 1.0, 4.0 * x * x * x - 3.0 * x]);
+        assert_eq!(basis.map_onto(5.0), basis.map_onto(2.0));
+    }
+
+    #[test]
+    fn test_fourier_range() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = space(3);
+        let basis = Fourier::new(&space, 4).unwrap();
+
+        for _ in 0..100 {
+            let mut val = space.sample(&mut rng);
+
+            val[0] *= 10.0;
+
+            let features = basis.map_onto(val);
+
+            assert_eq!(features[0], 1.0);
+            assert!(features.iter().all(|f| (-1.0..=1.0).contains(f)));
+        }
+    }
+
+    #[test]
+    fn test_tile_activations() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = space(2);
+        let basis = Tile::new(&space, 8, 5).unwrap();
+
+        for _ in 0..100 {
+            let val = space.sample(&mut rng);
+            let features = basis.map_onto(val.clone());
+            let active = basis.active(&val);
+
+            assert_eq!(features.iter().filter(|&&f| f == 1.0).count(), 8);
+            assert!(active.iter().enumerate().all(|(t, &i)| i / (basis.n_features() / 8) == t));
+        }
+
+        // Out-of-bound inputs activate the same tiles as the nearest point of the space.
+        assert_eq!(basis.active(&[-5.0, 10.0]), basis.active(&[0.0, 2.0]));
+    }
+
+    #[test]
+    fn test_map_into() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = space(2);
+        let polynomial = Polynomial::new(&space, 3).unwrap();
+        let fourier = Fourier::new(&space, 3).unwrap();
+        let tile = Tile::new(&space, 4, 3).unwrap();
+        let mut out = vec![5.0; 100];
+
+        for _ in 0..20 {
+            let val = space.sample(&mut rng);
+
+            polynomial.map_into(&val, &mut out);
+            assert_eq!(out, polynomial.map_onto(val.clone()));
+
+            fourier.map_into(&val, &mut out);
+            assert_eq!(out, fourier.map_onto(val.clone()));
+
+            tile.map_into(&val, &mut out);
+            assert_eq!(out, tile.map_onto(val));
+        }
+    }
+
+    #[test]
+    fn test_normalise_extreme() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(-f64::MAX, f64::MAX),
+            Interval::bounded(1e308, 1.7e308),
+            Interval::bounded(-1.0, 1.0),
+        ]);
+        let bounds = Normaliser::new(&space).unwrap();
+
+        assert_eq!(bounds.normalise(&[0.0, 1.35e308, 0.5]), vec![0.5, 0.5, 0.75]);
+        assert_eq!(bounds.normalise(&[f64::MAX, 1e308, 2.0]), vec![1.0, 0.0, 1.0]);
+        assert_eq!(bounds.normalise(&[-f64::MAX, f64::INFINITY, -2.0]), vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let space = ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(0.0)]);
+
+        assert!(Fourier::from_interval(Interval::left_bounded(0.0), 2).is_err());
+        assert_eq!(Polynomial::new(&space, 2), Err(BasisError::Unbounded { index: 1 }));
+        assert_eq!(Fourier::new(&space, 2), Err(BasisError::Unbounded { index: 1 }));
+        assert_eq!(Tile::new(&space, 2, 2), Err(BasisError::Unbounded { index: 1 }));
+
+        let infinite = ProductSpace::new(vec![Interval::bounded(0.0, f64::INFINITY)]);
+        let empty = ProductSpace::new(vec![Interval::unit(), Interval::bounded(1.0, 0.0)]);
+        let open = ProductSpace::new(vec![Interval::open(0.0, 0.0)]);
+
+        assert_eq!(Polynomial::new(&infinite, 2), Err(BasisError::Unbounded { index: 0 }));
+        assert_eq!(Fourier::new(&empty, 2), Err(BasisError::Unbounded { index: 1 }));
+        assert_eq!(Tile::new(&open, 2, 2), Err(BasisError::Unbounded { index: 0 }));
+        assert!(Polynomial::new(&ProductSpace::new(vec![Interval::bounded(1.0, 1.0)]), 2).is_ok());
+        assert_eq!(Tile::from_interval(Interval::unit(), 0, 4), Err(BasisError::NoTiles));
+        assert_eq!(Tile::from_interval(Interval::unit(), 4, 0), Err(BasisError::NoTiles));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let tile = Tile::new(&space(2), 4, 3).unwrap();
+        let json = serde_json::to_string(&tile).unwrap();
+
+        assert_eq!(serde_json::from_str::<Tile>(&json).unwrap(), tile);
+
+        let parse = |tilings: usize| serde_json::from_str::<Tile>(&format!(
+            r#"{{"tilings":{},"tiles_per_dim":3,"bounds":[[0.0,1.0]],"tiles_per_tiling":4}}"#,
+            tilings
+        ));
+
+        assert_eq!(parse(2).unwrap().n_features(), 8);
+        assert!(parse(0).is_err());
+
+        let fourier = Fourier::new(&space(2), 3).unwrap();
+        let json = serde_json::to_string(&fourier).unwrap();
+
+        assert_eq!(serde_json::from_str::<Fourier>(&json).unwrap(), fourier);
+
+        // The number of features is recomputed, and so subject to the size limit.
+        let bounds = ["[0.0,1.0]"; 10].join(",");
+
+        assert!(serde_json::from_str::<Polynomial>(
+            &format!(r#"{{"degree":30,"bounds":[{}],"n_features":1}}"#, bounds)
+        ).is_err());
+    }
+}
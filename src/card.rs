@@ -1,4 +1,4 @@
-use std::ops::Mul;
+use std::{fmt, ops::Mul};
 
 /// Measure of the cardinality (#) of a set.
 ///
@@ -28,38 +28,251 @@ use std::ops::Mul;
 ///
 /// assert_eq!(s1 * s2, Card::Finite(50));
 /// ```
+///
+/// Note: products whose size overflows `usize` are promoted to `Card::Infinite`.
+///
+/// # Serialisation
+///
+/// With the `serialize` feature, cardinalities are written to human-readable formats (e.g. JSON
+/// or YAML) as a plain number when finite and as the string `"infinite"` otherwise; the string is
+/// matched case-insensitively on read. Binary formats (e.g. bincode) use the enum representation.
+///
+/// New variants may be added in future releases, so matches outside of this crate must include a
+/// wildcard arm:
+/// ```compile_fail
+/// use spaces::Card;
+///
+/// fn size(card: Card) -> Option<usize> {
+///     match card {
+///         Card::Finite(n) => Some(n),
+///         Card::Infinite => None,
+///     }
+/// }
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum Card {
     Finite(usize),
     Infinite,
 }
 
+impl Card {
+    #[inline]
+    pub const fn finite(size: usize) -> Card { Card::Finite(size) }
+
+    #[inline]
+    pub const fn infinite() -> Card { Card::Infinite }
+
+    /// Returns `n!`, promoted to `Card::Infinite` if it overflows `usize`.
+    pub fn factorial(n: usize) -> Card {
+        (2..=n as u128).try_fold(1u128, |acc, i| fit(acc * i)).map_or(Card::Infinite, narrow)
+    }
+
+    /// Returns the number of ways to choose `k` of `n` elements, promoted to `Card::Infinite` if
+    /// it overflows `usize`.
+    ///
+    /// The count is zero if `k > n`.
+    pub fn binomial(n: usize, k: usize) -> Card {
+        if k > n { return Card::Finite(0); }
+
+        // The partial products C(n, i) increase up to i = min(k, n - k), so none overflow unless
+        // the result does.
+        let (n, k) = (n as u128, k.min(n - k) as u128);
+
+        (0..k).try_fold(1u128, |acc, i| fit(acc * (n - i) / (i + 1))).map_or(Card::Infinite, narrow)
+    }
+
+    /// Returns the cardinality of the disjoint union of sets with the given cardinalities.
+    ///
+    /// The empty sum is zero.
+    pub fn sum<I: IntoIterator<Item = Card>>(cards: I) -> Card {
+        cards.into_iter().try_fold(0usize, |acc, c| match c {
+            Card::Finite(n) => acc.checked_add(n),
+            Card::Infinite => None,
+        }).map_or(Card::Infinite, Card::Finite)
+    }
+
+    /// Returns the cardinality of the Cartesian product of sets with the given cardinalities.
+    ///
+    /// Zero is absorbing, even for infinite factors, and the empty product is one; `Mul` agrees.
+    pub fn product<I: IntoIterator<Item = Card>>(cards: I) -> Card {
+        let mut infinite = false;
+        let mut total = Some(1usize);
+
+        for c in cards {
+            match c {
+                Card::Finite(0) => return Card::Finite(0),
+                Card::Finite(n) => total = total.and_then(|t| t.checked_mul(n)),
+                Card::Infinite => infinite = true,
+            }
+        }
+
+        match total {
+            Some(n) if !infinite => Card::Finite(n),
+            _ => Card::Infinite,
+        }
+    }
+}
+
+// Returns `x` if it fits in a `usize`; widened intermediates of at most `usize::MAX` squared
+// cannot overflow a `u128`.
+fn fit(x: u128) -> Option<u128> { Some(x).filter(|&x| x <= usize::MAX as u128) }
+
+fn narrow(x: u128) -> Card { Card::Finite(x as usize) }
+
 impl Mul for Card {
     type Output = Card;
 
-    fn mul(self, rhs: Card) -> Card {
-        match (self, rhs) {
-            (Card::Infinite, _) | (_, Card::Infinite) => Card::Infinite,
-            (Card::Finite(0), Card::Finite(a)) | (Card::Finite(a), Card::Finite(0)) =>
-                Card::Finite(a),
-            (Card::Finite(ls), Card::Finite(rs)) => Card::Finite(ls * rs),
+    fn mul(self, rhs: Card) -> Card { Card::product(vec![self, rhs]) }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Card::Finite(n) => write!(f, "{}", n),
+            Card::Infinite => write!(f, "\u{221e}"),
         }
     }
 }
 
-impl Into<usize> for Card {
-    fn into(self) -> usize {
-        match self {
+/// Cardinality of a product space, retaining the cardinalities of its components.
+///
+/// The `Display` implementation shows the total cardinality, or the factorisation into
+/// components when the alternate flag is used:
+/// ```
+/// use spaces::{discrete::Ordinal, Card, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(5), Ordinal::new(2)]);
+/// let card = space.product_card();
+///
+/// assert_eq!(card.total(), Card::Finite(30));
+/// assert_eq!(format!("{}", card), "30");
+/// assert_eq!(format!("{:#}", card), "3 \u{d7} 5 \u{d7} 2");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProductCard(pub Vec<Card>);
+
+impl ProductCard {
+    /// Returns the cardinality of the product, computed as in `Space::card`.
+    ///
+    /// Products with any empty factor are themselves empty; see `Card::product`. The product of
+    /// no spaces has exactly one value, the empty tuple.
+    pub fn total(&self) -> Card {
+        Card::product(self.0.iter().cloned())
+    }
+}
+
+impl fmt::Display for ProductCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() { return write!(f, "{}", self.total()); }
+
+        for (i, c) in self.0.iter().enumerate() {
+            if i != 0 { write!(f, " \u{d7} ")?; }
+
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Card> for usize {
+    fn from(d: Card) -> usize {
+        match d {
             Card::Finite(e) => e,
             _ => panic!("Card type has no integer representation."),
         }
     }
 }
 
+#[cfg(feature = "serialize")]
+mod serde_impl {
+    use super::Card;
+    use serde::{
+        de::{self, Visitor},
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+    use std::{convert::TryFrom, fmt};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Card")]
+    enum RawCard {
+        Finite(usize),
+        Infinite,
+    }
+
+    impl Serialize for Card {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                match *self {
+                    Card::Finite(n) => serializer.serialize_u64(n as u64),
+                    Card::Infinite => serializer.serialize_str("infinite"),
+                }
+            } else {
+                match *self {
+                    Card::Finite(n) => RawCard::Finite(n),
+                    Card::Infinite => RawCard::Infinite,
+                }.serialize(serializer)
+            }
+        }
+    }
+
+    struct CardVisitor;
+
+    impl<'de> Visitor<'de> for CardVisitor {
+        type Value = Card;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a non-negative integer or the string \"infinite\"")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Card, E> {
+            usize::try_from(v).map(Card::Finite).map_err(|_| {
+                E::custom(format!("cardinality {} does not fit in usize", v))
+            })
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Card, E> {
+            if v < 0 {
+                return Err(E::custom(format!("cardinality must be non-negative, found {}", v)));
+            }
+
+            self.visit_u64(v as u64)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Card, E> {
+            Err(E::custom(format!("cardinality must be an integer, found {}", v)))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Card, E> {
+            if v.eq_ignore_ascii_case("infinite") {
+                Ok(Card::Infinite)
+            } else {
+                Err(E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Card {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(CardVisitor)
+            } else {
+                Ok(match RawCard::deserialize(deserializer)? {
+                    RawCard::Finite(n) => Card::Finite(n),
+                    RawCard::Infinite => Card::Infinite,
+                })
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Card;
+    use super::{Card, ProductCard};
 
     #[test]
     fn test_equality() {
@@ -89,8 +302,10 @@ mod tests {
         assert_eq!(Card::Finite(0), Card::Finite(0));
         assert_eq!(Card::Infinite * Card::Infinite, Card::Infinite);
 
-        assert_eq!(Card::Finite(0) * Card::Infinite, Card::Infinite);
-        assert_eq!(Card::Infinite * Card::Finite(0), Card::Infinite);
+        assert_eq!(Card::Finite(0) * Card::Infinite, Card::Finite(0));
+        assert_eq!(Card::Infinite * Card::Finite(0), Card::Finite(0));
+        assert_eq!(Card::Finite(0) * Card::Finite(5), Card::Finite(0));
+        assert_eq!(Card::Finite(5) * Card::Finite(0), Card::Finite(0));
 
         assert_eq!(Card::Finite(1) * Card::Infinite, Card::Infinite);
         assert_eq!(Card::Finite(5) * Card::Infinite, Card::Infinite);
@@ -101,6 +316,79 @@ mod tests {
         assert_eq!(Card::Finite(5) * Card::Finite(5), Card::Finite(25));
     }
 
+    #[test]
+    fn test_factorial() {
+        assert_eq!(Card::factorial(0), Card::Finite(1));
+        assert_eq!(Card::factorial(1), Card::Finite(1));
+        assert_eq!(Card::factorial(5), Card::Finite(120));
+        assert_eq!(Card::factorial(20), Card::Finite(2_432_902_008_176_640_000));
+        assert_eq!(Card::factorial(21), Card::Infinite);
+        assert_eq!(Card::factorial(35), Card::Infinite);
+        assert_eq!(Card::factorial(usize::MAX), Card::Infinite);
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(Card::binomial(52, 5), Card::Finite(2_598_960));
+        assert_eq!(Card::binomial(5, 0), Card::Finite(1));
+        assert_eq!(Card::binomial(5, 5), Card::Finite(1));
+        assert_eq!(Card::binomial(5, 6), Card::Finite(0));
+        assert_eq!(Card::binomial(0, 0), Card::Finite(1));
+        assert_eq!(Card::binomial(64, 32), Card::Finite(1_832_624_140_942_590_534));
+        assert_eq!(Card::binomial(68, 34), Card::Infinite);
+        assert_eq!(Card::binomial(usize::MAX, 1), Card::Finite(usize::MAX));
+        assert_eq!(Card::binomial(usize::MAX, usize::MAX - 1), Card::Finite(usize::MAX));
+        assert_eq!(Card::binomial(usize::MAX, 2), Card::Infinite);
+
+        for n in 0..30 {
+            let row: Vec<Card> = (0..=n).map(|k| Card::binomial(n, k)).collect();
+
+            assert_eq!(Card::sum(row.iter().cloned()), Card::Finite(1 << n));
+            assert_eq!(row, row.iter().rev().cloned().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_folds() {
+        assert_eq!(Card::sum(vec![]), Card::Finite(0));
+        assert_eq!(Card::product(vec![]), Card::Finite(1));
+        assert_eq!(Card::sum(vec![Card::Finite(3), Card::Finite(4)]), Card::Finite(7));
+        assert_eq!(Card::product(vec![Card::Finite(3), Card::Finite(4)]), Card::Finite(12));
+        assert_eq!(Card::sum(vec![Card::Finite(3), Card::Infinite]), Card::Infinite);
+        assert_eq!(Card::product(vec![Card::Finite(3), Card::Infinite]), Card::Infinite);
+        assert_eq!(Card::product(vec![Card::Infinite, Card::Finite(0)]), Card::Finite(0));
+        assert_eq!(Card::sum(vec![Card::Finite(usize::MAX), Card::Finite(1)]), Card::Infinite);
+        assert_eq!(Card::product(vec![Card::Finite(1 << 32); 2]), Card::Infinite);
+
+        // Products of factorials count the arrangements of labelled groups.
+        let cards = vec![Card::factorial(3), Card::factorial(4)];
+
+        assert_eq!(Card::product(cards.clone()), Card::Finite(144));
+        assert_eq!(Card::sum(cards), Card::Finite(30));
+    }
+
+    #[test]
+    fn test_mul_overflow() {
+        assert_eq!(Card::Finite(usize::MAX) * Card::Finite(1), Card::Finite(usize::MAX));
+        assert_eq!(Card::Finite(usize::MAX) * Card::Finite(2), Card::Infinite);
+        assert_eq!(Card::Finite(1 << 32) * Card::Finite(1 << 32), Card::Infinite);
+    }
+
+    #[test]
+    fn test_product_card() {
+        let card = ProductCard(vec![Card::Finite(3), Card::Infinite, Card::Finite(2)]);
+
+        assert_eq!(card.total(), Card::Infinite);
+        assert_eq!(card.to_string(), "\u{221e}");
+        assert_eq!(format!("{:#}", card), "3 \u{d7} \u{221e} \u{d7} 2");
+        assert_eq!(ProductCard(vec![]).total(), Card::Finite(1));
+
+        let card = ProductCard(vec![Card::Finite(3), Card::Finite(0), Card::Infinite]);
+
+        assert_eq!(card.total(), Card::Finite(0));
+        assert_eq!(format!("{:#}", card), "3 \u{d7} 0 \u{d7} \u{221e}");
+    }
+
     #[test]
     #[should_panic]
     fn test_into_infinite() {
@@ -110,11 +398,51 @@ mod tests {
 
     #[test]
     fn test_into_finite() {
-        for i in vec![0, 1, 5, 10] {
+        for i in [0, 1, 5, 10] {
             let d = Card::Finite(i);
             let v: usize = d.into();
 
             assert_eq!(v, i);
         }
     }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serialisation() {
+        extern crate bincode;
+        extern crate serde_json;
+        extern crate serde_yaml;
+
+        for card in [Card::Finite(0), Card::Finite(12), Card::Finite(usize::MAX), Card::Infinite] {
+            let json = serde_json::to_string(&card).unwrap();
+            let yaml = serde_yaml::to_string(&card).unwrap();
+            let bytes = bincode::serialize(&card).unwrap();
+
+            assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+            assert_eq!(serde_yaml::from_str::<Card>(&yaml).unwrap(), card);
+            assert_eq!(bincode::deserialize::<Card>(&bytes).unwrap(), card);
+        }
+
+        assert_eq!(serde_json::to_string(&Card::Finite(12)).unwrap(), "12");
+        assert_eq!(serde_json::to_string(&Card::Infinite).unwrap(), "\"infinite\"");
+        assert_eq!(serde_yaml::to_string(&Card::Infinite).unwrap(), "infinite\n");
+        assert_eq!(serde_json::from_str::<Card>("\"Infinite\"").unwrap(), Card::Infinite);
+        assert_eq!(serde_yaml::from_str::<Card>("INFINITE").unwrap(), Card::Infinite);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialisation_errors() {
+        extern crate serde_json;
+
+        let err = |s: &str| serde_json::from_str::<Card>(s).unwrap_err().to_string();
+
+        assert!(err("-1").starts_with("cardinality must be non-negative, found -1"));
+        assert!(err("1.5").starts_with("cardinality must be an integer, found 1.5"));
+        assert!(err("\"lots\"").starts_with(concat!(
+            "invalid value: string \"lots\", ",
+            "expected a non-negative integer or the string \"infinite\""
+        )));
+        assert!(err("null").contains("expected a non-negative integer"));
+    }
 }
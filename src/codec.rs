@@ -0,0 +1,549 @@
+//! Compact, space-aware binary encoding of values.
+//!
+//! Since a space fully determines the layout of its values, encodings need not carry any
+//! framing or type information:
+//!
+//! - finite index spaces (e.g. `Ordinal`, `Equipartition`) encode as LEB128 varints, as do the
+//!   grid indices of `Quantised` values;
+//! - integer spaces encode as (zigzag) varints;
+//! - `Binary` encodes as a single byte;
+//! - real spaces encode as fixed-width little-endian `f64`, or `f32` when wrapped in
+//!   `SinglePrecision`;
+//! - composite spaces concatenate the encodings of their components.
+//!
+//! ```
+//! use spaces::{codec::ValueCodec, discrete::Ordinal};
+//!
+//! let d = Ordinal::new(4);
+//! let mut buf = vec![];
+//!
+//! d.encode(&3, &mut buf);
+//!
+//! assert_eq!(buf.len(), 1);
+//! assert_eq!(d.decode(&buf), Ok((3, 1)));
+//! ```
+use crate::{
+    discrete::{Binary, Integers, Naturals, NonNegativeIntegers, NonZeroIntegers, Ordinal},
+    real::{NonNegativeReals, PositiveReals, Reals},
+    prelude::*,
+    Empty, Equipartition, Interval, PairSpace, ProductSpace, Quantised, SampleError, TwoSpace,
+};
+use std::{error::Error, fmt};
+
+/// Error type for failed decodings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodecError {
+    /// The input ended before a complete value was read.
+    UnexpectedEof,
+    /// A varint was longer than the maximum of 10 bytes.
+    VarintOverflow,
+    /// The decoded value at the given byte offset does not belong to the space.
+    InvalidValue(usize),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::UnexpectedEof => write!(f, "unexpected end of input"),
+            CodecError::VarintOverflow => write!(f, "varint exceeds 64 bits"),
+            CodecError::InvalidValue(offset) =>
+                write!(f, "value at byte offset {} is not contained in the space", offset),
+        }
+    }
+}
+
+impl Error for CodecError {}
+
+/// Trait for spaces that can encode and decode their values to and from bytes.
+pub trait ValueCodec: Space {
+    /// Append the encoding of `val` to `out`.
+    fn encode(&self, val: &Self::Value, out: &mut Vec<u8>);
+
+    /// Decode a value from the front of `bytes`, returning it along with the number of bytes
+    /// consumed.
+    fn decode(&self, bytes: &[u8]) -> Result<(Self::Value, usize), CodecError>;
+}
+
+fn encode_varint(mut val: u64, out: &mut Vec<u8>) {
+    while val >= 0x80 {
+        out.push((val as u8) | 0x80);
+        val >>= 7;
+    }
+
+    out.push(val as u8);
+}
+
+fn decode_varint(bytes: &[u8]) -> Result<(u64, usize), CodecError> {
+    let mut val = 0u64;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if i == 9 && b > 1 {
+            return Err(CodecError::VarintOverflow);
+        }
+
+        val |= u64::from(b & 0x7f) << (7 * i);
+
+        if b & 0x80 == 0 {
+            return Ok((val, i + 1));
+        }
+    }
+
+    Err(if bytes.len() >= 10 { CodecError::VarintOverflow } else { CodecError::UnexpectedEof })
+}
+
+fn zigzag(val: i64) -> u64 { ((val << 1) ^ (val >> 63)) as u64 }
+
+fn unzigzag(val: u64) -> i64 { ((val >> 1) as i64) ^ -((val & 1) as i64) }
+
+fn decode_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N], CodecError> {
+    bytes
+        .get(..N)
+        .map(|b| {
+            let mut arr = [0u8; N];
+            arr.copy_from_slice(b);
+            arr
+        })
+        .ok_or(CodecError::UnexpectedEof)
+}
+
+fn encode_f64(val: f64, out: &mut Vec<u8>) { out.extend_from_slice(&val.to_le_bytes()); }
+
+fn decode_f64(bytes: &[u8]) -> Result<(f64, usize), CodecError> {
+    decode_array::<8>(bytes).map(|b| (f64::from_le_bytes(b), 8))
+}
+
+fn encode_index(val: usize, out: &mut Vec<u8>) { encode_varint(val as u64, out) }
+
+fn decode_index(bytes: &[u8], size: usize) -> Result<(usize, usize), CodecError> {
+    let (val, n) = decode_varint(bytes)?;
+
+    if val < size as u64 { Ok((val as usize, n)) } else { Err(CodecError::InvalidValue(0)) }
+}
+
+fn offset_err(err: CodecError, offset: usize) -> CodecError {
+    match err {
+        CodecError::InvalidValue(o) => CodecError::InvalidValue(o + offset),
+        e => e,
+    }
+}
+
+impl ValueCodec for Empty {
+    fn encode(&self, _: &(), _: &mut Vec<u8>) {}
+
+    fn decode(&self, _: &[u8]) -> Result<((), usize), CodecError> { Ok(((), 0)) }
+}
+
+impl ValueCodec for Binary {
+    fn encode(&self, val: &bool, out: &mut Vec<u8>) { out.push(*val as u8) }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(bool, usize), CodecError> {
+        match bytes.first() {
+            Some(0) => Ok((false, 1)),
+            Some(1) => Ok((true, 1)),
+            Some(_) => Err(CodecError::InvalidValue(0)),
+            None => Err(CodecError::UnexpectedEof),
+        }
+    }
+}
+
+impl ValueCodec for Ordinal {
+    fn encode(&self, val: &usize, out: &mut Vec<u8>) { encode_index(*val, out) }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(usize, usize), CodecError> {
+        decode_index(bytes, self.card().into())
+    }
+}
+
+impl ValueCodec for Equipartition {
+    fn encode(&self, val: &usize, out: &mut Vec<u8>) { encode_index(*val, out) }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(usize, usize), CodecError> {
+        decode_index(bytes, self.n_partitions())
+    }
+}
+
+impl ValueCodec for Quantised {
+    fn encode(&self, val: &f64, out: &mut Vec<u8>) { encode_index(self.nearest_index(*val), out) }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(f64, usize), CodecError> {
+        decode_index(bytes, self.n_points()).map(|(i, n)| (self.point(i), n))
+    }
+}
+
+macro_rules! impl_codec_unsigned {
+    ($type:ty) => {
+        impl ValueCodec for $type {
+            fn encode(&self, val: &u64, out: &mut Vec<u8>) { encode_varint(*val, out) }
+
+            fn decode(&self, bytes: &[u8]) -> Result<(u64, usize), CodecError> {
+                let (val, n) = decode_varint(bytes)?;
+
+                if self.contains(val) { Ok((val, n)) } else { Err(CodecError::InvalidValue(0)) }
+            }
+        }
+    };
+}
+
+impl_codec_unsigned!(Naturals);
+impl_codec_unsigned!(NonNegativeIntegers);
+
+macro_rules! impl_codec_signed {
+    ($type:ty) => {
+        impl ValueCodec for $type {
+            fn encode(&self, val: &i64, out: &mut Vec<u8>) { encode_varint(zigzag(*val), out) }
+
+            fn decode(&self, bytes: &[u8]) -> Result<(i64, usize), CodecError> {
+                let (val, n) = decode_varint(bytes)?;
+                let val = unzigzag(val);
+
+                if self.contains(val) { Ok((val, n)) } else { Err(CodecError::InvalidValue(0)) }
+            }
+        }
+    };
+}
+
+impl_codec_signed!(Integers);
+impl_codec_signed!(NonZeroIntegers);
+impl_codec_signed!(Interval<i64>);
+
+/// Every `f64` is contained in `Reals`, as in an unbounded `Interval`.
+impl ValueCodec for Reals {
+    fn encode(&self, val: &f64, out: &mut Vec<u8>) { encode_f64(*val, out) }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(f64, usize), CodecError> { decode_f64(bytes) }
+}
+
+macro_rules! impl_codec_real {
+    ($type:ty) => {
+        impl ValueCodec for $type {
+            fn encode(&self, val: &f64, out: &mut Vec<u8>) { encode_f64(*val, out) }
+
+            fn decode(&self, bytes: &[u8]) -> Result<(f64, usize), CodecError> {
+                let (val, n) = decode_f64(bytes)?;
+
+                if self.contains(val) { Ok((val, n)) } else { Err(CodecError::InvalidValue(0)) }
+            }
+        }
+    };
+}
+
+impl_codec_real!(NonNegativeReals);
+impl_codec_real!(PositiveReals);
+impl_codec_real!(Interval<f64>);
+
+/// Wrapper around a real space whose values are encoded with single (`f32`) precision.
+///
+/// Values are rounded to the nearest `f32`, or to the adjacent `f32` on the interior side where
+/// that would leave the space; decoded values outside the space are rejected.
+///
+/// All other behaviour is forwarded to the wrapped space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SinglePrecision<S>(pub S);
+
+impl<S: Space<Value = f64>> Space for SinglePrecision<S> {
+    type Value = f64;
+
+    fn dim(&self) -> Dim { self.0.dim() }
+
+    fn shape(&self) -> Shape { self.0.shape() }
+
+    fn card(&self) -> Card { self.0.card() }
+}
+
+impl<S: Sample<Value = f64>> Sample for SinglePrecision<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 { self.0.sample(rng) }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, SampleError> {
+        self.0.try_sample(rng)
+    }
+}
+
+impl<S: BoundedSpace<Value = f64>> BoundedSpace for SinglePrecision<S> {
+    fn inf(&self) -> Option<f64> { self.0.inf() }
+
+    fn sup(&self) -> Option<f64> { self.0.sup() }
+
+    fn contains(&self, val: f64) -> bool { self.0.contains(val) }
+}
+
+impl<S: Surjection<X, f64>, X> Surjection<X, f64> for SinglePrecision<S> {
+    fn map_onto(&self, val: X) -> f64 { self.0.map_onto(val) }
+}
+
+// Steps `x` by one ulp towards `target`.
+fn f32_towards(x: f32, target: f64) -> f32 {
+    if x == 0.0 {
+        let tiny = f32::from_bits(1);
+
+        if target > 0.0 { tiny } else { -tiny }
+    } else if (f64::from(x) < target) == (x > 0.0) {
+        f32::from_bits(x.to_bits() + 1)
+    } else {
+        f32::from_bits(x.to_bits() - 1)
+    }
+}
+
+impl<S: BoundedSpace<Value = f64>> ValueCodec for SinglePrecision<S> {
+    fn encode(&self, val: &f64, out: &mut Vec<u8>) {
+        let mut x = *val as f32;
+
+        if !self.0.contains(f64::from(x)) && self.0.contains(*val) {
+            x = f32_towards(x, *val);
+        }
+
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(f64, usize), CodecError> {
+        let val = decode_array::<4>(bytes).map(|b| f64::from(f32::from_le_bytes(b)))?;
+
+        if self.0.contains(val) { Ok((val, 4)) } else { Err(CodecError::InvalidValue(0)) }
+    }
+}
+
+impl ValueCodec for SinglePrecision<Reals> {
+    fn encode(&self, val: &f64, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(*val as f32).to_le_bytes());
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(f64, usize), CodecError> {
+        decode_array::<4>(bytes).map(|b| (f64::from(f32::from_le_bytes(b)), 4))
+    }
+}
+
+impl<D1: ValueCodec, D2: ValueCodec> ValueCodec for PairSpace<D1, D2> {
+    fn encode(&self, val: &(D1::Value, D2::Value), out: &mut Vec<u8>) {
+        self.0.encode(&val.0, out);
+        self.1.encode(&val.1, out);
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(Self::Value, usize), CodecError> {
+        let (v1, n1) = self.0.decode(bytes)?;
+        let (v2, n2) = self.1.decode(&bytes[n1..]).map_err(|e| offset_err(e, n1))?;
+
+        Ok(((v1, v2), n1 + n2))
+    }
+}
+
+impl<D: ValueCodec> ValueCodec for TwoSpace<D> {
+    fn encode(&self, val: &[D::Value; 2], out: &mut Vec<u8>) {
+        self[0].encode(&val[0], out);
+        self[1].encode(&val[1], out);
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(Self::Value, usize), CodecError> {
+        let (v1, n1) = self[0].decode(bytes)?;
+        let (v2, n2) = self[1].decode(&bytes[n1..]).map_err(|e| offset_err(e, n1))?;
+
+        Ok(([v1, v2], n1 + n2))
+    }
+}
+
+/// # Panics
+///
+/// Panics on encoding a value that does not have one entry per component of the product.
+impl<D: ValueCodec> ValueCodec for ProductSpace<D> {
+    fn encode(&self, val: &Vec<D::Value>, out: &mut Vec<u8>) {
+        assert_eq!(
+            val.len(),
+            self.iter().len(),
+            "Value does not match the dimension of the space."
+        );
+
+        for (d, v) in self.iter().zip(val.iter()) {
+            d.encode(v, out);
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(Self::Value, usize), CodecError> {
+        let mut offset = 0;
+
+        let vals = self
+            .iter()
+            .map(|d| {
+                let (v, n) = d.decode(&bytes[offset..]).map_err(|e| offset_err(e, offset))?;
+
+                offset += n;
+
+                Ok(v)
+            })
+            .collect::<Result<Vec<_>, CodecError>>()?;
+
+        Ok((vals, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<S: ValueCodec>(space: &S, val: S::Value) -> usize
+    where S::Value: PartialEq + fmt::Debug {
+        let mut buf = vec![];
+
+        space.encode(&val, &mut buf);
+
+        assert_eq!(space.decode(&buf), Ok((val, buf.len())));
+
+        buf.len()
+    }
+
+    #[test]
+    fn test_varint() {
+        for &v in &[0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            let mut buf = vec![];
+
+            encode_varint(v, &mut buf);
+
+            assert_eq!(decode_varint(&buf), Ok((v, buf.len())));
+        }
+
+        assert_eq!(decode_varint(&[0x80]), Err(CodecError::UnexpectedEof));
+        assert_eq!(decode_varint(&[0xff; 11]), Err(CodecError::VarintOverflow));
+    }
+
+    #[test]
+    fn test_zigzag() {
+        for &v in &[0, 1, -1, 63, -64, i64::MAX, i64::MIN] {
+            assert_eq!(unzigzag(zigzag(v)), v);
+        }
+
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(round_trip(&Empty, ()), 0);
+    }
+
+    #[test]
+    fn test_binary() {
+        assert_eq!(round_trip(&Binary, false), 1);
+        assert_eq!(round_trip(&Binary, true), 1);
+        assert_eq!(Binary.decode(&[2]), Err(CodecError::InvalidValue(0)));
+        assert_eq!(Binary.decode(&[]), Err(CodecError::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let d = Ordinal::new(4);
+
+        for v in 0..4 {
+            assert_eq!(round_trip(&d, v), 1);
+        }
+
+        assert_eq!(round_trip(&Ordinal::new(1000), 999), 2);
+        assert_eq!(d.decode(&[4]), Err(CodecError::InvalidValue(0)));
+    }
+
+    #[test]
+    fn test_equipartition() {
+        let d = Equipartition::new(0.0, 1.0, 10);
+
+        assert_eq!(round_trip(&d, 9), 1);
+        assert_eq!(d.decode(&[10]), Err(CodecError::InvalidValue(0)));
+    }
+
+    #[test]
+    fn test_quantised() {
+        let d = Quantised::new(0.0, 1.0, 0.1);
+
+        assert_eq!(round_trip(&d, 0.30000000000000004), 1);
+        assert_eq!(d.decode(&[11]), Err(CodecError::InvalidValue(0)));
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(round_trip(&Integers, -1), 1);
+        assert_eq!(round_trip(&Integers, i64::MIN), 10);
+        assert_eq!(round_trip(&Interval::bounded(-5i64, 5i64), -5), 1);
+        assert_eq!(round_trip(&Naturals, 1), 1);
+        assert_eq!(round_trip(&NonNegativeIntegers, u64::MAX), 10);
+
+        assert_eq!(Naturals.decode(&[0]), Err(CodecError::InvalidValue(0)));
+        assert_eq!(NonZeroIntegers.decode(&[0]), Err(CodecError::InvalidValue(0)));
+    }
+
+    #[test]
+    fn test_reals() {
+        assert_eq!(round_trip(&Reals, -1.5), 8);
+        assert_eq!(round_trip(&Interval::bounded(0.0, 1.0), 0.1), 8);
+        assert_eq!(round_trip(&SinglePrecision(Interval::bounded(0.0, 1.0)), 0.5), 4);
+
+        let mut buf = vec![];
+
+        SinglePrecision(Reals).encode(&0.1, &mut buf);
+
+        assert_eq!(SinglePrecision(Reals).decode(&buf), Ok((f64::from(0.1f32), 4)));
+        assert_eq!(Reals.decode(&buf), Err(CodecError::UnexpectedEof));
+
+        // The nearest f32 to 0.1 lies above it, and so outside [0, 0.1].
+        let space = SinglePrecision(Interval::bounded(0.0, 0.1));
+        let above = (0.1f32).to_le_bytes();
+
+        assert_eq!(space.decode(&above), Err(CodecError::InvalidValue(0)));
+
+        buf.clear();
+        space.encode(&0.1, &mut buf);
+
+        let (val, _) = space.decode(&buf).unwrap();
+
+        assert!(val < 0.1 && f64::from(f32::from_le_bytes(above)) > 0.1);
+
+        for space in [Interval::open(-0.1, 0.1), Interval::bounded(-0.3, 0.0)] {
+            let space = SinglePrecision(space);
+
+            for x in [-0.3, -0.1, 0.0, 0.1] {
+                if space.contains(x) {
+                    buf.clear();
+                    space.encode(&x, &mut buf);
+
+                    assert!(space.decode(&buf).is_ok());
+                }
+            }
+        }
+
+        let encoded = |x: f64| x.to_le_bytes().to_vec();
+
+        let invalid = Err(CodecError::InvalidValue(0));
+
+        assert_eq!(Interval::bounded(0.0, 1.0).decode(&encoded(1.5)), invalid);
+        assert_eq!(Interval::open(0.0, 1.0).decode(&encoded(0.0)), invalid);
+        assert_eq!(Interval::bounded(0.0, 1.0).decode(&encoded(f64::NAN)), invalid);
+        assert_eq!(PositiveReals.decode(&encoded(0.0)), invalid);
+        assert_eq!(NonNegativeReals.decode(&encoded(-1.0)), invalid);
+        assert_eq!(Reals.decode(&encoded(1e300)), Ok((1e300, 8)));
+    }
+
+    #[test]
+    fn test_composites() {
+        assert_eq!(round_trip(&PairSpace::new(Ordinal::new(4), Reals), (2, 1.0)), 9);
+        assert_eq!(round_trip(&TwoSpace::new([Binary, Binary]), [true, false]), 2);
+
+        let space = ProductSpace::new(vec![SinglePrecision(Interval::<f64>::unit()); 3]);
+
+        assert_eq!(round_trip(&space, vec![0.0, 0.5, 1.0]), 12);
+    }
+
+    #[test]
+    fn test_composite_offsets() {
+        let space = ProductSpace::new(vec![Ordinal::new(4); 3]);
+
+        assert_eq!(space.decode(&[0, 1, 4]), Err(CodecError::InvalidValue(2)));
+        assert_eq!(space.decode(&[0, 1]), Err(CodecError::UnexpectedEof));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value does not match the dimension of the space.")]
+    fn test_product_length() {
+        ProductSpace::new(vec![Ordinal::new(4); 3]).encode(&vec![0, 1], &mut vec![]);
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(SinglePrecision(Interval::<f64>::unit()); sample, bounds, shape, serde);
+    }
+}
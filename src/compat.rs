@@ -0,0 +1,409 @@
+//! Compatibility checks between pairs of spaces.
+//!
+//! These utilities are intended for validating the wiring of space
+//! transformations (e.g. environment wrappers) before any values are mapped.
+//! A [`CompatReport`] lists every mismatch found between a source space and a
+//! target space, each tagged with a [`Severity`].
+//!
+//! With the `serialize` feature, [`upgrade_json`] also rewrites spaces serialised
+//! by older forks of this crate to the current schema.
+use crate::{prelude::*, ApproxEq};
+use std::{any::TypeId, fmt};
+
+/// Severity of a detected mismatch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The mapping is ill-defined and will likely misbehave at runtime.
+    Error,
+    /// The mapping is well-defined but may lose information.
+    Warning,
+}
+
+/// The property of the spaces in which a mismatch was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MismatchKind {
+    Dim,
+    Card,
+    Bounds,
+    ValueType,
+}
+
+/// A single incompatibility between a source and target space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub kind: MismatchKind,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Structured report of all mismatches between two spaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompatReport {
+    mismatches: Vec<Mismatch>,
+}
+
+impl CompatReport {
+    pub fn new() -> CompatReport { CompatReport::default() }
+
+    pub fn push(&mut self, kind: MismatchKind, severity: Severity, message: String) {
+        self.mismatches.push(Mismatch { kind, severity, message, });
+    }
+
+    pub fn mismatches(&self) -> &[Mismatch] { &self.mismatches }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Mismatch> {
+        self.mismatches.iter().filter(|m| m.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Mismatch> {
+        self.mismatches.iter().filter(|m| m.severity == Severity::Warning)
+    }
+
+    /// Returns true iff no mismatches of any severity were found.
+    pub fn is_empty(&self) -> bool { self.mismatches.is_empty() }
+
+    /// Returns true iff no mismatches of severity `Error` were found.
+    pub fn is_compatible(&self) -> bool { self.errors().next().is_none() }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, m) in self.mismatches.iter().enumerate() {
+            if i != 0 { writeln!(f)?; }
+
+            match m.severity {
+                Severity::Error => write!(f, "error: {}", m.message)?,
+                Severity::Warning => write!(f, "warning: {}", m.message)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_card(report: &mut CompatReport, from: Card, to: Card) {
+    match (from, to) {
+        (Card::Infinite, Card::Finite(_)) => report.push(
+            MismatchKind::Card, Severity::Warning,
+            "target card smaller than source card: mapping cannot be injective".to_owned(),
+        ),
+        (Card::Finite(_), Card::Infinite) => report.push(
+            MismatchKind::Card, Severity::Error,
+            "source card smaller than target card: mapping cannot be surjective".to_owned(),
+        ),
+        (Card::Finite(s), Card::Finite(t)) if t < s => report.push(
+            MismatchKind::Card, Severity::Warning,
+            format!(
+                "target card smaller than source card ({} < {}): mapping cannot be injective",
+                t, s
+            ),
+        ),
+        (Card::Finite(s), Card::Finite(t)) if t > s => report.push(
+            MismatchKind::Card, Severity::Error,
+            format!(
+                "source card smaller than target card ({} < {}): mapping cannot be surjective",
+                s, t
+            ),
+        ),
+        _ => {},
+    }
+}
+
+/// Compare the `dim`, `card` and value types of two spaces.
+///
+/// ```
+/// use spaces::{compat::check_surjection_compat, discrete::Ordinal};
+///
+/// let report = check_surjection_compat(&Ordinal::new(5), &Ordinal::new(2));
+///
+/// assert!(report.is_compatible());
+/// assert_eq!(report.warnings().count(), 1);
+/// ```
+pub fn check_surjection_compat<S1, S2>(from: &S1, to: &S2) -> CompatReport
+where
+    S1: Space,
+    S2: Space,
+    S1::Value: 'static,
+    S2::Value: 'static,
+{
+    let mut report = CompatReport::new();
+
+    let (dim_from, dim_to) = (from.dim(), to.dim());
+
+    if dim_from != dim_to {
+        report.push(
+            MismatchKind::Dim, Severity::Error,
+            format!("target dim {:?} differs from source dim {:?}", dim_to, dim_from),
+        );
+    }
+
+    check_card(&mut report, from.card(), to.card());
+
+    if TypeId::of::<S1::Value>() != TypeId::of::<S2::Value>() {
+        report.push(
+            MismatchKind::ValueType, Severity::Warning,
+            format!(
+                "value types differ: {} -> {}",
+                std::any::type_name::<S1::Value>(),
+                std::any::type_name::<S2::Value>()
+            ),
+        );
+    }
+
+    report
+}
+
+/// As `check_surjection_compat`, but additionally compares the bounds of two spaces sharing a
+/// value type.
+///
+/// Any region of the source space lying outside the bounds of the target is reported as a
+/// warning since those values will be clipped by the mapping.
+pub fn check_bounded_compat<S1, S2>(from: &S1, to: &S2) -> CompatReport
+where
+    S1: BoundedSpace,
+    S2: BoundedSpace<Value = S1::Value>,
+    S1::Value: PartialOrd + fmt::Debug + 'static,
+{
+    check_bounds(check_surjection_compat(from, to), from, to, |_, _| false)
+}
+
+/// As `check_bounded_compat`, but treating bounds that agree to within `eps` as equal.
+///
+/// This should be preferred for spaces over floats that may have been deserialised from a lossy
+/// format, where a bound of `0.1` can come back as `0.1000001`; see `ApproxEq`.
+/// ```
+/// use spaces::{compat::{check_bounded_compat, check_bounded_compat_approx}, real::Interval};
+///
+/// let (from, to) = (Interval::bounded(0.0, 0.1 + 0.2), Interval::bounded(0.0, 0.3));
+///
+/// assert_eq!(check_bounded_compat(&from, &to).warnings().count(), 1);
+/// assert!(check_bounded_compat_approx(&from, &to, 1e-9).is_empty());
+/// ```
+pub fn check_bounded_compat_approx<S1, S2>(from: &S1, to: &S2, eps: f64) -> CompatReport
+where
+    S1: BoundedSpace,
+    S2: BoundedSpace<Value = S1::Value>,
+    S1::Value: ApproxEq + PartialOrd + fmt::Debug + 'static,
+{
+    check_bounds(check_surjection_compat(from, to), from, to, |s, t| s.approx_eq(t, eps))
+}
+
+// Appends the bound mismatches between two spaces to `report`, skipping any bounds for which
+// `same` holds.
+fn check_bounds<S1, S2, F>(mut report: CompatReport, from: &S1, to: &S2, same: F) -> CompatReport
+where
+    S1: BoundedSpace,
+    S2: BoundedSpace<Value = S1::Value>,
+    S1::Value: PartialOrd + fmt::Debug,
+    F: Fn(&S1::Value, &S1::Value) -> bool,
+{
+    match (from.inf(), to.inf()) {
+        (None, Some(t)) => report.push(
+            MismatchKind::Bounds, Severity::Warning,
+            format!("source is unbounded below but target infimum is {:?}", t),
+        ),
+        (Some(s), Some(t)) if s < t && !same(&s, &t) => report.push(
+            MismatchKind::Bounds, Severity::Warning,
+            format!("source infimum {:?} lies below target infimum {:?}", s, t),
+        ),
+        _ => {},
+    }
+
+    match (from.sup(), to.sup()) {
+        (None, Some(t)) => report.push(
+            MismatchKind::Bounds, Severity::Warning,
+            format!("source is unbounded above but target supremum is {:?}", t),
+        ),
+        (Some(s), Some(t)) if s > t && !same(&s, &t) => report.push(
+            MismatchKind::Bounds, Severity::Warning,
+            format!("source supremum {:?} lies above target supremum {:?}", s, t),
+        ),
+        _ => {},
+    }
+
+    report
+}
+
+// Legacy names of the bounds of an interval, and the names they are upgraded to.
+#[cfg(feature = "serialize")]
+const LEGACY_BOUNDS: [(&str, &str); 4] =
+    [("lo", "lb"), ("left", "lb"), ("hi", "ub"), ("right", "ub")];
+
+/// Rewrite a JSON document holding spaces serialised by an older fork of this crate to the
+/// current schema.
+///
+/// Legacy documents name the bounds of intervals `lo`/`hi` or `left`/`right` rather than
+/// `lb`/`ub`, and write ordinal spaces as `{"size": n}` or `{"n": n}` rather than as `n`. Both
+/// are accepted directly when deserialising from a self-describing format, but not by positional
+/// formats such as bincode; documents upgraded here can be re-encoded in any format. Anything
+/// that does not match a legacy layout, including documents already in the current schema, is
+/// left unchanged.
+/// ```
+/// extern crate serde_json;
+/// extern crate spaces;
+///
+/// use serde_json::json;
+/// use spaces::compat::upgrade_json;
+///
+/// let legacy = json!({"observation_space": [{"lo": -1.0, "hi": 1.0}], "action_space": {"n": 3}});
+///
+/// assert_eq!(
+///     upgrade_json(legacy),
+///     json!({"observation_space": [{"lb": -1.0, "ub": 1.0}], "action_space": 3}),
+/// );
+/// ```
+#[cfg(feature = "serialize")]
+pub fn upgrade_json(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Array(vals) => Value::Array(vals.into_iter().map(upgrade_json).collect()),
+        Value::Object(map) => {
+            let size = map.get("size").or_else(|| map.get("n"));
+
+            if let Some(size) = size.filter(|v| map.len() == 1 && v.is_u64()) {
+                return size.clone();
+            }
+
+            let is_bound = |k: &str| {
+                k == "lb" || k == "ub" || k == "lb_kind" || k == "ub_kind"
+                    || LEGACY_BOUNDS.iter().any(|&(old, _)| k == old)
+            };
+            let is_interval = !map.is_empty() && map.keys().all(|k| is_bound(k));
+
+            Value::Object(map.into_iter().map(|(k, v)| {
+                match LEGACY_BOUNDS.iter().find(|&&(old, _)| is_interval && k == old) {
+                    Some(&(_, new)) => (new.to_owned(), v),
+                    None => (k, upgrade_json(v)),
+                }
+            }).collect())
+        },
+        _ => value,
+    }
+}
+
+
+/// Assert that two spaces are compatible, panicking with the report otherwise.
+///
+/// Only mismatches of severity `Error` cause a failure; warnings are ignored.
+///
+/// ```
+/// # #[macro_use] extern crate spaces;
+/// # fn main() {
+/// use spaces::discrete::Ordinal;
+///
+/// assert_compatible!(Ordinal::new(5), Ordinal::new(5));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_compatible {
+    ($from:expr, $to:expr) => {{
+        let report = $crate::compat::check_surjection_compat(&$from, &$to);
+
+        if !report.is_compatible() {
+            panic!("spaces are not compatible:\n{}", report);
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval, PairSpace};
+    use super::*;
+
+    #[test]
+    fn test_compatible() {
+        let report = check_surjection_compat(&Ordinal::new(5), &Ordinal::new(5));
+
+        assert!(report.is_empty());
+        assert!(report.is_compatible());
+
+        let report = check_bounded_compat(
+            &Interval::bounded(0.0, 1.0),
+            &Interval::bounded(-1.0, 1.0),
+        );
+
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_dim_mismatch() {
+        let report = check_surjection_compat(
+            &PairSpace::new(Ordinal::new(2), Ordinal::new(2)),
+            &Ordinal::new(4),
+        );
+        let errors: Vec<_> = report.errors().collect();
+
+        assert!(!report.is_compatible());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, MismatchKind::Dim);
+    }
+
+    #[test]
+    fn test_card_shrinkage() {
+        let report = check_surjection_compat(&Ordinal::new(5), &Ordinal::new(2));
+
+        assert!(report.is_compatible());
+        assert_eq!(report.mismatches(), &[Mismatch {
+            kind: MismatchKind::Card,
+            severity: Severity::Warning,
+            message: "target card smaller than source card (2 < 5): mapping cannot be injective"
+                .to_owned(),
+        }]);
+    }
+
+    #[test]
+    fn test_card_growth() {
+        let report = check_surjection_compat(&Ordinal::new(2), &Ordinal::new(5));
+
+        assert!(!report.is_compatible());
+        assert_eq!(report.errors().next().unwrap().kind, MismatchKind::Card);
+    }
+
+    #[test]
+    fn test_value_type() {
+        let report = check_surjection_compat(&Interval::bounded(0.0, 1.0), &Ordinal::new(5));
+
+        assert!(report.is_compatible());
+        assert!(report.warnings().any(|m| m.kind == MismatchKind::ValueType));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let report = check_bounded_compat(
+            &Interval::left_bounded(-1.0),
+            &Interval::bounded(0.0, 1.0),
+        );
+
+        assert!(report.is_compatible());
+        assert_eq!(report.warnings().filter(|m| m.kind == MismatchKind::Bounds).count(), 2);
+    }
+
+    #[test]
+    fn test_bounds_approx() {
+        let (from, to) = (Interval::bounded(0.3, 2.1), Interval::bounded(0.1 * 3.0, 0.7 * 3.0));
+
+        assert_eq!(check_bounded_compat(&from, &to).warnings().count(), 2);
+        assert!(check_bounded_compat_approx(&from, &to, 1e-9).is_empty());
+
+        let report = check_bounded_compat_approx(
+            &Interval::bounded(0.0, 1.0 + 1e-6),
+            &Interval::bounded(0.0, 1.0),
+            1e-9,
+        );
+
+        assert_eq!(report.warnings().filter(|m| m.kind == MismatchKind::Bounds).count(), 1);
+    }
+
+    #[test]
+    fn test_assert_compatible() {
+        assert_compatible!(Ordinal::new(5), Ordinal::new(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_compatible_panics() {
+        assert_compatible!(PairSpace::new(Ordinal::new(2), Ordinal::new(2)), Ordinal::new(4));
+    }
+}
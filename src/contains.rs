@@ -0,0 +1,24 @@
+use std::{error::Error, fmt};
+
+/// Error returned by batched membership checks, identifying the first value not in the space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainsError {
+    /// Position of the offending value in the batch.
+    pub index: usize,
+    /// `Debug` rendering of the offending value.
+    pub value: String,
+}
+
+impl ContainsError {
+    pub fn new<T: fmt::Debug + ?Sized>(index: usize, value: &T) -> ContainsError {
+        ContainsError { index, value: format!("{:?}", value), }
+    }
+}
+
+impl fmt::Display for ContainsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value {} at index {} is not contained in the space", self.value, self.index)
+    }
+}
+
+impl Error for ContainsError {}
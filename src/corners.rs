@@ -0,0 +1,222 @@
+use crate::{prelude::*, ProductSpace};
+use std::{error::Error, fmt, iter::FusedIterator};
+
+/// Maximum number of components over which `ProductSpace::corners` enumerates by default, such
+/// that at most `2^CORNER_LIMIT` corners are produced.
+pub const CORNER_LIMIT: usize = 24;
+
+/// Error type for products whose corners cannot be enumerated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CornerError {
+    /// The component at `index` is unbounded below or above.
+    Unbounded { index: usize },
+    /// The product has more than `limit` components.
+    TooManyComponents { n_components: usize, limit: usize },
+}
+
+impl fmt::Display for CornerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CornerError::Unbounded { index } =>
+                write!(f, "component {} is unbounded and has no extreme values", index),
+            CornerError::TooManyComponents { n_components, limit } => write!(
+                f, "refusing to enumerate the corners of {} components (limit is {})",
+                n_components, limit
+            ),
+        }
+    }
+}
+
+impl Error for CornerError {}
+
+/// Lazy iterator over the corners of a product space; see `ProductSpace::corners`.
+#[derive(Clone, Debug)]
+pub struct Corners<V> {
+    bounds: Extremes<V>,
+    next: u64,
+    end: u64,
+}
+
+impl<V: Clone> Iterator for Corners<V> {
+    type Item = Vec<V>;
+
+    fn next(&mut self) -> Option<Vec<V>> {
+        if self.next >= self.end { return None; }
+
+        let bits = self.next;
+
+        self.next += 1;
+
+        Some(self.bounds.iter().enumerate().map(|(i, (lb, ub))| {
+            if bits & (1 << i) == 0 { lb.clone() } else { ub.clone() }
+        }).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.end - self.next) as usize;
+
+        (n, Some(n))
+    }
+}
+
+impl<V: Clone> ExactSizeIterator for Corners<V> {}
+
+impl<V: Clone> FusedIterator for Corners<V> {}
+
+// Infimum and supremum of each component of a product.
+type Extremes<V> = Vec<(V, V)>;
+
+impl<D: BoundedSpace> ProductSpace<D>
+where D::Value: PartialOrd
+{
+    fn extremes(&self) -> Result<Extremes<D::Value>, CornerError> {
+        self.iter().enumerate().map(|(index, d)| match (d.inf(), d.sup()) {
+            (Some(lb), Some(ub)) => Ok((lb, ub)),
+            _ => Err(CornerError::Unbounded { index, }),
+        }).collect()
+    }
+
+    /// Returns a lazy iterator over the `2^n` corners of the product, where `n` is the number of
+    /// components.
+    ///
+    /// Each corner takes either the infimum or the supremum of every component; the first
+    /// component varies fastest, starting from the infimum. Corners are not contained in the
+    /// space if any component excludes its bounds, as for open intervals. Returns an error if any
+    /// component is unbounded, or if there are more than `CORNER_LIMIT` components; see
+    /// `corners_capped`.
+    /// ```
+    /// use spaces::{real::Interval, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Interval::unit(), Interval::symmetric_unit()]);
+    /// let corners: Vec<_> = space.corners().unwrap().collect();
+    ///
+    /// assert_eq!(corners, vec![
+    ///     vec![0.0, -1.0],
+    ///     vec![1.0, -1.0],
+    ///     vec![0.0, 1.0],
+    ///     vec![1.0, 1.0],
+    /// ]);
+    /// ```
+    pub fn corners(&self) -> Result<Corners<D::Value>, CornerError> {
+        self.corners_capped(CORNER_LIMIT)
+    }
+
+    /// As `corners`, but enumerating over at most `limit` components, with `limit` itself capped
+    /// at 63.
+    pub fn corners_capped(&self, limit: usize) -> Result<Corners<D::Value>, CornerError> {
+        let n_components = self.iter().len();
+        let limit = limit.min(63);
+
+        if n_components > limit {
+            return Err(CornerError::TooManyComponents { n_components, limit, });
+        }
+
+        Ok(Corners { bounds: self.extremes()?, next: 0, end: 1 << n_components, })
+    }
+
+    /// Returns every distinct combination of the infimum and supremum of each component, in the
+    /// same order as `corners`.
+    ///
+    /// Unlike `corners`, components whose infimum and supremum coincide contribute a single
+    /// value, and only the remaining components count towards `CORNER_LIMIT`.
+    /// ```
+    /// use spaces::{discrete::Ordinal, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(1), Ordinal::new(2)]);
+    ///
+    /// assert_eq!(space.extreme_values().unwrap(), vec![
+    ///     vec![0, 0, 0],
+    ///     vec![2, 0, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![2, 0, 1],
+    /// ]);
+    /// ```
+    pub fn extreme_values(&self) -> Result<Vec<Vec<D::Value>>, CornerError> {
+        let extremes = self.extremes()?;
+        let varying: Vec<usize> = (0..extremes.len()).filter(|&i| {
+            extremes[i].0 != extremes[i].1
+        }).collect();
+
+        if varying.len() > CORNER_LIMIT {
+            return Err(CornerError::TooManyComponents {
+                n_components: varying.len(),
+                limit: CORNER_LIMIT,
+            });
+        }
+
+        Ok((0..1u64 << varying.len()).map(|bits| {
+            let mut val: Vec<D::Value> = extremes.iter().map(|(lb, _)| lb.clone()).collect();
+
+            for (j, &i) in varying.iter().enumerate() {
+                if bits & (1 << j) != 0 { val[i] = extremes[i].1.clone(); }
+            }
+
+            val
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval};
+    use super::*;
+
+    #[test]
+    fn test_corners() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-2.0, 2.0),
+            Interval::bounded(5.0, 6.0),
+        ]);
+        let corners = space.corners().unwrap();
+
+        assert_eq!(corners.len(), 8);
+
+        let corners: Vec<_> = corners.collect();
+
+        for (i, c) in corners.iter().enumerate() {
+            assert!(space.contains(c));
+            assert!(corners[..i].iter().all(|other| other != c));
+        }
+
+        assert_eq!(corners[0], vec![0.0, -2.0, 5.0]);
+        assert_eq!(corners[7], vec![1.0, 2.0, 6.0]);
+
+        let empty: Vec<_> = ProductSpace::<Interval>::empty().corners().unwrap().collect();
+
+        assert_eq!(empty, vec![Vec::<f64>::new()]);
+    }
+
+    #[test]
+    fn test_corner_errors() {
+        let space = ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(0.0)]);
+
+        assert_eq!(space.corners().err(), Some(CornerError::Unbounded { index: 1 }));
+        assert_eq!(space.extreme_values(), Err(CornerError::Unbounded { index: 1 }));
+
+        let space = ProductSpace::new(vec![Interval::<f64>::unit(); 60]);
+
+        assert_eq!(
+            space.corners().err(),
+            Some(CornerError::TooManyComponents { n_components: 60, limit: CORNER_LIMIT })
+        );
+        assert_eq!(space.corners_capped(100).unwrap().size_hint().0, 1 << 60);
+        assert_eq!(
+            ProductSpace::new(vec![Interval::<f64>::unit(); 64]).corners_capped(100).err(),
+            Some(CornerError::TooManyComponents { n_components: 64, limit: 63 })
+        );
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let space = ProductSpace::new(vec![Ordinal::new(1); 40]);
+
+        assert_eq!(space.extreme_values().unwrap(), vec![vec![0; 40]]);
+
+        let space = ProductSpace::new(vec![Interval::bounded(1.0, 1.0), Interval::unit()]);
+
+        assert_eq!(space.extreme_values().unwrap(), vec![vec![1.0, 0.0], vec![1.0, 1.0]]);
+        assert_eq!(space.corners().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{prelude::*, BoundKind, Interval, PairSpace, ProductSpace, TwoSpace};
+
+/// Trait for spaces whose values can be differenced, e.g. for models that predict the change
+/// `s' - s` of an observation rather than `s'` itself.
+///
+/// The differences of values of a space lie in its `delta_space`, the Minkowski difference of the
+/// space with itself; `apply_delta` adds a difference back onto a value, projecting the result
+/// onto the original space, such that `apply_delta(b, delta(a, b))` recovers `a`.
+/// ```
+/// use spaces::{real::Interval, Delta, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(-2.0, 4.0)]);
+/// let d = space.delta(&vec![0.75, 1.0], &vec![0.25, 3.0]);
+///
+/// assert_eq!(space.delta_space(), ProductSpace::new(vec![
+///     Interval::bounded(-1.0, 1.0),
+///     Interval::bounded(-6.0, 6.0),
+/// ]));
+/// assert_eq!(d, vec![0.5, -2.0]);
+/// assert_eq!(space.apply_delta(&vec![0.25, 3.0], &d), vec![0.75, 1.0]);
+/// assert_eq!(space.apply_delta(&vec![0.75, 3.0], &d), vec![1.0, 1.0]);
+/// ```
+pub trait Delta: Space + Sized {
+    /// Returns the space of differences between values of the space.
+    fn delta_space(&self) -> Self;
+
+    /// Returns the difference `a - b` of two values of the space.
+    fn delta(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+
+    /// Returns `a + d`, projected onto the space.
+    fn apply_delta(&self, a: &Self::Value, d: &Self::Value) -> Self::Value;
+}
+
+// Kind of both bounds of the difference of an interval with itself: the extreme differences are
+// attained only if both bounds are.
+fn delta_kind(lb_kind: BoundKind, ub_kind: BoundKind) -> BoundKind {
+    if lb_kind == BoundKind::Open || ub_kind == BoundKind::Open {
+        BoundKind::Open
+    } else {
+        BoundKind::Closed
+    }
+}
+
+/// Differences lie in `[lb - ub, ub - lb]`, which is unbounded if either bound is missing or the
+/// width of the interval overflows; the delta space of an empty interval is itself.
+impl Delta for Interval<f64> {
+    fn delta_space(&self) -> Interval<f64> {
+        if self.is_empty() { return *self; }
+
+        let kind = delta_kind(self.lb_kind, self.ub_kind);
+        let width = match (self.lb, self.ub) {
+            (Some(lb), Some(ub)) if (ub - lb).is_finite() => Some(ub - lb),
+            _ => None,
+        };
+
+        Interval::new(width.map(|w| -w), width).with_kinds(kind, kind)
+    }
+
+    fn delta(&self, a: &f64, b: &f64) -> f64 { a - b }
+
+    fn apply_delta(&self, a: &f64, d: &f64) -> f64 { self.map_onto(a + d) }
+}
+
+/// Differences lie in `[lo - hi, hi - lo]`, where `lo` and `hi` are the smallest and largest
+/// values of the interval, which is unbounded if either bound is missing or the width does not fit
+/// in an `i64`; differences themselves saturate at the extremes of `i64`. The delta space of an
+/// empty interval is itself.
+impl Delta for Interval<i64> {
+    fn delta_space(&self) -> Interval<i64> {
+        if self.is_empty() { return *self; }
+
+        let width = match self.tight_bounds() {
+            (Some(lo), Some(hi)) if hi - lo <= i64::MAX as i128 => Some((hi - lo) as i64),
+            _ => None,
+        };
+
+        Interval::new(width.map(|w| -w), width)
+    }
+
+    fn delta(&self, a: &i64, b: &i64) -> i64 { a.saturating_sub(*b) }
+
+    fn apply_delta(&self, a: &i64, d: &i64) -> i64 { self.map_onto(a.saturating_add(*d)) }
+}
+
+/// Components are differenced independently.
+///
+/// # Panics
+///
+/// Panics if either value does not have one entry per component.
+impl<D: Delta> Delta for ProductSpace<D> {
+    fn delta_space(&self) -> ProductSpace<D> {
+        ProductSpace::new(self.iter().map(|d| d.delta_space()).collect())
+    }
+
+    fn delta(&self, a: &Vec<D::Value>, b: &Vec<D::Value>) -> Vec<D::Value> {
+        assert_eq!(a.len(), self.iter().len(), "Value does not match the dimension of the space.");
+        assert_eq!(b.len(), self.iter().len(), "Value does not match the dimension of the space.");
+
+        self.iter().zip(a.iter().zip(b.iter())).map(|(d, (a, b))| d.delta(a, b)).collect()
+    }
+
+    fn apply_delta(&self, a: &Vec<D::Value>, d: &Vec<D::Value>) -> Vec<D::Value> {
+        assert_eq!(a.len(), self.iter().len(), "Value does not match the dimension of the space.");
+        assert_eq!(d.len(), self.iter().len(), "Value does not match the dimension of the space.");
+
+        self.iter().zip(a.iter().zip(d.iter())).map(|(s, (a, d))| s.apply_delta(a, d)).collect()
+    }
+}
+
+/// Components are differenced independently.
+impl<D: Delta> Delta for TwoSpace<D> {
+    fn delta_space(&self) -> TwoSpace<D> {
+        TwoSpace::new([self[0].delta_space(), self[1].delta_space()])
+    }
+
+    fn delta(&self, a: &[D::Value; 2], b: &[D::Value; 2]) -> [D::Value; 2] {
+        [self[0].delta(&a[0], &b[0]), self[1].delta(&a[1], &b[1])]
+    }
+
+    fn apply_delta(&self, a: &[D::Value; 2], d: &[D::Value; 2]) -> [D::Value; 2] {
+        [self[0].apply_delta(&a[0], &d[0]), self[1].apply_delta(&a[1], &d[1])]
+    }
+}
+
+/// Components are differenced independently.
+impl<D1: Delta, D2: Delta> Delta for PairSpace<D1, D2> {
+    fn delta_space(&self) -> PairSpace<D1, D2> {
+        PairSpace::new(self.0.delta_space(), self.1.delta_space())
+    }
+
+    fn delta(
+        &self,
+        a: &(D1::Value, D2::Value),
+        b: &(D1::Value, D2::Value),
+    ) -> (D1::Value, D2::Value) {
+        (self.0.delta(&a.0, &b.0), self.1.delta(&a.1, &b.1))
+    }
+
+    fn apply_delta(
+        &self,
+        a: &(D1::Value, D2::Value),
+        d: &(D1::Value, D2::Value),
+    ) -> (D1::Value, D2::Value) {
+        (self.0.apply_delta(&a.0, &d.0), self.1.apply_delta(&a.1, &d.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    // Checks, for random pairs of values, that the difference lies in the delta space and that
+    // applying it recovers the original value to within `tol`.
+    fn check_f64(space: &Interval<f64>, tol: f64) {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let delta_space = space.delta_space();
+
+        for _ in 0..1000 {
+            let (a, b) = (space.sample(&mut rng), space.sample(&mut rng));
+            let d = space.delta(&b, &a);
+
+            assert!(delta_space.contains(d), "{} not in {}", d, delta_space);
+            assert!((space.apply_delta(&a, &d) - b).abs() <= tol, "{} + {} != {}", a, d, b);
+        }
+    }
+
+    #[test]
+    fn test_interval_f64() {
+        check_f64(&Interval::bounded(0.0, 1.0), 1e-15);
+        check_f64(&Interval::bounded(-3.0, 7.5), 1e-14);
+        check_f64(&Interval::open(0.1, 0.7), 1e-15);
+        check_f64(&Interval::bounded(-f64::MAX / 2.0, f64::MAX / 2.0), 1e-15 * f64::MAX);
+
+        assert_eq!(Interval::bounded(1.0, 3.0).delta_space(), Interval::bounded(-2.0, 2.0));
+        assert_eq!(Interval::open(1.0, 3.0).delta_space(), Interval::open(-2.0, 2.0));
+        assert_eq!(
+            Interval::bounded(1.0, 3.0).with_kinds(BoundKind::Closed, BoundKind::Open)
+                .delta_space(),
+            Interval::open(-2.0, 2.0)
+        );
+        assert_eq!(Interval::left_bounded(0.0).delta_space(), Interval::unbounded());
+        assert_eq!(Interval::bounded(-f64::MAX, f64::MAX).delta_space(), Interval::unbounded());
+        assert!(Interval::open(0.0, 0.0).delta_space().is_empty());
+
+        assert_eq!(Interval::bounded(0.0, 1.0).apply_delta(&0.5, &0.75), 1.0);
+        assert_eq!(Interval::bounded(0.0, 1.0).apply_delta(&0.5, &-0.75), 0.0);
+        assert!(Interval::open(0.0, 1.0).apply_delta(&0.5, &0.75) < 1.0);
+    }
+
+    #[test]
+    fn test_interval_i64() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = Interval::bounded(-5i64, 10).with_kinds(BoundKind::Open, BoundKind::Closed);
+
+        assert_eq!(space.delta_space(), Interval::bounded(-14, 14));
+
+        for _ in 0..1000 {
+            let (a, b) = (space.sample(&mut rng), space.sample(&mut rng));
+            let d = space.delta(&b, &a);
+
+            assert!(space.delta_space().contains(d));
+            assert_eq!(space.apply_delta(&a, &d), b);
+        }
+
+        let full = Interval::bounded(i64::MIN, i64::MAX);
+
+        assert_eq!(full.delta_space(), Interval::unbounded());
+        assert_eq!(
+            Interval::bounded(0i64, i64::MAX).delta_space(),
+            Interval::bounded(-i64::MAX, i64::MAX)
+        );
+        assert_eq!(full.delta(&i64::MAX, &-1), i64::MAX);
+        assert_eq!(full.apply_delta(&i64::MAX, &i64::MAX), i64::MAX);
+        assert_eq!(Interval::bounded(0i64, 3).apply_delta(&2, &-7), 0);
+    }
+
+    #[test]
+    fn test_products() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-4.0, 2.0),
+            Interval::open(100.0, 101.0),
+        ]);
+        let delta_space = space.delta_space();
+
+        for _ in 0..1000 {
+            let (a, b) = (space.sample(&mut rng), space.sample(&mut rng));
+            let d = space.delta(&b, &a);
+            let c = space.apply_delta(&a, &d);
+
+            assert!(delta_space.contains(&d));
+            assert!(c.iter().zip(b.iter()).all(|(c, b)| (c - b).abs() < 1e-12), "{:?}", c);
+        }
+
+        let space = TwoSpace::new([Interval::bounded(0i64, 4), Interval::bounded(1i64, 2)]);
+
+        let delta_space = space.delta_space();
+
+        assert_eq!([delta_space[0], delta_space[1]], [
+            Interval::bounded(-4, 4),
+            Interval::bounded(-1, 1),
+        ]);
+        assert_eq!(space.delta(&[4, 1], &[1, 2]), [3, -1]);
+        assert_eq!(space.apply_delta(&[1, 2], &[3, -1]), [4, 1]);
+        assert_eq!(space.apply_delta(&[1, 2], &[4, 1]), [4, 2]);
+
+        let space = PairSpace::new(Interval::bounded(0i64, 4), Interval::bounded(0.0, 2.0));
+
+        let delta_space = space.delta_space();
+
+        assert_eq!(delta_space.0, Interval::bounded(-4, 4));
+        assert_eq!(delta_space.1, Interval::bounded(-2.0, 2.0));
+        assert_eq!(space.delta(&(3, 0.5), &(1, 2.0)), (2, -1.5));
+        assert_eq!(space.apply_delta(&(1, 2.0), &(2, -1.5)), (3, 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_product() {
+        ProductSpace::new(vec![Interval::<f64>::unit(); 2]).delta(&vec![0.0], &vec![1.0, 0.0]);
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{
+    discrete::{Binary, Ordinal, OrdinalIndex},
+    normal::standard_log_normaliser,
+    prelude::*,
+    Interval,
+    PairSpace,
+    Pmf,
+    ProductSpace,
+    SampleError,
+    SampleWith,
+    Stacked,
+    TruncatedNormal,
+    TwoSpace,
+};
+use rand::distributions::Distribution;
+use std::{collections::VecDeque, f64::consts::{LN_2, PI}};
+
+/// Trait for distributions with a known (log-)density.
+///
+/// The density is taken with respect to the counting measure for discrete values, such that it
+/// is a probability, and the Lebesgue measure for real values.
+pub trait LogDensity<T> {
+    /// Returns the natural logarithm of the density at `val`, which is negative infinity outside
+    /// of the support.
+    fn log_density(&self, val: &T) -> f64;
+}
+
+impl<S: FiniteSpace> LogDensity<S::Value> for Pmf<S>
+where S::Value: PartialEq
+{
+    fn log_density(&self, val: &S::Value) -> f64 { self.log_prob(val) }
+}
+
+impl<S: BoundedSpace<Value = f64>> LogDensity<f64> for TruncatedNormal<S> {
+    /// A degenerate base holding a single value has a log-density of zero at that value, as a
+    /// point mass.
+    fn log_density(&self, val: &f64) -> f64 {
+        let lb = self.base().inf().unwrap_or(f64::NEG_INFINITY);
+        let ub = self.base().sup().unwrap_or(f64::INFINITY);
+
+        if !self.base().contains(*val) { return f64::NEG_INFINITY; }
+        if lb == ub { return 0.0; }
+
+        let (a, b) = ((lb - self.mean()) / self.std(), (ub - self.mean()) / self.std());
+        let z = (val - self.mean()) / self.std();
+
+        -z * z / 2.0 - (2.0 * PI).sqrt().ln() - self.std().ln() - standard_log_normaliser(a, b)
+    }
+}
+
+/// Trait for distributions that can compute the probability they assign to a space.
+pub trait LogMass<S> {
+    /// Returns the natural logarithm of the probability that a draw lies within `space`.
+    fn log_mass(&self, space: &S) -> f64;
+}
+
+impl<P: FiniteSpace, S: BoundedSpace<Value = P::Value>> LogMass<S> for Pmf<P>
+where P::Value: PartialOrd
+{
+    fn log_mass(&self, space: &S) -> f64 {
+        self.space().iter()
+            .zip(self.probs().iter())
+            .filter_map(|(v, &p)| if space.contains(v) { Some(p) } else { None })
+            .sum::<f64>()
+            .ln()
+    }
+}
+
+impl<B, S> LogMass<S> for TruncatedNormal<B>
+where
+    B: BoundedSpace<Value = f64>,
+    S: BoundedSpace<Value = f64>,
+{
+    fn log_mass(&self, space: &S) -> f64 {
+        let lb = self.base().inf().unwrap_or(f64::NEG_INFINITY);
+        let ub = self.base().sup().unwrap_or(f64::INFINITY);
+        let lo = space.inf().map_or(lb, |x| x.max(lb));
+        let hi = space.sup().map_or(ub, |x| x.min(ub));
+
+        if lo > hi { return f64::NEG_INFINITY; }
+        if lb == ub { return if space.contains(lb) { 0.0 } else { f64::NEG_INFINITY }; }
+        if lo == hi { return f64::NEG_INFINITY; }
+
+        let z = |x: f64| (x - self.mean()) / self.std();
+
+        standard_log_normaliser(z(lo), z(hi)) - standard_log_normaliser(z(lb), z(ub))
+    }
+}
+
+/// Trait for spaces that can draw values together with their log-density under the sampling
+/// distribution, e.g. for the importance weights of off-policy evaluation.
+///
+/// For the uniform distributions of `Sample`, this is the negative logarithm of the cardinality
+/// of a finite space, or of the volume of a continuous one; products sum the log-densities of
+/// their components, which are drawn independently. Exponentiating the log-density and summing
+/// or integrating over the space thus gives one. Values that are contained in a space with a
+/// single value have a log-density of zero.
+/// ```
+/// extern crate rand;
+///
+/// use spaces::{discrete::Ordinal, real::Interval, DensitySample, PairSpace};
+///
+/// let space = PairSpace::new(Ordinal::new(4), Interval::bounded(0.0, 2.0));
+/// let (_, logp) = space.sample_with_logp(&mut rand::thread_rng());
+///
+/// assert!((logp + 8f64.ln()).abs() < 1e-12);
+/// assert!(Interval::left_bounded(0.0).try_sample_with_logp(&mut rand::thread_rng()).is_err());
+/// ```
+pub trait DensitySample: Sample {
+    /// Draw a value together with its log-density, or return an error if the distribution is
+    /// undefined; see `Sample::try_sample`.
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::Value, f64), SampleError>;
+
+    /// Draw a value together with its log-density.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution is undefined; see `try_sample_with_logp`.
+    fn sample_with_logp<R: Rng + ?Sized>(&self, rng: &mut R) -> (Self::Value, f64) {
+        self.try_sample_with_logp(rng)
+            .unwrap_or_else(|e| panic!("Cannot sample from space: {}.", e))
+    }
+}
+
+// Returns the log-density of the uniform distribution over `n` values.
+fn uniform_log_prob(n: f64) -> f64 { -n.ln() }
+
+/// The volume is formed such that it does not overflow, even between the extreme finite values.
+impl DensitySample for Interval<f64> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(f64, f64), SampleError> {
+        let val = self.try_sample(rng)?;
+        let (lb, ub) = (self.inf().unwrap(), self.sup().unwrap());
+        let logp = if lb == ub { 0.0 } else { -((ub / 2.0 - lb / 2.0).ln() + LN_2) };
+
+        Ok((val, logp))
+    }
+}
+
+impl DensitySample for Interval<i64> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(i64, f64), SampleError> {
+        let val = self.try_sample(rng)?;
+        let (lo, hi) = self.tight_bounds();
+
+        Ok((val, uniform_log_prob((hi.unwrap() - lo.unwrap() + 1) as f64)))
+    }
+}
+
+impl<I: OrdinalIndex> DensitySample for Ordinal<I> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(I, f64), SampleError> {
+        Ok((self.try_sample(rng)?, uniform_log_prob(self.len() as f64)))
+    }
+}
+
+impl DensitySample for Binary {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(bool, f64), SampleError> {
+        Ok((self.sample(rng), -LN_2))
+    }
+}
+
+/// The log-density is that of the truncated normal distribution; see `LogDensity`.
+impl<S: BoundedSpace<Value = f64>> DensitySample for TruncatedNormal<S> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(f64, f64), SampleError> {
+        let val = self.try_sample(rng)?;
+
+        Ok((val, self.log_density(&val)))
+    }
+}
+
+/// The log-density is that of the distribution at the accepted value, renormalised by the
+/// probability of acceptance, i.e. the mass that the distribution assigns to the base space.
+impl<S, D> DensitySample for SampleWith<S, D>
+where
+    S: BoundedSpace,
+    S::Value: PartialOrd,
+    D: Distribution<S::Value> + LogDensity<S::Value> + LogMass<S>,
+{
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(S::Value, f64), SampleError> {
+        let val = self.try_sample(rng)?;
+        let dist = self.distribution();
+        let logp = dist.log_density(&val) - dist.log_mass(self.base());
+
+        Ok((val, logp))
+    }
+}
+
+impl<D: DensitySample> DensitySample for ProductSpace<D> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Vec<D::Value>, f64), SampleError> {
+        let mut logp = 0.0;
+        let vals = self.iter().map(|d| d.try_sample_with_logp(rng).map(|(v, lp)| {
+            logp += lp;
+
+            v
+        })).collect::<Result<_, _>>()?;
+
+        Ok((vals, logp))
+    }
+}
+
+impl<D: DensitySample> DensitySample for TwoSpace<D> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<([D::Value; 2], f64), SampleError> {
+        let (v0, lp0) = self[0].try_sample_with_logp(rng)?;
+        let (v1, lp1) = self[1].try_sample_with_logp(rng)?;
+
+        Ok(([v0, v1], lp0 + lp1))
+    }
+}
+
+impl<D1: DensitySample, D2: DensitySample> DensitySample for PairSpace<D1, D2> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<((D1::Value, D2::Value), f64), SampleError> {
+        let (v0, lp0) = self.0.try_sample_with_logp(rng)?;
+        let (v1, lp1) = self.1.try_sample_with_logp(rng)?;
+
+        Ok(((v0, v1), lp0 + lp1))
+    }
+}
+
+impl<S: DensitySample> DensitySample for Stacked<S> {
+    fn try_sample_with_logp<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(VecDeque<S::Value>, f64), SampleError> {
+        let mut logp = 0.0;
+        let vals = (0..self.k()).map(|_| self.base().try_sample_with_logp(rng).map(|(v, lp)| {
+            logp += lp;
+
+            v
+        })).collect::<Result<_, _>>()?;
+
+        Ok((vals, logp))
+    }
+}
+
+impl<S: FiniteSpace> Pmf<S>
+where S::Value: PartialEq
+{
+    /// Draw a value according to the pmf, together with its log-probability.
+    pub fn sample_with_logp<R: Rng + ?Sized>(&self, rng: &mut R) -> (S::Value, f64) {
+        let val = self.sample(rng);
+        let logp = self.log_prob(&val);
+
+        (val, logp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    // Integrates `f` over `[lb, ub]` with Simpson's rule on `n` (even) subintervals.
+    fn simpson<F: Fn(f64) -> f64>(f: F, lb: f64, ub: f64, n: usize) -> f64 {
+        let h = (ub - lb) / n as f64;
+        let inner: f64 = (1..n).map(|i| {
+            let w = if i % 2 == 1 { 4.0 } else { 2.0 };
+
+            w * f(lb + i as f64 * h)
+        }).sum();
+
+        (f(lb) + f(ub) + inner) * h / 3.0
+    }
+
+    #[test]
+    fn test_interval() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for &(lb, ub) in [(0.0, 1.0), (-3.0, 7.5), (1e-3, 2e-3), (-f64::MAX, f64::MAX)].iter() {
+            let d = Interval::bounded(lb, ub);
+            let (val, logp) = d.sample_with_logp(&mut rng);
+
+            assert!(d.contains(val));
+            assert!((logp.exp() * (ub / 2.0 - lb / 2.0) * 2.0 - 1.0).abs() < 1e-12, "{}", d);
+        }
+
+        let (val, logp) = Interval::bounded(-5i64, 10).sample_with_logp(&mut rng);
+
+        assert!((-5..=10).contains(&val));
+        assert!((logp.exp() * 16.0 - 1.0).abs() < 1e-12);
+        assert_eq!(Interval::bounded(2.0, 2.0).sample_with_logp(&mut rng), (2.0, 0.0));
+    }
+
+    #[test]
+    fn test_pmf() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let pmf = Pmf::new(Ordinal::new(4), vec![0.1, 0.2, 0.3, 0.4]).unwrap();
+        let total: f64 = (0..4).map(|v| pmf.log_density(&v).exp()).sum();
+
+        assert!((total - 1.0).abs() < 1e-12);
+
+        for _ in 0..100 {
+            let (val, logp) = pmf.sample_with_logp(&mut rng);
+
+            assert_eq!(logp, pmf.probs()[val].ln());
+        }
+
+        let space = SampleWith::new(Ordinal::new(4), pmf.clone());
+        let (val, logp) = space.sample_with_logp(&mut rng);
+
+        assert_eq!(pmf.log_mass(&Ordinal::new(4)), 0.0);
+        assert_eq!(logp, pmf.log_prob(&val));
+    }
+
+    #[test]
+    fn test_pmf_wider_support() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let pmf = Pmf::new(Ordinal::new(4), vec![0.1, 0.2, 0.3, 0.4]).unwrap();
+        let space = SampleWith::new(Ordinal::new(2), pmf.clone());
+
+        assert!((pmf.log_mass(&Ordinal::new(2)) - 0.3f64.ln()).abs() < 1e-12);
+
+        for _ in 0..100 {
+            let (val, logp) = space.sample_with_logp(&mut rng);
+            let expected = [1.0 / 3.0, 2.0 / 3.0][val];
+
+            assert!(val < 2);
+            assert!((logp.exp() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let cases = [(-1.0, 1.0, 0.0, 1.0), (-3.0, 5.0, 1.0, 2.0), (0.5, 3.0, 0.0, 1.0),
+                     (-9.0, -8.0, 0.0, 1.0), (0.0, 1.0, 0.25, 0.1)];
+
+        for &(lb, ub, mean, std) in cases.iter() {
+            let space = TruncatedNormal::new(Interval::bounded(lb, ub), mean, std);
+            let total = simpson(|x| space.log_density(&x).exp(), lb, ub, 10_000);
+
+            assert!((total - 1.0).abs() < 1e-6, "mass {} over [{}, {}]", total, lb, ub);
+
+            for _ in 0..100 {
+                let (val, logp) = space.sample_with_logp(&mut rng);
+
+                assert!(space.contains(val));
+                assert_eq!(logp, space.log_density(&val));
+            }
+        }
+
+        let normal = TruncatedNormal::new(Interval::<f64>::unbounded(), 1.0, 2.0);
+        let unit = Interval::bounded(0.0, 1.0);
+        let mass = simpson(|x| normal.log_density(&x).exp(), 0.0, 1.0, 10_000);
+
+        assert!((normal.log_mass(&unit).exp() - mass).abs() < 1e-6);
+
+        let half = TruncatedNormal::new(Interval::left_bounded(0.0), 0.0, 1.0);
+
+        assert!((half.log_mass(&Interval::bounded(-1.0, 1.0)) - 0.6826894921370859f64.ln()).abs()
+            < 1e-6);
+        assert_eq!(half.log_mass(&Interval::bounded(-2.0, -1.0)), f64::NEG_INFINITY);
+
+        assert!((half.log_density(&0.0) - (2.0 / PI).sqrt().ln()).abs() < 1e-6);
+        assert_eq!(half.log_density(&-1.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_products() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let d = ProductSpace::new(vec![Interval::bounded(0.0, 2.0), Interval::bounded(0.0, 4.0)]);
+        let (val, logp) = d.sample_with_logp(&mut rng);
+
+        assert!(d.contains(&val));
+        assert!((logp + 8f64.ln()).abs() < 1e-12);
+
+        let (_, logp) =
+            TwoSpace::new([Ordinal::new(3), Ordinal::new(5)]).sample_with_logp(&mut rng);
+
+        assert!((logp + 15f64.ln()).abs() < 1e-12);
+
+        let (window, logp) = Stacked::new(Binary, 3).sample_with_logp(&mut rng);
+
+        assert_eq!(window.len(), 3);
+        assert!((logp + 8f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(
+            Interval::left_bounded(0.0).try_sample_with_logp(&mut rng),
+            Err(SampleError::Unbounded)
+        );
+        assert_eq!(
+            Interval::bounded(0.0, f64::INFINITY).try_sample_with_logp(&mut rng),
+            Err(SampleError::NoDistribution)
+        );
+        assert_eq!(Ordinal::new(0).try_sample_with_logp(&mut rng), Err(SampleError::Empty));
+        assert_eq!(
+            ProductSpace::new(vec![Interval::unit(), Interval::open(0.0, 0.0)])
+                .try_sample_with_logp(&mut rng),
+            Err(SampleError::Empty)
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use crate::{
+    codec::SinglePrecision,
+    discrete::{Binary, Integers, Naturals, NonNegativeIntegers, NonZeroIntegers, Ordinal},
+    real::{NonNegativeReals, PositiveReals, Reals},
+    prelude::*,
+    BoundKind, Empty, Equipartition, Interval, PairSpace, ProductSpace, Quantised, TwoSpace,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hash, used wherever a hash must be stable across runs and crate versions.
+pub(crate) fn fnv1a(state: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(state, |h, &b| (h ^ u64::from(b)).wrapping_mul(FNV_PRIME))
+}
+
+/// Kind of a scalar (1-dimensional) space.
+///
+/// With the `serialize` feature, kinds are written to human-readable formats as a map tagged by
+/// a `type` field, e.g. `{"type": "discrete", "n": 3}`. Formats that are not self-describing
+/// (e.g. bincode) cannot decode such maps, so they identify the kind by its variant index instead.
+/// In human-readable formats, closed bounds and empty exclusions are omitted.
+///
+/// The bound kinds of integers and reals follow those of `Interval`, such that a missing bound is
+/// always closed, and `excluded` lists the values removed from an integer interval, e.g. zero for
+/// `NonZeroIntegers`.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ScalarKind {
+    Empty,
+    Binary,
+    Discrete { n: usize },
+    Integer {
+        lb: Option<i64>,
+        ub: Option<i64>,
+        lb_kind: BoundKind,
+        ub_kind: BoundKind,
+        excluded: Vec<i64>,
+    },
+    Real { lb: Option<f64>, ub: Option<f64>, lb_kind: BoundKind, ub_kind: BoundKind },
+    Partition { lb: f64, ub: f64, n: usize },
+    Quantised { lb: f64, ub: f64, step: f64 },
+}
+
+impl ScalarKind {
+    /// Returns the kind of the closed integer interval `[lb, ub]`, where `None` is unbounded.
+    pub fn integer(lb: Option<i64>, ub: Option<i64>) -> ScalarKind {
+        ScalarKind::Integer {
+            lb, ub,
+            lb_kind: BoundKind::Closed,
+            ub_kind: BoundKind::Closed,
+            excluded: vec![],
+        }
+    }
+
+    /// Returns the kind of the closed real interval `[lb, ub]`, where `None` is unbounded.
+    pub fn real(lb: Option<f64>, ub: Option<f64>) -> ScalarKind {
+        ScalarKind::Real { lb, ub, lb_kind: BoundKind::Closed, ub_kind: BoundKind::Closed, }
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod serde_impl {
+    use super::ScalarKind;
+    use crate::BoundKind;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    fn is_closed(kind: &BoundKind) -> bool { *kind == BoundKind::Closed }
+
+    // The two representations of `ScalarKind`, which must mirror its definition exactly. Only
+    // self-describing formats may skip fields, so the attributes of the optional fields differ.
+    macro_rules! scalar_kind_repr {
+        (
+            $name:ident,
+            kind: $(#[$kind:meta])*,
+            excluded: $(#[$excluded:meta])*,
+            $($attr:tt)*
+        ) => {
+            #[derive(Serialize, Deserialize)]
+            #[serde(remote = "ScalarKind", rename = "ScalarKind", $($attr)*)]
+            enum $name {
+                Empty,
+                Binary,
+                Discrete { n: usize },
+                Integer {
+                    lb: Option<i64>,
+                    ub: Option<i64>,
+                    $(#[$kind])* lb_kind: BoundKind,
+                    $(#[$kind])* ub_kind: BoundKind,
+                    $(#[$excluded])* excluded: Vec<i64>,
+                },
+                Real {
+                    lb: Option<f64>,
+                    ub: Option<f64>,
+                    $(#[$kind])* lb_kind: BoundKind,
+                    $(#[$kind])* ub_kind: BoundKind,
+                },
+                Partition { lb: f64, ub: f64, n: usize },
+                Quantised { lb: f64, ub: f64, step: f64 },
+            }
+        };
+    }
+
+    scalar_kind_repr!(
+        TaggedScalarKind,
+        kind: #[serde(default, skip_serializing_if = "is_closed")],
+        excluded: #[serde(default, skip_serializing_if = "Vec::is_empty")],
+        tag = "type", rename_all = "snake_case"
+    );
+    scalar_kind_repr!(IndexedScalarKind, kind:, excluded:, rename_all = "snake_case");
+
+    impl Serialize for ScalarKind {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                TaggedScalarKind::serialize(self, serializer)
+            } else {
+                IndexedScalarKind::serialize(self, serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ScalarKind {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                TaggedScalarKind::deserialize(deserializer)
+            } else {
+                IndexedScalarKind::deserialize(deserializer)
+            }
+        }
+    }
+}
+
+/// Stable, reduced schema describing the structure of a space.
+///
+/// Unlike the serialised form of a space itself, this representation does not depend on the
+/// concrete types in use and is intended for display and cross-language tooling.
+/// ```
+/// use spaces::{discrete::Ordinal, Describe, PairSpace, ScalarKind, SpaceDescription};
+///
+/// let space = PairSpace::new(Ordinal::new(2), Ordinal::new(3));
+///
+/// assert_eq!(space.describe(), SpaceDescription::Product(vec![
+///     SpaceDescription::Scalar(ScalarKind::Discrete { n: 2 }),
+///     SpaceDescription::Scalar(ScalarKind::Discrete { n: 3 }),
+/// ]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum SpaceDescription {
+    Scalar(ScalarKind),
+    Product(Vec<SpaceDescription>),
+}
+
+impl SpaceDescription {
+    /// Returns a stable 64-bit hash of the description.
+    ///
+    /// The hash is computed with FNV-1a over the `Display` rendering of the description, and is
+    /// therefore stable across runs, platforms and versions of the crate.
+    pub fn stable_hash(&self) -> u64 { fnv1a(FNV_OFFSET, self.to_string().as_bytes()) }
+
+    /// Returns the JSON serialisation of the description in a canonical, byte-stable form,
+    /// suitable for hashing, e.g. for content-addressed caching of experiment configurations.
+    ///
+    /// The output contains no whitespace, object keys are sorted, and floats are written in their
+    /// shortest round-trip form (always with a fraction or exponent), with negative zero written as
+    /// zero. Equal descriptions therefore produce identical bytes, however they were constructed.
+    /// ```
+    /// use spaces::{discrete::Ordinal, Describe, Interval, PairSpace, SpaceDescription};
+    ///
+    /// let built = PairSpace::new(Interval::bounded(-0.0, 1.0), Ordinal::new(3)).describe();
+    /// let parsed: SpaceDescription = "interval(0, 1e0) x discrete(3)".parse().unwrap();
+    ///
+    /// assert_eq!(built.canonical_json(), parsed.canonical_json());
+    /// assert_eq!(
+    ///     built.canonical_json(),
+    ///     r#"{"product":[{"scalar":{"lb":0.0,"type":"real","ub":1.0}},"#.to_owned()
+    ///         + r#"{"scalar":{"n":3,"type":"discrete"}}]}"#
+    /// );
+    /// ```
+    #[cfg(feature = "serialize")]
+    pub fn canonical_json(&self) -> String {
+        let value = serde_json::to_value(self).expect("Descriptions are always serialisable.");
+        let mut json = String::new();
+
+        write_canonical(&value, &mut json);
+
+        json
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{:1$}", "", 2 * depth)?;
+
+        match self {
+            SpaceDescription::Scalar(kind) => write!(f, "{}", kind),
+            SpaceDescription::Product(components) => {
+                write!(f, "product")?;
+
+                for c in components.iter() {
+                    writeln!(f)?;
+                    c.fmt_indented(f, depth + 1)?;
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+fn write_canonical(value: &serde_json::Value, json: &mut String) {
+    use serde_json::Value;
+
+    match value {
+        Value::Number(n) => match n.as_f64() {
+            // Negative zero compares equal to zero, so is written as such.
+            Some(x) if n.is_f64() =>
+                json.push_str(&format!("{:?}", if x == 0.0 { 0.0 } else { x })),
+            _ => json.push_str(&n.to_string()),
+        },
+        Value::Array(vals) => {
+            json.push('[');
+
+            for (i, v) in vals.iter().enumerate() {
+                if i > 0 { json.push(','); }
+
+                write_canonical(v, json);
+            }
+
+            json.push(']');
+        },
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            json.push('{');
+
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 { json.push(','); }
+
+                json.push_str(&Value::from(k.as_str()).to_string());
+                json.push(':');
+                write_canonical(v, json);
+            }
+
+            json.push('}');
+        },
+        _ => json.push_str(&value.to_string()),
+    }
+}
+
+fn fmt_bound<T: fmt::Display>(bound: &Option<T>, unbounded: &str) -> String {
+    bound.as_ref().map_or_else(|| unbounded.to_owned(), |b| b.to_string())
+}
+
+// Writes `name[lb, ub]`, with parentheses in place of the brackets of open bounds.
+fn fmt_interval<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    name: &str,
+    lb: &Option<T>,
+    ub: &Option<T>,
+    lb_kind: BoundKind,
+    ub_kind: BoundKind,
+) -> fmt::Result {
+    let open = if lb_kind == BoundKind::Open { '(' } else { '[' };
+    let close = if ub_kind == BoundKind::Open { ')' } else { ']' };
+
+    write!(f, "{}{}{}, {}{}", name, open, fmt_bound(lb, "-inf"), fmt_bound(ub, "inf"), close)
+}
+
+impl fmt::Display for ScalarKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScalarKind::Empty => write!(f, "empty"),
+            ScalarKind::Binary => write!(f, "binary"),
+            ScalarKind::Discrete { n } => write!(f, "discrete({})", n),
+            ScalarKind::Integer { lb, ub, lb_kind, ub_kind, excluded } => {
+                fmt_interval(f, "integer", lb, ub, *lb_kind, *ub_kind)?;
+
+                if excluded.is_empty() { return Ok(()); }
+
+                let excluded: Vec<_> = excluded.iter().map(|x| x.to_string()).collect();
+
+                write!(f, " \\ {{{}}}", excluded.join(", "))
+            },
+            ScalarKind::Real { lb, ub, lb_kind, ub_kind } =>
+                fmt_interval(f, "real", lb, ub, *lb_kind, *ub_kind),
+            ScalarKind::Partition { lb, ub, n } => write!(f, "partition([{}, {}], {})", lb, ub, n),
+            ScalarKind::Quantised { lb, ub, step } =>
+                write!(f, "quantised([{}, {}], {})", lb, ub, step),
+        }
+    }
+}
+
+impl fmt::Display for SpaceDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.fmt_indented(f, 0) }
+}
+
+/// Trait for spaces that can describe their own structure.
+pub trait Describe: Space {
+    /// Return a structural description of the space.
+    fn describe(&self) -> SpaceDescription;
+
+    /// Return a random number generator seeded by `master_seed` and the description of the space.
+    ///
+    /// Identical spaces yield identical streams for a given `master_seed`, while any change to
+    /// the parameters of the space yields a different stream.
+    ///
+    /// The generator is ChaCha8, a fixed algorithm, so that streams are also reproducible across
+    /// platforms and versions of `rand`.
+    fn seeded_rng(&self, master_seed: u64) -> ChaCha8Rng {
+        let h = fnv1a(self.describe().stable_hash(), &master_seed.to_le_bytes());
+
+        ChaCha8Rng::seed_from_u64(h)
+    }
+}
+
+macro_rules! impl_describe_scalar {
+    ($type:ty, $self:ident => $kind:expr) => {
+        impl Describe for $type {
+            fn describe(&$self) -> SpaceDescription { SpaceDescription::Scalar($kind) }
+        }
+    };
+}
+
+impl_describe_scalar!(Empty, self => ScalarKind::Empty);
+impl_describe_scalar!(Binary, self => ScalarKind::Binary);
+impl_describe_scalar!(Ordinal, self => ScalarKind::Discrete { n: self.size() });
+impl_describe_scalar!(Naturals, self => ScalarKind::integer(Some(1), None));
+impl_describe_scalar!(NonNegativeIntegers, self => ScalarKind::integer(Some(0), None));
+impl_describe_scalar!(Integers, self => ScalarKind::integer(None, None));
+impl_describe_scalar!(NonZeroIntegers, self => ScalarKind::Integer {
+    lb: None, ub: None, lb_kind: BoundKind::Closed, ub_kind: BoundKind::Closed, excluded: vec![0],
+});
+impl_describe_scalar!(Interval<i64>, self => ScalarKind::Integer {
+    lb: self.lb, ub: self.ub, lb_kind: self.lb_kind, ub_kind: self.ub_kind, excluded: vec![],
+});
+impl_describe_scalar!(Reals, self => ScalarKind::real(None, None));
+impl_describe_scalar!(NonNegativeReals, self => ScalarKind::real(Some(0.0), None));
+impl_describe_scalar!(PositiveReals, self => ScalarKind::Real {
+    lb: Some(0.0), ub: None, lb_kind: BoundKind::Open, ub_kind: BoundKind::Closed,
+});
+impl_describe_scalar!(Interval<f64>, self => ScalarKind::Real {
+    lb: self.lb, ub: self.ub, lb_kind: self.lb_kind, ub_kind: self.ub_kind,
+});
+impl_describe_scalar!(Equipartition, self => ScalarKind::Partition {
+    lb: self.lb, ub: self.ub, n: self.n_partitions(),
+});
+impl_describe_scalar!(Quantised, self => ScalarKind::Quantised {
+    lb: self.lb, ub: self.ub, step: self.step,
+});
+
+impl<S: Describe<Value = f64>> Describe for SinglePrecision<S> {
+    fn describe(&self) -> SpaceDescription { self.0.describe() }
+}
+
+impl<D1: Describe, D2: Describe> Describe for PairSpace<D1, D2> {
+    fn describe(&self) -> SpaceDescription {
+        SpaceDescription::Product(vec![self.0.describe(), self.1.describe()])
+    }
+}
+
+impl<D: Describe> Describe for TwoSpace<D> {
+    fn describe(&self) -> SpaceDescription {
+        SpaceDescription::Product(vec![self[0].describe(), self[1].describe()])
+    }
+}
+
+impl<D: Describe> Describe for ProductSpace<D> {
+    fn describe(&self) -> SpaceDescription {
+        SpaceDescription::Product(self.iter().map(|d| d.describe()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(Binary.describe(), SpaceDescription::Scalar(ScalarKind::Binary));
+        assert_eq!(
+            Interval::left_bounded(0.0f64).describe(),
+            SpaceDescription::Scalar(ScalarKind::real(Some(0.0), None))
+        );
+        assert_eq!(
+            Equipartition::new(0.0, 1.0, 4).describe(),
+            SpaceDescription::Scalar(ScalarKind::Partition { lb: 0.0, ub: 1.0, n: 4 })
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let space = PairSpace::new(
+            ProductSpace::new(vec![Interval::unit(), Interval::bounded(-1.0, 1.0)]),
+            PairSpace::new(Ordinal::new(3), Binary),
+        );
+
+        assert_eq!(space.describe(), SpaceDescription::Product(vec![
+            SpaceDescription::Product(vec![
+                SpaceDescription::Scalar(ScalarKind::real(Some(0.0), Some(1.0))),
+                SpaceDescription::Scalar(ScalarKind::real(Some(-1.0), Some(1.0))),
+            ]),
+            SpaceDescription::Product(vec![
+                SpaceDescription::Scalar(ScalarKind::Discrete { n: 3 }),
+                SpaceDescription::Scalar(ScalarKind::Binary),
+            ]),
+        ]));
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        // Golden values: these must never change between runs or crate versions.
+        assert_eq!(Ordinal::new(6).describe().stable_hash(), 0x0767_ff6f_4a1d_bf8b);
+        assert_eq!(Binary.describe().stable_hash(), 0xee88_5e74_47d3_d73c);
+
+        assert_ne!(
+            Ordinal::new(6).describe().stable_hash(),
+            Ordinal::new(7).describe().stable_hash()
+        );
+
+        let hash = |d: SpaceDescription| d.stable_hash();
+
+        assert_ne!(hash(PositiveReals.describe()), hash(NonNegativeReals.describe()));
+        assert_ne!(hash(NonZeroIntegers.describe()), hash(Integers.describe()));
+        assert_ne!(
+            hash(Interval::open(0.0, 1.0).describe()),
+            hash(Interval::bounded(0.0, 1.0).describe())
+        );
+        assert_ne!(
+            hash(Interval::bounded(0i64, 5).with_kinds(BoundKind::Closed, BoundKind::Open)
+                .describe()),
+            hash(Interval::bounded(0i64, 5).describe())
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_canonical_json() {
+        let built = ProductSpace::new(vec![
+            Interval::bounded(-1.0, 0.5),
+            Interval::left_bounded(-0.0),
+        ]).describe();
+        let reordered: SpaceDescription = serde_json::from_str(r#"{"product": [
+            {"scalar": {"ub": 5e-1, "lb": -1, "type": "real"}},
+            {"scalar": {"type": "real", "lb": 0.0, "ub": null}}
+        ]}"#).unwrap();
+
+        assert_eq!(built.canonical_json(), reordered.canonical_json());
+        assert_eq!(
+            built.canonical_json(),
+            r#"{"product":[{"scalar":{"lb":-1.0,"type":"real","ub":0.5}},"#.to_owned()
+                + r#"{"scalar":{"lb":0.0,"type":"real","ub":null}}]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<SpaceDescription>(&built.canonical_json()).unwrap(),
+            built
+        );
+
+        let open = PairSpace::new(PositiveReals, NonZeroIntegers).describe();
+
+        assert_eq!(
+            open.canonical_json(),
+            r#"{"product":[{"scalar":{"lb":0.0,"lb_kind":"open","type":"real","ub":null}},"#
+                .to_owned()
+                + r#"{"scalar":{"excluded":[0],"lb":null,"type":"integer","ub":null}}]}"#
+        );
+        assert_eq!(serde_json::from_str::<SpaceDescription>(&open.canonical_json()).unwrap(), open);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_canonical_hash() {
+        let hash = |d: SpaceDescription| fnv1a(FNV_OFFSET, d.canonical_json().as_bytes());
+        let base = PairSpace::new(Interval::bounded(0.0, 1.0), Equipartition::new(-2.0, 2.0, 8));
+        let variants = [
+            PairSpace::new(Interval::bounded(1e-12, 1.0), Equipartition::new(-2.0, 2.0, 8)),
+            PairSpace::new(Interval::bounded(0.0, 1.5), Equipartition::new(-2.0, 2.0, 8)),
+            PairSpace::new(Interval::bounded(0.0, 1.0), Equipartition::new(-3.0, 2.0, 8)),
+            PairSpace::new(Interval::bounded(0.0, 1.0), Equipartition::new(-2.0, 2.1, 8)),
+            PairSpace::new(Interval::bounded(0.0, 1.0), Equipartition::new(-2.0, 2.0, 9)),
+        ];
+
+        assert_eq!(hash(base.describe()), hash(base.clone().describe()));
+
+        for v in variants.iter() {
+            assert_ne!(hash(base.describe()), hash(v.describe()), "{}", v.describe());
+        }
+
+        assert_ne!(
+            hash(Interval::bounded(0.0, 1.0).describe()),
+            hash(Interval::left_bounded(0.0).describe())
+        );
+    }
+
+    #[test]
+    fn test_seeded_rng() {
+        use crate::Sample;
+
+        fn stream<S: Describe + Sample>(space: &S, seed: u64) -> Vec<S::Value> {
+            let mut rng = space.seeded_rng(seed);
+
+            (0..32).map(|_| space.sample(&mut rng)).collect()
+        }
+
+        let d = Ordinal::new(1000);
+
+        assert_eq!(stream(&d, 0), stream(&Ordinal::new(1000), 0));
+        assert_ne!(stream(&d, 0), stream(&d, 1));
+        assert_ne!(stream(&d, 0), stream(&Ordinal::new(1001), 0));
+
+        // Golden values: the stream must not depend on the platform or the version of `rand`.
+        assert_eq!(&stream(&d, 0)[..4], &[326, 593, 425, 922]);
+    }
+
+    #[test]
+    fn test_display() {
+        let space = PairSpace::new(
+            ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(-1.0)]),
+            Ordinal::new(3),
+        );
+
+        assert_eq!(
+            space.describe().to_string(),
+            "product\n  product\n    real[0, 1]\n    real[-1, inf]\n  discrete(3)"
+        );
+        assert_eq!(PositiveReals.describe().to_string(), "real(0, inf]");
+        assert_eq!(NonZeroIntegers.describe().to_string(), "integer[-inf, inf] \\ {0}");
+        assert_eq!(
+            Interval::bounded(0i64, 5).with_kinds(BoundKind::Closed, BoundKind::Open)
+                .describe().to_string(),
+            "integer[0, 5)"
+        );
+    }
+}
@@ -26,12 +26,19 @@ use std::ops::Add;
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum Dim {
     Finite(usize),
     Infinite,
 }
 
 impl Dim {
+    #[inline]
+    pub const fn finite(dim: usize) -> Dim { Dim::Finite(dim) }
+
+    #[inline]
+    pub const fn infinite() -> Dim { Dim::Infinite }
+
     pub fn one() -> Dim { Dim::Finite(1) }
 }
 
@@ -46,9 +53,9 @@ impl Add for Dim {
     }
 }
 
-impl Into<usize> for Dim {
-    fn into(self) -> usize {
-        match self {
+impl From<Dim> for usize {
+    fn from(d: Dim) -> usize {
+        match d {
             Dim::Finite(e) => e,
             _ => panic!("Dim type has no integer representation."),
         }
@@ -108,7 +115,7 @@ mod tests {
 
     #[test]
     fn test_into_finite() {
-        for i in vec![0, 1, 5, 10] {
+        for i in [0, 1, 5, 10] {
             let d = Dim::Finite(i);
             let v: usize = d.into();
 
@@ -0,0 +1,595 @@
+use crate::{description::{fnv1a, FNV_OFFSET}, limits::DebugCapped, prelude::*};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    iter::{FromIterator, FusedIterator},
+    ops::Range,
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+/// Error returned when an alphabet would contain no characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmptyAlphabetError;
+
+impl fmt::Display for EmptyAlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an alphabet must contain at least one character")
+    }
+}
+
+impl Error for EmptyAlphabetError {}
+
+/// Type representing a finite alphabet of characters.
+///
+/// Characters are deduplicated on construction, and enumerated in the order in which they were
+/// first given. Equality is therefore order-sensitive, since two alphabets with the same
+/// characters in a different order assign them different indices; use `same_elements` for
+/// order-insensitive comparison, or `sorted` to construct alphabets in canonical order.
+/// ```
+/// use spaces::{discrete::Alphabet, Card, FiniteSpace, Space, Surjection};
+///
+/// let d = Alphabet::new("héllo");
+///
+/// assert_eq!(d.card(), Card::Finite(4));
+/// assert_eq!(d.iter().collect::<String>(), "hélo");
+/// assert_eq!(d.map_onto('é'), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(into = "String", try_from = "String"))]
+pub struct Alphabet {
+    // Shared, such that clones and iterators need not copy the characters.
+    storage: Arc<Storage>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Storage {
+    chars: Vec<char>,
+    // Characters paired with their index, sorted by character for lookup.
+    lookup: Vec<(char, usize)>,
+}
+
+// Storage of the live interned alphabets, keyed by their canonical hash.
+type InternTable = HashMap<u64, Vec<Weak<Storage>>>;
+
+fn intern_table() -> &'static Mutex<InternTable> {
+    static TABLE: OnceLock<Mutex<InternTable>> = OnceLock::new();
+
+    TABLE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+impl Alphabet {
+    /// Construct an alphabet from the characters of a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is empty.
+    pub fn new(chars: &str) -> Alphabet { Alphabet::from_chars(chars.chars()) }
+
+    /// Construct an alphabet from a sequence of characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is empty.
+    pub fn from_chars<I: IntoIterator<Item = char>>(chars: I) -> Alphabet {
+        Alphabet::try_from_chars(chars).expect("An alphabet must contain at least one character.")
+    }
+
+    /// As `from_chars`, but returns `None` for empty sequences instead of panicking.
+    pub fn try_from_chars<I: IntoIterator<Item = char>>(chars: I) -> Option<Alphabet> {
+        let mut unique = vec![];
+        let mut lookup: Vec<(char, usize)> = vec![];
+
+        for c in chars {
+            if let Err(pos) = lookup.binary_search_by_key(&c, |&(l, _)| l) {
+                lookup.insert(pos, (c, unique.len()));
+                unique.push(c);
+            }
+        }
+
+        if unique.is_empty() {
+            None
+        } else {
+            Some(Alphabet { storage: Arc::new(Storage { chars: unique, lookup, }), })
+        }
+    }
+
+    /// Construct an alphabet from a sequence of characters, enumerated in ascending order.
+    ///
+    /// Alphabets constructed this way are independent of the order in which the characters are
+    /// given, and so have stable indices across processes.
+    /// ```
+    /// use spaces::discrete::Alphabet;
+    ///
+    /// let a = Alphabet::sorted("cab".chars());
+    /// let b = Alphabet::sorted("bca".chars());
+    ///
+    /// assert_eq!(a.chars(), &['a', 'b', 'c']);
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.canonical_hash(), b.canonical_hash());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is empty.
+    pub fn sorted<I: IntoIterator<Item = char>>(chars: I) -> Alphabet {
+        let mut chars: Vec<char> = chars.into_iter().collect();
+
+        chars.sort_unstable();
+
+        Alphabet::from_chars(chars)
+    }
+
+    /// Construct an alphabet as `from_chars`, sharing its storage with every live interned
+    /// alphabet of the same characters in the same order.
+    ///
+    /// Interned alphabets are tracked process-wide by weak reference, so the storage is freed once
+    /// the last alphabet sharing it is dropped. Interning is opt-in: alphabets constructed in any
+    /// other way never share storage with one another.
+    /// ```
+    /// use spaces::discrete::Alphabet;
+    ///
+    /// let a = Alphabet::interned("abc".chars());
+    /// let b = Alphabet::interned("abc".chars());
+    ///
+    /// assert_eq!(a.chars().as_ptr(), b.chars().as_ptr());
+    /// assert_eq!(a.strong_count(), 2);
+    /// assert_eq!(Alphabet::new("abc").strong_count(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is empty.
+    pub fn interned<I: IntoIterator<Item = char>>(chars: I) -> Alphabet {
+        let alphabet = Alphabet::from_chars(chars);
+        let hash = alphabet.canonical_hash();
+        let mut table = intern_table().lock().unwrap_or_else(|e| e.into_inner());
+
+        table.retain(|_, entries| {
+            entries.retain(|e| e.strong_count() > 0);
+
+            !entries.is_empty()
+        });
+
+        let entries = table.entry(hash).or_default();
+        let shared = entries.iter()
+            .filter_map(Weak::upgrade)
+            .find(|storage| storage.chars == alphabet.storage.chars);
+
+        match shared {
+            Some(storage) => Alphabet { storage, },
+            None => {
+                entries.push(Arc::downgrade(&alphabet.storage));
+
+                alphabet
+            },
+        }
+    }
+
+    /// Returns the number of alphabets, clones and iterators sharing the storage of this one.
+    pub fn strong_count(&self) -> usize { Arc::strong_count(&self.storage) }
+
+    /// The 26 lowercase ASCII letters, `a` to `z`.
+    pub fn ascii_lowercase() -> Alphabet { Alphabet::from_chars('a'..='z') }
+
+    /// The 10 ASCII digits, `0` to `9`.
+    pub fn digits() -> Alphabet { Alphabet::from_chars('0'..='9') }
+
+    /// The 95 printable ASCII characters, from space (`0x20`) to tilde (`0x7e`).
+    pub fn printable_ascii() -> Alphabet { Alphabet::from_chars(' '..='~') }
+
+    /// Returns the characters of the alphabet in enumeration order.
+    pub fn chars(&self) -> &[char] { &self.storage.chars }
+
+    /// Returns true iff both alphabets contain the same characters, regardless of their order.
+    pub fn same_elements(&self, other: &Alphabet) -> bool {
+        let (a, b) = (&self.storage.lookup, &other.storage.lookup);
+
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.0 == b.0)
+    }
+
+    /// Returns a stable 64-bit digest of the characters in enumeration order.
+    ///
+    /// The digest is computed with FNV-1a over the UTF-8 encoding of the characters, and is
+    /// stable across runs, platforms and versions of the crate. It is suitable for detecting
+    /// alphabets whose indices disagree, e.g. when stored alongside experiment metadata.
+    pub fn canonical_hash(&self) -> u64 {
+        let mut buf = [0; 4];
+
+        self.chars().iter().fold(FNV_OFFSET, |h, c| fnv1a(h, c.encode_utf8(&mut buf).as_bytes()))
+    }
+
+    /// Returns true iff `c` is a member of the alphabet.
+    pub fn contains(&self, c: char) -> bool { self.index_of(c).is_some() }
+
+    fn index_of(&self, c: char) -> Option<usize> {
+        let lookup = &self.storage.lookup;
+
+        lookup.binary_search_by_key(&c, |&(l, _)| l).ok().map(|i| lookup[i].1)
+    }
+}
+
+impl Space for Alphabet {
+    type Value = char;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    fn card(&self) -> Card { Card::Finite(self.chars().len()) }
+}
+
+impl Sample for Alphabet {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        let chars = self.chars();
+
+        chars[rng.gen_range(0..chars.len())]
+    }
+}
+
+impl FiniteSpace for Alphabet {
+    type Iter = Self::IntoIter;
+
+    fn iter(&self) -> Self::Iter { self.clone().into_iter() }
+
+    fn len(&self) -> usize { self.chars().len() }
+
+    fn is_empty(&self) -> bool { false }
+
+    fn to_index(&self, val: &char) -> Option<usize> { self.index_of(*val) }
+
+    fn from_index(&self, idx: usize) -> Option<char> { self.chars().get(idx).cloned() }
+}
+
+impl IntoIterator for Alphabet {
+    type Item = char;
+    type IntoIter = AlphabetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let range = 0..self.len();
+
+        AlphabetIter { storage: self.storage, range, }
+    }
+}
+
+/// Iterator over the characters of an `Alphabet`, in enumeration order.
+#[derive(Clone, Debug)]
+pub struct AlphabetIter {
+    storage: Arc<Storage>,
+    range: Range<usize>,
+}
+
+impl Iterator for AlphabetIter {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let chars = &self.storage.chars;
+
+        self.range.next().map(|i| chars[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.range.size_hint() }
+
+    fn nth(&mut self, n: usize) -> Option<char> {
+        let chars = &self.storage.chars;
+
+        self.range.nth(n).map(|i| chars[i])
+    }
+}
+
+impl DoubleEndedIterator for AlphabetIter {
+    fn next_back(&mut self) -> Option<char> {
+        let chars = &self.storage.chars;
+
+        self.range.next_back().map(|i| chars[i])
+    }
+}
+
+impl ExactSizeIterator for AlphabetIter {}
+
+impl FusedIterator for AlphabetIter {}
+
+impl FromIterator<char> for Alphabet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Alphabet { Alphabet::from_chars(iter) }
+}
+
+impl<'a> From<&'a str> for Alphabet {
+    fn from(chars: &'a str) -> Alphabet { Alphabet::new(chars) }
+}
+
+impl From<Alphabet> for String {
+    fn from(alphabet: Alphabet) -> String { alphabet.chars().iter().collect() }
+}
+
+impl TryFrom<String> for Alphabet {
+    type Error = EmptyAlphabetError;
+
+    fn try_from(chars: String) -> Result<Alphabet, EmptyAlphabetError> {
+        Alphabet::try_from_chars(chars.chars()).ok_or(EmptyAlphabetError)
+    }
+}
+
+/// Maps a character onto its index in the alphabet.
+///
+/// # Panics
+///
+/// Panics if the character is not a member of the alphabet.
+impl Surjection<char, usize> for Alphabet {
+    fn map_onto(&self, val: char) -> usize {
+        self.index_of(val).expect("Value is not contained in the space.")
+    }
+}
+
+/// Maps an index onto the corresponding character, clamping indices beyond the end of the
+/// alphabet to the last character.
+impl Surjection<usize, char> for Alphabet {
+    fn map_onto(&self, val: usize) -> char {
+        let chars = self.chars();
+
+        chars[val.min(chars.len() - 1)]
+    }
+}
+
+/// Prints a summary of the alphabet, `Alphabet { len, first, last }`; the alternate format,
+/// `{:#?}`, instead lists the first `DEBUG_LIMIT` characters.
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alternate = f.alternate();
+        let chars = self.chars();
+        let mut s = f.debug_struct("Alphabet");
+
+        s.field("len", &chars.len());
+
+        if alternate {
+            s.field("chars", &DebugCapped(chars.iter()));
+        } else {
+            s.field("first", &chars[0]).field("last", &chars[chars.len() - 1]);
+        }
+
+        s.finish()
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, c) in self.chars().iter().enumerate() {
+            if i != 0 { write!(f, ", ")?; }
+
+            write!(f, "{:?}", c)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DEBUG_LIMIT;
+    use super::*;
+
+    #[cfg(feature = "serialize")]
+    extern crate serde_test;
+    #[cfg(feature = "serialize")]
+    use self::serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn test_dedup() {
+        let d = Alphabet::new("abracadabra");
+
+        assert_eq!(d.chars(), &['a', 'b', 'r', 'c', 'd']);
+        assert_eq!(d.card(), Card::Finite(5));
+        assert_eq!(d, Alphabet::from_chars(vec!['a', 'b', 'r', 'c', 'd']));
+    }
+
+    #[test]
+    fn test_unicode() {
+        let d = Alphabet::new("日本語ß🦀本");
+
+        assert_eq!(d.len(), 5);
+        assert_eq!(d.iter().collect::<String>(), "日本語ß🦀");
+        assert!(d.contains('🦀'));
+        assert!(!d.contains('a'));
+        assert_eq!(d.to_index(&'ß'), Some(3));
+        assert_eq!(d.from_index(4), Some('🦀'));
+        assert_eq!(d.iter().rev().collect::<String>(), "🦀ß語本日");
+        assert_eq!(d.iter().nth(2), Some('語'));
+        assert_eq!(d.iter().len(), 5);
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Alphabet::ascii_lowercase().len(), 26);
+        assert_eq!(Alphabet::digits().iter().collect::<String>(), "0123456789");
+        assert_eq!(Alphabet::printable_ascii().len(), 95);
+        assert!(Alphabet::printable_ascii().contains(' '));
+        assert!(!Alphabet::printable_ascii().contains('\n'));
+        assert_eq!(Alphabet::try_from("ab".to_owned()), Ok(Alphabet::new("ab")));
+        assert_eq!(Alphabet::try_from(String::new()), Err(EmptyAlphabetError));
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        let a = Alphabet::new("dcba");
+        let b = Alphabet::new("abcd");
+
+        assert_ne!(a, b);
+        assert!(a.same_elements(&b));
+        assert_ne!(a.canonical_hash(), b.canonical_hash());
+        assert_ne!(a.to_index(&'a'), b.to_index(&'a'));
+
+        let a = Alphabet::sorted("dcbaa".chars());
+        let b = Alphabet::sorted("abcd".chars());
+
+        assert_eq!(a, b);
+        assert_eq!(a.canonical_hash(), b.canonical_hash());
+        assert_eq!(a.to_index(&'a'), Some(0));
+        assert_eq!(a.canonical_hash(), Alphabet::new("abcd").canonical_hash());
+
+        assert!(!a.same_elements(&Alphabet::new("abc")));
+        assert!(!a.same_elements(&Alphabet::new("abce")));
+    }
+
+    // Returns true iff a live interned alphabet has exactly the given characters.
+    fn is_interned(chars: &[char]) -> bool {
+        intern_table().lock().unwrap().values()
+            .flatten()
+            .filter_map(Weak::upgrade)
+            .any(|storage| storage.chars == chars)
+    }
+
+    #[test]
+    fn test_interned_shared() {
+        let vocab = || (0x4e00..0x4e00 + 30_000).filter_map(char::from_u32);
+        let a = Alphabet::interned(vocab());
+        let b = Alphabet::interned(vocab());
+
+        assert!(Arc::ptr_eq(&a.storage, &b.storage));
+        assert_eq!(a.strong_count(), 2);
+        assert_eq!(a, b);
+
+        let c = b.clone();
+        let iter = c.iter();
+
+        assert_eq!(a.strong_count(), 4);
+
+        drop((b, c, iter));
+
+        assert_eq!(a.strong_count(), 1);
+
+        let d = Alphabet::from_chars(vocab());
+
+        assert!(!Arc::ptr_eq(&a.storage, &d.storage));
+        assert_eq!((a.strong_count(), d.strong_count()), (1, 1));
+        assert_eq!(a, d);
+    }
+
+    #[test]
+    fn test_interned_distinct() {
+        let a = Alphabet::interned("αβγ".chars());
+        let b = Alphabet::interned("γβα".chars());
+        let c = Alphabet::interned("αβ".chars());
+
+        assert!(!Arc::ptr_eq(&a.storage, &b.storage));
+        assert!(!Arc::ptr_eq(&a.storage, &c.storage));
+        assert_eq!(b.chars(), &['γ', 'β', 'α']);
+        assert_eq!(c.chars(), &['α', 'β']);
+        assert_eq!(a.strong_count(), 1);
+    }
+
+    #[test]
+    fn test_interned_collected() {
+        let chars = ['ア', 'イ', 'ウ'];
+        let a = Alphabet::interned(chars.iter().cloned());
+
+        assert!(is_interned(&chars));
+
+        drop(a);
+
+        assert!(!is_interned(&chars));
+
+        let b = Alphabet::interned(chars.iter().cloned());
+
+        assert_eq!(b.strong_count(), 1);
+        assert!(is_interned(&chars));
+    }
+
+    #[test]
+    fn test_interned_threads() {
+        let chars = "ᚠᚢᚦᚨᚱᚲ";
+        let alphabets: Vec<Alphabet> = (0..8)
+            .map(|_| std::thread::spawn(move || Alphabet::interned(chars.chars())))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        assert!(alphabets.iter().all(|a| Arc::ptr_eq(&a.storage, &alphabets[0].storage)));
+        assert_eq!(alphabets[0].strong_count(), 8);
+    }
+
+    #[test]
+    fn test_canonical_hash_stable() {
+        // FNV-1a of the bytes "ab".
+        assert_eq!(Alphabet::new("ab").canonical_hash(), 0x089c_4407_b545_986a);
+        assert_ne!(Alphabet::new("ab").canonical_hash(), Alphabet::new("ba").canonical_hash());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty() {
+        Alphabet::new("");
+    }
+
+    #[test]
+    fn test_surjections() {
+        let d = Alphabet::new("xyz");
+
+        for (i, c) in d.iter().enumerate() {
+            let idx: usize = d.map_onto(c);
+            let chr: char = d.map_onto(i);
+
+            assert_eq!(idx, i);
+            assert_eq!(chr, c);
+        }
+
+        let chr: char = d.map_onto(10usize);
+
+        assert_eq!(chr, 'z');
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let d = Alphabet::new("aé🦀");
+
+        for _ in 0..100 {
+            assert!(d.contains(d.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Alphabet::new("ab'").to_string(), "{'a', 'b', '\\''}");
+    }
+
+    #[test]
+    fn test_debug() {
+        let d = Alphabet::sorted((0..50_000).filter_map(char::from_u32));
+
+        assert_eq!(
+            format!("{:?}", d),
+            "Alphabet { len: 50000, first: '\\0', last: '\u{c34f}' }"
+        );
+        assert_eq!(
+            format!("{:?}", Alphabet::new("a")),
+            "Alphabet { len: 1, first: 'a', last: 'a' }"
+        );
+        assert_eq!(
+            format!("{:#?}", Alphabet::new("ab")),
+            "Alphabet {\n    len: 2,\n    chars: [\n        'a',\n        'b',\n    ],\n}"
+        );
+
+        let full = format!("{:#?}", d);
+
+        assert_eq!(full.lines().count(), 3 + DEBUG_LIMIT + 3);
+        assert!(full.ends_with(&format!("        ... {} more,\n    ],\n}}", 50_000 - DEBUG_LIMIT)));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serialisation() {
+        assert_tokens(&Alphabet::new("aé🦀a"), &[Token::String("aé🦀")]);
+        assert_de_tokens_error::<Alphabet>(
+            &[Token::String("")],
+            "an alphabet must contain at least one character",
+        );
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Alphabet::new("aé🦀"); shape, finite, fmt, serde);
+        assert_space_laws!(Alphabet::printable_ascii(); shape, finite, fmt, serde);
+    }
+}
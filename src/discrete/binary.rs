@@ -1,8 +1,8 @@
 use crate::prelude::*;
-use std::{fmt};
+use std::{fmt, iter::FusedIterator};
 
 /// Type representing binary (base-2) values.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Binary;
 
@@ -14,6 +14,10 @@ impl Space for Binary {
     fn card(&self) -> Card { Card::Finite(2) }
 }
 
+impl Sample for Binary {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool { rng.gen() }
+}
+
 impl BoundedSpace for Binary {
     fn inf(&self) -> Option<bool> { Some(false) }
 
@@ -26,25 +30,80 @@ impl FiniteSpace for Binary {
     type Iter = Self::IntoIter;
 
     fn iter(&self) -> Self::Iter {
-        self.clone().into_iter()
+        self.into_iter()
+    }
+
+    fn len(&self) -> usize { 2 }
+
+    fn is_empty(&self) -> bool { false }
+
+    fn to_index(&self, val: &bool) -> Option<usize> { Some(*val as usize) }
+
+    fn from_index(&self, idx: usize) -> Option<bool> {
+        match idx {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
     }
 }
 
 impl IntoIterator for Binary {
     type Item = <Self as Space>::Value;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = BinaryIter;
+
+    fn into_iter(self) -> Self::IntoIter { BinaryIter { front: 0, back: 2, } }
+}
+
+/// Iterator over the values of `Binary`, yielding `false` then `true`.
+#[derive(Clone, Debug)]
+pub struct BinaryIter {
+    front: u8,
+    back: u8,
+}
+
+impl Iterator for BinaryIter {
+    type Item = bool;
 
-    fn into_iter(self) -> Self::IntoIter {
-        vec![false, true].into_iter()
+    fn next(&mut self) -> Option<bool> {
+        if self.front >= self.back { return None; }
+
+        self.front += 1;
+
+        Some(self.front == 2)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.back - self.front) as usize;
+
+        (n, Some(n))
     }
 }
 
+impl DoubleEndedIterator for BinaryIter {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front >= self.back { return None; }
+
+        self.back -= 1;
+
+        Some(self.back == 1)
+    }
+}
+
+impl ExactSizeIterator for BinaryIter {}
+
+impl FusedIterator for BinaryIter {}
+
 impl_union_intersect!(Binary, Binary);
 
 impl Surjection<bool, bool> for Binary {
     fn map_onto(&self, val: bool) -> bool { val }
 }
 
+impl Surjection<usize, bool> for Binary {
+    fn map_onto(&self, val: usize) -> bool { val != 0 }
+}
+
 impl fmt::Display for Binary {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{{0, 1}}")
@@ -53,6 +112,7 @@ impl fmt::Display for Binary {
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
     use super::*;
 
     #[cfg(feature = "serialize")]
@@ -60,6 +120,29 @@ mod tests {
     #[cfg(feature = "serialize")]
     use self::serde_test::{assert_tokens, Token};
 
+    #[test]
+    fn test_iter() {
+        let mut iter = Binary.iter();
+
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.clone().collect::<Vec<_>>(), vec![false, true]);
+        assert_eq!(iter.clone().rev().collect::<Vec<_>>(), vec![true, false]);
+        assert_eq!(iter.next_back(), Some(true));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(false));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        // Enumerating a product through the iterator matches enumerating it by index.
+        let space = crate::ProductSpace::new(vec![Binary; 4]);
+        let values: Vec<Vec<bool>> =
+            (0..4).map(|_| Binary.iter()).multi_cartesian_product().collect();
+
+        assert_eq!(values.len(), 16);
+        assert_eq!(values[5], vec![false, true, false, true]);
+        assert_eq!(values, space.iter_order(crate::EnumOrder::Lex).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_dim() {
         let d = Binary;
@@ -74,23 +157,48 @@ mod tests {
         assert_eq!(d.card(), Card::Finite(2));
     }
 
+    #[test]
+    fn test_len() {
+        let d = Binary;
+
+        assert_eq!(d.len(), 2);
+        assert!(!d.is_empty());
+    }
+
     #[test]
     fn test_bounds() {
         let d = Binary;
 
-        assert_eq!(d.inf().unwrap(), false);
-        assert_eq!(d.sup().unwrap(), true);
+        assert!(!d.inf().unwrap());
+        assert!(d.sup().unwrap());
 
         assert!(d.contains(false));
         assert!(d.contains(true));
     }
 
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let n_true = (0..1000).filter(|_| Binary.sample(&mut rng)).count();
+
+        assert!(n_true > 0 && n_true < 1000);
+    }
+
     #[test]
     fn test_surjection() {
         let d = Binary;
 
-        assert_eq!(d.map_onto(true), true);
-        assert_eq!(d.map_onto(false), false);
+        assert!(d.map_onto(true));
+        assert!(!d.map_onto(false));
+    }
+
+    #[test]
+    fn test_surjection_usize() {
+        let d = Binary;
+
+        assert!(!d.map_onto(0usize));
+        assert!(d.map_onto(1usize));
+        assert!(d.map_onto(5usize));
     }
 
     #[cfg(feature = "serialize")]
@@ -100,4 +208,9 @@ mod tests {
 
         assert_tokens(&d, &[Token::UnitStruct { name: "Binary" }]);
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Binary; sample, bounds, shape, finite, fmt, serde);
+    }
 }
@@ -5,7 +5,7 @@ use crate::{
 use std::fmt;
 
 /// Type representing the set of integers, Z.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Integers;
 
@@ -27,6 +27,8 @@ impl BoundedSpace for Integers {
 
 impl_union_intersect!(Integers, Integers);
 
+impl_unbounded_sample!(Integers);
+
 impl fmt::Display for Integers {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\u{2124}")
@@ -34,7 +36,7 @@ impl fmt::Display for Integers {
 }
 
 /// Type representing the set of non-zero integers, Z*.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct NonZeroIntegers;
 
@@ -56,6 +58,8 @@ impl BoundedSpace for NonZeroIntegers {
 
 impl_union_intersect!(NonZeroIntegers, NonZeroIntegers);
 
+impl_unbounded_sample!(NonZeroIntegers);
+
 impl fmt::Display for NonZeroIntegers {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\u{2124}(>0)")
@@ -63,7 +67,7 @@ impl fmt::Display for NonZeroIntegers {
 }
 
 /// Type representing the set of non-negative integers, Z(≥0).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct NonNegativeIntegers;
 
@@ -85,6 +89,8 @@ impl BoundedSpace for NonNegativeIntegers {
 
 impl_union_intersect!(NonNegativeIntegers, NonNegativeIntegers);
 
+impl_unbounded_sample!(NonNegativeIntegers);
+
 impl fmt::Display for NonNegativeIntegers {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\u{2124}(≥0)")
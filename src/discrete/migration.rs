@@ -0,0 +1,314 @@
+use crate::{discrete::Ordinal, prelude::*, try_map_each, ProductSpace, TableMap};
+use std::{error::Error, fmt};
+
+/// Error type for invalid migrations between ordinal spaces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MigrationError {
+    /// The target of an embedding has fewer values than the source.
+    TooSmall { from: usize, to: usize },
+    /// The target of a restriction has more values than the source.
+    TooLarge { from: usize, to: usize },
+    /// The fallback value is not contained in the target space.
+    InvalidFallback { fallback: usize, size: usize },
+    /// The product spaces, or fallbacks, do not have the same number of components.
+    ComponentMismatch { expected: usize, found: usize },
+    /// The migration of the component at `index` is invalid.
+    Component { index: usize, error: Box<MigrationError> },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::TooSmall { from, to } =>
+                write!(f, "cannot embed a space of size {} into one of size {}", from, to),
+            MigrationError::TooLarge { from, to } =>
+                write!(f, "cannot restrict a space of size {} to one of size {}", from, to),
+            MigrationError::InvalidFallback { fallback, size } =>
+                write!(f, "fallback {} is not contained in a space of size {}", fallback, size),
+            MigrationError::ComponentMismatch { expected, found } =>
+                write!(f, "expected {} components, found {}", expected, found),
+            MigrationError::Component { index, error } =>
+                write!(f, "component {}: {}", index, error),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+/// Mapping of stored values between two versions of an ordinal space.
+///
+/// Values shared by both spaces are mapped onto themselves; values beyond the end of the target
+/// space are mapped onto a fallback value. Embeddings into larger spaces never need the fallback.
+/// ```
+/// use spaces::{discrete::Ordinal, Surjection};
+///
+/// let old = Ordinal::new(7);
+/// let map = old.restrict_to(&Ordinal::new(5), 0).unwrap();
+///
+/// assert_eq!(map.map_onto(3), 3);
+/// assert_eq!(map.map_onto(6), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "MigrationRepr"))]
+pub struct Migration {
+    from: Ordinal,
+    to: Ordinal,
+    fallback: usize,
+}
+
+// Deserialized migrations are checked as by `Ordinal::embed_into` and `Ordinal::restrict_to`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Migration")]
+struct MigrationRepr {
+    from: Ordinal,
+    to: Ordinal,
+    fallback: usize,
+}
+
+#[cfg(feature = "serialize")]
+impl std::convert::TryFrom<MigrationRepr> for Migration {
+    type Error = MigrationError;
+
+    fn try_from(repr: MigrationRepr) -> Result<Migration, MigrationError> {
+        // Embeddings never use the fallback, and so are built without it.
+        if repr.to.size() > repr.from.size() || repr.to.size() == 0 {
+            repr.from.embed_into(&repr.to)
+        } else {
+            repr.from.restrict_to(&repr.to, repr.fallback)
+        }
+    }
+}
+
+impl Migration {
+    #[inline]
+    pub fn from(&self) -> Ordinal { self.from }
+
+    #[inline]
+    pub fn to(&self) -> Ordinal { self.to }
+
+    /// Returns the migration as an explicit lookup table, e.g. for composition with a
+    /// permutation via `TableMap::then`.
+    pub fn to_table(&self) -> TableMap<Ordinal, Ordinal> {
+        let table = (0..self.from.size()).map(|i| self.map_onto(i)).collect();
+
+        TableMap::new(self.from, self.to, table).unwrap()
+    }
+}
+
+/// # Panics
+///
+/// Panics if the value is not contained in the source space.
+impl Surjection<usize, usize> for Migration {
+    fn map_onto(&self, val: usize) -> usize {
+        assert!(self.from.contains(val), "Value is not contained in the domain.");
+
+        if val < self.to.size() { val } else { self.fallback }
+    }
+}
+
+impl Ordinal {
+    /// Returns the identity embedding of this space into `larger`.
+    pub fn embed_into(&self, larger: &Ordinal) -> Result<Migration, MigrationError> {
+        if larger.size() < self.size() {
+            return Err(MigrationError::TooSmall { from: self.size(), to: larger.size(), });
+        }
+
+        Ok(Migration { from: *self, to: *larger, fallback: 0, })
+    }
+
+    /// Returns the restriction of this space to `smaller`, mapping values that no longer exist
+    /// onto `fallback`.
+    pub fn restrict_to(
+        &self,
+        smaller: &Ordinal,
+        fallback: usize,
+    ) -> Result<Migration, MigrationError> {
+        if smaller.size() > self.size() {
+            return Err(MigrationError::TooLarge { from: self.size(), to: smaller.size(), });
+        }
+
+        if !smaller.contains(fallback) {
+            return Err(MigrationError::InvalidFallback { fallback, size: smaller.size(), });
+        }
+
+        Ok(Migration { from: *self, to: *smaller, fallback, })
+    }
+}
+
+/// Component-wise migration between two versions of a product of ordinal spaces.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ProductMigration(Vec<Migration>);
+
+impl ProductMigration {
+    pub fn components(&self) -> &[Migration] { &self.0 }
+}
+
+/// # Panics
+///
+/// Panics if there is not exactly one value per component; see `try_map_each`.
+impl Surjection<Vec<usize>, Vec<usize>> for ProductMigration {
+    fn map_onto(&self, val: Vec<usize>) -> Vec<usize> {
+        try_map_each(&self.0, val).unwrap_or_else(|e| panic!("Cannot map value: {}.", e))
+    }
+}
+
+fn check_components(expected: usize, found: usize) -> Result<(), MigrationError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(MigrationError::ComponentMismatch { expected, found, })
+    }
+}
+
+fn in_component(index: usize) -> impl Fn(MigrationError) -> MigrationError {
+    move |error| MigrationError::Component { index, error: Box::new(error), }
+}
+
+impl ProductSpace<Ordinal> {
+    /// Returns the component-wise identity embedding of this space into `larger`.
+    pub fn embed_into(
+        &self,
+        larger: &ProductSpace<Ordinal>,
+    ) -> Result<ProductMigration, MigrationError> {
+        check_components(self.iter().len(), larger.iter().len())?;
+
+        self.iter()
+            .zip(larger.iter())
+            .enumerate()
+            .map(|(i, (d, l))| d.embed_into(l).map_err(in_component(i)))
+            .collect::<Result<_, _>>()
+            .map(ProductMigration)
+    }
+
+    /// Returns the component-wise restriction of this space to `smaller`, with one fallback
+    /// value per component.
+    pub fn restrict_to(
+        &self,
+        smaller: &ProductSpace<Ordinal>,
+        fallbacks: &[usize],
+    ) -> Result<ProductMigration, MigrationError> {
+        check_components(self.iter().len(), smaller.iter().len())?;
+        check_components(self.iter().len(), fallbacks.len())?;
+
+        self.iter()
+            .zip(smaller.iter())
+            .zip(fallbacks)
+            .enumerate()
+            .map(|(i, ((d, s), &f))| d.restrict_to(s, f).map_err(in_component(i)))
+            .collect::<Result<_, _>>()
+            .map(ProductMigration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed() {
+        let map = Ordinal::new(5).embed_into(&Ordinal::new(7)).unwrap();
+
+        for i in 0..5 {
+            assert_eq!(map.map_onto(i), i);
+        }
+
+        assert_eq!(
+            Ordinal::new(7).embed_into(&Ordinal::new(5)),
+            Err(MigrationError::TooSmall { from: 7, to: 5 })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_embed_out_of_domain() {
+        Ordinal::new(5).embed_into(&Ordinal::new(7)).unwrap().map_onto(5);
+    }
+
+    #[test]
+    fn test_restrict() {
+        let map = Ordinal::new(7).restrict_to(&Ordinal::new(5), 2).unwrap();
+
+        assert_eq!((0..7).map(|i| map.map_onto(i)).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 2, 2]);
+        assert_eq!(
+            Ordinal::new(7).restrict_to(&Ordinal::new(5), 5),
+            Err(MigrationError::InvalidFallback { fallback: 5, size: 5 })
+        );
+        assert_eq!(
+            Ordinal::new(5).restrict_to(&Ordinal::new(7), 0),
+            Err(MigrationError::TooLarge { from: 5, to: 7 })
+        );
+    }
+
+    #[test]
+    fn test_permutation() {
+        let old = Ordinal::new(5);
+        let new = Ordinal::new(7);
+        let perm = TableMap::new(new, new, vec![6, 5, 4, 3, 2, 1, 0]).unwrap();
+        let map = old.embed_into(&new).unwrap().to_table().then(perm).unwrap();
+
+        assert_eq!(map.table(), &[6, 5, 4, 3, 2]);
+        assert_eq!(map.map_onto(1), 5);
+    }
+
+    #[test]
+    fn test_product() {
+        let old = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+        let new = ProductSpace::new(vec![Ordinal::new(5), Ordinal::new(2)]);
+
+        assert_eq!(
+            old.embed_into(&new),
+            Err(MigrationError::Component {
+                index: 1,
+                error: Box::new(MigrationError::TooSmall { from: 4, to: 2 }),
+            })
+        );
+        assert_eq!(
+            old.restrict_to(&new, &[0]),
+            Err(MigrationError::ComponentMismatch { expected: 2, found: 1 })
+        );
+
+        let narrow = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(2)]);
+        let map = old.restrict_to(&narrow, &[0, 1]).unwrap();
+
+        assert_eq!(map.map_onto(vec![2, 3]), vec![2, 1]);
+        assert_eq!(map.map_onto(vec![1, 0]), vec![1, 0]);
+
+        let wide = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(6)]);
+
+        assert_eq!(old.embed_into(&wide).unwrap().map_onto(vec![2, 3]), vec![2, 3]);
+        assert!(try_map_each(map.components(), vec![2]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot map value: expected 2 values, one per map, but found 3.")]
+    fn test_product_length() {
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+
+        space.embed_into(&space).unwrap().map_onto(vec![0, 1, 2]);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let map = Ordinal::new(7).restrict_to(&Ordinal::new(5), 2).unwrap();
+        let json = serde_json::to_string(&map).unwrap();
+
+        assert_eq!(serde_json::from_str::<Migration>(&json).unwrap(), map);
+
+        let err = |json: &str| serde_json::from_str::<Migration>(json).is_err();
+
+        assert!(err(r#"{"from":7,"to":5,"fallback":5}"#));
+        assert!(err(r#"{"from":7,"to":0,"fallback":0}"#));
+
+        let product = |fallback: usize| serde_json::from_str::<ProductMigration>(&format!(
+            r#"[{{"from":3,"to":3,"fallback":0}},{{"from":4,"to":2,"fallback":{}}}]"#, fallback
+        ));
+
+        assert!(product(1).is_ok());
+        assert!(product(2).is_err());
+    }
+}
@@ -2,7 +2,10 @@
 
 pub type Interval = crate::Interval<i64>;
 
+import_all!(alphabet);
 import_all!(binary);
 import_all!(ordinal);
 import_all!(naturals);
 import_all!(integers);
+import_all!(truncated);
+import_all!(migration);
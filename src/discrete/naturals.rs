@@ -1,11 +1,31 @@
-use crate::prelude::*;
+use crate::{discrete::Interval, prelude::*};
 use std::fmt;
 
 /// Type representing the set of natural numbers, N.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Naturals;
 
+impl Naturals {
+    /// Returns the space of natural numbers no greater than `n`, i.e. the integer interval
+    /// `[1, n]`, which is empty if `n` is zero.
+    ///
+    /// Integer intervals hold `i64` values, so `n` is silently capped at `i64::MAX`. Use
+    /// `Interval::try_from(Naturals.truncate(n))` to reject such bounds instead, or
+    /// `Naturals.truncate(n)` to keep them.
+    /// ```
+    /// use spaces::{discrete::Naturals, BoundedSpace, Card, Space};
+    ///
+    /// let space = Naturals::bounded_above(10);
+    ///
+    /// assert_eq!(space.card(), Card::Finite(10));
+    /// assert!(space.contains(10) && !space.contains(0));
+    /// ```
+    pub fn bounded_above(n: u64) -> Interval {
+        Interval::bounded(1, n.min(i64::MAX as u64) as i64)
+    }
+}
+
 impl Space for Naturals {
     type Value = u64;
 
@@ -26,8 +46,22 @@ impl Surjection<u64, u64> for Naturals {
     fn map_onto(&self, val: u64) -> u64 { val.max(1) }
 }
 
+/// Values are rounded to the nearest integer, clamped below at 1, and saturate at `u64::MAX`;
+/// `NaN` maps to 1.
+///
+/// Every `f64` above 2^53 is already an integer, so such values are mapped exactly, but not every
+/// integer above 2^53 is representable: e.g. `2^53 + 1` rounds to `2^53` as an `f64`.
+impl Surjection<f64, u64> for Naturals {
+    fn map_onto(&self, val: f64) -> u64 {
+        // Casts from floats saturate at the bounds of u64, and map NaN to zero.
+        (val.round() as u64).max(1)
+    }
+}
+
 impl_union_intersect!(Naturals, Naturals);
 
+impl_unbounded_sample!(Naturals);
+
 impl fmt::Display for Naturals {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\u{2115}")
@@ -36,8 +70,31 @@ impl fmt::Display for Naturals {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        discrete::{Integers, NonNegativeIntegers, NonZeroIntegers},
+        SampleError,
+    };
+    use std::convert::TryFrom;
     use super::*;
 
+    #[test]
+    fn test_default() {
+        fn make<S: Space + Default>() -> S { S::default() }
+
+        assert_eq!(make::<Naturals>(), Naturals);
+        assert_eq!(make::<Integers>().card(), Card::Infinite);
+    }
+
+    #[test]
+    fn test_try_sample() {
+        let mut rng = ::rand::thread_rng();
+
+        assert_eq!(Naturals.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(Integers.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(NonZeroIntegers.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(NonNegativeIntegers.try_sample(&mut rng), Err(SampleError::Unbounded));
+    }
+
     #[cfg(feature = "serialize")]
     extern crate serde_test;
     #[cfg(feature = "serialize")]
@@ -66,6 +123,10 @@ mod tests {
 
         assert!(d.contains(1));
         assert!(!d.contains(0));
+
+        assert!(d.is_lower_bounded());
+        assert!(!d.is_upper_bounded());
+        assert!(!d.is_bounded());
     }
 
     #[test]
@@ -74,6 +135,48 @@ mod tests {
 
         assert_eq!(d.map_onto(0), 1);
         assert_eq!(d.map_onto(1), 1);
+        assert_eq!(d.map_onto(7u64), 7);
+    }
+
+    #[test]
+    fn test_surjection_reals() {
+        let d = Naturals;
+        let map = |x: f64| -> u64 { d.map_onto(x) };
+
+        assert_eq!(map(2.4), 2);
+        assert_eq!(map(2.5), 3);
+        assert_eq!(map(0.4), 1);
+        assert_eq!(map(-3.0), 1);
+        assert_eq!(map(f64::NEG_INFINITY), 1);
+        assert_eq!(map(f64::NAN), 1);
+        assert_eq!(map(f64::INFINITY), u64::MAX);
+        assert_eq!(map(1e30), u64::MAX);
+
+        // Above 2^53, floats are spaced more than one apart and are mapped exactly.
+        let big = 2f64.powi(53);
+
+        assert_eq!(map(big), 1 << 53);
+        assert_eq!(map(big + 1.0), 1 << 53);
+        assert_eq!(map(big + 2.0), (1 << 53) + 2);
+        assert_eq!(map(2f64.powi(60) + 0.5), 1 << 60);
+    }
+
+    #[test]
+    fn test_bounded_above() {
+        let space = Naturals::bounded_above(5);
+
+        assert_eq!(space.card(), Card::Finite(5));
+        assert_eq!(space.inf(), Some(1));
+        assert_eq!(space.sup(), Some(5));
+        assert!(space.contains(1) && space.contains(5));
+        assert!(!space.contains(0) && !space.contains(6));
+
+        assert_eq!(Naturals::bounded_above(1).card(), Card::Finite(1));
+        assert_eq!(Naturals::bounded_above(0).card(), Card::Finite(0));
+
+        // Bounds beyond the range of `i64` are capped, unlike the checked conversion.
+        assert_eq!(Naturals::bounded_above(u64::MAX).sup(), Some(i64::MAX));
+        assert!(Interval::try_from(Naturals.truncate(u64::MAX)).is_err());
     }
 
     #[cfg(feature = "serialize")]
@@ -83,4 +186,13 @@ mod tests {
 
         assert_tokens(&d, &[Token::UnitStruct { name: "Naturals" }]);
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Naturals; sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Integers; sample, bounds, shape, fmt);
+        assert_space_laws!(NonZeroIntegers; sample, bounds, shape, fmt);
+        assert_space_laws!(NonNegativeIntegers; sample, bounds, shape, fmt);
+        assert_space_laws!(Naturals::bounded_above(10); sample, bounds, shape, fmt, serde);
+    }
 }
@@ -1,95 +1,537 @@
-use crate::prelude::*;
-use std::{cmp, fmt, ops::Range};
+use crate::{discrete::Binary, prelude::*, RowsError, SampleError, SizeLimitError};
+use std::{
+    cmp,
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+/// Unsigned integer types that may be used as the values of an `Ordinal` space.
+///
+/// This is implemented for `u8`, `u16`, `u32` and `usize`; narrower types reduce the memory
+/// needed to store large numbers of values, e.g. in replay buffers.
+pub trait OrdinalIndex: Copy + Ord + fmt::Debug + fmt::Display + 'static {
+    /// The largest representable value, as a `u64`.
+    const MAX: u64;
+
+    /// Convert from a `usize`, which must be at most `MAX`.
+    fn from_usize(i: usize) -> Self;
+
+    /// Convert into a `usize`, losslessly.
+    fn into_usize(self) -> usize;
+}
+
+macro_rules! impl_ordinal_index {
+    ($($t:ty),+) => {$(
+        impl OrdinalIndex for $t {
+            const MAX: u64 = <$t>::MAX as u64;
+
+            #[inline]
+            fn from_usize(i: usize) -> $t { i as $t }
+
+            #[inline]
+            fn into_usize(self) -> usize { self as usize }
+        }
+    )+};
+}
+
+impl_ordinal_index!(u8, u16, u32, usize);
+
+/// Error returned when the values of an `Ordinal` space do not fit in its value type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexTypeError {
+    /// Requested size of the space.
+    pub size: usize,
+    /// Largest value representable by the value type.
+    pub max: u64,
+}
+
+impl fmt::Display for IndexTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "an ordinal space of size {} cannot be represented by values of at most {}",
+            self.size, self.max
+        )
+    }
+}
+
+impl Error for IndexTypeError {}
+
+/// Error returned by the batch one-hot encoding and decoding of `Ordinal` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneHotError {
+    /// The value at position `row` of the batch is not contained in the space.
+    OutOfRange { row: usize, value: usize },
+    /// The encoded batch would be too large to materialise.
+    SizeLimit(SizeLimitError),
+    /// A row of the encoded batch does not have one column per value of the space.
+    Rows(RowsError),
+}
+
+impl fmt::Display for OneHotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OneHotError::OutOfRange { row, value } =>
+                write!(f, "value {} at row {} is not contained in the space", value, row),
+            OneHotError::SizeLimit(e) => e.fmt(f),
+            OneHotError::Rows(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for OneHotError {}
 
 /// Type representing a finite, ordinal set of values.
-#[derive(Debug, Clone, Copy)]
+///
+/// Values are of type `I`, which defaults to `usize`; narrower types may be used via
+/// `Ordinal::try_new`, which checks that every value of the space fits:
+/// ```
+/// use spaces::{discrete::Ordinal, FiniteSpace};
+///
+/// let d = Ordinal::<u8>::try_new(256).unwrap();
+///
+/// assert_eq!(d.iter().last(), Some(255u8));
+/// assert!(Ordinal::<u8>::try_new(257).is_err());
+/// ```
+///
+/// Sizes are stored, and serialised, as `usize` regardless of the value type, such that the
+/// serialised form does not depend on `I`.
+///
+/// Note: spaces of any size up to `usize::MAX` may be constructed. Iteration is lazy but may be
+/// astronomically long for such sizes, and methods that materialise the space (e.g. `one_hot`)
+/// are guarded by `MATERIALISE_LIMIT`.
+///
+/// Note: unlike `Interval`, `Ordinal` deliberately does not implement `Mul<usize>`. Scaling the
+/// values `{0, ..., n-1}` by `k` yields `{0, k, ..., k(n-1)}`, which is not itself an `Ordinal`, so
+/// there is no scaled space for which the matching value map would be a surjection.
+///
+/// The default space has size 2, matching `Binary`.
+#[derive(Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct Ordinal(usize);
+#[cfg_attr(feature = "serialize", serde(
+    into = "OrdinalRepr",
+    try_from = "OrdinalRepr",
+    bound = "I: OrdinalIndex"
+))]
+pub struct Ordinal<I = usize>(usize, PhantomData<I>);
+
+#[cfg(feature = "serialize")]
+#[derive(Serialize)]
+#[serde(rename = "Ordinal")]
+struct OrdinalRepr(usize);
+
+// Human-readable formats also accept the maps `{"size": n}` and `{"n": n}` written by older forks
+// of this crate; binary formats only ever held the bare size.
+#[cfg(feature = "serialize")]
+impl<'de> serde::Deserialize<'de> for OrdinalRepr {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<OrdinalRepr, D::Error> {
+        use serde::{de::{self, MapAccess, Visitor}, Deserialize};
+
+        struct SizeVisitor;
+
+        impl<'de> Visitor<'de> for SizeVisitor {
+            type Value = usize;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "the size of an ordinal space")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+                usize::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<usize, E> {
+                usize::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<usize, A::Error> {
+                let mut size = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "size" | "n" if size.is_some() => {
+                            return Err(de::Error::duplicate_field("size"))
+                        },
+                        "size" | "n" => size = Some(map.next_value()?),
+                        _ => return Err(de::Error::unknown_field(&key, &["size", "n"])),
+                    }
+                }
+
+                size.ok_or_else(|| de::Error::missing_field("size"))
+            }
+
+            fn visit_newtype_struct<D: serde::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<usize, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(self)
+                } else {
+                    usize::deserialize(deserializer)
+                }
+            }
+        }
 
-impl Ordinal {
+        deserializer.deserialize_newtype_struct("Ordinal", SizeVisitor).map(OrdinalRepr)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<I: OrdinalIndex> From<Ordinal<I>> for OrdinalRepr {
+    fn from(d: Ordinal<I>) -> OrdinalRepr { OrdinalRepr(d.0) }
+}
+
+#[cfg(feature = "serialize")]
+impl<I: OrdinalIndex> TryFrom<OrdinalRepr> for Ordinal<I> {
+    type Error = IndexTypeError;
+
+    fn try_from(repr: OrdinalRepr) -> Result<Ordinal<I>, IndexTypeError> {
+        Ordinal::try_new(repr.0)
+    }
+}
 
+impl Ordinal {
     pub fn new(size: usize) -> Ordinal {
-        Ordinal(size)
+        Ordinal(size, PhantomData)
+    }
+
+    /// Maps `val` onto the space, clamping values beyond the end of the space to the largest
+    /// value.
+    ///
+    /// Values that do not fit in a `usize` (e.g. on 32-bit targets) are clamped in the same way.
+    pub fn map_clamped(&self, val: u64) -> usize {
+        let max = self.0.saturating_sub(1);
+
+        usize::try_from(val).map_or(max, |v| v.min(max))
+    }
+
+    /// Maps `val` onto the space by taking its remainder modulo the size of the space.
+    ///
+    /// Note: unless the size of the space divides `2^64`, uniformly random inputs do not map onto
+    /// uniformly random values; the lowest `2^64 mod n` values are very slightly more likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the space is empty.
+    pub fn map_modulo(&self, val: u64) -> usize {
+        if self.0 == 0 { panic!("Cannot map onto an empty space.") }
+
+        (val % self.0 as u64) as usize
     }
 }
 
+impl<I: OrdinalIndex> Ordinal<I> {
+    /// Construct a space of `size` values of type `I`, or return an error if the largest value,
+    /// `size - 1`, is not representable by `I`.
+    pub fn try_new(size: usize) -> Result<Ordinal<I>, IndexTypeError> {
+        if size > 0 && (size - 1) as u64 > I::MAX {
+            Err(IndexTypeError { size, max: I::MAX, })
+        } else {
+            Ok(Ordinal(size, PhantomData))
+        }
+    }
+
+    /// Returns the number of values in the space.
+    #[inline]
+    pub fn size(&self) -> usize { self.0 }
+
+    /// Returns the one-hot encoding of `val`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` is not contained in the space.
+    pub fn one_hot(&self, val: I) -> Result<Vec<f64>, SizeLimitError> {
+        let mut encoding = vec![];
+
+        self.one_hot_into(val, &mut encoding).map(|_| encoding)
+    }
+
+    /// Write the one-hot encoding of `val` into `out`, reusing its allocation.
+    ///
+    /// `out` is resized to the size of the space, and its previous contents are discarded; it
+    /// is left untouched if an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` is not contained in the space.
+    pub fn one_hot_into(&self, val: I, out: &mut Vec<f64>) -> Result<(), SizeLimitError> {
+        assert!(self.contains(val), "Value is not contained in the space.");
+
+        let n = SizeLimitError::check(self.0)?;
+
+        out.clear();
+        out.resize(n, 0.0);
+        out[val.into_usize()] = 1.0;
+
+        Ok(())
+    }
+
+    /// Returns the one-hot encodings of `vals`, as a row-major matrix with one row per value and
+    /// one column per value of the space.
+    ///
+    /// The matrix is written in place into a single zeroed buffer, rather than assembled from
+    /// the encodings of each value.
+    /// ```
+    /// use spaces::discrete::Ordinal;
+    ///
+    /// let d = Ordinal::new(3);
+    /// let encoded = d.one_hot_batch(&[2, 0]).unwrap();
+    ///
+    /// assert_eq!(encoded, vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    /// assert_eq!(d.from_one_hot_batch(&encoded), Ok(vec![2, 0]));
+    /// ```
+    pub fn one_hot_batch(&self, vals: &[I]) -> Result<Vec<f64>, OneHotError> {
+        if let Some((row, val)) = vals.iter().enumerate().find(|(_, v)| !self.contains(**v)) {
+            return Err(OneHotError::OutOfRange { row, value: val.into_usize() });
+        }
+
+        let n = vals.len().saturating_mul(self.0);
+        let mut encoded = vec![0.0; SizeLimitError::check(n).map_err(OneHotError::SizeLimit)?];
+
+        for (i, val) in vals.iter().enumerate() {
+            encoded[i * self.0 + val.into_usize()] = 1.0;
+        }
+
+        Ok(encoded)
+    }
+
+    /// Decode a row-major matrix of encodings, as produced by `one_hot_batch`, taking the
+    /// position of the largest entry of each row; ties are broken towards the first.
+    ///
+    /// Rows need not be exact one-hot encodings, such that e.g. the scores or probabilities
+    /// output by a classifier may be decoded directly.
+    pub fn from_one_hot_batch(&self, encoded: &[f64]) -> Result<Vec<I>, OneHotError> {
+        if self.0 == 0 {
+            return if encoded.is_empty() {
+                Ok(vec![])
+            } else {
+                Err(OneHotError::Rows(RowsError { row: 0, expected: 0, found: encoded.len(), }))
+            };
+        }
+
+        crate::rows::check_input(encoded, self.0).map_err(OneHotError::Rows)?;
+
+        Ok(encoded.chunks(self.0).map(|row| {
+            I::from_usize((1..row.len()).fold(0, |i, j| if row[j] > row[i] { j } else { i }))
+        }).collect())
+    }
+}
+
+impl Default for Ordinal {
+    fn default() -> Ordinal { Ordinal::new(2) }
+}
+
 impl From<usize> for Ordinal {
     fn from(t: usize) -> Ordinal {
         Ordinal::new(t)
     }
 }
 
-impl Space for Ordinal {
-    type Value = usize;
+impl From<Binary> for Ordinal {
+    fn from(_: Binary) -> Ordinal {
+        Ordinal::new(2)
+    }
+}
+
+impl<I: OrdinalIndex> Space for Ordinal<I> {
+    type Value = I;
 
     fn dim(&self) -> Dim { Dim::one() }
 
     fn card(&self) -> Card { Card::Finite(self.0) }
 }
 
-impl BoundedSpace for Ordinal {
-    fn inf(&self) -> Option<usize> { Some(0) }
+impl<I: OrdinalIndex> Sample for Ordinal<I> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> I {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from ordinal: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<I, SampleError> {
+        if self.0 == 0 { return Err(SampleError::Empty); }
+
+        Ok(I::from_usize(rng.gen_range(0..self.0)))
+    }
+}
+
+impl<I: OrdinalIndex> BoundedSpace for Ordinal<I> {
+    /// The empty ordinal has neither an infimum nor a supremum.
+    fn inf(&self) -> Option<I> { if self.0 == 0 { None } else { Some(I::from_usize(0)) } }
 
-    fn sup(&self) -> Option<usize> { Some(self.0 - 1) }
+    fn sup(&self) -> Option<I> { self.0.checked_sub(1).map(I::from_usize) }
 
-    fn contains(&self, val: usize) -> bool { val < self.0 }
+    fn contains(&self, val: I) -> bool { val.into_usize() < self.0 }
 }
 
-impl FiniteSpace for Ordinal {
+impl<I: OrdinalIndex> FiniteSpace for Ordinal<I> {
     type Iter = Self::IntoIter;
 
     fn iter(&self) -> Self::Iter {
-        self.clone().into_iter()
+        self.into_iter()
     }
-}
 
-impl IntoIterator for Ordinal {
-    type Item = <Self as Space>::Value;
-    type IntoIter = std::ops::Range<Self::Item>;
+    fn len(&self) -> usize { self.0 }
+
+    fn is_empty(&self) -> bool { self.0 == 0 }
+
+    fn to_index(&self, val: &I) -> Option<usize> {
+        if self.contains(*val) { Some(val.into_usize()) } else { None }
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.range()
+    fn from_index(&self, idx: usize) -> Option<I> {
+        if idx < self.0 { Some(I::from_usize(idx)) } else { None }
     }
 }
 
-impl FiniteOrderedSpace for Ordinal {
-    fn range(&self) -> Range<Self::Value> { 0..self.0 }
+impl<I: OrdinalIndex> IntoIterator for Ordinal<I> {
+    type Item = I;
+    type IntoIter = OrdinalIter<I>;
+
+    fn into_iter(self) -> OrdinalIter<I> { OrdinalIter { range: 0..self.0, index: PhantomData, } }
+}
+
+/// Iterator over the values of an `Ordinal` space, in ascending order.
+///
+/// Values are counted as `usize` and converted on the fly, since `Range<I>` is not an iterator
+/// for generic `I`.
+#[derive(Clone, Debug)]
+pub struct OrdinalIter<I> {
+    range: Range<usize>,
+    index: PhantomData<I>,
+}
+
+impl<I: OrdinalIndex> Iterator for OrdinalIter<I> {
+    type Item = I;
+
+    fn next(&mut self) -> Option<I> { self.range.next().map(I::from_usize) }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.range.size_hint() }
+
+    fn nth(&mut self, n: usize) -> Option<I> { self.range.nth(n).map(I::from_usize) }
+}
+
+impl<I: OrdinalIndex> DoubleEndedIterator for OrdinalIter<I> {
+    fn next_back(&mut self) -> Option<I> { self.range.next_back().map(I::from_usize) }
 }
 
-impl Union for Ordinal {
-    fn union(self, other: &Ordinal) -> Ordinal {
-        Ordinal::new(self.0.max(other.0))
+impl<I: OrdinalIndex> ExactSizeIterator for OrdinalIter<I> {}
+
+impl<I: OrdinalIndex> FusedIterator for OrdinalIter<I> {}
+
+impl<I: OrdinalIndex> FiniteOrderedSpace for Ordinal<I> {
+    fn range(&self) -> RangeInclusive<I> {
+        match self.0.checked_sub(1) {
+            Some(last) => I::from_usize(0)..=I::from_usize(last),
+            None => I::from_usize(1)..=I::from_usize(0),
+        }
     }
 }
 
-impl Intersection for Ordinal {
-    fn intersect(self, other: &Ordinal) -> Ordinal {
-        Ordinal::new(self.0.min(other.0))
+impl<I: OrdinalIndex> Union for Ordinal<I> {
+    fn union(self, other: &Ordinal<I>) -> Ordinal<I> {
+        Ordinal(self.0.max(other.0), PhantomData)
     }
 }
 
-impl Surjection<usize, usize> for Ordinal {
-    fn map_onto(&self, val: usize) -> usize { val as usize }
+impl<I: OrdinalIndex> Intersection for Ordinal<I> {
+    fn intersect(self, other: &Ordinal<I>) -> Ordinal<I> {
+        Ordinal(self.0.min(other.0), PhantomData)
+    }
+}
+
+/// Maps `val` onto itself, clamping values beyond the end of the space to the largest value.
+impl<I: OrdinalIndex> Surjection<I, I> for Ordinal<I> {
+    fn map_onto(&self, val: I) -> I { val.min(I::from_usize(self.0.saturating_sub(1))) }
+}
+
+/// Maps `val` onto the space using `Ordinal::map_clamped`.
+impl Surjection<u64, usize> for Ordinal {
+    fn map_onto(&self, val: u64) -> usize { self.map_clamped(val) }
+}
+
+/// Maps `false` to `0` and `true` to `1`, clamped to the largest value as in
+/// `Ordinal::map_clamped`.
+impl Surjection<bool, usize> for Ordinal {
+    fn map_onto(&self, val: bool) -> usize { self.map_clamped(val as u64) }
 }
 
-impl cmp::PartialEq for Ordinal {
-    fn eq(&self, other: &Ordinal) -> bool { self.0.eq(&other.0) }
+impl<I> cmp::PartialEq for Ordinal<I> {
+    fn eq(&self, other: &Ordinal<I>) -> bool { self.0.eq(&other.0) }
+}
+
+impl<I> fmt::Debug for Ordinal<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Ordinal").field(&self.0).finish()
+    }
 }
 
-impl fmt::Display for Ordinal {
+impl<I: OrdinalIndex> fmt::Display for Ordinal<I> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[0..{}]", self.sup().unwrap())
+        match self.sup() {
+            Some(sup) => write!(f, "[0..{}]", sup),
+            None => write!(f, "\u{2205}"),
+        }
+    }
+}
+
+/// Proportional surjection between two differently-sized `Ordinal` spaces.
+///
+/// The value `i` of `from` is mapped onto `floor(i * to / from)`, such that the values of `from`
+/// are split into (nearly) equally-sized, contiguous buckets. Values beyond the end of `from` are
+/// first clamped to its largest value.
+/// ```
+/// use spaces::{discrete::{Ordinal, Rescale}, Surjection};
+///
+/// let map = Rescale::new(Ordinal::new(10), Ordinal::new(3));
+///
+/// assert_eq!(map.map_onto(0), 0);
+/// assert_eq!(map.map_onto(5), 1);
+/// assert_eq!(map.map_onto(9), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Rescale {
+    pub from: Ordinal,
+    pub to: Ordinal,
+}
+
+impl Rescale {
+    pub fn new(from: Ordinal, to: Ordinal) -> Rescale {
+        if from.0 == 0 || to.0 == 0 {
+            panic!("Cannot rescale between empty spaces.")
+        }
+
+        Rescale { from, to, }
+    }
+}
+
+impl Surjection<usize, usize> for Rescale {
+    fn map_onto(&self, val: usize) -> usize {
+        let val = self.from.map_onto(val) as u128;
+
+        (val * self.to.0 as u128 / self.from.0 as u128) as usize
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::MATERIALISE_LIMIT;
     use super::*;
 
     #[cfg(feature = "serialize")]
     extern crate serde_test;
     #[cfg(feature = "serialize")]
-    use self::serde_test::{assert_tokens, Token};
+    use self::serde_test::{
+        assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+    };
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Ordinal::default().size(), 2);
+    }
 
     #[test]
     fn test_card() {
@@ -102,6 +544,21 @@ mod tests {
         check(5);
         check(10);
         check(100);
+
+        assert_eq!(Ordinal::new(0).inf(), None);
+        assert_eq!(Ordinal::new(0).sup(), None);
+        assert_eq!(Ordinal::new(0).to_string(), "\u{2205}");
+    }
+
+    #[test]
+    fn test_len() {
+        for &size in &[0, 1, 5, 10] {
+            let d = Ordinal::new(size);
+
+            assert_eq!(d.size(), size);
+            assert_eq!(d.len(), size);
+            assert_eq!(d.is_empty(), size == 0);
+        }
     }
 
     #[test]
@@ -122,27 +579,322 @@ mod tests {
         check(100);
     }
 
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let d = Ordinal::new(5);
+        let mut seen = [false; 5];
+
+        for _ in 0..1000 {
+            let v = d.sample(&mut rng);
+
+            assert!(d.contains(v));
+
+            seen[v] = true;
+        }
+
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_try_sample() {
+        let mut rng = ::rand::thread_rng();
+
+        assert!(Ordinal::new(1).try_sample(&mut rng).is_ok());
+        assert_eq!(Ordinal::new(0).try_sample(&mut rng), Err(SampleError::Empty));
+    }
+
     #[test]
     fn test_range() {
-        assert_eq!(Ordinal::new(1).range(), 0..1);
-        assert_eq!(Ordinal::new(5).range(), 0..5);
-        assert_eq!(Ordinal::new(10).range(), 0..10);
+        assert_eq!(Ordinal::new(1).range(), 0..=0);
+        assert_eq!(Ordinal::new(5).range(), 0..=4);
+        assert_eq!(Ordinal::new(10).range(), 0..=9);
+        assert!(Ordinal::new(0).range().is_empty());
+
+        // The range ends at the largest value of the index type, rather than wrapping past it.
+        let bytes = Ordinal::<u8>::try_new(256).unwrap();
+
+        assert_eq!(bytes.range(), 0..=u8::MAX);
+        assert_eq!(bytes.range().count(), bytes.len());
+        assert!(bytes.range().eq(bytes.iter()));
     }
 
     #[test]
     fn test_surjection() {
         let d = Ordinal::new(10);
 
-        assert_eq!(d.map_onto(0), 0);
-        assert_eq!(d.map_onto(1), 1);
-        assert_eq!(d.map_onto(2), 2);
-        assert_eq!(d.map_onto(3), 3);
-        assert_eq!(d.map_onto(4), 4);
-        assert_eq!(d.map_onto(5), 5);
-        assert_eq!(d.map_onto(6), 6);
-        assert_eq!(d.map_onto(7), 7);
-        assert_eq!(d.map_onto(8), 8);
-        assert_eq!(d.map_onto(9), 9);
+        assert_eq!(d.map_onto(0usize), 0);
+        assert_eq!(d.map_onto(1usize), 1);
+        assert_eq!(d.map_onto(2usize), 2);
+        assert_eq!(d.map_onto(3usize), 3);
+        assert_eq!(d.map_onto(4usize), 4);
+        assert_eq!(d.map_onto(5usize), 5);
+        assert_eq!(d.map_onto(6usize), 6);
+        assert_eq!(d.map_onto(7usize), 7);
+        assert_eq!(d.map_onto(8usize), 8);
+        assert_eq!(d.map_onto(9usize), 9);
+
+        assert_eq!(d.map_onto(10usize), 9);
+        assert_eq!(d.map_onto(250usize), 9);
+        assert_eq!(d.map_onto(usize::MAX), 9);
+    }
+
+    #[test]
+    fn test_map_u64() {
+        let d = Ordinal::new(10);
+
+        assert_eq!(d.map_clamped(3), 3);
+        assert_eq!(d.map_clamped(10), 9);
+        assert_eq!(d.map_clamped(u64::MAX), 9);
+        assert_eq!(d.map_onto(250u64), 9);
+
+        assert_eq!(d.map_modulo(3), 3);
+        assert_eq!(d.map_modulo(13), 3);
+        assert_eq!(d.map_modulo(u64::MAX), 5);
+
+        // Values around the 32-bit boundary must not be truncated.
+        let boundary = u64::from(u32::MAX);
+        let d = Ordinal::new(u32::MAX as usize);
+
+        assert_eq!(d.map_clamped(boundary - 1), u32::MAX as usize - 1);
+        assert_eq!(d.map_clamped(boundary), u32::MAX as usize - 1);
+        assert_eq!(d.map_clamped(boundary + 1), u32::MAX as usize - 1);
+
+        assert_eq!(d.map_modulo(boundary - 1), u32::MAX as usize - 1);
+        assert_eq!(d.map_modulo(boundary), 0);
+        assert_eq!(d.map_modulo(boundary + 1), 1);
+        assert_eq!(Ordinal::new(3).map_modulo(boundary + 1), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_modulo_empty() {
+        Ordinal::new(0).map_modulo(1);
+    }
+
+    #[test]
+    fn test_rescale() {
+        let down = Rescale::new(Ordinal::new(10), Ordinal::new(3));
+
+        assert_eq!((0..10).map(|i| down.map_onto(i)).collect::<Vec<_>>(), vec![
+            0, 0, 0, 0, 1, 1, 1, 2, 2, 2
+        ]);
+        assert_eq!(down.map_onto(10), 2);
+        assert_eq!(down.map_onto(250), 2);
+
+        let up = Rescale::new(Ordinal::new(3), Ordinal::new(10));
+
+        assert_eq!((0..3).map(|i| up.map_onto(i)).collect::<Vec<_>>(), vec![0, 3, 6]);
+
+        let huge = Rescale::new(Ordinal::new(usize::MAX), Ordinal::new(4));
+
+        assert_eq!(huge.map_onto(usize::MAX - 1), 3);
+        assert_eq!(huge.map_onto(usize::MAX / 4), 0);
+        assert_eq!(huge.map_onto(usize::MAX / 4 + 1), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rescale_empty() {
+        Rescale::new(Ordinal::new(0), Ordinal::new(3));
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let d = Ordinal::new(4);
+
+        assert_eq!(d.one_hot(0), Ok(vec![1.0, 0.0, 0.0, 0.0]));
+        assert_eq!(d.one_hot(3), Ok(vec![0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_one_hot_into() {
+        let d = Ordinal::new(4);
+        let mut out = vec![2.0; 7];
+
+        for i in d.iter() {
+            assert_eq!(d.one_hot_into(i, &mut out), Ok(()));
+            assert_eq!(Ok(out.clone()), d.one_hot(i));
+        }
+
+        out.clear();
+
+        assert_eq!(d.one_hot_into(1, &mut out), Ok(()));
+        assert_eq!(out, vec![0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_one_hot_batch() {
+        let d = Ordinal::new(4);
+        let vals = [3, 0, 2, 3, 1];
+        let encoded = d.one_hot_batch(&vals).unwrap();
+        let naive: Vec<f64> = vals.iter().flat_map(|&v| d.one_hot(v).unwrap()).collect();
+
+        assert_eq!(encoded, naive);
+        assert_eq!(d.from_one_hot_batch(&encoded), Ok(vals.to_vec()));
+
+        for row in encoded.chunks(4) {
+            assert_eq!(row.iter().filter(|&&x| x == 1.0).count(), 1);
+            assert_eq!(row.iter().sum::<f64>(), 1.0);
+        }
+
+        assert_eq!(d.one_hot_batch(&[]), Ok(vec![]));
+        assert_eq!(d.from_one_hot_batch(&[]), Ok(vec![]));
+        assert_eq!(d.one_hot_batch(&[1]), Ok(vec![0.0, 1.0, 0.0, 0.0]));
+        assert_eq!(d.from_one_hot_batch(&[0.0, 1.0, 0.0, 0.0]), Ok(vec![1]));
+
+        let d = Ordinal::<u8>::try_new(256).unwrap();
+        let encoded = d.one_hot_batch(&[255, 0, 255]).unwrap();
+
+        assert_eq!(encoded.len(), 3 * 256);
+        assert_eq!((encoded[255], encoded[256], encoded[767]), (1.0, 1.0, 1.0));
+        assert_eq!(d.from_one_hot_batch(&encoded), Ok(vec![255, 0, 255]));
+    }
+
+    #[test]
+    fn test_from_one_hot_batch_argmax() {
+        let d = Ordinal::new(3);
+
+        assert_eq!(d.from_one_hot_batch(&[0.2, 0.5, 0.3, 0.0, 0.0, 0.0, -1.0, 4.0, 4.0]),
+            Ok(vec![1, 0, 1]));
+        assert_eq!(Ordinal::new(0).from_one_hot_batch(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_one_hot_batch_errors() {
+        let d = Ordinal::new(4);
+
+        assert_eq!(
+            d.one_hot_batch(&[0, 3, 4, 9]),
+            Err(OneHotError::OutOfRange { row: 2, value: 4 })
+        );
+        assert_eq!(
+            d.from_one_hot_batch(&[0.0; 6]),
+            Err(OneHotError::Rows(RowsError { row: 1, expected: 4, found: 2, }))
+        );
+        assert_eq!(
+            Ordinal::new(0).from_one_hot_batch(&[1.0]),
+            Err(OneHotError::Rows(RowsError { row: 0, expected: 0, found: 1, }))
+        );
+        assert_eq!(
+            Ordinal::new(usize::MAX).one_hot_batch(&[0]),
+            Err(OneHotError::SizeLimit(SizeLimitError {
+                size: usize::MAX,
+                limit: MATERIALISE_LIMIT,
+            }))
+        );
+        assert_eq!(
+            Ordinal::new(MATERIALISE_LIMIT).one_hot_batch(&[0, 1]),
+            Err(OneHotError::SizeLimit(SizeLimitError {
+                size: 2 * MATERIALISE_LIMIT,
+                limit: MATERIALISE_LIMIT,
+            }))
+        );
+        assert_eq!(
+            d.one_hot_batch(&[7]).unwrap_err().to_string(),
+            "value 7 at row 0 is not contained in the space"
+        );
+    }
+
+    #[test]
+    fn test_huge() {
+        for &size in &[usize::MAX, u32::MAX as usize] {
+            let d = Ordinal::new(size);
+
+            assert_eq!(d.sup(), Some(size - 1));
+            assert!(d.contains(size - 1));
+            assert!(!d.contains(size));
+            assert_eq!(d.iter().nth(3), Some(3));
+
+            assert_eq!(d.one_hot(size - 1), Err(SizeLimitError { size, limit: MATERIALISE_LIMIT }));
+            assert_eq!(d.try_collect_all(), Err(SizeLimitError { size, limit: MATERIALISE_LIMIT }));
+        }
+
+        assert_eq!(Ordinal::new(3).try_collect_all(), Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_sample_unbiased() {
+        // With n = 2/3 of the range of usize, a naive `rng.gen::<usize>() % n` would return
+        // values below n / 2 with probability 2/3 rather than 1/2.
+        let mut rng = ::rand::thread_rng();
+        let d = Ordinal::new(usize::MAX / 3 * 2);
+        let n_low = (0..10_000).filter(|_| d.sample(&mut rng) < d.size() / 2).count();
+
+        assert!(n_low > 4_700 && n_low < 5_300, "n_low = {}", n_low);
+    }
+
+    #[test]
+    fn test_binary_interop() {
+        let d = Ordinal::from(Binary);
+
+        assert_eq!(d, Ordinal::new(2));
+        assert_eq!(d.card(), Binary.card());
+
+        assert_eq!(d.map_onto(false), 0);
+        assert_eq!(d.map_onto(true), 1);
+        assert_eq!(Ordinal::new(1).map_onto(true), 0);
+        assert_eq!(Ordinal::new(1).map_onto(false), 0);
+
+        for v in d.iter() {
+            let b: bool = Binary.map_onto(v);
+            let i: usize = d.map_onto(b);
+
+            assert_eq!(i, v);
+        }
+    }
+
+    #[test]
+    fn test_narrow_index() {
+        let mut rng = ::rand::thread_rng();
+        let d = Ordinal::<u8>::try_new(256).unwrap();
+
+        assert_eq!(d.card(), Card::Finite(256));
+        assert_eq!(d.sup(), Some(255u8));
+        assert_eq!(d.iter().len(), 256);
+        assert!(d.iter().map(usize::from).eq(0..256));
+        assert_eq!(d.iter().rev().nth(1), Some(254));
+        assert_eq!(d.to_index(&200), Some(200));
+        assert_eq!(d.from_index(256), None);
+        assert_eq!(d.one_hot(3).unwrap()[3], 1.0);
+        assert_eq!(d.map_onto(7u8), 7);
+
+        for _ in 0..100 {
+            let v: u8 = d.sample(&mut rng);
+
+            assert!(d.contains(v));
+        }
+
+        assert_eq!(
+            Ordinal::<u8>::try_new(257),
+            Err(IndexTypeError { size: 257, max: 255 })
+        );
+        assert_eq!(Ordinal::<u16>::try_new(65_536).map(|d| d.sup()), Ok(Some(u16::MAX)));
+        assert!(Ordinal::<u16>::try_new(65_537).is_err());
+        assert_eq!(Ordinal::<u8>::try_new(0).map(|d| d.is_empty()), Ok(true));
+
+        let small = Ordinal::<u16>::try_new(10).unwrap();
+
+        assert_eq!(small.map_onto(100u16), 9);
+        assert_eq!(small.range(), 0..=9);
+        assert_eq!(small.to_string(), "[0..9]");
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_narrow_serialisation() {
+        extern crate serde_json;
+
+        let d = Ordinal::<u8>::try_new(256).unwrap();
+        let json = serde_json::to_string(&d).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&Ordinal::new(256)).unwrap());
+        assert_eq!(serde_json::from_str::<Ordinal<u8>>(&json).unwrap(), d);
+        assert!(serde_json::from_str::<Ordinal<u8>>("257").is_err());
+        assert_tokens(
+            &Ordinal::<u32>::try_new(5).unwrap().readable(),
+            &[Token::NewtypeStruct { name: "Ordinal", }, Token::U64(5)],
+        );
     }
 
     #[cfg(feature = "serialize")]
@@ -150,18 +902,54 @@ mod tests {
     fn test_serialisation() {
         fn check(size: usize) {
             let d = Ordinal::new(size);
+            let tokens = [Token::NewtypeStruct { name: "Ordinal", }, Token::U64(size as u64)];
 
-            assert_tokens(
-                &d,
-                &[
-                    Token::NewtypeStruct { name: "Ordinal", },
-                    Token::U64(size as u64),
-                ],
-            );
+            assert_tokens(&d.readable(), &tokens);
+            assert_tokens(&d.compact(), &tokens);
         }
 
         check(5);
         check(10);
         check(100);
     }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_legacy_deserialisation() {
+        for &key in &["size", "n"] {
+            assert_de_tokens(&Ordinal::new(7).readable(), &[
+                Token::NewtypeStruct { name: "Ordinal", },
+                Token::Map { len: Some(1), },
+                Token::Str(key),
+                Token::U64(7),
+                Token::MapEnd,
+            ]);
+        }
+
+        assert_de_tokens_error::<Readable<Ordinal>>(
+            &[
+                Token::NewtypeStruct { name: "Ordinal", },
+                Token::Map { len: Some(1), },
+                Token::Str("len"),
+            ],
+            "unknown field `len`, expected `size` or `n`",
+        );
+        assert_de_tokens_error::<Readable<Ordinal>>(
+            &[
+                Token::NewtypeStruct { name: "Ordinal", },
+                Token::Map { len: Some(0), },
+                Token::MapEnd,
+            ],
+            "missing field `size`",
+        );
+    }
+
+    #[test]
+    fn test_laws() {
+        for &size in &[0, 1, 5, 100, u32::MAX as usize, usize::MAX] {
+            assert_space_laws!(Ordinal::new(size); sample, bounds, shape, finite, fmt, serde);
+        }
+
+        assert_space_laws!(Ordinal::<u8>::try_new(256).unwrap(); sample, bounds, finite, serde);
+    }
 }
@@ -0,0 +1,340 @@
+use crate::{
+    discrete::{Integers, Interval, Naturals},
+    prelude::*,
+    SampleError,
+};
+use std::{convert::TryFrom, error::Error, fmt, ops::RangeInclusive};
+
+/// Error returned when a truncation of the natural numbers extends beyond the values of an
+/// integer interval.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TruncationRangeError {
+    /// Largest value of the truncation.
+    pub max: u64,
+}
+
+impl fmt::Display for TruncationRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "naturals truncated to {} exceed the largest integer, {}", self.max, i64::MAX)
+    }
+}
+
+impl Error for TruncationRangeError {}
+
+/// Finite view of the natural numbers no greater than `max`, i.e. `[1, max]`; see
+/// `Naturals::truncate`.
+///
+/// Unlike `Naturals::bounded_above`, the view remembers the space it truncates: `covers`
+/// distinguishes values that are cut off by the truncation from those outside of the natural
+/// numbers altogether. Enumeration and indexing are lazy, such that even `max = u64::MAX` has a
+/// well-defined cardinality.
+/// ```
+/// use spaces::{discrete::Naturals, Card, FiniteSpace, Space};
+///
+/// let space = Naturals.truncate(3);
+///
+/// assert_eq!(space.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// assert!(space.covers(4) && !space.covers(0));
+/// assert_eq!(Naturals.truncate(u64::MAX).card(), Card::Finite(u64::MAX as usize));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TruncatedNaturals {
+    max: u64,
+}
+
+impl Naturals {
+    /// Returns the finite view of the natural numbers no greater than `max`, which is empty if
+    /// `max` is zero.
+    pub fn truncate(&self, max: u64) -> TruncatedNaturals { TruncatedNaturals { max, } }
+}
+
+impl TruncatedNaturals {
+    #[inline]
+    pub fn base(&self) -> Naturals { Naturals }
+
+    #[inline]
+    pub fn max(&self) -> u64 { self.max }
+
+    /// Returns true iff `val` is contained in the base space, whether or not it is cut off by
+    /// the truncation.
+    pub fn covers(&self, val: u64) -> bool { Naturals.contains(val) }
+}
+
+impl Space for TruncatedNaturals {
+    type Value = u64;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    /// The cardinality is promoted to `Card::Infinite` if it overflows `usize`, as it may on
+    /// 32-bit targets.
+    fn card(&self) -> Card { usize::try_from(self.max).map_or(Card::Infinite, Card::Finite) }
+}
+
+impl BoundedSpace for TruncatedNaturals {
+    fn inf(&self) -> Option<u64> { if self.max == 0 { None } else { Some(1) } }
+
+    fn sup(&self) -> Option<u64> { if self.max == 0 { None } else { Some(self.max) } }
+
+    fn contains(&self, val: u64) -> bool { val >= 1 && val <= self.max }
+}
+
+impl Sample for TruncatedNaturals {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from naturals: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<u64, SampleError> {
+        if self.max == 0 { return Err(SampleError::Empty); }
+
+        Ok(rng.gen_range(1..=self.max))
+    }
+}
+
+impl IntoIterator for TruncatedNaturals {
+    type Item = u64;
+    type IntoIter = RangeInclusive<u64>;
+
+    fn into_iter(self) -> RangeInclusive<u64> { 1..=self.max }
+}
+
+impl FiniteSpace for TruncatedNaturals {
+    type Iter = Self::IntoIter;
+
+    fn iter(&self) -> Self::Iter { self.into_iter() }
+
+    /// Saturates at `usize::MAX`, where the cardinality is infinite.
+    fn len(&self) -> usize { usize::try_from(self.max).unwrap_or(usize::MAX) }
+
+    fn is_empty(&self) -> bool { self.max == 0 }
+
+    fn to_index(&self, val: &u64) -> Option<usize> {
+        if self.contains(*val) { usize::try_from(val - 1).ok() } else { None }
+    }
+
+    fn from_index(&self, idx: usize) -> Option<u64> {
+        u64::try_from(idx).ok().and_then(|i| i.checked_add(1)).filter(|&v| v <= self.max)
+    }
+}
+
+/// Fails iff `max` exceeds `i64::MAX`.
+impl TryFrom<TruncatedNaturals> for Interval {
+    type Error = TruncationRangeError;
+
+    fn try_from(space: TruncatedNaturals) -> Result<Interval, TruncationRangeError> {
+        i64::try_from(space.max)
+            .map(|_| Naturals::bounded_above(space.max))
+            .map_err(|_| TruncationRangeError { max: space.max, })
+    }
+}
+
+impl fmt::Display for TruncatedNaturals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{2115}(\u{2264}{})", self.max)
+    }
+}
+
+/// Finite view of the integers in `[lo, hi]`; see `Integers::truncate`.
+///
+/// As with `TruncatedNaturals`, `covers` reports whether a value lies in the base space, which
+/// for the integers is every value.
+/// ```
+/// use spaces::{discrete::{Integers, Interval}, FiniteSpace};
+///
+/// let space = Integers.truncate(-1, 1);
+///
+/// assert_eq!(space.iter().collect::<Vec<_>>(), vec![-1, 0, 1]);
+/// assert!(space.covers(5) && space.to_index(&5).is_none());
+/// assert_eq!(Interval::from(space), Interval::bounded(-1, 1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TruncatedIntegers {
+    lo: i64,
+    hi: i64,
+}
+
+impl Integers {
+    /// Returns the finite view of the integers in `[lo, hi]`, which is empty if `lo > hi`.
+    pub fn truncate(&self, lo: i64, hi: i64) -> TruncatedIntegers { TruncatedIntegers { lo, hi, } }
+}
+
+impl TruncatedIntegers {
+    #[inline]
+    pub fn base(&self) -> Integers { Integers }
+
+    #[inline]
+    pub fn lo(&self) -> i64 { self.lo }
+
+    #[inline]
+    pub fn hi(&self) -> i64 { self.hi }
+
+    /// Returns true iff `val` is contained in the base space, whether or not it is cut off by
+    /// the truncation.
+    pub fn covers(&self, val: i64) -> bool { Integers.contains(val) }
+}
+
+impl Space for TruncatedIntegers {
+    type Value = i64;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    /// The cardinality is promoted to `Card::Infinite` if it overflows `usize`, as it does for
+    /// the whole range of `i64`.
+    fn card(&self) -> Card {
+        let n = (self.hi as i128 - self.lo as i128 + 1).max(0);
+
+        usize::try_from(n).map_or(Card::Infinite, Card::Finite)
+    }
+}
+
+impl BoundedSpace for TruncatedIntegers {
+    fn inf(&self) -> Option<i64> { if self.lo > self.hi { None } else { Some(self.lo) } }
+
+    fn sup(&self) -> Option<i64> { if self.lo > self.hi { None } else { Some(self.hi) } }
+
+    fn contains(&self, val: i64) -> bool { val >= self.lo && val <= self.hi }
+}
+
+impl Sample for TruncatedIntegers {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from integers: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<i64, SampleError> {
+        if self.lo > self.hi { return Err(SampleError::Empty); }
+
+        Ok(rng.gen_range(self.lo..=self.hi))
+    }
+}
+
+impl IntoIterator for TruncatedIntegers {
+    type Item = i64;
+    type IntoIter = RangeInclusive<i64>;
+
+    fn into_iter(self) -> RangeInclusive<i64> { self.lo..=self.hi }
+}
+
+impl FiniteSpace for TruncatedIntegers {
+    type Iter = Self::IntoIter;
+
+    fn iter(&self) -> Self::Iter { self.into_iter() }
+
+    /// Saturates at `usize::MAX`, where the cardinality is infinite.
+    fn len(&self) -> usize {
+        let n = (self.hi as i128 - self.lo as i128 + 1).max(0);
+
+        usize::try_from(n).unwrap_or(usize::MAX)
+    }
+
+    fn is_empty(&self) -> bool { self.lo > self.hi }
+
+    fn to_index(&self, val: &i64) -> Option<usize> {
+        if !self.contains(*val) { return None; }
+
+        usize::try_from(*val as i128 - self.lo as i128).ok()
+    }
+
+    fn from_index(&self, idx: usize) -> Option<i64> {
+        i64::try_from(self.lo as i128 + idx as i128).ok().filter(|&v| v <= self.hi)
+    }
+}
+
+impl From<TruncatedIntegers> for Interval {
+    fn from(space: TruncatedIntegers) -> Interval { Interval::bounded(space.lo, space.hi) }
+}
+
+impl fmt::Display for TruncatedIntegers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{2124}[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enumeration() {
+        let naturals = Naturals.truncate(4);
+        let integers = Integers.truncate(-2, 1);
+
+        assert_eq!(naturals.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!((naturals.inf(), naturals.sup()), (Some(1), Some(4)));
+        assert_eq!(naturals.to_index(&4), Some(3));
+        assert_eq!((naturals.to_index(&0), naturals.from_index(4)), (None, None));
+
+        assert_eq!(integers.iter().collect::<Vec<_>>(), vec![-2, -1, 0, 1]);
+        assert_eq!(integers.card(), Card::Finite(4));
+        assert_eq!((integers.to_index(&-2), integers.from_index(3)), (Some(0), Some(1)));
+        assert_eq!(integers.from_index(4), None);
+
+        assert!(Naturals.truncate(0).is_empty() && Integers.truncate(1, 0).is_empty());
+        assert_eq!(Integers.truncate(1, 0).inf(), None);
+        assert_eq!(Naturals.truncate(0).to_string(), "\u{2115}(\u{2264}0)");
+    }
+
+    #[test]
+    fn test_covers() {
+        let naturals = Naturals.truncate(10);
+        let integers = Integers.truncate(0, 10);
+
+        // Cut off by the truncation, but contained in the base space.
+        assert!(!naturals.contains(11) && naturals.covers(11));
+        assert!(!integers.contains(-1) && integers.covers(-1));
+
+        // Outside of the base space altogether.
+        assert!(!naturals.contains(0) && !naturals.covers(0));
+        assert!(naturals.contains(10) && naturals.covers(10));
+    }
+
+    #[test]
+    fn test_extremes() {
+        let naturals = Naturals.truncate(u64::MAX);
+
+        let expected = usize::try_from(u64::MAX).map_or(Card::Infinite, Card::Finite);
+
+        assert_eq!(naturals.card(), expected);
+        assert_eq!(naturals.iter().next_back(), Some(u64::MAX));
+        assert_eq!(naturals.to_index(&u64::MAX), usize::try_from(u64::MAX - 1).ok());
+        assert_eq!(naturals.from_index(0), Some(1));
+        assert!(naturals.try_collect_all().is_err());
+
+        let integers = Integers.truncate(i64::MIN, i64::MAX);
+
+        assert_eq!(integers.card(), Card::Infinite);
+        assert_eq!(Integers.truncate(i64::MIN + 1, i64::MAX).card(), Card::Finite(usize::MAX));
+        assert_eq!(integers.iter().next_back(), Some(i64::MAX));
+        assert_eq!(integers.to_index(&i64::MIN), Some(0));
+        assert_eq!(integers.from_index(1), Some(i64::MIN + 1));
+        assert!(integers.try_collect_all().is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Interval::try_from(Naturals.truncate(5)), Ok(Naturals::bounded_above(5)));
+        assert_eq!(Interval::try_from(Naturals.truncate(0)).map(|d| d.is_empty()), Ok(true));
+        assert_eq!(
+            Interval::try_from(Naturals.truncate(u64::MAX)),
+            Err(TruncationRangeError { max: u64::MAX })
+        );
+
+        for &(lo, hi) in [(-3, 3), (i64::MIN, i64::MAX), (5, 4)].iter() {
+            let space = Integers.truncate(lo, hi);
+            let interval = Interval::from(space);
+
+            assert_eq!((interval.inf(), interval.sup()), (space.inf(), space.sup()));
+            assert_eq!(interval.is_empty(), space.is_empty());
+            assert_eq!(interval.contains(lo), space.contains(lo));
+            assert_eq!(interval.card(), space.card());
+        }
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Naturals.truncate(10); sample, bounds, shape, finite, fmt, serde);
+        assert_space_laws!(Integers.truncate(-5, 5); sample, bounds, shape, finite, fmt, serde);
+        empty_space_laws!(Naturals.truncate(0); sample, bounds, finite);
+        empty_space_laws!(Integers.truncate(3, -3); sample, bounds, finite);
+    }
+}
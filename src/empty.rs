@@ -1,8 +1,9 @@
-use crate::{Space, Card, Dim, Surjection};
-use std::fmt;
+use crate::{BoundedSpace, Space, Card, Dim, FiniteSpace, Sample, SampleError, Surjection};
+use rand::Rng;
+use std::{fmt, iter};
 
 /// A space filled with... nothing.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Empty;
 
@@ -14,8 +15,47 @@ impl Space for Empty {
     fn card(&self) -> Card { Card::Finite(0) }
 }
 
+/// Note: `sample` trivially returns `()`, while `try_sample` reports that the space is empty.
+impl Sample for Empty {
+    fn sample<R: Rng + ?Sized>(&self, _: &mut R) {}
+
+    fn try_sample<R: Rng + ?Sized>(&self, _: &mut R) -> Result<(), SampleError> {
+        Err(SampleError::Empty)
+    }
+}
+
+/// The empty space has no bounds, and contains nothing.
+impl BoundedSpace for Empty {
+    fn inf(&self) -> Option<()> { None }
+
+    fn sup(&self) -> Option<()> { None }
+
+    fn contains(&self, _: ()) -> bool { false }
+}
+
+impl FiniteSpace for Empty {
+    type Iter = iter::Empty<()>;
+
+    fn iter(&self) -> Self::Iter { iter::empty() }
+
+    fn len(&self) -> usize { 0 }
+
+    fn is_empty(&self) -> bool { true }
+
+    fn to_index(&self, _: &()) -> Option<usize> { None }
+
+    fn from_index(&self, _: usize) -> Option<()> { None }
+}
+
+impl IntoIterator for Empty {
+    type Item = ();
+    type IntoIter = iter::Empty<()>;
+
+    fn into_iter(self) -> Self::IntoIter { iter::empty() }
+}
+
 impl<T> Surjection<T, ()> for Empty {
-    fn map_onto(&self, _: T) -> () { () }
+    fn map_onto(&self, _: T) {}
 }
 
 impl fmt::Display for Empty {
@@ -28,6 +68,11 @@ impl fmt::Display for Empty {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_try_sample() {
+        assert_eq!(Empty.try_sample(&mut ::rand::thread_rng()), Err(SampleError::Empty));
+    }
+
     #[cfg(feature = "serialize")]
     extern crate serde_test;
     #[cfg(feature = "serialize")]
@@ -45,8 +90,7 @@ mod tests {
         let d = Empty;
 
         for i in 0..10 {
-            assert_eq!(d.map_onto(i), ());
-            assert_eq!(d.map_onto(i), ());
+            let _: () = d.map_onto(i);
         }
     }
 
@@ -57,4 +101,9 @@ mod tests {
 
         assert_tokens(&d, &[Token::UnitStruct { name: "Empty" }]);
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Empty; bounds, shape, finite, fmt, serde);
+    }
 }
@@ -0,0 +1,225 @@
+use crate::{prelude::*, SampleError, SizeLimitError};
+use std::{collections::HashMap, fmt, hash::Hash, ops::Deref};
+
+/// Finite space with a precomputed enumeration, for constant-time index lookups.
+///
+/// Every value of the base space is materialised on construction, together with a hash map from
+/// values to their indices, such that `to_index` and `from_index` take constant time regardless
+/// of how the base space computes them; the default implementations of `FiniteSpace` scan the
+/// enumeration in linear time. Everything else is that of the base space, to which `Enumerated`
+/// also dereferences.
+/// ```
+/// use spaces::{discrete::Alphabet, FiniteSpace};
+///
+/// let space = Alphabet::new("héllo").enumerated().unwrap();
+///
+/// assert_eq!(space.to_index(&'l'), Some(2));
+/// assert_eq!(space.from_index(3), Some('o'));
+/// assert_eq!(space.chars(), &['h', 'é', 'l', 'o']);
+/// ```
+#[derive(Clone)]
+pub struct Enumerated<S: FiniteSpace> {
+    space: S,
+    values: Vec<S::Value>,
+    indices: HashMap<S::Value, usize>,
+}
+
+impl<S: FiniteSpace> Enumerated<S>
+where S::Value: Hash + Eq
+{
+    /// Enumerate `space`, or return an error if it has more than `MATERIALISE_LIMIT` values.
+    pub fn new(space: S) -> Result<Enumerated<S>, SizeLimitError> {
+        let values = space.try_collect_all()?;
+        let indices = values.iter().cloned().enumerate().map(|(i, v)| (v, i)).collect();
+
+        Ok(Enumerated { space, values, indices, })
+    }
+}
+
+impl<S: FiniteSpace> Enumerated<S> {
+    #[inline]
+    pub fn base(&self) -> &S { &self.space }
+
+    /// Returns the values of the space in enumeration order.
+    #[inline]
+    pub fn values(&self) -> &[S::Value] { &self.values }
+
+    pub fn into_base(self) -> S { self.space }
+}
+
+impl<S: FiniteSpace> Deref for Enumerated<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S { &self.space }
+}
+
+impl<S: FiniteSpace> Space for Enumerated<S> {
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.space.dim() }
+
+    fn shape(&self) -> Shape { self.space.shape() }
+
+    fn card(&self) -> Card { self.space.card() }
+}
+
+impl<S: FiniteSpace + Sample> Sample for Enumerated<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value { self.space.sample(rng) }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+        self.space.try_sample(rng)
+    }
+}
+
+impl<S: FiniteSpace + BoundedSpace> BoundedSpace for Enumerated<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.space.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.space.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { self.space.contains(val) }
+}
+
+impl<S: FiniteSpace> FiniteSpace for Enumerated<S>
+where S::Value: Hash + Eq
+{
+    type Iter = S::Iter;
+
+    fn len(&self) -> usize { self.values.len() }
+
+    fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    fn iter(&self) -> S::Iter { self.space.iter() }
+
+    fn to_index(&self, val: &S::Value) -> Option<usize> { self.indices.get(val).cloned() }
+
+    fn from_index(&self, idx: usize) -> Option<S::Value> { self.values.get(idx).cloned() }
+}
+
+impl<S: FiniteSpace> IntoIterator for Enumerated<S> {
+    type Item = S::Value;
+    type IntoIter = ::std::vec::IntoIter<S::Value>;
+
+    fn into_iter(self) -> Self::IntoIter { self.values.into_iter() }
+}
+
+/// Enumerations are equal iff their base spaces are.
+impl<S: FiniteSpace + PartialEq> PartialEq for Enumerated<S> {
+    fn eq(&self, other: &Enumerated<S>) -> bool { self.space == other.space }
+}
+
+/// Prints the base space and the number of values, rather than the enumeration itself.
+impl<S: FiniteSpace + fmt::Debug> fmt::Debug for Enumerated<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Enumerated")
+            .field("space", &self.space)
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+impl<S: FiniteSpace + fmt::Display> fmt::Display for Enumerated<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.space) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        discrete::{Alphabet, Binary, Ordinal},
+        testing::for_all,
+        Equipartition,
+        MATERIALISE_LIMIT,
+    };
+    use std::vec;
+    use super::*;
+
+    // Finite space relying on the default, linear-time, index lookups of `FiniteSpace`.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Words(Vec<&'static str>);
+
+    impl Space for Words {
+        type Value = &'static str;
+
+        fn dim(&self) -> Dim { Dim::one() }
+
+        fn card(&self) -> Card { Card::Finite(self.0.len()) }
+    }
+
+    impl FiniteSpace for Words {
+        type Iter = vec::IntoIter<&'static str>;
+
+        fn iter(&self) -> Self::Iter { self.0.clone().into_iter() }
+    }
+
+    impl IntoIterator for Words {
+        type Item = &'static str;
+        type IntoIter = vec::IntoIter<&'static str>;
+
+        fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
+    }
+
+    // Checks that the enumeration agrees with the base space on every value and index.
+    fn check<S: FiniteSpace + Clone>(space: S)
+    where S::Value: Hash + Eq + fmt::Debug
+    {
+        let enumerated = space.clone().enumerated().unwrap();
+
+        assert_eq!(enumerated.len(), space.len());
+        assert_eq!(enumerated.card(), space.card());
+        assert_eq!(for_all(&space, |v| enumerated.to_index(v) == space.to_index(v)), Ok(()));
+
+        for i in 0..=space.len() {
+            assert_eq!(enumerated.from_index(i), space.from_index(i));
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_base() {
+        check(Binary);
+        check(Ordinal::new(0));
+        check(Ordinal::new(1000));
+        check(Equipartition::new(0.0, 1.0, 10));
+        check(Alphabet::printable_ascii());
+        check(Words(vec!["the", "quick", "brown", "fox"]));
+    }
+
+    #[test]
+    fn test_lookups() {
+        let space = Words((0..5000).map(|i| &*Box::leak(i.to_string().into_boxed_str())).collect());
+        let enumerated = space.clone().enumerated().unwrap();
+
+        assert_eq!(enumerated.to_index(&"4999"), Some(4999));
+        assert_eq!(enumerated.to_index(&"5000"), None);
+        assert_eq!(enumerated.from_index(17), Some("17"));
+        assert_eq!(enumerated.values().len(), 5000);
+        assert_eq!(enumerated.base(), &space);
+        assert_eq!(enumerated.into_iter().nth(3), Some("3"));
+    }
+
+    #[test]
+    fn test_forwarding() {
+        let space = Alphabet::new("abc").enumerated().unwrap();
+
+        assert!(space.contains('b'));
+        assert_eq!(space.chars(), &['a', 'b', 'c']);
+        assert_eq!(space.to_string(), "{'a', 'b', 'c'}");
+        assert_eq!(format!("{:?}", Ordinal::new(3).enumerated().unwrap()),
+            "Enumerated { space: Ordinal(3), len: 3 }");
+        assert_eq!(Ordinal::new(3).enumerated().unwrap().sup(), Some(2));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        assert_eq!(
+            Ordinal::new(MATERIALISE_LIMIT + 1).enumerated().unwrap_err(),
+            SizeLimitError { size: MATERIALISE_LIMIT + 1, limit: MATERIALISE_LIMIT }
+        );
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Ordinal::new(10).enumerated().unwrap(); sample, bounds, finite, fmt);
+        assert_space_laws!(Words(vec!["a", "b"]).enumerated().unwrap(); shape, finite);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{parse::FromDescription, prelude::*, Describe, SpaceDescription};
+use std::{error::Error, fmt};
+
+/// Error type for transitions that are not valid under an `EnvSpec`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SpecError {
+    /// The observation, rendered with `Debug`, is not contained in the observation space.
+    InvalidObservation(String),
+    /// The action, rendered with `Debug`, is not contained in the action space.
+    InvalidAction(String),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecError::InvalidObservation(v) =>
+                write!(f, "observation {} is not contained in the observation space", v),
+            SpecError::InvalidAction(v) =>
+                write!(f, "action {} is not contained in the action space", v),
+        }
+    }
+}
+
+impl Error for SpecError {}
+
+/// Observation and action spaces of an environment.
+///
+/// The serialised form uses the `observation_space` and `action_space` keys of the gym spec
+/// format, with each space in its own serialised form.
+/// ```
+/// use spaces::{discrete::Ordinal, real::Interval, EnvSpec, SpecError};
+///
+/// let spec = EnvSpec::new(Interval::bounded(-1.0, 1.0), Ordinal::new(3));
+///
+/// assert_eq!(spec.validate_transition(&0.5, &2), Ok(()));
+/// assert_eq!(spec.validate_transition(&0.5, &3), Err(SpecError::InvalidAction("3".into())));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct EnvSpec<O, A> {
+    #[cfg_attr(feature = "serialize", serde(rename = "observation_space"))]
+    pub observation: O,
+    #[cfg_attr(feature = "serialize", serde(rename = "action_space"))]
+    pub action: A,
+}
+
+impl<O: Space, A: Space> EnvSpec<O, A> {
+    pub fn new(observation: O, action: A) -> EnvSpec<O, A> { EnvSpec { observation, action, } }
+}
+
+impl<O: BoundedSpace, A: BoundedSpace> EnvSpec<O, A>
+where
+    O::Value: PartialOrd + fmt::Debug,
+    A::Value: PartialOrd + fmt::Debug,
+{
+    /// Check that `obs` and `act` are contained in the observation and action spaces,
+    /// respectively; the observation is checked first.
+    pub fn validate_transition(&self, obs: &O::Value, act: &A::Value) -> Result<(), SpecError> {
+        if !self.observation.contains(obs.clone()) {
+            return Err(SpecError::InvalidObservation(format!("{:?}", obs)));
+        }
+
+        if !self.action.contains(act.clone()) {
+            return Err(SpecError::InvalidAction(format!("{:?}", act)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<O: fmt::Display, A: fmt::Display> fmt::Display for EnvSpec<O, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "observation: {}, action: {}", self.observation, self.action)
+    }
+}
+
+/// Environment spec whose spaces are only known at runtime, e.g. when loaded from a file.
+///
+/// The spaces are held by their descriptions, and so the spec carries only their structure;
+/// concrete spaces can be recovered with `to_typed`.
+pub type EnvSpecDyn = EnvSpec<SpaceDescription, SpaceDescription>;
+
+impl EnvSpecDyn {
+    /// Construct a dynamic spec from the descriptions of a typed spec.
+    pub fn describe<O: Describe, A: Describe>(spec: &EnvSpec<O, A>) -> EnvSpecDyn {
+        EnvSpec { observation: spec.observation.describe(), action: spec.action.describe(), }
+    }
+
+    /// Construct a typed spec from the descriptions, if both are of the right kind.
+    pub fn to_typed<O, A>(&self) -> Option<EnvSpec<O, A>>
+    where O: FromDescription, A: FromDescription
+    {
+        Some(EnvSpec {
+            observation: O::from_description(&self.observation)?,
+            action: A::from_description(&self.action)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval, ProductSpace, ScalarKind};
+    use super::*;
+
+    #[test]
+    fn test_validate_transition() {
+        let spec = EnvSpec::new(Interval::bounded(0.0, 1.0), Ordinal::new(2));
+
+        assert_eq!(spec.validate_transition(&0.0, &1), Ok(()));
+        assert_eq!(
+            spec.validate_transition(&1.5, &5),
+            Err(SpecError::InvalidObservation("1.5".to_owned()))
+        );
+        assert_eq!(
+            spec.validate_transition(&0.5, &2).unwrap_err().to_string(),
+            "action 2 is not contained in the action space"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let spec = EnvSpec::new(Interval::bounded(0.0, 1.0), Ordinal::new(2));
+
+        assert_eq!(spec.to_string(), "observation: [0, 1], action: [0..1]");
+    }
+
+    #[test]
+    fn test_describe() {
+        let spec = EnvSpec::new(
+            ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]),
+            Ordinal::new(4),
+        );
+        let dynamic = EnvSpecDyn::describe(&spec);
+
+        assert_eq!(dynamic.action, SpaceDescription::Scalar(ScalarKind::Discrete { n: 4 }));
+        assert_eq!(dynamic.observation, spec.observation.describe());
+        assert_eq!(dynamic.to_typed(), Some(spec.clone()));
+        assert_eq!(dynamic.to_typed::<Ordinal, Ordinal>(), None);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{prelude::*, SampleError};
+use rand::RngCore;
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Finaliser of the SplitMix64 generator (Steele et al., 2014), a bijective mixing function.
+pub(crate) fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(GOLDEN_GAMMA);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Sampler drawing values of a space in counter mode: every draw is determined by a master seed
+/// together with the episode and step at which it is made, so that any single draw can be
+/// recomputed in isolation, without replaying the draws that preceded it.
+///
+/// The generator for `(master_seed, episode, step)` is a SplitMix64 stream whose initial state
+/// is `splitmix64(splitmix64(splitmix64(master_seed) ^ episode) ^ step)`, where `splitmix64`
+/// adds `0x9e3779b97f4a7c15` and applies the SplitMix64 finaliser. This derivation, and the
+/// generator itself, are stable across platforms and versions of the crate; the values drawn
+/// also depend on the sampling algorithm of the space, which is pinned by golden-value tests.
+/// ```
+/// use spaces::{discrete::Ordinal, EpisodeSampler};
+///
+/// let space = Ordinal::new(100);
+/// let sampler = EpisodeSampler::new(&space, 42);
+///
+/// let later = sampler.sample(7, 300);
+/// let first = sampler.sample(0, 0);
+///
+/// assert_eq!(sampler.sample(7, 300), later);
+/// assert_eq!(EpisodeSampler::new(&space, 42).sample(0, 0), first);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EpisodeSampler<'a, S> {
+    space: &'a S,
+    master_seed: u64,
+}
+
+impl<'a, S> EpisodeSampler<'a, S> {
+    pub fn new(space: &'a S, master_seed: u64) -> EpisodeSampler<'a, S> {
+        EpisodeSampler { space, master_seed, }
+    }
+
+    #[inline]
+    pub fn space(&self) -> &'a S { self.space }
+
+    #[inline]
+    pub fn master_seed(&self) -> u64 { self.master_seed }
+
+    /// Returns the generator for the draw at `step` of `episode`.
+    pub fn rng(&self, episode: u64, step: u64) -> EpisodeRng {
+        let state = splitmix64(splitmix64(splitmix64(self.master_seed) ^ episode) ^ step);
+
+        EpisodeRng { state, }
+    }
+}
+
+impl<'a, S: Sample> EpisodeSampler<'a, S> {
+    /// Draw the value at `step` of `episode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no uniform distribution over the space exists; see `try_sample`.
+    pub fn sample(&self, episode: u64, step: u64) -> S::Value {
+        self.space.sample(&mut self.rng(episode, step))
+    }
+
+    /// Draw the value at `step` of `episode`, or return an error if no uniform distribution over
+    /// the space exists.
+    pub fn try_sample(&self, episode: u64, step: u64) -> Result<S::Value, SampleError> {
+        self.space.try_sample(&mut self.rng(episode, step))
+    }
+}
+
+/// SplitMix64 generator for a single draw of an `EpisodeSampler`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpisodeRng {
+    state: u64,
+}
+
+impl RngCore for EpisodeRng {
+    fn next_u32(&mut self) -> u32 { (self.next_u64() >> 32) as u32 }
+
+    fn next_u64(&mut self) -> u64 {
+        let x = splitmix64(self.state);
+
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval};
+    use super::*;
+
+    #[test]
+    fn test_splitmix64() {
+        // Reference outputs of SplitMix64 seeded with zero.
+        let mut rng = EpisodeRng { state: 0, };
+
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+        assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
+    }
+
+    #[test]
+    fn test_golden() {
+        // Golden values: these must never change between runs or crate versions.
+        let discrete = Ordinal::new(10);
+        let interval = Interval::bounded(-1.0, 1.0);
+        let discrete = EpisodeSampler::new(&discrete, 1234);
+        let interval = EpisodeSampler::new(&interval, 1234);
+
+        assert_eq!(discrete.rng(5, 0).next_u64(), 15_919_272_122_643_431_626);
+        assert_eq!((0..4).map(|s| discrete.sample(5, s)).collect::<Vec<_>>(), vec![2, 7, 3, 6]);
+        assert_eq!(
+            (0..3).map(|s| interval.sample(5, s)).collect::<Vec<_>>(),
+            vec![0.725970941975794, -0.21942203119529569, -0.31934203217629864]
+        );
+    }
+
+    #[test]
+    fn test_isolation() {
+        let space = Interval::bounded(0.0, 1.0);
+        let sampler = EpisodeSampler::new(&space, 7);
+        let forward: Vec<Vec<f64>> = (0..8)
+            .map(|e| (0..8).map(|s| sampler.sample(e, s)).collect())
+            .collect();
+
+        for e in (0..8).rev() {
+            for s in (0..8).rev() {
+                assert_eq!(sampler.sample(e, s), forward[e as usize][s as usize]);
+            }
+        }
+
+        let mut flat: Vec<f64> = forward.into_iter().flatten().collect();
+
+        flat.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        flat.dedup();
+
+        assert_eq!(flat.len(), 64);
+        assert_ne!(sampler.sample(0, 1), sampler.sample(1, 0));
+        assert_ne!(sampler.sample(0, 0), EpisodeSampler::new(&space, 8).sample(0, 0));
+    }
+
+    #[test]
+    fn test_try_sample() {
+        let space = Interval::<f64>::left_bounded(0.0);
+
+        assert_eq!(EpisodeSampler::new(&space, 0).try_sample(0, 0), Err(SampleError::Unbounded));
+    }
+}
@@ -0,0 +1,254 @@
+//! Error types of the crate, gathered in one place.
+//!
+//! Each fallible operation returns its own specific error type, all of which are re-exported
+//! here. Every one of them converts into the top-level `Error` via `From`, so code that draws on
+//! several parts of the crate can propagate failures with `?` into a single type; the specific
+//! error is preserved as the `source` of the top-level one, which itself displays only a short
+//! summary, so that error reports print each message once.
+//! ```
+//! use spaces::{discrete::Ordinal, parse::FromDescription, Error, Interval, Sample};
+//!
+//! fn load(desc: &str) -> Result<usize, Error> {
+//!     let space = Ordinal::from_description(&desc.parse()?).unwrap_or(Ordinal::new(1));
+//!
+//!     Ok(space.try_sample(&mut rand::thread_rng())?)
+//! }
+//!
+//! assert!(load("discrete(3)").unwrap() < 3);
+//! assert!(matches!(load("nonsense"), Err(Error::Parse(_))));
+//! ```
+pub use crate::{
+    basis::BasisError,
+    codec::CodecError,
+    discrete::{
+        EmptyAlphabetError, IndexTypeError, MigrationError, OneHotError, TruncationRangeError,
+    },
+    mixture::MixtureError,
+    parse::{ParseError, ParseErrorKind},
+    patch::{PatchError, PatchErrorKind, ScalarError},
+    registry::RegistryError,
+    ComponentError,
+    ContainsError,
+    CornerError,
+    FixError,
+    FlatLengthError,
+    GridError,
+    MapLengthError,
+    NormalParamsError,
+    ParseValueError,
+    ParseValueErrorKind,
+    PmfError,
+    QuantisedError,
+    RowsError,
+    SampleError,
+    ScheduleError,
+    SizeLimitError,
+    SpecError,
+    TableMapError,
+    ZeroRepeatsError,
+};
+#[cfg(feature = "serialize")]
+pub use crate::gym::{ImportError, ImportErrorKind};
+use std::{error, fmt};
+
+/// Top-level error type, wrapping each of the specific error types of the crate.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A space could not be sampled from.
+    Sample(SampleError),
+    /// An operation would materialise too many elements.
+    SizeLimit(SizeLimitError),
+    /// A value is not contained in a space.
+    Contains(ContainsError),
+    /// A batch of rows does not match the width of a space.
+    Rows(RowsError),
+    /// A component index is out of range for a product space.
+    Component(ComponentError),
+    /// A fixed component of a partial sample is invalid.
+    Fix(FixError),
+    /// A flat value does not match the flat encoding of a space.
+    FlatLength(FlatLengthError),
+    /// A vector of values does not match a vector of maps.
+    MapLength(MapLengthError),
+    /// A grid cell or index is invalid.
+    Grid(GridError),
+    /// The corners of a product cannot be enumerated.
+    Corner(CornerError),
+    /// Two spaces cannot be interpolated between.
+    Schedule(ScheduleError),
+    /// A lookup table is invalid.
+    TableMap(TableMapError),
+    /// A probability mass function is invalid.
+    Pmf(PmfError),
+    /// A quantised interval is invalid.
+    Quantised(QuantisedError),
+    /// The parameters of a truncated normal distribution are invalid.
+    NormalParams(NormalParamsError),
+    /// The weights of a mixture are invalid.
+    Mixture(MixtureError),
+    /// A repeated space would not repeat its values.
+    ZeroRepeats(ZeroRepeatsError),
+    /// A transition is not valid under an environment spec.
+    Spec(SpecError),
+    /// An alphabet would contain no characters.
+    EmptyAlphabet(EmptyAlphabetError),
+    /// An ordinal does not fit its index type.
+    IndexType(IndexTypeError),
+    /// A migration between ordinals is invalid.
+    Migration(MigrationError),
+    /// A batch of ordinal values could not be one-hot encoded or decoded.
+    OneHot(OneHotError),
+    /// A truncation of the natural numbers does not fit an integer interval.
+    TruncationRange(TruncationRangeError),
+    /// A feature basis could not be constructed.
+    Basis(BasisError),
+    /// A value could not be decoded.
+    Codec(CodecError),
+    /// A space description could not be parsed.
+    Parse(ParseError),
+    /// A value could not be parsed.
+    ParseValue(ParseValueError),
+    /// A patch to a space description could not be applied.
+    Patch(PatchError),
+    /// A space could not be registered.
+    Registry(RegistryError),
+    /// A space exported from another library could not be imported.
+    #[cfg(feature = "serialize")]
+    Import(ImportError),
+}
+
+macro_rules! impl_error_variants {
+    ($($(#[$meta:meta])* $variant:ident($type:ty) => $context:expr),+ $(,)?) => {
+        impl Error {
+            /// Returns the wrapped error.
+            pub fn inner(&self) -> &(dyn error::Error + 'static) {
+                match self {
+                    $($(#[$meta])* Error::$variant(e) => e,)+
+                }
+            }
+
+            fn context(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* Error::$variant(_) => $context,)+
+                }
+            }
+        }
+
+        $($(#[$meta])* impl From<$type> for Error {
+            fn from(e: $type) -> Error { Error::$variant(e) }
+        })+
+    };
+}
+
+impl_error_variants!(
+    Sample(SampleError) => "a space could not be sampled from",
+    SizeLimit(SizeLimitError) => "an operation would materialise too many elements",
+    Contains(ContainsError) => "a value is not contained in a space",
+    Rows(RowsError) => "a batch of rows does not match the width of a space",
+    Component(ComponentError) => "a component index is out of range for a product space",
+    Fix(FixError) => "a fixed component of a partial sample is invalid",
+    FlatLength(FlatLengthError) => "a flat value does not match the flat encoding of a space",
+    MapLength(MapLengthError) => "a vector of values does not match a vector of maps",
+    Grid(GridError) => "a grid cell or index is invalid",
+    Corner(CornerError) => "the corners of a product cannot be enumerated",
+    Schedule(ScheduleError) => "two spaces cannot be interpolated between",
+    TableMap(TableMapError) => "a lookup table is invalid",
+    Pmf(PmfError) => "a probability mass function is invalid",
+    Quantised(QuantisedError) => "a quantised interval is invalid",
+    NormalParams(NormalParamsError) => "a truncated normal has invalid parameters",
+    Mixture(MixtureError) => "the weights of a mixture are invalid",
+    ZeroRepeats(ZeroRepeatsError) => "a repeated space would not repeat its values",
+    Spec(SpecError) => "a transition is not valid under an environment spec",
+    EmptyAlphabet(EmptyAlphabetError) => "an alphabet would contain no characters",
+    IndexType(IndexTypeError) => "an ordinal does not fit its index type",
+    Migration(MigrationError) => "a migration between ordinals is invalid",
+    OneHot(OneHotError) => "a batch of ordinal values could not be one-hot encoded or decoded",
+    TruncationRange(TruncationRangeError) => "a truncation of Naturals does not fit in i64",
+    Basis(BasisError) => "a feature basis could not be constructed",
+    Codec(CodecError) => "a value could not be decoded",
+    Parse(ParseError) => "a space description could not be parsed",
+    ParseValue(ParseValueError) => "a value could not be parsed",
+    Patch(PatchError) => "a patch to a space description could not be applied",
+    Registry(RegistryError) => "a space could not be registered",
+    #[cfg(feature = "serialize")]
+    Import(ImportError) => "a space exported from another library could not be imported",
+);
+
+/// Displays a short description of the kind of failure; the details are left to the wrapped
+/// error, which is the `source`.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.context()) }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> { Some(self.inner()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::ValueCodec,
+        discrete::{Binary, Ordinal},
+        Interval,
+        Pmf,
+        Sample,
+        SpaceDescription,
+        TableMap,
+    };
+    use std::error::Error as StdError;
+    use super::*;
+
+    fn sample_unbounded() -> Result<f64, Error> {
+        Ok(Interval::left_bounded(0.0).try_sample(&mut rand::thread_rng())?)
+    }
+
+    fn decode_empty() -> Result<bool, Error> { Ok(Binary.decode(&[])?.0) }
+
+    fn parse_nonsense() -> Result<SpaceDescription, Error> { Ok("nonsense".parse()?) }
+
+    fn table_too_short() -> Result<TableMap<Ordinal, Ordinal>, Error> {
+        Ok(TableMap::new(Ordinal::new(3), Ordinal::new(2), vec![0, 1])?)
+    }
+
+    fn pmf_unnormalised() -> Result<Pmf<Ordinal>, Error> {
+        Ok(Pmf::new(Ordinal::new(2), vec![0.5, 0.6])?)
+    }
+
+    #[test]
+    fn test_variants() {
+        assert_eq!(sample_unbounded(), Err(Error::Sample(SampleError::Unbounded)));
+        assert_eq!(decode_empty(), Err(Error::Codec(CodecError::UnexpectedEof)));
+        assert!(matches!(
+            parse_nonsense(),
+            Err(Error::Parse(ParseError { kind: ParseErrorKind::UnknownSpace(_), .. }))
+        ));
+        assert_eq!(
+            table_too_short().unwrap_err(),
+            Error::TableMap(TableMapError::LengthMismatch { expected: 3, found: 2 })
+        );
+        assert!(matches!(pmf_unnormalised(), Err(Error::Pmf(PmfError::NotNormalised { .. }))));
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let err = sample_unbounded().unwrap_err();
+        let source = err.source().unwrap();
+
+        assert_eq!(source.downcast_ref::<SampleError>(), Some(&SampleError::Unbounded));
+        assert_eq!(err.to_string(), "a space could not be sampled from");
+
+        let err = parse_nonsense().unwrap_err();
+
+        assert!(err.source().unwrap().downcast_ref::<ParseError>().is_some());
+        assert_ne!(err.to_string(), err.source().unwrap().to_string());
+        assert!(err.source().unwrap().source().is_none());
+
+        let boxed: Box<dyn StdError> = Box::new(decode_empty().unwrap_err());
+
+        assert_eq!(
+            boxed.source().and_then(|e| e.downcast_ref::<CodecError>()),
+            Some(&CodecError::UnexpectedEof)
+        );
+    }
+}
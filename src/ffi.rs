@@ -0,0 +1,540 @@
+//! C interface to spaces given as JSON descriptions, enabled by the `ffi` feature.
+//!
+//! A space is parsed from the JSON representation of a `SpaceDescription` into an opaque handle,
+//! which must be released with `spaces_free`. Values cross the interface as arrays of `f64`, with
+//! one entry per non-empty leaf of the description in depth-first order: binary leaves take `0`
+//! or `1`, discrete and partition leaves take the index of a value, and all other leaves take the
+//! value itself.
+//!
+//! None of the functions unwind into the caller: null pointers and invalid arguments are reported
+//! through the return value, as are panics, which are caught at the boundary. A C header is
+//! checked in at `include/spaces.h`; it is regenerated with cbindgen when building with the
+//! feature, and the tests fail if the two differ.
+//!
+//! ```
+//! use spaces::ffi::*;
+//! use std::ffi::CString;
+//!
+//! let json = CString::new(r#"{"product": [
+//!     {"scalar": {"type": "discrete", "n": 3}},
+//!     {"scalar": {"type": "real", "lb": 0, "ub": 1}}
+//! ]}"#).unwrap();
+//!
+//! unsafe {
+//!     let space = spaces_parse(json.as_ptr());
+//!     let mut val = [0.0; 2];
+//!
+//!     assert_eq!(spaces_sample_f64(space, 7, val.as_mut_ptr(), 2), SpacesStatus::Ok);
+//!     assert!(spaces_contains_f64(space, val.as_ptr(), 2));
+//!
+//!     spaces_free(space);
+//! }
+//! ```
+use crate::{
+    discrete::Ordinal,
+    prelude::*,
+    Interval, ProductCard, Quantised, SampleError, ScalarKind, SpaceDescription,
+};
+use rand::{rngs::SmallRng, SeedableRng};
+use std::{ffi::CStr, os::raw::c_char, panic, ptr, slice};
+
+/// Outcome of a call through the C interface.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpacesStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// The length of a value did not match the dimensionality of the space.
+    LengthMismatch = 2,
+    /// The space admits no uniform distribution, e.g. as it is unbounded or empty.
+    Unsampleable = 3,
+    /// The call panicked; the panic was caught at the boundary.
+    Panicked = 4,
+}
+
+// Leaf of a description, as the typed space that implements it over `f64` values.
+enum Leaf {
+    Empty,
+    Binary,
+    Index(Ordinal),
+    Integer(Interval<i64>, Vec<i64>),
+    Real(Interval<f64>),
+    Quantised(Quantised),
+}
+
+impl Leaf {
+    // Returns `None` if the parameters of the kind are invalid.
+    fn new(kind: &ScalarKind) -> Option<Leaf> {
+        Some(match *kind {
+            ScalarKind::Empty => Leaf::Empty,
+            ScalarKind::Binary => Leaf::Binary,
+            ScalarKind::Discrete { n } | ScalarKind::Partition { n, .. } =>
+                Leaf::Index(Ordinal::new(n)),
+            ScalarKind::Integer { lb, ub, lb_kind, ub_kind, ref excluded } => {
+                let d = Interval::new(lb, ub).with_kinds(lb_kind, ub_kind);
+                let mut excluded: Vec<i64> =
+                    excluded.iter().cloned().filter(|&x| d.contains(x)).collect();
+
+                excluded.sort_unstable();
+                excluded.dedup();
+
+                Leaf::Integer(d, excluded)
+            },
+            ScalarKind::Real { lb, ub, lb_kind, ub_kind } =>
+                Leaf::Real(Interval::new(lb, ub).with_kinds(lb_kind, ub_kind)),
+            ScalarKind::Quantised { lb, ub, step } => {
+                let valid = step > 0.0 && step.is_finite()
+                    && lb.is_finite() && ub.is_finite() && lb <= ub;
+
+                if !valid { return None; }
+
+                Leaf::Quantised(Quantised::new(lb, ub, step))
+            },
+        })
+    }
+
+    fn card(&self) -> Card {
+        match self {
+            Leaf::Empty => Card::Finite(0),
+            Leaf::Binary => Card::Finite(2),
+            Leaf::Index(d) => d.card(),
+            Leaf::Integer(d, excluded) => match d.card() {
+                Card::Finite(n) => Card::Finite(n - excluded.len()),
+                card => card,
+            },
+            Leaf::Real(d) => d.card(),
+            Leaf::Quantised(d) => d.card(),
+        }
+    }
+
+    fn contains(&self, val: f64) -> bool {
+        // Exactly representable integers, within the range of `i64`.
+        let integral = val.fract() == 0.0 && val >= i64::MIN as f64 && val < i64::MAX as f64;
+
+        match self {
+            Leaf::Empty => false,
+            Leaf::Binary => val == 0.0 || val == 1.0,
+            Leaf::Index(d) => integral && val >= 0.0 && (val as u64 as usize) < d.size(),
+            Leaf::Integer(d, excluded) =>
+                integral && d.contains(val as i64) && !excluded.contains(&(val as i64)),
+            Leaf::Real(d) => d.contains(val),
+            Leaf::Quantised(d) => d.contains(val),
+        }
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, SampleError> {
+        match self {
+            Leaf::Empty => Err(SampleError::Empty),
+            Leaf::Binary => Ok(if rng.gen() { 1.0 } else { 0.0 }),
+            Leaf::Index(d) => d.try_sample(rng).map(|i| i as f64),
+            Leaf::Integer(_, _) if self.card() == Card::Finite(0) => Err(SampleError::Empty),
+            // Rejection sampling, which terminates as at least one value is not excluded.
+            Leaf::Integer(d, excluded) => loop {
+                let i = d.try_sample(rng)?;
+
+                if !excluded.contains(&i) { return Ok(i as f64); }
+            },
+            Leaf::Real(d) => d.try_sample(rng),
+            Leaf::Quantised(d) => d.try_sample(rng),
+        }
+    }
+}
+
+/// Space parsed from a JSON description, opaque to C.
+pub struct OpaqueSpace {
+    description: SpaceDescription,
+    leaves: Vec<Leaf>,
+}
+
+impl OpaqueSpace {
+    fn new(description: SpaceDescription) -> Option<OpaqueSpace> {
+        let leaves = description.leaves().into_iter().map(Leaf::new).collect::<Option<_>>()?;
+
+        Some(OpaqueSpace { description, leaves, })
+    }
+
+    /// Returns the description from which the space was parsed.
+    pub fn description(&self) -> &SpaceDescription { &self.description }
+
+    fn values(&self) -> impl Iterator<Item = &Leaf> {
+        self.leaves.iter().filter(|l| !matches!(l, Leaf::Empty))
+    }
+
+    fn dim(&self) -> usize { self.values().count() }
+
+    fn is_empty(&self) -> bool { self.leaves.iter().any(|l| matches!(l, Leaf::Empty)) }
+}
+
+// Runs `f`, returning `default` if it panics.
+fn guard<T, F: FnOnce() -> T + panic::UnwindSafe>(default: T, f: F) -> T {
+    panic::catch_unwind(f).unwrap_or(default)
+}
+
+/// Parse a space from a nul-terminated JSON description, returning null if `json` is null or does
+/// not describe a valid space.
+///
+/// # Safety
+///
+/// `json` must be null or point to a nul-terminated string. The returned handle must be released
+/// with `spaces_free`, exactly once.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_parse(json: *const c_char) -> *mut OpaqueSpace {
+    if json.is_null() { return ptr::null_mut(); }
+
+    let json = CStr::from_ptr(json);
+
+    guard(ptr::null_mut(), || {
+        json.to_str().ok()
+            .and_then(|json| serde_json::from_str::<SpaceDescription>(json).ok())
+            .and_then(OpaqueSpace::new)
+            .map_or(ptr::null_mut(), |space| Box::into_raw(Box::new(space)))
+    })
+}
+
+/// Release a space returned by `spaces_parse`; null is ignored.
+///
+/// # Safety
+///
+/// `space` must be null or a handle returned by `spaces_parse` that has not yet been released.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_free(space: *mut OpaqueSpace) {
+    if !space.is_null() {
+        let space = Box::from_raw(space);
+
+        guard((), panic::AssertUnwindSafe(move || drop(space)));
+    }
+}
+
+/// Write the number of entries in a value of the space to `out`.
+///
+/// # Safety
+///
+/// `space` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_dim(space: *const OpaqueSpace, out: *mut u64) -> SpacesStatus {
+    if space.is_null() || out.is_null() { return SpacesStatus::NullPointer; }
+
+    let space = &*space;
+
+    match guard(None, || Some(space.dim() as u64)) {
+        Some(dim) => {
+            *out = dim;
+
+            SpacesStatus::Ok
+        },
+        None => SpacesStatus::Panicked,
+    }
+}
+
+/// Write the number of values in the space to `out` and return true if it is finite and fits in
+/// a `u64`; otherwise, or if either pointer is null, return false and leave `out` untouched.
+///
+/// # Safety
+///
+/// `space` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_card_finite(space: *const OpaqueSpace, out: *mut u64) -> bool {
+    if space.is_null() || out.is_null() { return false; }
+
+    let space = &*space;
+    let card = guard(Card::Infinite, || {
+        ProductCard(space.leaves.iter().map(Leaf::card).collect()).total()
+    });
+
+    match card {
+        Card::Finite(n) => {
+            *out = n as u64;
+
+            true
+        },
+        _ => false,
+    }
+}
+
+/// Return true if the `len` entries at `ptr` are a value of the space; false if not, or if the
+/// arguments are invalid.
+///
+/// # Safety
+///
+/// `space` must be null or a live handle, and `ptr` must be null or valid for `len` reads.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_contains_f64(
+    space: *const OpaqueSpace,
+    ptr: *const f64,
+    len: usize,
+) -> bool {
+    if space.is_null() || ptr.is_null() { return false; }
+
+    let space = &*space;
+    let val = slice::from_raw_parts(ptr, len);
+
+    guard(false, || {
+        len == space.dim()
+            && !space.is_empty()
+            && space.values().zip(val).all(|(leaf, &x)| leaf.contains(x))
+    })
+}
+
+/// Draw a value uniformly at random, from a generator seeded with `seed`, and write its `len`
+/// entries to `out`; nothing is written unless the status is `Ok`.
+///
+/// # Safety
+///
+/// `space` must be null or a live handle, and `out` must be null or valid for `len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn spaces_sample_f64(
+    space: *const OpaqueSpace,
+    seed: u64,
+    out: *mut f64,
+    len: usize,
+) -> SpacesStatus {
+    if space.is_null() || out.is_null() { return SpacesStatus::NullPointer; }
+
+    let space = &*space;
+
+    if len != space.dim() { return SpacesStatus::LengthMismatch; }
+
+    let sample = guard(None, || {
+        let mut rng = SmallRng::seed_from_u64(seed);
+
+        Some(if space.is_empty() {
+            Err(SampleError::Empty)
+        } else {
+            space.values().map(|leaf| leaf.try_sample(&mut rng)).collect::<Result<Vec<_>, _>>()
+        })
+    });
+
+    match sample {
+        Some(Ok(val)) => {
+            slice::from_raw_parts_mut(out, len).copy_from_slice(&val);
+
+            SpacesStatus::Ok
+        },
+        Some(Err(_)) => SpacesStatus::Unsampleable,
+        None => SpacesStatus::Panicked,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+    use super::*;
+
+    fn scalar(kind: &str) -> String { format!(r#"{{"scalar": {}}}"#, kind) }
+
+    fn product(components: &[String]) -> String {
+        format!(r#"{{"product": [{}]}}"#, components.join(", "))
+    }
+
+    fn cartpole() -> String {
+        product(&[
+            scalar(r#"{"type": "real", "lb": -4.8, "ub": 4.8}"#),
+            scalar(r#"{"type": "real", "lb": null, "ub": null}"#),
+            scalar(r#"{"type": "binary"}"#),
+            product(&[
+                scalar(r#"{"type": "discrete", "n": 3}"#),
+                scalar(r#"{"type": "quantised", "lb": 0, "ub": 1, "step": 0.25}"#),
+            ]),
+        ])
+    }
+
+    // Parses `json` through the C interface, panicking if it is rejected.
+    fn parse(json: &str) -> *mut OpaqueSpace {
+        let json = CString::new(json).unwrap();
+        let space = unsafe { spaces_parse(json.as_ptr()) };
+
+        assert!(!space.is_null(), "{} was rejected", json.to_str().unwrap());
+
+        space
+    }
+
+    fn dim(space: *const OpaqueSpace) -> u64 {
+        let mut dim = 0;
+
+        assert_eq!(unsafe { spaces_dim(space, &mut dim) }, SpacesStatus::Ok);
+
+        dim
+    }
+
+    fn card(space: *const OpaqueSpace) -> Option<u64> {
+        let mut card = 0;
+
+        if unsafe { spaces_card_finite(space, &mut card) } { Some(card) } else { None }
+    }
+
+    fn contains(space: *const OpaqueSpace, val: &[f64]) -> bool {
+        unsafe { spaces_contains_f64(space, val.as_ptr(), val.len()) }
+    }
+
+    fn sample(space: *const OpaqueSpace, seed: u64, val: &mut [f64]) -> SpacesStatus {
+        unsafe { spaces_sample_f64(space, seed, val.as_mut_ptr(), val.len()) }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let space = parse(&cartpole());
+        let mut val = [f64::NAN; 5];
+
+        assert_eq!(dim(space), 5);
+        assert_eq!(card(space), None);
+        assert_eq!(sample(space, 0, &mut val), SpacesStatus::Unsampleable);
+        assert!(val.iter().all(|x| x.is_nan()));
+
+        assert!(contains(space, &[4.8, -1e300, 1.0, 2.0, 0.75]));
+        assert!(!contains(space, &[4.9, 0.0, 1.0, 2.0, 0.75]));
+        assert!(!contains(space, &[0.0, 0.0, 0.5, 2.0, 0.75]));
+        assert!(!contains(space, &[0.0, 0.0, 1.0, 3.0, 0.75]));
+        assert!(!contains(space, &[0.0, 0.0, 1.0, 1.5, 0.75]));
+        assert!(!contains(space, &[0.0, 0.0, 1.0, 2.0, 0.7]));
+
+        unsafe { spaces_free(space) };
+    }
+
+    #[test]
+    fn test_sample() {
+        let nonempty = vec![
+            scalar(r#"{"type": "real", "lb": -1, "ub": 1}"#),
+            scalar(r#"{"type": "integer", "lb": -3, "ub": 3}"#),
+            scalar(r#"{"type": "partition", "lb": 0, "ub": 1, "n": 4}"#),
+            scalar(r#"{"type": "binary"}"#),
+            scalar(r#"{"type": "quantised", "lb": 0, "ub": 1, "step": 0.5}"#),
+        ];
+        let mut empty = nonempty.clone();
+
+        empty.insert(3, scalar(r#"{"type": "empty"}"#));
+
+        let (space, nonempty) = (parse(&product(&empty)), parse(&product(&nonempty)));
+        let (mut a, mut b) = ([0.0; 5], [0.0; 5]);
+
+        assert_eq!(dim(space), 5);
+        assert_eq!(card(space), Some(0));
+        assert_eq!(card(nonempty), None);
+        assert_eq!(sample(space, 0, &mut a), SpacesStatus::Unsampleable);
+        assert!(!contains(space, &a));
+
+        for seed in 0..100 {
+            assert_eq!(sample(nonempty, seed, &mut a), SpacesStatus::Ok);
+            assert_eq!(sample(nonempty, seed, &mut b), SpacesStatus::Ok);
+            assert_eq!(a, b);
+            assert!(contains(nonempty, &a), "{:?} is not contained", a);
+        }
+
+        unsafe {
+            spaces_free(space);
+            spaces_free(nonempty);
+        }
+    }
+
+    #[test]
+    fn test_card() {
+        let finite = parse(&product(&[
+            scalar(r#"{"type": "binary"}"#),
+            product(&[scalar(r#"{"type": "discrete", "n": 3}"#)]),
+            scalar(r#"{"type": "integer", "lb": -1, "ub": 1}"#),
+            scalar(r#"{"type": "quantised", "lb": 0, "ub": 1, "step": 0.1}"#),
+        ]));
+        let single = parse(&scalar(r#"{"type": "discrete", "n": 7}"#));
+        let overflow = parse(&product(&vec![scalar(r#"{"type": "discrete", "n": 65536}"#); 5]));
+        let integers = parse(&scalar(&format!(
+            r#"{{"type": "integer", "lb": {}, "ub": {}}}"#,
+            i64::MIN,
+            i64::MAX
+        )));
+
+        assert_eq!(card(finite), Some(2 * 3 * 3 * 11));
+        assert_eq!(card(single), Some(7));
+        assert_eq!(dim(single), 1);
+        assert_eq!(card(overflow), None);
+        assert_eq!(card(integers), None);
+
+        unsafe {
+            spaces_free(finite);
+            spaces_free(single);
+            spaces_free(overflow);
+            spaces_free(integers);
+        }
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        for json in [
+            String::new(),
+            "{".to_owned(),
+            r#"{"type": "binary"}"#.to_owned(),
+            scalar(r#"{"type": "circle"}"#),
+            scalar(r#"{"type": "discrete", "n": -1}"#),
+            product(&[scalar(r#"{"type": "quantised", "lb": 1, "ub": 0, "step": 0.1}"#)]),
+            scalar(r#"{"type": "quantised", "lb": 0, "ub": 1, "step": 0}"#),
+        ].iter() {
+            let json = CString::new(json.as_str()).unwrap();
+
+            assert!(unsafe { spaces_parse(json.as_ptr()) }.is_null(), "{:?}", json);
+        }
+
+        let invalid_utf8 = CString::new(vec![b'"', 0xff, b'"']).unwrap();
+
+        assert!(unsafe { spaces_parse(invalid_utf8.as_ptr()) }.is_null());
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let space = parse(&scalar(r#"{"type": "binary"}"#));
+        let (mut out, mut val) = (0u64, [0.0]);
+
+        unsafe {
+            assert!(spaces_parse(ptr::null()).is_null());
+            spaces_free(ptr::null_mut());
+
+            assert_eq!(spaces_dim(ptr::null(), &mut out), SpacesStatus::NullPointer);
+            assert_eq!(spaces_dim(space, ptr::null_mut()), SpacesStatus::NullPointer);
+            assert!(!spaces_card_finite(ptr::null(), &mut out));
+            assert!(!spaces_card_finite(space, ptr::null_mut()));
+            assert!(!spaces_contains_f64(ptr::null(), val.as_ptr(), 1));
+            assert!(!spaces_contains_f64(space, ptr::null(), 1));
+            assert_eq!(
+                spaces_sample_f64(ptr::null(), 0, val.as_mut_ptr(), 1),
+                SpacesStatus::NullPointer
+            );
+            assert_eq!(
+                spaces_sample_f64(space, 0, ptr::null_mut(), 1),
+                SpacesStatus::NullPointer
+            );
+
+            spaces_free(space);
+        }
+
+        assert_eq!(out, 0);
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let space = parse(&product(&vec![scalar(r#"{"type": "binary"}"#); 2]));
+        let mut val = [0.0; 3];
+
+        assert!(!contains(space, &[0.0]));
+        assert!(!contains(space, &[0.0; 3]));
+        assert_eq!(sample(space, 0, &mut val[..1]), SpacesStatus::LengthMismatch);
+        assert_eq!(sample(space, 0, &mut val), SpacesStatus::LengthMismatch);
+        assert_eq!(val, [0.0; 3]);
+
+        unsafe { spaces_free(space) };
+    }
+
+    #[test]
+    fn test_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/spaces.h"));
+
+        assert!(
+            generated == include_str!("../include/spaces.h"),
+            "include/spaces.h is out of date; copy it from {}.",
+            concat!(env!("OUT_DIR"), "/spaces.h")
+        );
+    }
+
+    #[test]
+    fn test_panics_are_caught() {
+        assert_eq!(guard(0, || panic!("boom")), 0);
+        assert_eq!(guard(SpacesStatus::Ok, || SpacesStatus::Panicked), SpacesStatus::Panicked);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    description::{fnv1a, FNV_OFFSET},
+    discrete::{Alphabet, Binary, Ordinal, OrdinalIndex},
+    prelude::*,
+    Empty, Equipartition, Interval, LogPartition, Nominal, Ordered, PairSpace, ProductSpace,
+    Quantised, TwoSpace,
+};
+
+/// Number of buckets into which the width of a bounded real interval is divided by default when
+/// fingerprinting its values.
+pub const FINGERPRINT_BUCKETS: f64 = (1u64 << 20) as f64;
+
+/// Trait for spaces whose values can be reduced to a stable 64-bit fingerprint, e.g. for
+/// deduplicating visited states in a search, including values that contain floats and so do not
+/// implement `Hash`.
+///
+/// Values that compare equal always have equal fingerprints, and fingerprints are stable across
+/// runs, platforms and versions of the crate. Finite spaces hash the index of the value, so all
+/// values outside of the space share one fingerprint. Real intervals hash the bucket containing
+/// the value, such that nearby values within one bucket intentionally collide; see
+/// `Interval::<f64>::fingerprint_with_resolution`. Products mix the fingerprints of their
+/// components in order, such that swapping components changes the fingerprint.
+/// ```
+/// use spaces::{discrete::Ordinal, Fingerprint, Interval, PairSpace};
+///
+/// let space = PairSpace::new(Ordinal::new(4), Interval::bounded(0.0, 1.0));
+///
+/// assert_eq!(space.value_fingerprint(&(2, 0.5)), space.value_fingerprint(&(2, 0.5)));
+/// assert_ne!(space.value_fingerprint(&(2, 0.5)), space.value_fingerprint(&(3, 0.5)));
+/// assert_ne!(space.value_fingerprint(&(2, 0.5)), space.value_fingerprint(&(2, 0.75)));
+/// ```
+pub trait Fingerprint: Space {
+    /// Returns the fingerprint of `val`.
+    fn value_fingerprint(&self, val: &Self::Value) -> u64;
+}
+
+fn hash_u64(x: u64) -> u64 { fnv1a(FNV_OFFSET, &x.to_le_bytes()) }
+
+// Mixes the fingerprints of components in order.
+fn mix<I: IntoIterator<Item = u64>>(fingerprints: I) -> u64 {
+    fingerprints.into_iter().fold(FNV_OFFSET, |h, fp| fnv1a(h, &fp.to_le_bytes()))
+}
+
+macro_rules! impl_fingerprint_finite {
+    ($([$($params:tt)*] $type:ty $({ $($bound:tt)* })?),+ $(,)?) => {$(
+        /// Values are fingerprinted by their index.
+        impl<$($params)*> Fingerprint for $type where $($($bound)*)? {
+            fn value_fingerprint(&self, val: &Self::Value) -> u64 {
+                hash_u64(self.to_index(val).map_or(u64::MAX, |idx| idx as u64))
+            }
+        }
+    )+};
+}
+
+impl_fingerprint_finite!(
+    [] Empty,
+    [] Binary,
+    [] Alphabet,
+    [I: OrdinalIndex] Ordinal<I>,
+    [] Equipartition,
+    [] LogPartition,
+    [] Quantised,
+    [S: FiniteSpace] Nominal<S> { S::Value: PartialEq },
+    [S: FiniteSpace] Ordered<S> { S::Value: PartialEq },
+);
+
+/// Values are exact, and are fingerprinted as such.
+impl Fingerprint for Interval<i64> {
+    fn value_fingerprint(&self, val: &i64) -> u64 { hash_u64(*val as u64) }
+}
+
+impl Interval<f64> {
+    /// Returns the fingerprint of the bucket of width `resolution` containing `val`.
+    ///
+    /// Buckets are half-open, `[lb + k * resolution, lb + (k + 1) * resolution)` for integers
+    /// `k`, and are counted from zero if the interval has no lower bound. Values in one bucket
+    /// have equal fingerprints, so two values that differ by less than `resolution` may or may not
+    /// collide depending on where the boundaries fall.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is not positive and finite.
+    pub fn fingerprint_with_resolution(&self, val: f64, resolution: f64) -> u64 {
+        assert!(
+            resolution > 0.0 && resolution.is_finite(),
+            "The resolution of a fingerprint must be positive and finite."
+        );
+
+        let bucket = ((val - self.lb.unwrap_or(0.0)) / resolution).floor();
+
+        hash_u64(bucket as i64 as u64)
+    }
+
+    /// Returns the default resolution of fingerprints: the width of the interval divided by
+    /// `FINGERPRINT_BUCKETS`, or `1 / FINGERPRINT_BUCKETS` if the interval is unbounded or
+    /// degenerate.
+    pub fn fingerprint_resolution(&self) -> f64 {
+        match (self.lb, self.ub) {
+            (Some(lb), Some(ub)) if ub > lb && (ub - lb).is_finite() =>
+                (ub - lb) / FINGERPRINT_BUCKETS,
+            _ => 1.0 / FINGERPRINT_BUCKETS,
+        }
+    }
+}
+
+/// Values are fingerprinted by their bucket at the default resolution; see
+/// `fingerprint_resolution`.
+impl Fingerprint for Interval<f64> {
+    fn value_fingerprint(&self, val: &f64) -> u64 {
+        self.fingerprint_with_resolution(*val, self.fingerprint_resolution())
+    }
+}
+
+/// Components are mixed in order; the fingerprint also depends on the number of entries.
+impl<D: Fingerprint> Fingerprint for ProductSpace<D> {
+    fn value_fingerprint(&self, val: &Vec<D::Value>) -> u64 {
+        let fps = self.iter().zip(val.iter()).map(|(d, v)| d.value_fingerprint(v));
+
+        mix(fps.chain(Some(val.len() as u64)))
+    }
+}
+
+/// Components are mixed in order.
+impl<D: Fingerprint> Fingerprint for TwoSpace<D> {
+    fn value_fingerprint(&self, val: &[D::Value; 2]) -> u64 {
+        let fps = [self[0].value_fingerprint(&val[0]), self[1].value_fingerprint(&val[1])];
+
+        mix(fps.iter().copied())
+    }
+}
+
+/// Components are mixed in order.
+impl<D1: Fingerprint, D2: Fingerprint> Fingerprint for PairSpace<D1, D2> {
+    fn value_fingerprint(&self, val: &(D1::Value, D2::Value)) -> u64 {
+        let fps = [self.0.value_fingerprint(&val.0), self.1.value_fingerprint(&val.1)];
+
+        mix(fps.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden() {
+        // Golden values: these must never change between runs or crate versions.
+        assert_eq!(Ordinal::new(5).value_fingerprint(&3), 0xc7c2_bf3b_3309_83e6);
+        assert_eq!(Interval::bounded(0.0, 1.0).value_fingerprint(&0.5), 0xc414_d69a_b88c_07fd);
+        assert_eq!(
+            ProductSpace::new(vec![Ordinal::new(3); 2]).value_fingerprint(&vec![1, 2]),
+            0x9967_b484_cec5_a378
+        );
+    }
+
+    #[test]
+    fn test_equal_values() {
+        let d = Interval::bounded(-1.0, 1.0);
+
+        assert_eq!(d.value_fingerprint(&0.0), d.value_fingerprint(&-0.0));
+        assert_eq!(Binary.value_fingerprint(&true), Ordinal::new(2).value_fingerprint(&1));
+        assert_eq!(
+            Nominal::new(Ordinal::new(3)).value_fingerprint(&2),
+            Ordinal::new(3).value_fingerprint(&2)
+        );
+        assert_eq!(
+            Alphabet::new("abc").value_fingerprint(&'d'),
+            Alphabet::new("abc").value_fingerprint(&'e')
+        );
+        assert_ne!(
+            Interval::<i64>::unbounded().value_fingerprint(&-1),
+            Interval::<i64>::unbounded().value_fingerprint(&1)
+        );
+        assert_ne!(
+            Ordinal::new(3).value_fingerprint(&0),
+            Ordinal::new(3).value_fingerprint(&1)
+        );
+    }
+
+    #[test]
+    fn test_order_sensitive() {
+        let product = ProductSpace::new(vec![Ordinal::new(3); 3]);
+        let two = TwoSpace::new([Interval::bounded(0.0, 1.0); 2]);
+        let pair = PairSpace::new(Ordinal::new(3), Ordinal::new(3));
+
+        assert_ne!(
+            product.value_fingerprint(&vec![1, 2, 0]),
+            product.value_fingerprint(&vec![2, 1, 0])
+        );
+        assert_ne!(two.value_fingerprint(&[0.25, 0.75]), two.value_fingerprint(&[0.75, 0.25]));
+        assert_ne!(pair.value_fingerprint(&(0, 2)), pair.value_fingerprint(&(2, 0)));
+        assert_ne!(
+            ProductSpace::new(vec![Ordinal::new(3); 2]).value_fingerprint(&vec![0, 0]),
+            ProductSpace::new(vec![Ordinal::new(3); 3]).value_fingerprint(&vec![0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_buckets() {
+        let d = Interval::bounded(1.0, 2.0);
+        let fp = |x: f64| d.fingerprint_with_resolution(x, 0.25);
+
+        assert_eq!(fp(1.0), fp(1.2499));
+        assert_ne!(fp(1.2499), fp(1.25));
+        assert_eq!(fp(1.25), fp(1.49));
+        assert_ne!(fp(1.75), fp(2.0));
+
+        let res = d.fingerprint_resolution();
+
+        assert_eq!(res, 1.0 / FINGERPRINT_BUCKETS);
+        assert_eq!(d.value_fingerprint(&1.5), d.value_fingerprint(&(1.5 + res / 2.0)));
+        assert_ne!(d.value_fingerprint(&1.5), d.value_fingerprint(&(1.5 - res / 2.0)));
+        assert_ne!(d.value_fingerprint(&1.5), d.value_fingerprint(&(1.5 + res)));
+
+        let unbounded = Interval::<f64>::unbounded();
+        let fp = |x: f64| unbounded.fingerprint_with_resolution(x, 0.5);
+
+        assert_eq!(fp(0.0), fp(0.4));
+        assert_ne!(fp(0.0), fp(-0.1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_resolution() { Interval::bounded(0.0, 1.0).fingerprint_with_resolution(0.5, 0.0); }
+}
@@ -0,0 +1,459 @@
+use crate::{
+    discrete::{Binary, Ordinal, OrdinalIndex},
+    prelude::*,
+    Equipartition,
+    Interval,
+    PairSpace,
+    ProductSpace,
+    Quantised,
+};
+use std::{error::Error, fmt, iter::FusedIterator, mem, slice::Iter as SliceIter};
+
+/// Trait for spaces whose values can be encoded as flat vectors of reals with box bounds, e.g.
+/// for use with black-box optimisers.
+///
+/// Real components occupy one entry each with their own bounds, finite discrete components
+/// (`Ordinal`, `Equipartition`) occupy one entry bounded by `[0, n - 1]`, and `Binary` occupies
+/// one entry bounded by `[0, 1]`. Products concatenate the encodings of their components, in
+/// order.
+/// ```
+/// use spaces::{discrete::Ordinal, real::Interval, BoundedSpace, FlatBounds, PairSpace};
+///
+/// let space = PairSpace::new(Interval::bounded(-1.0, 1.0), Ordinal::new(5));
+///
+/// assert_eq!(space.bounds_arrays(), Some((vec![-1.0, 0.0], vec![1.0, 4.0])));
+/// assert_eq!(space.value_from_flat(&[0.25, 2.6]), (0.25, 3));
+/// ```
+pub trait FlatBounds: Space {
+    /// Returns the number of entries in the flat encoding of a value.
+    fn flat_dim(&self) -> usize;
+
+    /// Returns the lower and upper bounds of each entry of the flat encoding, or `None` if any
+    /// entry is unbounded.
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)>;
+
+    /// Decode a flat encoding into a value of the space, clamping each entry onto its bounds and
+    /// rounding discrete entries to the nearest value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `x` is not `flat_dim()`.
+    fn value_from_flat(&self, x: &[f64]) -> Self::Value;
+}
+
+fn check_len(x: &[f64], n: usize) {
+    assert_eq!(x.len(), n, "Flat value does not match the dimension of the space.");
+}
+
+// Rounds `x` onto the nearest index in `0..n`, with `NaN` mapping onto zero.
+fn nearest_index(x: f64, n: usize) -> usize {
+    if x.is_nan() { return 0; }
+
+    clip!(0.0f64, x.round(), (n - 1) as f64) as usize
+}
+
+impl FlatBounds for Interval<f64> {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        Some((vec![self.lb?], vec![self.ub?]))
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> f64 {
+        check_len(x, 1);
+
+        self.map_onto(x[0])
+    }
+}
+
+impl FlatBounds for Interval<i64> {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        Some((vec![self.inf()? as f64], vec![self.sup()? as f64]))
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> i64 {
+        check_len(x, 1);
+
+        let x = if x[0].is_nan() { 0 } else { x[0].round() as i64 };
+
+        self.map_onto(x)
+    }
+}
+
+impl FlatBounds for Binary {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> { Some((vec![0.0], vec![1.0])) }
+
+    fn value_from_flat(&self, x: &[f64]) -> bool {
+        check_len(x, 1);
+
+        x[0] >= 0.5
+    }
+}
+
+/// Empty ordinals have no bounds arrays.
+impl<I: OrdinalIndex> FlatBounds for Ordinal<I> {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        if self.size() == 0 { return None; }
+
+        Some((vec![0.0], vec![(self.size() - 1) as f64]))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the space is empty.
+    fn value_from_flat(&self, x: &[f64]) -> I {
+        check_len(x, 1);
+
+        assert!(self.size() > 0, "Cannot decode a value of an empty space.");
+
+        I::from_usize(nearest_index(x[0], self.size()))
+    }
+}
+
+impl FlatBounds for Equipartition {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        Some((vec![0.0], vec![(self.n_partitions() - 1) as f64]))
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> usize {
+        check_len(x, 1);
+
+        nearest_index(x[0], self.n_partitions())
+    }
+}
+
+/// Quantised spaces are encoded by value, rather than by index, and decoded onto the nearest
+/// grid point.
+impl FlatBounds for Quantised {
+    fn flat_dim(&self) -> usize { 1 }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        Some((vec![self.lb], vec![self.ub]))
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> f64 {
+        check_len(x, 1);
+
+        self.map_onto(x[0])
+    }
+}
+
+fn concat<I>(bounds: I) -> Option<(Vec<f64>, Vec<f64>)>
+where I: IntoIterator<Item = Option<(Vec<f64>, Vec<f64>)>> {
+    bounds.into_iter().try_fold((vec![], vec![]), |(mut lbs, mut ubs), b| {
+        let (lb, ub) = b?;
+
+        lbs.extend(lb);
+        ubs.extend(ub);
+
+        Some((lbs, ubs))
+    })
+}
+
+impl<D: FlatBounds> FlatBounds for ProductSpace<D> {
+    fn flat_dim(&self) -> usize { self.iter().map(|d| d.flat_dim()).sum() }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        concat(self.iter().map(|d| d.bounds_arrays()))
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> Vec<D::Value> {
+        check_len(x, self.flat_dim());
+
+        let mut offset = 0;
+
+        self.iter().map(|d| {
+            let n = d.flat_dim();
+            let val = d.value_from_flat(&x[offset..offset + n]);
+
+            offset += n;
+
+            val
+        }).collect()
+    }
+}
+
+impl<D1: FlatBounds, D2: FlatBounds> FlatBounds for PairSpace<D1, D2> {
+    fn flat_dim(&self) -> usize { self.0.flat_dim() + self.1.flat_dim() }
+
+    fn bounds_arrays(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        concat(vec![self.0.bounds_arrays(), self.1.bounds_arrays()])
+    }
+
+    fn value_from_flat(&self, x: &[f64]) -> (D1::Value, D2::Value) {
+        check_len(x, self.flat_dim());
+
+        let (x0, x1) = x.split_at(self.0.flat_dim());
+
+        (self.0.value_from_flat(x0), self.1.value_from_flat(x1))
+    }
+}
+
+/// Error returned when a flat value does not have one entry per entry of the flat encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlatLengthError {
+    /// Length that the value was required to have, i.e. `flat_dim()`.
+    pub expected: usize,
+    /// Length that the value actually had.
+    pub found: usize,
+}
+
+impl FlatLengthError {
+    fn check(x: &[f64], expected: usize) -> Result<(), FlatLengthError> {
+        match x.len() {
+            found if found != expected => Err(FlatLengthError { expected, found, }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for FlatLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "flat value has length {}, but a length of {} was expected",
+            self.found, self.expected
+        )
+    }
+}
+
+impl Error for FlatLengthError {}
+
+/// Iterator over views of the components of a flat value; see `ProductSpace::split_value`.
+#[derive(Clone, Debug)]
+pub struct SplitValue<'s, 'a, D> {
+    components: SliceIter<'s, D>,
+    rest: &'a [f64],
+}
+
+impl<'s, 'a, D: FlatBounds> Iterator for SplitValue<'s, 'a, D> {
+    type Item = &'a [f64];
+
+    fn next(&mut self) -> Option<&'a [f64]> {
+        let (head, tail) = self.rest.split_at(self.components.next()?.flat_dim());
+
+        self.rest = tail;
+
+        Some(head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.components.size_hint() }
+}
+
+impl<'s, 'a, D: FlatBounds> ExactSizeIterator for SplitValue<'s, 'a, D> {}
+
+impl<'s, 'a, D: FlatBounds> FusedIterator for SplitValue<'s, 'a, D> {}
+
+/// Iterator over mutable views of the components of a flat value; see
+/// `ProductSpace::split_value_mut`.
+#[derive(Debug)]
+pub struct SplitValueMut<'s, 'a, D> {
+    components: SliceIter<'s, D>,
+    rest: &'a mut [f64],
+}
+
+impl<'s, 'a, D: FlatBounds> Iterator for SplitValueMut<'s, 'a, D> {
+    type Item = &'a mut [f64];
+
+    fn next(&mut self) -> Option<&'a mut [f64]> {
+        let n = self.components.next()?.flat_dim();
+        let (head, tail) = mem::take(&mut self.rest).split_at_mut(n);
+
+        self.rest = tail;
+
+        Some(head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.components.size_hint() }
+}
+
+impl<'s, 'a, D: FlatBounds> ExactSizeIterator for SplitValueMut<'s, 'a, D> {}
+
+impl<'s, 'a, D: FlatBounds> FusedIterator for SplitValueMut<'s, 'a, D> {}
+
+impl<D: FlatBounds> ProductSpace<D> {
+    /// Split a flat value into one view per component, in order, without copying.
+    ///
+    /// View `i` holds the `flat_dim()` entries of component `i`, such that the views line up
+    /// with `component_dims()` for products of scalars. The views are produced lazily, so no
+    /// allocation takes place; collect them if random access is needed.
+    /// ```
+    /// use spaces::{Interval, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![
+    ///     ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]),
+    ///     ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 3]),
+    /// ]);
+    /// let x = [0.1, 0.2, 0.3, 0.4, 0.5];
+    /// let views: Vec<&[f64]> = space.split_value(&x).unwrap().collect();
+    ///
+    /// assert_eq!(views, vec![&x[..2], &x[2..]]);
+    /// ```
+    pub fn split_value<'s, 'a>(
+        &'s self,
+        x: &'a [f64],
+    ) -> Result<SplitValue<'s, 'a, D>, FlatLengthError> {
+        FlatLengthError::check(x, self.flat_dim())?;
+
+        Ok(SplitValue { components: self.iter(), rest: x, })
+    }
+
+    /// Split a flat value into one mutable view per component, e.g. to normalise components in
+    /// place; see `split_value`.
+    pub fn split_value_mut<'s, 'a>(
+        &'s self,
+        x: &'a mut [f64],
+    ) -> Result<SplitValueMut<'s, 'a, D>, FlatLengthError> {
+        FlatLengthError::check(x, self.flat_dim())?;
+
+        Ok(SplitValueMut { components: self.iter(), rest: x, })
+    }
+}
+
+impl<D1: FlatBounds, D2: FlatBounds> PairSpace<D1, D2> {
+    /// Split a flat value into views of its two components, without copying.
+    pub fn split_value<'a>(
+        &self,
+        x: &'a [f64],
+    ) -> Result<(&'a [f64], &'a [f64]), FlatLengthError> {
+        FlatLengthError::check(x, self.flat_dim())?;
+
+        Ok(x.split_at(self.0.flat_dim()))
+    }
+
+    /// Split a flat value into mutable views of its two components, without copying.
+    pub fn split_value_mut<'a>(
+        &self,
+        x: &'a mut [f64],
+    ) -> Result<(&'a mut [f64], &'a mut [f64]), FlatLengthError> {
+        FlatLengthError::check(x, self.flat_dim())?;
+
+        Ok(x.split_at_mut(self.0.flat_dim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use super::*;
+
+    fn check_consistency<S>(space: &S, contains: impl Fn(&S::Value) -> bool)
+    where S: FlatBounds {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (lbs, ubs) = space.bounds_arrays().unwrap();
+
+        assert_eq!(lbs.len(), space.flat_dim());
+        assert_eq!(ubs.len(), space.flat_dim());
+
+        for _ in 0..1000 {
+            let x: Vec<f64> =
+                lbs.iter().zip(ubs.iter()).map(|(&lb, &ub)| rng.gen_range(lb..=ub)).collect();
+
+            assert!(contains(&space.value_from_flat(&x)));
+        }
+
+        assert!(contains(&space.value_from_flat(&lbs)));
+        assert!(contains(&space.value_from_flat(&ubs)));
+    }
+
+    #[test]
+    fn test_bounds_arrays() {
+        let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::unit()]);
+
+        assert_eq!(space.bounds_arrays(), Some((vec![-1.0, 0.0], vec![1.0, 1.0])));
+        assert_eq!(Binary.bounds_arrays(), Some((vec![0.0], vec![1.0])));
+        assert_eq!(Ordinal::new(4).bounds_arrays(), Some((vec![0.0], vec![3.0])));
+        assert_eq!(Ordinal::new(0).bounds_arrays(), None);
+        assert_eq!(Interval::<f64>::left_bounded(0.0).bounds_arrays(), None);
+        assert_eq!(
+            ProductSpace::<Interval>::new(vec![Interval::unit(), Interval::unbounded()])
+                .bounds_arrays(),
+            None
+        );
+        assert_eq!(ProductSpace::<Interval>::empty().bounds_arrays(), Some((vec![], vec![])));
+    }
+
+    #[test]
+    fn test_value_from_flat_rounds() {
+        let d = Ordinal::new(4);
+
+        assert_eq!(d.value_from_flat(&[1.4]), 1);
+        assert_eq!(d.value_from_flat(&[1.6]), 2);
+        assert_eq!(d.value_from_flat(&[10.0]), 3);
+        assert_eq!(d.value_from_flat(&[f64::NAN]), 0);
+        assert!(!Binary.value_from_flat(&[0.4]));
+        assert!(Binary.value_from_flat(&[0.5]));
+        assert_eq!(Interval::<i64>::bounded(-3, 3).value_from_flat(&[-2.7]), -3);
+        assert_eq!(Quantised::new(0.0, 1.0, 0.25).value_from_flat(&[0.3]), 0.25);
+    }
+
+    #[test]
+    fn test_consistency() {
+        let reals = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::open(0.0, 1.0)]);
+
+        check_consistency(&reals, |v| reals.contains(v));
+        check_consistency(&Ordinal::new(7), |&v| Ordinal::new(7).contains(v));
+        check_consistency(&Equipartition::new(0.0, 1.0, 3), |&v| v < 3);
+        check_consistency(&Interval::<i64>::bounded(-2, 5), |&v| (-2..=5).contains(&v));
+        check_consistency(&Quantised::new(-1.0, 1.0, 0.5), |&v| {
+            Quantised::new(-1.0, 1.0, 0.5).contains(v)
+        });
+
+        let mixed = PairSpace::new(
+            ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(5)]),
+            PairSpace::new(Binary, Interval::bounded(2.0, 4.0)),
+        );
+
+        assert_eq!(mixed.flat_dim(), 4);
+        check_consistency(&mixed, |(a, (_, x))| {
+            a[0] < 3 && a[1] < 5 && Interval::bounded(2.0, 4.0).contains(*x)
+        });
+    }
+
+    #[test]
+    fn test_split_value() {
+        let space = ProductSpace::new(vec![
+            ProductSpace::<Interval>::new(vec![Interval::unit(); 2]),
+            ProductSpace::new(vec![Interval::unit(); 3]),
+            ProductSpace::new(vec![Interval::unit(); 1]),
+        ]);
+        let mut x = vec![0.0, 1.0, 1.0, 1.0, 2.0, 3.0];
+
+        for view in space.split_value_mut(&mut x).unwrap().skip(1).take(1) {
+            let total: f64 = view.iter().sum();
+
+            view.iter_mut().for_each(|v| *v /= total);
+        }
+
+        assert_eq!(x, vec![0.0, 1.0, 0.25, 0.25, 0.5, 3.0]);
+        assert_eq!(space.split_value(&x).unwrap().len(), 3);
+        assert_eq!(space.split_value(&x).unwrap().map(|v| v.len()).collect::<Vec<_>>(), [2, 3, 1]);
+        assert_eq!(
+            space.split_value(&x[1..]).unwrap_err(),
+            FlatLengthError { expected: 6, found: 5 }
+        );
+
+        let units = ProductSpace::<Interval>::new(vec![Interval::unit(); 2]);
+        let pair = PairSpace::new(Ordinal::new(3), units);
+        let mut y = [2.0, 0.5, 0.25];
+
+        pair.split_value_mut(&mut y).unwrap().1[1] = 1.0;
+
+        assert_eq!(pair.split_value(&y), Ok((&[2.0][..], &[0.5, 1.0][..])));
+        assert_eq!(pair.split_value(&[]), Err(FlatLengthError { expected: 3, found: 0 }));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_value_from_flat_wrong_len() {
+        ProductSpace::<Interval>::new(vec![Interval::unit(); 3]).value_from_flat(&[0.0, 0.0]);
+    }
+}
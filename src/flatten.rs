@@ -0,0 +1,327 @@
+use crate::{Interval, ProductSpace, ScalarKind, SizeLimitError, SpaceDescription};
+use std::ops::Range;
+
+/// Map from the paths of the leaves of a `SpaceDescription` onto the columns they occupy in its
+/// flattened box.
+///
+/// Paths are the dotted positions of the leaves in the tree of products, e.g. `"1.0"` for the
+/// first component of the second component; the path of a scalar root is the empty string.
+/// Ranges are listed in depth-first order, and tile the full width of the box.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlattenIndex(Vec<(String, Range<usize>)>);
+
+impl FlattenIndex {
+    /// Returns the columns occupied by the leaf at `path`, if any.
+    pub fn get(&self, path: &str) -> Option<Range<usize>> {
+        self.0.iter().find(|(p, _)| p == path).map(|(_, r)| r.clone())
+    }
+
+    /// Iterate over the leaf paths and their columns, in depth-first order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Range<usize>)> {
+        self.0.iter().map(|(p, r)| (p.as_str(), r.clone()))
+    }
+
+    /// Returns the number of leaves.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Returns true iff there are no leaves.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Returns the total number of columns.
+    pub fn width(&self) -> usize { self.0.last().map_or(0, |(_, r)| r.end) }
+}
+
+/// Returns the number of columns occupied by a leaf, and whether it is one-hot encoded.
+fn leaf_layout(kind: &ScalarKind) -> (usize, bool) {
+    match kind {
+        ScalarKind::Empty => (0, false),
+        ScalarKind::Binary => (2, true),
+        ScalarKind::Discrete { n } | ScalarKind::Partition { n, .. } => (*n, true),
+        _ => (1, false),
+    }
+}
+
+fn leaf_bounds(kind: &ScalarKind) -> Interval {
+    match *kind {
+        ScalarKind::Integer { lb, ub, lb_kind, ub_kind, .. } =>
+            Interval::new(lb.map(|x| x as f64), ub.map(|x| x as f64)).with_kinds(lb_kind, ub_kind),
+        ScalarKind::Real { lb, ub, lb_kind, ub_kind } =>
+            Interval::new(lb, ub).with_kinds(lb_kind, ub_kind),
+        ScalarKind::Quantised { lb, ub, .. } => Interval::bounded(lb, ub),
+        _ => Interval::bounded(0.0, 1.0),
+    }
+}
+
+impl SpaceDescription {
+    pub(crate) fn visit_leaves<'a, F>(&'a self, path: &mut String, f: &mut F)
+    where F: FnMut(&str, &'a ScalarKind)
+    {
+        match self {
+            SpaceDescription::Scalar(kind) => f(path, kind),
+            SpaceDescription::Product(components) => for (i, c) in components.iter().enumerate() {
+                let len = path.len();
+
+                if len > 0 { path.push('.'); }
+
+                path.push_str(&i.to_string());
+                c.visit_leaves(path, f);
+                path.truncate(len);
+            },
+        }
+    }
+
+    pub(crate) fn leaves(&self) -> Vec<&ScalarKind> {
+        let mut leaves = vec![];
+
+        self.visit_leaves(&mut String::new(), &mut |_, kind| leaves.push(kind));
+
+        leaves
+    }
+
+    // Returns the width of the flattened box, provided it is within `MATERIALISE_LIMIT`.
+    fn flat_width(&self) -> Result<usize, SizeLimitError> {
+        let width =
+            self.leaves().into_iter().fold(0usize, |w, k| w.saturating_add(leaf_layout(k).0));
+
+        SizeLimitError::check(width)
+    }
+
+    /// Flatten the description into a single box, along with the columns of each leaf.
+    ///
+    /// Finite leaves (`binary`, `discrete` and `partition`) are one-hot encoded, with one
+    /// `[0, 1]` column per value; all other leaves occupy a single column with the bounds of the
+    /// leaf, and `empty` leaves occupy none. Integer leaves are therefore never one-hot encoded,
+    /// even when bounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flattened box has more than `MATERIALISE_LIMIT` columns; see
+    /// `try_flatten`.
+    /// ```
+    /// use spaces::{ScalarKind, SpaceDescription};
+    ///
+    /// let desc = SpaceDescription::Product(vec![
+    ///     SpaceDescription::Scalar(ScalarKind::Discrete { n: 3 }),
+    ///     SpaceDescription::Product(vec![
+    ///         SpaceDescription::Scalar(ScalarKind::real(Some(-1.0), Some(1.0))),
+    ///         SpaceDescription::Scalar(ScalarKind::Binary),
+    ///     ]),
+    /// ]);
+    /// let (space, index) = desc.flatten();
+    ///
+    /// assert_eq!(space.iter().len(), 6);
+    /// assert_eq!(index.get("0"), Some(0..3));
+    /// assert_eq!(index.get("1.0"), Some(3..4));
+    /// assert_eq!(index.get("1.1"), Some(4..6));
+    /// ```
+    pub fn flatten(&self) -> (ProductSpace<Interval>, FlattenIndex) {
+        self.try_flatten().unwrap_or_else(|e| panic!("Cannot flatten space: {}.", e))
+    }
+
+    /// Flatten the description as in `flatten`, or return an error if the flattened box has
+    /// more than `MATERIALISE_LIMIT` columns (e.g. for one-hot encodings of large discrete
+    /// leaves).
+    pub fn try_flatten(&self) -> Result<(ProductSpace<Interval>, FlattenIndex), SizeLimitError> {
+        let mut columns = Vec::with_capacity(self.flat_width()?);
+        let mut ranges = vec![];
+
+        self.visit_leaves(&mut String::new(), &mut |path, kind| {
+            let (width, _) = leaf_layout(kind);
+            let start = columns.len();
+
+            columns.extend((0..width).map(|_| leaf_bounds(kind)));
+            ranges.push((path.to_owned(), start..start + width));
+        });
+
+        Ok((ProductSpace::new(columns), FlattenIndex(ranges)))
+    }
+
+    /// Flatten a value, given as one number per non-empty leaf in depth-first order, into a row
+    /// of the flattened box.
+    ///
+    /// Finite leaves are given by their index (with `binary` values as `0` or `1`), and are
+    /// expanded into their one-hot encoding; all other leaves are copied verbatim.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the number of non-empty leaves, or if the
+    /// flattened box has more than `MATERIALISE_LIMIT` columns.
+    pub fn flatten_value(&self, leaves: &[f64]) -> Vec<f64> {
+        let width = self.flat_width().unwrap_or_else(|e| panic!("Cannot flatten value: {}.", e));
+        let kinds: Vec<&ScalarKind> =
+            self.leaves().into_iter().filter(|k| leaf_layout(k).0 > 0).collect();
+
+        assert_eq!(kinds.len(), leaves.len(), "Value does not match the leaves of the space.");
+
+        kinds.into_iter().zip(leaves).fold(Vec::with_capacity(width), |mut row, (kind, &x)| {
+            match leaf_layout(kind) {
+                (width, true) =>
+                    row.extend((0..width).map(|i| if i as f64 == x { 1.0 } else { 0.0 })),
+                _ => row.push(x),
+            }
+
+            row
+        })
+    }
+
+    /// Inverse of `flatten_value`, recovering the leaf values from a row of the flattened box.
+    ///
+    /// One-hot encoded leaves are decoded to the index of their largest column, such that this
+    /// is an exact inverse for values in the space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the row does not match the width of the flattened box.
+    pub fn unflatten_value(&self, row: &[f64]) -> Vec<f64> {
+        let mut leaves = vec![];
+        let mut offset = 0;
+
+        for kind in self.leaves() {
+            let (width, one_hot) = leaf_layout(kind);
+
+            if width == 0 { continue; }
+
+            let cols = row
+                .get(offset..offset + width)
+                .expect("Value does not match the dimension of the space.");
+
+            leaves.push(if one_hot {
+                let argmax = (1..width).fold(0, |b, i| if cols[i] > cols[b] { i } else { b });
+
+                argmax as f64
+            } else {
+                cols[0]
+            });
+
+            offset += width;
+        }
+
+        assert_eq!(offset, row.len(), "Value does not match the dimension of the space.");
+
+        leaves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoundKind, MATERIALISE_LIMIT};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use super::*;
+
+    fn scalar(kind: ScalarKind) -> SpaceDescription { SpaceDescription::Scalar(kind) }
+
+    fn nested() -> SpaceDescription {
+        SpaceDescription::Product(vec![
+            SpaceDescription::Product(vec![
+                scalar(ScalarKind::real(Some(-1.0), Some(1.0))),
+                scalar(ScalarKind::Discrete { n: 4 }),
+            ]),
+            scalar(ScalarKind::Binary),
+            scalar(ScalarKind::Empty),
+            SpaceDescription::Product(vec![
+                scalar(ScalarKind::integer(Some(-3), Some(3))),
+                SpaceDescription::Product(vec![
+                    scalar(ScalarKind::Partition { lb: 0.0, ub: 1.0, n: 3 }),
+                    scalar(ScalarKind::Quantised { lb: 0.0, ub: 1.0, step: 0.25 }),
+                ]),
+            ]),
+        ])
+    }
+
+    fn sample_leaf<R: Rng>(rng: &mut R, kind: &ScalarKind) -> Option<f64> {
+        Some(match *kind {
+            ScalarKind::Empty => return None,
+            ScalarKind::Binary => rng.gen_range(0..2) as f64,
+            ScalarKind::Discrete { n } | ScalarKind::Partition { n, .. } =>
+                rng.gen_range(0..n) as f64,
+            ScalarKind::Integer { lb, ub, .. } => rng.gen_range(lb.unwrap()..=ub.unwrap()) as f64,
+            ScalarKind::Real { lb, ub, .. } => rng.gen_range(lb.unwrap()..=ub.unwrap()),
+            ScalarKind::Quantised { lb, ub, step } =>
+                lb + step * rng.gen_range(0..=((ub - lb) / step) as usize) as f64,
+        })
+    }
+
+    #[test]
+    fn test_index_tiles_width() {
+        let (space, index) = nested().flatten();
+        let mut end = 0;
+
+        for (_, range) in index.iter() {
+            assert_eq!(range.start, end);
+
+            end = range.end;
+        }
+
+        assert_eq!(end, index.width());
+        assert_eq!(index.width(), space.iter().len());
+        assert_eq!(index.width(), 1 + 4 + 2 + 1 + 3 + 1);
+        assert_eq!(index.len(), 7);
+        assert_eq!(index.get("2"), Some(7..7));
+        assert_eq!(index.get("3.1.0"), Some(8..11));
+        assert_eq!(index.get("4"), None);
+        assert_eq!(space[8], Interval::bounded(0.0, 1.0));
+        assert_eq!(space[7], Interval::bounded(-3.0, 3.0));
+
+        let (_, index) = scalar(ScalarKind::Discrete { n: 5 }).flatten();
+
+        assert_eq!(index.get(""), Some(0..5));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let desc = nested();
+        let (space, _) = desc.flatten();
+
+        for _ in 0..200 {
+            let leaves: Vec<f64> =
+                desc.leaves().into_iter().filter_map(|k| sample_leaf(&mut rng, k)).collect();
+            let row = desc.flatten_value(&leaves);
+
+            assert_eq!(row.len(), space.iter().len());
+            assert!(space.contains(&row));
+            assert_eq!(desc.unflatten_value(&row), leaves);
+        }
+    }
+
+    #[test]
+    fn test_open_bounds() {
+        let open = BoundKind::Open;
+        let desc = SpaceDescription::Product(vec![
+            scalar(ScalarKind::Real { lb: Some(0.0), ub: Some(1.0), lb_kind: open, ub_kind: open }),
+            scalar(ScalarKind::Integer {
+                lb: Some(-2),
+                ub: None,
+                lb_kind: open,
+                ub_kind: BoundKind::Closed,
+                excluded: vec![],
+            }),
+        ]);
+        let (space, _) = desc.flatten();
+
+        assert_eq!(space[0], Interval::open(0.0, 1.0));
+        assert_eq!(space[1], Interval::left_bounded(-2.0).with_kinds(open, BoundKind::Closed));
+        assert!(!space.contains(&[0.0, 0.0]) && !space.contains(&[0.5, -2.0]));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let huge = scalar(ScalarKind::Discrete { n: MATERIALISE_LIMIT + 1 });
+        let desc = SpaceDescription::Product(vec![huge.clone(), huge]);
+
+        assert_eq!(
+            desc.try_flatten().unwrap_err(),
+            SizeLimitError { size: 2 * MATERIALISE_LIMIT + 2, limit: MATERIALISE_LIMIT }
+        );
+        assert_eq!(
+            scalar(ScalarKind::Discrete { n: MATERIALISE_LIMIT }).flat_width(),
+            Ok(MATERIALISE_LIMIT)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_flatten_wrong_length() {
+        nested().flatten_value(&[0.0, 1.0]);
+    }
+}
@@ -0,0 +1,338 @@
+//! Import of spaces exported from gym (or a similar library), tolerating the forms seen in
+//! real-world exports; see `import_gym_json`.
+use crate::parse;
+use serde_json::{Map, Value};
+use std::{error::Error, fmt};
+
+type Result<T> = ::std::result::Result<T, ImportError>;
+
+/// How `import_gym_json` treats the forms that it can coerce into the schema of this crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Coerce infinite bounds written as strings, and drop unknown keys.
+    #[default]
+    Lenient,
+    /// Reject infinite bounds written as strings, and unknown keys, with an `ImportError`.
+    Strict,
+}
+
+/// The reason that a document could not be imported by `import_gym_json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ImportErrorKind {
+    /// An infinite bound was written as a string, which strict mode rejects.
+    StringBound(String),
+    /// A key is not part of the layout of the space, which strict mode rejects.
+    UnknownKey(String),
+    /// A bound is neither a number, null nor an infinite string; the value is rendered as JSON.
+    InvalidBound(String),
+    /// The lower and upper bounds, or the shape, of a box disagree in length.
+    ShapeMismatch,
+    /// A kind of gym space that has no counterpart in this crate.
+    Unsupported(String),
+    /// The document is not valid JSON, or does not deserialise into the requested type.
+    Invalid(String),
+}
+
+/// Error returned by `import_gym_json`, locating the offending value within the document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportError {
+    /// Location of the value, e.g. `$.observation_space.low[1]`.
+    pub path: String,
+    pub kind: ImportErrorKind,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {}: ", self.path)?;
+
+        match &self.kind {
+            ImportErrorKind::StringBound(s) =>
+                write!(f, "infinite bound given as the string \"{}\" in strict mode", s),
+            ImportErrorKind::UnknownKey(k) => write!(f, "unknown key \"{}\" in strict mode", k),
+            ImportErrorKind::InvalidBound(v) => write!(f, "invalid bound {}", v),
+            ImportErrorKind::ShapeMismatch => write!(f, "bounds and shape of box disagree"),
+            ImportErrorKind::Unsupported(name) => write!(f, "unsupported space \"{}\"", name),
+            ImportErrorKind::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+// Names of the bounds of a box or interval, and the names they are imported as.
+const GYM_BOUNDS: [(&str, &str); 8] = [
+    ("low", "lb"), ("high", "ub"), ("lb", "lb"), ("ub", "ub"),
+    ("lo", "lb"), ("left", "lb"), ("hi", "ub"), ("right", "ub"),
+];
+
+// Keys of gym spaces that carry no information for this crate, and are ignored in either mode.
+const GYM_METADATA: [&str; 3] = ["type", "dtype", "shape"];
+
+/// Rewrite a JSON document holding spaces exported from gym (or a similar library) to the schema
+/// of this crate, tolerating the forms seen in real-world exports.
+///
+/// Spaces are recognised by their `type` tag (`Box`, `Discrete`, `MultiDiscrete`,
+/// `MultiBinary` or `Tuple`), or, untagged, by their keys: any object with bounds is a box or
+/// interval, and an object with a size `n` is discrete. Boxes become arrays of intervals,
+/// flattened from their `shape` in row-major order, and scalar bounds are broadcast over the
+/// shape; multi-discrete and multi-binary spaces become arrays of ordinal sizes. Objects tagged
+/// with any other `type`, e.g. the lowercase tags of `ScalarKind`, are left unchanged, but
+/// anything else is imported recursively, which makes it suitable for whole `EnvSpec`s.
+///
+/// Bounds may be numbers (with integers coerced to floats where needed), `null`, or infinite
+/// strings such as `"inf"`, `"-inf"` or `"-Infinity"`; missing and infinite bounds are both
+/// unbounded. The `dtype` and `shape` keys are checked where relevant and otherwise ignored, as is
+/// `type`. In `ImportMode::Lenient`, unknown keys are dropped and infinite strings are accepted;
+/// `ImportMode::Strict` rejects both with a descriptive error.
+/// ```
+/// extern crate serde_json;
+/// extern crate spaces;
+///
+/// use serde_json::json;
+/// use spaces::gym::{import_gym_json, ImportErrorKind, ImportMode};
+///
+/// let gym = json!({
+///     "type": "Box", "low": [-1, "-inf"], "high": ["inf", 2.5e1], "shape": [2],
+///     "dtype": "float32", "bounded_below": [true, false]
+/// });
+///
+/// assert_eq!(
+///     import_gym_json(gym.clone(), ImportMode::Lenient),
+///     Ok(json!([{"lb": -1}, {"ub": 25.0}])),
+/// );
+/// assert!(matches!(
+///     import_gym_json(gym, ImportMode::Strict).unwrap_err().kind,
+///     ImportErrorKind::StringBound(_) | ImportErrorKind::UnknownKey(_)
+/// ));
+/// ```
+pub fn import_gym_json(value: Value, mode: ImportMode) -> Result<Value> {
+    import(value, mode, "$")
+}
+
+/// As `import_gym_json`, but parses the document from `json` and deserialises the result into
+/// `T`, e.g. an `EnvSpec<ProductSpace<Interval>, Ordinal>`.
+pub fn from_gym_json<T>(json: &str, mode: ImportMode) -> Result<T>
+where T: serde::de::DeserializeOwned
+{
+    let invalid = |e: serde_json::Error| ImportError {
+        path: "$".to_owned(),
+        kind: ImportErrorKind::Invalid(e.to_string()),
+    };
+    let value = serde_json::from_str(json).map_err(invalid)?;
+
+    serde_json::from_value(import_gym_json(value, mode)?).map_err(invalid)
+}
+
+fn error<T>(path: &str, kind: ImportErrorKind) -> Result<T> {
+    Err(ImportError { path: path.to_owned(), kind, })
+}
+
+// Checks that every key of `map` is either in `known` or metadata, dropping unknown keys.
+fn check_keys(map: &Map<String, Value>, known: &[&str], mode: ImportMode, path: &str)
+    -> Result<()>
+{
+    let unknown = map.keys().find(|k| !known.contains(&k.as_str())
+        && !GYM_METADATA.contains(&k.as_str()));
+
+    match unknown {
+        Some(k) if mode == ImportMode::Strict =>
+            error(&format!("{}.{}", path, k), ImportErrorKind::UnknownKey(k.clone())),
+        _ => Ok(()),
+    }
+}
+
+// Returns the number of values in a space of the given `shape`, if any.
+fn shape_len(shape: Option<&Value>, path: &str) -> Result<Option<usize>> {
+    match shape {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Array(dims)) => dims
+            .iter()
+            .map(|d| d.as_u64().map(|d| d as usize))
+            .try_fold(1usize, |n, d| d.and_then(|d| n.checked_mul(d)))
+            .map(Some)
+            .map_or_else(|| invalid(path, "shape"), Ok),
+        Some(_) => invalid(path, "shape"),
+    }
+}
+
+fn invalid<T>(path: &str, what: &str) -> Result<T> {
+    error(path, ImportErrorKind::Invalid(format!("invalid {}", what)))
+}
+
+// Imports a single bound, returning `None` if it is unbounded.
+fn bound(value: &Value, mode: ImportMode, path: &str) -> Result<Option<Value>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Number(_) => Ok(Some(value.clone())),
+        Value::String(s) if parse::is_infinity(s.trim()) => match mode {
+            ImportMode::Lenient => Ok(None),
+            ImportMode::Strict => error(path, ImportErrorKind::StringBound(s.clone())),
+        },
+        _ => error(path, ImportErrorKind::InvalidBound(value.to_string())),
+    }
+}
+
+fn interval(lb: Option<Value>, ub: Option<Value>, kinds: &[(&str, &Value)]) -> Value {
+    let mut map = Map::new();
+
+    if let Some(lb) = lb { map.insert("lb".to_owned(), lb); }
+    if let Some(ub) = ub { map.insert("ub".to_owned(), ub); }
+
+    for &(k, v) in kinds {
+        map.insert(k.to_owned(), v.clone());
+    }
+
+    Value::Object(map)
+}
+
+fn import_box(map: Map<String, Value>, mode: ImportMode, path: &str) -> Result<Value> {
+    let mut known: Vec<&str> = GYM_BOUNDS.iter().map(|&(k, _)| k).collect();
+
+    known.extend(["lb_kind", "ub_kind"]);
+    check_keys(&map, &known, mode, path)?;
+
+    let side = |name: &str| -> Result<Option<(String, &Value)>> {
+        let mut found = GYM_BOUNDS.iter().filter(|&&(_, to)| to == name)
+            .filter_map(|&(from, _)| map.get(from).map(|v| (format!("{}.{}", path, from), v)));
+
+        match (found.next(), found.next()) {
+            (Some((p, _)), Some(_)) => error(&p, ImportErrorKind::Invalid(
+                format!("duplicate {} bound", if name == "lb" { "lower" } else { "upper" })
+            )),
+            (first, _) => Ok(first),
+        }
+    };
+    let (lb, ub) = (side("lb")?, side("ub")?);
+    let kinds: Vec<(&str, &Value)> = ["lb_kind", "ub_kind"]
+        .iter()
+        .filter_map(|&k| map.get(k).map(|v| (k, v)))
+        .collect();
+    let len = |side: &Option<(String, &Value)>| match side {
+        Some((_, Value::Array(vals))) => Some(vals.len()),
+        _ => None,
+    };
+    let shape = shape_len(map.get("shape"), &format!("{}.shape", path))?;
+    let n = match (len(&lb), len(&ub), shape) {
+        (None, None, None) => {
+            let lb = lb.map_or(Ok(None), |(p, v)| bound(v, mode, &p))?;
+            let ub = ub.map_or(Ok(None), |(p, v)| bound(v, mode, &p))?;
+
+            return Ok(interval(lb, ub, &kinds));
+        },
+        (a, b, c) => {
+            let lens = [a, b, c];
+            let n = lens.iter().flatten().copied().next().unwrap();
+
+            if lens.iter().flatten().any(|&m| m != n) {
+                return error(path, ImportErrorKind::ShapeMismatch);
+            }
+
+            n
+        },
+    };
+
+    // Bounds of the `i`th interval, broadcasting scalars over the shape.
+    let nth = |side: &Option<(String, &Value)>, i: usize| match side {
+        Some((p, Value::Array(vals))) => bound(&vals[i], mode, &format!("{}[{}]", p, i)),
+        Some((p, v)) => bound(v, mode, p),
+        None => Ok(None),
+    };
+
+    (0..n).map(|i| Ok(interval(nth(&lb, i)?, nth(&ub, i)?, &kinds))).collect()
+}
+
+fn size(map: &Map<String, Value>, key: &str, path: &str) -> Result<Value> {
+    match map.get(key) {
+        Some(n) if n.is_u64() => Ok(n.clone()),
+        _ => invalid(&format!("{}.{}", path, key), "size"),
+    }
+}
+
+fn import_discrete(map: Map<String, Value>, mode: ImportMode, path: &str) -> Result<Value> {
+    check_keys(&map, &["n", "size", "start"], mode, path)?;
+
+    if map.get("start").is_some_and(|s| s.as_i64() != Some(0)) {
+        return error(&format!("{}.start", path), ImportErrorKind::Unsupported(
+            "Discrete with a non-zero start".to_owned()
+        ));
+    }
+
+    size(&map, if map.contains_key("n") { "n" } else { "size" }, path)
+}
+
+pub(super) fn import(value: Value, mode: ImportMode, path: &str) -> Result<Value> {
+    let mut map = match value {
+        Value::Array(vals) => return vals
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| import(v, mode, &format!("{}[{}]", path, i)))
+            .collect(),
+        Value::Object(map) => map,
+        _ => return Ok(value),
+    };
+    let tag = map.get("type").and_then(Value::as_str).map(str::to_owned);
+
+    match tag.as_deref() {
+        Some("Box") => import_box(map, mode, path),
+        Some("Discrete") => import_discrete(map, mode, path),
+        Some("MultiDiscrete") => {
+            check_keys(&map, &["nvec"], mode, path)?;
+
+            match map.remove("nvec") {
+                Some(Value::Array(sizes)) if sizes.iter().all(Value::is_u64) =>
+                    Ok(Value::Array(sizes)),
+                _ => invalid(&format!("{}.nvec", path), "sizes"),
+            }
+        },
+        Some("MultiBinary") => {
+            check_keys(&map, &["n"], mode, path)?;
+
+            // The size may also be given as a shape, in which case the space is flattened.
+            let n = match map.get("n") {
+                shape @ Some(Value::Array(_)) =>
+                    shape_len(shape, &format!("{}.n", path))?.unwrap(),
+                _ => size(&map, "n", path)?.as_u64().unwrap() as usize,
+            };
+
+            Ok(Value::Array(vec![Value::from(2); n]))
+        },
+        Some("Tuple") => {
+            check_keys(&map, &["spaces"], mode, path)?;
+
+            match map.remove("spaces") {
+                Some(spaces @ Value::Array(_)) =>
+                    import(spaces, mode, &format!("{}.spaces", path)),
+                _ => invalid(&format!("{}.spaces", path), "spaces"),
+            }
+        },
+        Some(name @ ("Dict" | "Text" | "Graph" | "Sequence" | "OneOf")) =>
+            error(path, ImportErrorKind::Unsupported(name.to_owned())),
+
+        // Tagged with something other than a gym space, e.g. a `SpaceDescription`.
+        Some(_) => Ok(Value::Object(map)),
+
+        None if GYM_BOUNDS.iter().any(|&(k, _)| map.contains_key(k)) =>
+            import_box(map, mode, path),
+        None if map.contains_key("n") || map.contains_key("size") => {
+            let scalars = map.values().all(|v| !v.is_object() && !v.is_array());
+
+            if scalars {
+                import_discrete(map, mode, path)
+            } else {
+                import_object(map, mode, path)
+            }
+        },
+        None => import_object(map, mode, path),
+    }
+}
+
+fn import_object(map: Map<String, Value>, mode: ImportMode, path: &str) -> Result<Value> {
+    map.into_iter()
+        .map(|(k, v)| {
+            let v = import(v, mode, &format!("{}.{}", path, k))?;
+
+            Ok((k, v))
+        })
+        .collect()
+}
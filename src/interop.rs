@@ -0,0 +1,215 @@
+//! Tensor shapes for the inputs and outputs of models, enabled by the `interop` feature.
+//!
+//! Both conversions follow the flattened encoding of `SpaceDescription::flatten`: finite leaves
+//! occupy one column per value, and all other leaves a single column each. Shapes are plain
+//! vectors of `i64` extents, as taken by tensor libraries such as `tch` and `ndarray`, such that
+//! no dependency on either is required.
+//!
+//! ```
+//! use spaces::{discrete::Ordinal, interop::{OutputSpec, TensorSpec}, Interval, PairSpace};
+//!
+//! let space = PairSpace::new(Ordinal::new(3), Interval::bounded(-1.0, 1.0));
+//!
+//! assert_eq!(space.input_shape(), vec![4]);
+//! assert_eq!(space.output_spec(), OutputSpec::Composite(vec![
+//!     OutputSpec::Categorical { n: 3 },
+//!     OutputSpec::ContinuousBox { dim: 1, low: vec![-1.0], high: vec![1.0] },
+//! ]));
+//! ```
+use crate::{Describe, ScalarKind, SpaceDescription};
+
+/// Specification of the output heads of a model producing values of a space.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum OutputSpec {
+    /// Head over `n` categories, e.g. the logits of a finite space.
+    Categorical { n: usize },
+    /// Head over `dim` real values, with (possibly infinite) bounds `low` and `high`.
+    ContinuousBox { dim: usize, low: Vec<f64>, high: Vec<f64> },
+    /// One head per component of a product, in order.
+    Composite(Vec<OutputSpec>),
+}
+
+impl OutputSpec {
+    /// Build the specification of a description.
+    ///
+    /// Finite leaves (`binary`, `discrete` and `partition`) are categorical, and all other leaves
+    /// are continuous, with `empty` leaves of dimension zero. Products whose components are all
+    /// continuous are merged into a single box, such that e.g. a vector of intervals has one
+    /// head; all other products are composite.
+    pub fn from_description(desc: &SpaceDescription) -> OutputSpec {
+        match desc {
+            SpaceDescription::Scalar(kind) => OutputSpec::from_kind(kind),
+            SpaceDescription::Product(components) => {
+                let specs: Vec<_> = components.iter().map(OutputSpec::from_description).collect();
+
+                if specs.iter().all(|s| matches!(s, OutputSpec::ContinuousBox { .. })) {
+                    specs.into_iter().fold(OutputSpec::continuous(vec![], vec![]), |acc, s| {
+                        match (acc, s) {
+                            (
+                                OutputSpec::ContinuousBox { mut low, mut high, .. },
+                                OutputSpec::ContinuousBox { low: l, high: h, .. },
+                            ) => {
+                                low.extend(l);
+                                high.extend(h);
+
+                                OutputSpec::continuous(low, high)
+                            },
+                            _ => unreachable!(),
+                        }
+                    })
+                } else {
+                    OutputSpec::Composite(specs)
+                }
+            },
+        }
+    }
+
+    fn from_kind(kind: &ScalarKind) -> OutputSpec {
+        let bound = |b: Option<f64>, default: f64| b.unwrap_or(default);
+
+        match *kind {
+            ScalarKind::Empty => OutputSpec::continuous(vec![], vec![]),
+            ScalarKind::Binary => OutputSpec::Categorical { n: 2 },
+            ScalarKind::Discrete { n } | ScalarKind::Partition { n, .. } =>
+                OutputSpec::Categorical { n, },
+            ScalarKind::Integer { lb, ub, .. } => OutputSpec::continuous(
+                vec![bound(lb.map(|x| x as f64), f64::NEG_INFINITY)],
+                vec![bound(ub.map(|x| x as f64), f64::INFINITY)],
+            ),
+            ScalarKind::Real { lb, ub, .. } => OutputSpec::continuous(
+                vec![bound(lb, f64::NEG_INFINITY)],
+                vec![bound(ub, f64::INFINITY)],
+            ),
+            ScalarKind::Quantised { lb, ub, .. } => OutputSpec::continuous(vec![lb], vec![ub]),
+        }
+    }
+
+    fn continuous(low: Vec<f64>, high: Vec<f64>) -> OutputSpec {
+        OutputSpec::ContinuousBox { dim: low.len(), low, high, }
+    }
+
+    /// Returns the total number of output units over all heads, which equals the width of the
+    /// flattened encoding.
+    pub fn width(&self) -> usize {
+        match self {
+            OutputSpec::Categorical { n } => *n,
+            OutputSpec::ContinuousBox { dim, .. } => *dim,
+            OutputSpec::Composite(specs) => specs.iter().map(|s| s.width()).sum(),
+        }
+    }
+}
+
+/// Trait for spaces whose values are fed to, or produced by, a model.
+pub trait TensorSpec: Describe {
+    /// Returns the shape of the tensor holding an encoded value.
+    ///
+    /// This is the shape of the space if it tiles the flattened encoding exactly, e.g. `[h, w]`
+    /// for a stack of `h` vectors of `w` intervals, and otherwise a vector of the width of the
+    /// flattened encoding, e.g. whenever finite leaves are one-hot encoded. Scalars have shape
+    /// `[1]`.
+    fn input_shape(&self) -> Vec<i64> {
+        let width = self.describe().flatten().1.width();
+        let shape = self.shape();
+
+        if !shape.is_scalar() && shape.len() == width {
+            shape.extents().iter().map(|&n| n as i64).collect()
+        } else {
+            vec![width as i64]
+        }
+    }
+
+    /// Returns the specification of the output heads; see `OutputSpec::from_description`.
+    fn output_spec(&self) -> OutputSpec { OutputSpec::from_description(&self.describe()) }
+}
+
+impl<S: Describe + ?Sized> TensorSpec for S {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, prelude::*, Interval, PairSpace, ProductSpace, Stacked};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    // Dense layer without bias, checking the width of its input.
+    struct Linear {
+        weights: Vec<Vec<f64>>,
+    }
+
+    impl Linear {
+        fn new(input_shape: &[i64], outputs: usize) -> Linear {
+            let inputs = input_shape.iter().product::<i64>() as usize;
+
+            Linear { weights: vec![vec![0.5; inputs]; outputs], }
+        }
+
+        fn forward(&self, x: &[f64]) -> Vec<f64> {
+            self.weights.iter().map(|row| {
+                assert_eq!(row.len(), x.len(), "Input does not match the layer.");
+
+                row.iter().zip(x).map(|(w, x)| w * x).sum()
+            }).collect()
+        }
+    }
+
+    fn image() -> Stacked<ProductSpace<Interval>> {
+        Stacked::new(ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 4]), 3)
+    }
+
+    fn box_space() -> ProductSpace<Interval> {
+        ProductSpace::new(vec![Interval::bounded(-2.0, 2.0), Interval::bounded(0.0, 5.0)])
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(Ordinal::new(5).input_shape(), vec![5]);
+        assert_eq!(Ordinal::new(5).output_spec(), OutputSpec::Categorical { n: 5 });
+        assert_eq!(Interval::<f64>::unbounded().input_shape(), vec![1]);
+        assert_eq!(
+            Interval::<i64>::right_bounded(3).output_spec(),
+            OutputSpec::ContinuousBox { dim: 1, low: vec![f64::NEG_INFINITY], high: vec![3.0] }
+        );
+    }
+
+    #[test]
+    fn test_composite() {
+        let space = PairSpace::new(image(), PairSpace::new(Ordinal::new(4), box_space()));
+        let expected = OutputSpec::Composite(vec![
+            OutputSpec::ContinuousBox { dim: 12, low: vec![0.0; 12], high: vec![1.0; 12] },
+            OutputSpec::Composite(vec![
+                OutputSpec::Categorical { n: 4 },
+                OutputSpec::ContinuousBox {
+                    dim: 2,
+                    low: vec![-2.0, 0.0],
+                    high: vec![2.0, 5.0],
+                },
+            ]),
+        ]);
+
+        assert_eq!(image().input_shape(), vec![3, 4]);
+        assert_eq!(box_space().input_shape(), vec![2]);
+        assert_eq!(space.input_shape(), vec![18]);
+        assert_eq!(space.output_spec(), expected);
+        assert_eq!(expected.width(), space.describe().flatten().1.width());
+    }
+
+    #[test]
+    fn test_linear_model() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = PairSpace::new(image(), PairSpace::new(Ordinal::new(4), box_space()));
+        let desc = space.describe();
+        let model = Linear::new(&space.input_shape(), space.output_spec().width());
+
+        for _ in 0..10 {
+            let (img, (d, b)) = space.sample(&mut rng);
+            let leaves: Vec<f64> = img.iter().flatten().cloned()
+                .chain(Some(d as f64))
+                .chain(b)
+                .collect();
+            let output = model.forward(&desc.flatten_value(&leaves));
+
+            assert_eq!(output.len(), 18);
+            assert!(output.iter().all(|y| y.is_finite()));
+        }
+    }
+}
@@ -1,10 +1,6 @@
-use crate::prelude::*;
+use crate::{AffineMap, SampleError, prelude::*};
 use num_traits::{Zero, One};
-use std::{
-    cmp,
-    fmt,
-    f64::{INFINITY, NEG_INFINITY},
-};
+use std::{cmp::{self, Ordering}, convert::TryFrom, fmt, ops::{Add, Mul, Neg, Sub}};
 
 fn both<T>(opta: Option<T>, optb: Option<T>) -> Option<(T, T)> {
     match (opta, optb) {
@@ -13,21 +9,102 @@ fn both<T>(opta: Option<T>, optb: Option<T>) -> Option<(T, T)> {
     }
 }
 
+/// Kind of an endpoint of an `Interval`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum BoundKind {
+    /// The endpoint is contained in the interval.
+    #[default]
+    Closed,
+    /// The endpoint is excluded from the interval.
+    Open,
+}
+
+// Picks whichever of two bounds lies further in the direction `ord`, breaking ties between equal
+// values in favour of the kind `tie`.
+fn pick<T: PartialOrd>(
+    a: (T, BoundKind),
+    b: (T, BoundKind),
+    ord: Ordering,
+    tie: BoundKind,
+) -> (T, BoundKind) {
+    match a.0.partial_cmp(&b.0) {
+        Some(Ordering::Equal) => (a.0, if a.1 == tie || b.1 == tie { tie } else { a.1 }),
+        Some(o) if o == ord => a,
+        _ => b,
+    }
+}
+
 /// Generalisation of a interval.
+///
+/// A missing bound denotes an unbounded side of the interval. Bounds are closed unless made open
+/// with `Interval::open` or `Interval::with_kinds`; either way, `inf` and `sup` report the
+/// bounds themselves, which for open bounds are the infimum and supremum rather than the minimum
+/// and maximum of the interval. Intervals that contain no values, e.g. with `lb > ub`, have a
+/// cardinality of zero and report neither bound. The default interval is the unit interval,
+/// `[0, 1]`.
+///
+/// All operations respect openness: `contains` excludes open bounds, projection (`map_onto`)
+/// onto an open bound lands on the nearest contained value (i.e. one representable step inside),
+/// and sampling draws from the tightest closed interval of representable contained values, so
+/// never returns an open bound.
+/// ```
+/// use spaces::{real::Interval, BoundedSpace, Intersection, Surjection};
+///
+/// let d = Interval::open(0.0, 1.0);
+///
+/// assert!(!d.contains(0.0));
+/// assert_eq!(d.inf(), Some(0.0));
+/// assert!(d.map_onto(-1.0) > 0.0);
+/// assert!(Interval::bounded(0.0, 1.0).intersect(&Interval::open(1.0, 2.0)).is_empty());
+/// ```
+///
+/// # Serialisation
+///
+/// With the `serialize` feature, intervals over `f64` and `i64` are serialised as a struct with
+/// optional fields `lb` and `ub`, and the kinds of each bound as `lb_kind` and `ub_kind`. Missing
+/// bounds are never written as IEEE infinities: in human-readable formats (e.g. JSON or YAML) they
+/// are omitted entirely, as are closed bound kinds, and in binary formats (e.g. bincode) they are
+/// written as `None`. Infinite `f64` bounds are treated as missing in both directions, and `NaN`
+/// bounds are rejected on deserialisation.
 #[derive(Eq, Clone, Copy)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Interval<T = f64> {
     pub(crate) lb: Option<T>,
     pub(crate) ub: Option<T>,
+    pub(crate) lb_kind: BoundKind,
+    pub(crate) ub_kind: BoundKind,
 }
 
 impl<T> Interval<T> {
     pub fn new(lb: Option<T>, ub: Option<T>) -> Interval<T> {
         Interval {
             lb, ub,
+            lb_kind: BoundKind::Closed,
+            ub_kind: BoundKind::Closed,
+        }
+    }
+
+    /// Construct the interval `(lb, ub)`, excluding both bounds.
+    pub fn open(lb: T, ub: T) -> Interval<T> {
+        Interval::bounded(lb, ub).with_kinds(BoundKind::Open, BoundKind::Open)
+    }
+
+    /// Return the interval with the given kinds of bound; kinds of missing bounds are ignored.
+    pub fn with_kinds(self, lb_kind: BoundKind, ub_kind: BoundKind) -> Interval<T> {
+        Interval {
+            lb_kind: if self.lb.is_some() { lb_kind } else { BoundKind::Closed },
+            ub_kind: if self.ub.is_some() { ub_kind } else { BoundKind::Closed },
+            ..self
         }
     }
 
+    #[inline]
+    pub fn lb_kind(&self) -> BoundKind { self.lb_kind }
+
+    #[inline]
+    pub fn ub_kind(&self) -> BoundKind { self.ub_kind }
+
     pub fn unbounded() -> Interval<T> {
         Interval::new(None, None)
     }
@@ -47,6 +124,30 @@ impl<T> Interval<T> {
     pub fn unit() -> Interval<T> where T: Zero + One {
         Interval::bounded(T::zero(), T::one())
     }
+
+    /// Returns the interval `[-1, 1]`.
+    pub fn symmetric_unit() -> Interval<T> where T: One + Neg<Output = T> {
+        Interval::bounded(-T::one(), T::one())
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+    fn admits(&self, val: &T) -> bool {
+        let above = |lb: &T| match self.lb_kind {
+            BoundKind::Closed => val >= lb,
+            BoundKind::Open => val > lb,
+        };
+        let below = |ub: &T| match self.ub_kind {
+            BoundKind::Closed => val <= ub,
+            BoundKind::Open => val < ub,
+        };
+
+        self.lb.as_ref().map_or(true, above) && self.ub.as_ref().map_or(true, below)
+    }
+}
+
+impl<T: Zero + One> Default for Interval<T> {
+    fn default() -> Interval<T> { Interval::unit() }
 }
 
 impl Space for Interval<f64> {
@@ -54,118 +155,514 @@ impl Space for Interval<f64> {
 
     fn dim(&self) -> Dim { Dim::one() }
 
-    fn card(&self) -> Card { Card::Infinite }
+    fn card(&self) -> Card { if self.is_empty() { Card::Finite(0) } else { Card::Infinite } }
 }
 
-impl BoundedSpace for Interval<f64> {
-    fn inf(&self) -> Option<f64> { self.lb }
+// Draws a value uniformly from `[lo, hi]`, for finite `lo <= hi`.
+//
+// The uniform sampler of `rand` rejects ranges whose width, scaled up slightly, overflows; such
+// ranges are sampled at a quarter of their scale and scaled back, which is exact for normal
+// values and so keeps the draw in range, at the cost of a coarser grid of attainable values.
+pub(crate) fn uniform_f64<R: Rng + ?Sized>(rng: &mut R, lo: f64, hi: f64) -> f64 {
+    if hi - lo <= f64::MAX / 2.0 {
+        rng.gen_range(lo..=hi)
+    } else {
+        (4.0 * rng.gen_range(lo / 4.0..=hi / 4.0)).clamp(lo, hi)
+    }
+}
 
-    fn sup(&self) -> Option<f64> { self.ub }
+/// Values are drawn uniformly between the smallest and largest representable values of the
+/// interval. Any finite bounds are supported, including those whose difference overflows, and
+/// degenerate intervals of one or two representable values.
+impl Sample for Interval<f64> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from interval: {}.", e))
+    }
 
-    fn contains(&self, val: f64) -> bool {
-        self.lb.map_or(true, |inf| val >= inf) && self.ub.map_or(true, |sup| val <= sup)
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, SampleError> {
+        match (self.lb, self.ub) {
+            (Some(lb), Some(ub)) if !lb.is_finite() || !ub.is_finite() =>
+                Err(SampleError::NoDistribution),
+            (Some(_), Some(_)) => match self.tight_bounds() {
+                (Some(lo), Some(hi)) if lo <= hi => Ok(uniform_f64(rng, lo, hi)),
+                _ => Err(SampleError::Empty),
+            },
+            _ => Err(SampleError::Unbounded),
+        }
     }
 }
 
+// Returns the least f64 greater than `x`; `f64::next_up` is not available on older compilers.
+pub(crate) fn next_up(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        x
+    } else if x == 0.0 {
+        f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+// Returns the greatest f64 less than `x`.
+pub(crate) fn next_down(x: f64) -> f64 { -next_up(-x) }
+
+impl Interval<f64> {
+    // Returns the smallest and largest representable values in the interval, given its bounds.
+    pub(crate) fn tight_bounds(&self) -> (Option<f64>, Option<f64>) {
+        let open = |k: BoundKind| k == BoundKind::Open;
+        let lo = self.lb.map(|lb| if open(self.lb_kind) { next_up(lb) } else { lb });
+        let hi = self.ub.map(|ub| if open(self.ub_kind) { next_down(ub) } else { ub });
+
+        (lo, hi)
+    }
+
+    /// Returns true iff the interval contains no representable values.
+    pub fn is_empty(&self) -> bool {
+        match self.tight_bounds() {
+            (Some(lo), Some(hi)) => lo > hi,
+            _ => false,
+        }
+    }
+
+    /// Draw a value from a normal distribution with the given `mean` and `std`, truncated to the
+    /// interval.
+    ///
+    /// Unlike naive rejection sampling, this remains efficient when the interval lies far into
+    /// the tail of the distribution. Missing bounds are permitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is empty, or `std` is not finite and strictly positive.
+    pub fn sample_normal<R: Rng + ?Sized>(&self, rng: &mut R, mean: f64, std: f64) -> f64 {
+        let (lo, hi) = self.tight_bounds();
+        let lb = lo.unwrap_or(f64::NEG_INFINITY);
+        let ub = hi.unwrap_or(f64::INFINITY);
+
+        crate::normal::truncated_normal(rng, lb, ub, mean, std)
+            .unwrap_or_else(|e| panic!("Cannot sample from truncated normal: {}.", e))
+    }
+}
+
+/// Empty intervals have neither an infimum nor a supremum.
+impl BoundedSpace for Interval<f64> {
+    fn inf(&self) -> Option<f64> { if self.is_empty() { None } else { self.lb } }
+
+    fn sup(&self) -> Option<f64> { if self.is_empty() { None } else { self.ub } }
+
+    fn contains(&self, val: f64) -> bool { self.admits(&val) }
+}
+
 impl Surjection<f64, f64> for Interval<f64> {
     fn map_onto(&self, val: f64) -> f64 {
-        let val = self.lb.map_or(val, |inf| val.max(inf));
-        let val = self.ub.map_or(val, |sup| val.min(sup));
+        let (lo, hi) = self.tight_bounds();
+        let val = lo.map_or(val, |inf| val.max(inf));
+
+        hi.map_or(val, |sup| val.min(sup))
+    }
+}
 
-        val
+impl Interval<f64> {
+    /// Return the image of `self` under `map`, along with the map itself.
+    ///
+    /// The returned map transforms values of `self` onto values of the new interval.
+    pub fn affine(self, map: AffineMap) -> (Interval<f64>, AffineMap) {
+        (map.apply_interval(self), map)
     }
 }
 
+impl Add<f64> for Interval<f64> {
+    type Output = Interval<f64>;
+
+    fn add(self, rhs: f64) -> Interval<f64> { AffineMap::shifting(rhs).apply_interval(self) }
+}
+
+impl Sub<f64> for Interval<f64> {
+    type Output = Interval<f64>;
+
+    fn sub(self, rhs: f64) -> Interval<f64> { AffineMap::shifting(-rhs).apply_interval(self) }
+}
+
+impl Mul<f64> for Interval<f64> {
+    type Output = Interval<f64>;
+
+    fn mul(self, rhs: f64) -> Interval<f64> { AffineMap::scaling(rhs).apply_interval(self) }
+}
+
+impl Neg for Interval<f64> {
+    type Output = Interval<f64>;
+
+    fn neg(self) -> Interval<f64> { AffineMap::scaling(-1.0).apply_interval(self) }
+}
+
 impl Space for Interval<i64> {
     type Value = i64;
 
     fn dim(&self) -> Dim { Dim::one() }
 
+    /// The cardinality is promoted to `Card::Infinite` if it overflows `usize`, as it does for
+    /// the whole range of `i64`.
     fn card(&self) -> Card {
-        match (self.lb, self.ub) {
-            (Some(lb), Some(ub)) => Card::Finite((ub - lb + 1) as usize),
+        match self.tight_bounds() {
+            (Some(lo), Some(hi)) =>
+                usize::try_from((hi - lo + 1).max(0)).map_or(Card::Infinite, Card::Finite),
             _ => Card::Infinite,
         }
     }
 }
 
-impl BoundedSpace for Interval<i64> {
-    fn inf(&self) -> Option<i64> { self.lb }
+impl Interval<i64> {
+    // Returns the smallest and largest values in the interval, widened to avoid overflow at the
+    // extremes of i64.
+    pub(crate) fn tight_bounds(&self) -> (Option<i128>, Option<i128>) {
+        let lo = self.lb.map(|lb| lb as i128 + (self.lb_kind == BoundKind::Open) as i128);
+        let hi = self.ub.map(|ub| ub as i128 - (self.ub_kind == BoundKind::Open) as i128);
 
-    fn sup(&self) -> Option<i64> { self.ub }
+        (lo, hi)
+    }
 
-    fn contains(&self, val: i64) -> bool {
-        self.lb.map_or(true, |inf| val >= inf) && self.ub.map_or(true, |sup| val <= sup)
+    /// Returns true iff the interval contains no values.
+    pub fn is_empty(&self) -> bool {
+        match self.tight_bounds() {
+            (Some(lo), Some(hi)) => lo > hi,
+            _ => false,
+        }
+    }
+}
+
+impl Sample for Interval<i64> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from interval: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<i64, SampleError> {
+        match self.tight_bounds() {
+            (Some(lo), Some(hi)) if lo > hi => Err(SampleError::Empty),
+            (Some(lo), Some(hi)) => Ok(rng.gen_range(lo as i64..=hi as i64)),
+            _ => Err(SampleError::Unbounded),
+        }
     }
 }
 
+/// Empty intervals have neither an infimum nor a supremum.
+impl BoundedSpace for Interval<i64> {
+    fn inf(&self) -> Option<i64> { if self.is_empty() { None } else { self.lb } }
+
+    fn sup(&self) -> Option<i64> { if self.is_empty() { None } else { self.ub } }
+
+    fn contains(&self, val: i64) -> bool { self.admits(&val) }
+}
+
 impl Surjection<i64, i64> for Interval<i64> {
     fn map_onto(&self, val: i64) -> i64 {
-        let val = self.lb.map_or(val, |inf| val.max(inf));
-        let val = self.ub.map_or(val, |sup| val.min(sup));
+        let (lo, hi) = self.tight_bounds();
+        let val = lo.map_or(val as i128, |inf| (val as i128).max(inf));
 
-        val
+        hi.map_or(val, |sup| val.min(sup)) as i64
+    }
+}
+
+impl<T: Clone + cmp::PartialOrd> Interval<T> {
+    // Combines the bounds of two intervals, moving the lower bound in the direction `lb_ord` and
+    // the upper bound in the opposite direction, with ties between bounds broken towards `tie`.
+    fn combine(self, other: &Self, lb_ord: Ordering, tie: BoundKind) -> Self {
+        let (lb_kind, ub_kind) = (self.lb_kind, self.ub_kind);
+        let lb = both(self.lb, other.lb.clone())
+            .map(|(a, b)| pick((a, lb_kind), (b, other.lb_kind), lb_ord, tie));
+        let ub = both(self.ub, other.ub.clone())
+            .map(|(a, b)| pick((a, ub_kind), (b, other.ub_kind), lb_ord.reverse(), tie));
+
+        Interval {
+            lb_kind: lb.as_ref().map_or(BoundKind::Closed, |b| b.1),
+            ub_kind: ub.as_ref().map_or(BoundKind::Closed, |b| b.1),
+            lb: lb.map(|b| b.0),
+            ub: ub.map(|b| b.0),
+        }
     }
 }
 
 impl<T: Clone + cmp::PartialOrd> Union for Interval<T> {
     fn union(self, other: &Self) -> Self {
-        Interval::new(
-            both(self.lb, other.lb.clone()).map(|(a, b)| {
-                if a < b { a } else { b }
-            }),
-            both(self.ub, other.ub.clone()).map(|(a, b)| {
-                if a > b { a } else { b }
-            }),
-        )
+        self.combine(other, Ordering::Less, BoundKind::Closed)
     }
 }
 
 impl<T: Clone + cmp::PartialOrd> Intersection for Interval<T> {
     fn intersect(self, other: &Self) -> Self {
-        Interval::new(
-            both(self.lb, other.lb.clone()).map(|(a, b)| {
-                if a > b { a } else { b }
-            }),
-            both(self.ub, other.ub.clone()).map(|(a, b)| {
-                if a < b { a } else { b }
-            }),
-        )
+        self.combine(other, Ordering::Greater, BoundKind::Open)
     }
 }
 
 impl<T: cmp::PartialEq> cmp::PartialEq for Interval<T> {
-    fn eq(&self, other: &Interval<T>) -> bool { self.lb.eq(&other.lb) && self.ub.eq(&other.ub) }
+    fn eq(&self, other: &Interval<T>) -> bool {
+        self.lb.eq(&other.lb) && self.ub.eq(&other.ub)
+            && self.lb_kind == other.lb_kind && self.ub_kind == other.ub_kind
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for Interval<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Interval")
-            .field("lb", &self.lb)
-            .field("ub", &self.ub)
-            .finish()
+        let mut s = f.debug_struct("Interval");
+
+        s.field("lb", &self.lb).field("ub", &self.ub);
+
+        if self.lb_kind == BoundKind::Open { s.field("lb_kind", &self.lb_kind); }
+        if self.ub_kind == BoundKind::Open { s.field("ub_kind", &self.ub_kind); }
+
+        s.finish()
     }
 }
 
 impl<T: fmt::Display> fmt::Display for Interval<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match (&self.lb, &self.ub) {
-            (Some(lb), Some(ub)) => write!(f, "[{}, {}]", lb, ub),
-            (Some(lb), None) => write!(f, "[{}, {}]", lb, INFINITY),
-            (None, Some(ub)) => write!(f, "[{}, {}]", NEG_INFINITY, ub),
-            (None, None) => write!(f, "[{}, {}]", NEG_INFINITY, INFINITY),
+        let open = if self.lb_kind == BoundKind::Open { "(" } else { "[" };
+        let close = if self.ub_kind == BoundKind::Open { ")" } else { "]" };
+
+        write!(f, "{}", open)?;
+
+        match &self.lb {
+            Some(lb) => write!(f, "{}, ", lb)?,
+            None => write!(f, "{}, ", f64::NEG_INFINITY)?,
+        }
+
+        match &self.ub {
+            Some(ub) => write!(f, "{}", ub)?,
+            None => write!(f, "{}", f64::INFINITY)?,
+        }
+
+        write!(f, "{}", close)
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod serde_impl {
+    use super::{BoundKind, Interval};
+    use serde::{
+        de::Error,
+        ser::SerializeStruct,
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+
+    // Bounds are also accepted under the names used by older forks of this crate; see
+    // `compat::upgrade_json`.
+    #[derive(Deserialize)]
+    #[serde(rename = "Interval")]
+    struct RawInterval<T> {
+        #[serde(default, alias = "lo", alias = "left")]
+        lb: Option<T>,
+        #[serde(default, alias = "hi", alias = "right")]
+        ub: Option<T>,
+        #[serde(default)]
+        lb_kind: BoundKind,
+        #[serde(default)]
+        ub_kind: BoundKind,
+    }
+
+    fn serialize_bounds<S, T>(d: Interval<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        if serializer.is_human_readable() {
+            let open = |k: BoundKind| k == BoundKind::Open;
+            let len = d.lb.is_some() as usize + d.ub.is_some() as usize
+                + open(d.lb_kind) as usize + open(d.ub_kind) as usize;
+            let mut state = serializer.serialize_struct("Interval", len)?;
+
+            match d.lb {
+                Some(lb) => state.serialize_field("lb", &Some(lb))?,
+                None => state.skip_field("lb")?,
+            }
+
+            match d.ub {
+                Some(ub) => state.serialize_field("ub", &Some(ub))?,
+                None => state.skip_field("ub")?,
+            }
+
+            if open(d.lb_kind) {
+                state.serialize_field("lb_kind", &d.lb_kind)?;
+            } else {
+                state.skip_field("lb_kind")?;
+            }
+
+            if open(d.ub_kind) {
+                state.serialize_field("ub_kind", &d.ub_kind)?;
+            } else {
+                state.skip_field("ub_kind")?;
+            }
+
+            state.end()
+        } else {
+            let mut state = serializer.serialize_struct("Interval", 4)?;
+
+            state.serialize_field("lb", &d.lb)?;
+            state.serialize_field("ub", &d.ub)?;
+            state.serialize_field("lb_kind", &d.lb_kind)?;
+            state.serialize_field("ub_kind", &d.ub_kind)?;
+            state.end()
+        }
+    }
+
+    impl Serialize for Interval<f64> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let finite = |b: &f64| b.is_finite();
+            let d = Interval::new(self.lb.filter(finite), self.ub.filter(finite))
+                .with_kinds(self.lb_kind, self.ub_kind);
+
+            serialize_bounds(d, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Interval<f64> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let raw = RawInterval::<f64>::deserialize(deserializer)?;
+
+            if raw.lb.is_some_and(f64::is_nan) || raw.ub.is_some_and(f64::is_nan) {
+                return Err(D::Error::custom("interval bounds must not be NaN"));
+            }
+
+            Ok(Interval::new(
+                raw.lb.filter(|b| b.is_finite()),
+                raw.ub.filter(|b| b.is_finite()),
+            ).with_kinds(raw.lb_kind, raw.ub_kind))
+        }
+    }
+
+    impl Serialize for Interval<i64> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_bounds(*self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Interval<i64> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let raw = RawInterval::<i64>::deserialize(deserializer)?;
+
+            Ok(Interval::new(raw.lb, raw.ub).with_kinds(raw.lb_kind, raw.ub_kind))
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::ContainsError;
     use super::*;
 
     #[cfg(feature = "serialize")]
     extern crate serde_test;
     #[cfg(feature = "serialize")]
-    use self::serde_test::{assert_tokens, Token};
+    use self::serde_test::{assert_tokens, Configure, Token};
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Interval::<f64>::default(), Interval::bounded(0.0, 1.0));
+        assert_eq!(Interval::<i64>::default(), Interval::bounded(0, 1));
+    }
+
+    #[test]
+    fn test_open_contains() {
+        let d = Interval::bounded(0.0, 1.0).with_kinds(BoundKind::Open, BoundKind::Closed);
+
+        assert!(!d.contains(0.0));
+        assert!(d.contains(5e-324));
+        assert!(d.contains(1.0));
+        assert_eq!(d.inf(), Some(0.0));
+        assert_eq!(d.sup(), Some(1.0));
+
+        let d = Interval::open(-2i64, 2);
+
+        assert!(!d.contains(-2) && !d.contains(2));
+        assert!(d.contains(-1) && d.contains(1));
+        assert_eq!(d.card(), Card::Finite(3));
+        assert_eq!(Interval::open(0i64, 1).card(), Card::Finite(0));
+        assert_eq!(Interval::open(i64::MAX - 1, i64::MAX).card(), Card::Finite(0));
+    }
+
+    #[test]
+    fn test_open_sample() {
+        let mut rng = ::rand::thread_rng();
+        let d = Interval::open(0.0, 5e-323);
+
+        for _ in 0..100 {
+            let v = d.sample(&mut rng);
+
+            assert!(v > 0.0 && v < 5e-323);
+        }
+
+        let d = Interval::open(0i64, 2);
+
+        for _ in 0..100 {
+            assert_eq!(d.sample(&mut rng), 1);
+        }
+
+        let k = BoundKind::Open;
+        let x = 1.0f64;
+
+        assert_eq!(Interval::open(x, next_up(x)).try_sample(&mut rng), Err(SampleError::Empty));
+        assert_eq!(
+            Interval::bounded(1.0, 1.0).with_kinds(k, BoundKind::Closed).try_sample(&mut rng),
+            Err(SampleError::Empty)
+        );
+        assert_eq!(Interval::open(1i64, 2).try_sample(&mut rng), Err(SampleError::Empty));
+    }
+
+    #[test]
+    fn test_open_projection() {
+        let d = Interval::open(0.0, 1.0);
+
+        assert_eq!(d.map_onto(-1.0), next_up(0.0));
+        assert_eq!(d.map_onto(2.0), next_down(1.0));
+        assert_eq!(d.map_onto(0.5), 0.5);
+        assert!(d.contains(d.map_onto(0.0)));
+
+        let d = Interval::open(0i64, 10);
+
+        assert_eq!(d.map_onto(-5), 1);
+        assert_eq!(d.map_onto(10), 9);
+    }
+
+    #[test]
+    fn test_open_set_operations() {
+        let closed = Interval::bounded(0.0, 1.0);
+        let open = Interval::open(1.0, 2.0);
+        let meet = closed.intersect(&open);
+
+        assert!(meet.is_empty());
+        assert!(!meet.contains(1.0));
+        assert!(!closed.is_empty());
+
+        let join = closed.union(&open);
+
+        assert_eq!(
+            join,
+            Interval::bounded(0.0, 2.0).with_kinds(BoundKind::Closed, BoundKind::Open)
+        );
+        assert!(join.contains(1.0));
+
+        // Ties keep the closed bound in unions, and the open bound in intersections.
+        let half = Interval::bounded(0.0, 1.0).with_kinds(BoundKind::Open, BoundKind::Closed);
+
+        assert_eq!(closed.union(&half), closed);
+        assert_eq!(closed.intersect(&half), half);
+        assert!(Interval::open(0i64, 1).intersect(&Interval::bounded(0, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_open_display() {
+        assert_eq!(Interval::open(0.0, 1.0).to_string(), "(0, 1)");
+        assert_eq!(
+            Interval::left_bounded(0.0).with_kinds(BoundKind::Open, BoundKind::Open).to_string(),
+            "(0, inf]"
+        );
+        assert_eq!(
+            -Interval::open(0.0, 1.0).with_kinds(BoundKind::Closed, BoundKind::Open),
+            Interval::bounded(-1.0, 0.0).with_kinds(BoundKind::Open, BoundKind::Closed)
+        );
+    }
 
     #[test]
     fn test_card() {
@@ -180,6 +677,23 @@ mod tests {
         assert_eq!(Interval::<i64>::unbounded().card(), Card::Infinite);
         assert_eq!(Interval::left_bounded(0i64).card(), Card::Infinite);
         assert_eq!(Interval::right_bounded(0i64).card(), Card::Infinite);
+
+        assert_eq!(Interval::bounded(i64::MIN, i64::MAX).card(), Card::Infinite);
+        assert_eq!(Interval::bounded(i64::MIN, -1).card(), Card::Finite(1 << 63));
+    }
+
+    #[test]
+    fn test_contains_all() {
+        let d = Interval::bounded(0.0, 1.0);
+
+        assert_eq!(d.contains_all(&[]), Ok(()));
+        assert_eq!(d.contains_all(&[0.0, 0.5, 1.0]), Ok(()));
+        assert_eq!(d.contains_all(&[-0.5, 0.5, 2.0]), Err(ContainsError::new(0, &-0.5)));
+        assert_eq!(d.contains_all(&[0.0, 0.5, 2.0]), Err(ContainsError::new(2, &2.0)));
+        assert_eq!(
+            d.contains_all(&[0.0, 2.0]).unwrap_err().to_string(),
+            "value 2.0 at index 1 is not contained in the space"
+        );
     }
 
     #[test]
@@ -218,6 +732,92 @@ mod tests {
         check(-5, 0);
     }
 
+    #[test]
+    fn test_sample_degenerate() {
+        let mut rng = ::rand::thread_rng();
+
+        assert_eq!(Interval::bounded(1.0, 1.0).sample(&mut rng), 1.0);
+        assert_eq!(Interval::bounded(1i64, 1i64).sample(&mut rng), 1);
+    }
+
+    #[test]
+    fn test_sample_extreme() {
+        let mut rng = ::rand::thread_rng();
+        let tiny = f64::from_bits(1);
+
+        for &(lb, ub) in [
+            (1e308, 1.7e308),
+            (-1e308, 1e308),
+            (-f64::MAX, f64::MAX),
+            (0.0, f64::MAX),
+            (-f64::MAX, -1e308),
+            (tiny, 2.0 * tiny),
+            (-tiny, tiny),
+            (0.0, f64::MIN_POSITIVE),
+        ].iter() {
+            let d = Interval::bounded(lb, ub);
+            let vals: Vec<f64> = (0..1000).map(|_| d.sample(&mut rng)).collect();
+
+            assert!(vals.iter().all(|&x| d.contains(x)), "sample outside of {}", d);
+            assert!(vals.iter().any(|&x| x != vals[0]), "degenerate samples from {}", d);
+        }
+
+        let d = Interval::bounded(-f64::MAX, f64::MAX);
+        let n_positive = (0..1000).filter(|_| d.sample(&mut rng) > 0.0).count();
+
+        assert!(n_positive > 400 && n_positive < 600);
+
+        // Intervals narrower than machine epsilon hold just their two endpoints.
+        for &lb in [1.0, -1.0, 1e300, tiny].iter() {
+            let ub = next_up(lb);
+            let d = Interval::bounded(lb, ub);
+
+            assert!((0..100).all(|_| {
+                let x = d.sample(&mut rng);
+
+                x == lb || x == ub
+            }));
+        }
+
+        let d = Interval::open(1.0, next_up(next_up(1.0)));
+
+        assert_eq!(d.sample(&mut rng), next_up(1.0));
+
+        let d = Interval::open(1.0, next_up(1.0));
+
+        assert_eq!(d.try_sample(&mut rng), Err(SampleError::Empty));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_unbounded() {
+        Interval::<f64>::left_bounded(0.0).sample(&mut ::rand::thread_rng());
+    }
+
+    #[test]
+    fn test_try_sample() {
+        let mut rng = ::rand::thread_rng();
+
+        assert!(Interval::bounded(-5.0, 5.0).try_sample(&mut rng).is_ok());
+        assert_eq!(Interval::bounded(1i64, 1i64).try_sample(&mut rng), Ok(1));
+
+        assert_eq!(Interval::left_bounded(0.0).try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(Interval::right_bounded(0.0).try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(Interval::<i64>::unbounded().try_sample(&mut rng), Err(SampleError::Unbounded));
+
+        assert_eq!(Interval::bounded(1.0, 0.0).try_sample(&mut rng), Err(SampleError::Empty));
+        assert_eq!(Interval::bounded(1i64, 0i64).try_sample(&mut rng), Err(SampleError::Empty));
+
+        assert_eq!(
+            Interval::bounded(0.0, f64::INFINITY).try_sample(&mut rng),
+            Err(SampleError::NoDistribution)
+        );
+        assert_eq!(
+            Interval::bounded(f64::NAN, 1.0).try_sample(&mut rng),
+            Err(SampleError::NoDistribution)
+        );
+    }
+
     #[test]
     fn test_surjection_f64() {
         let d = Interval::<f64>::bounded(0.0, 5.0);
@@ -240,6 +840,39 @@ mod tests {
         assert_eq!(d.map_onto(10), 5);
     }
 
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(Interval::unit() * 2.0 - 1.0, Interval::bounded(-1.0, 1.0));
+        assert_eq!(Interval::unit() * 2.0 - 1.0, Interval::symmetric_unit());
+        assert_eq!(Interval::<i64>::symmetric_unit(), Interval::bounded(-1, 1));
+        assert_eq!(Interval::bounded(-1.0, 1.0) + 1.0, Interval::bounded(0.0, 2.0));
+        assert_eq!(-Interval::bounded(1.0, 2.0), Interval::bounded(-2.0, -1.0));
+        assert_eq!(-Interval::left_bounded(1.0), Interval::right_bounded(-1.0));
+    }
+
+    #[test]
+    fn test_negative_scaling() {
+        assert_eq!(Interval::bounded(1.0, 2.0) * -2.0, Interval::bounded(-4.0, -2.0));
+        assert_eq!(Interval::right_bounded(1.0) * -3.0, Interval::left_bounded(-3.0));
+        assert_eq!(Interval::bounded(1.0, 2.0) * 0.0, Interval::bounded(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_affine_contains() {
+        let d = Interval::bounded(-2.0, 3.0);
+
+        for &(scale, shift) in &[(2.0, -1.0), (-0.5, 4.0), (-3.0, -3.0), (1.0, 0.0)] {
+            let (t, map) = d.affine(AffineMap::new(scale, shift));
+
+            for i in 0..=10 {
+                let x = -2.0 + 0.5 * i as f64;
+
+                assert!(d.contains(x));
+                assert!(t.contains(map.map_onto(x)));
+            }
+        }
+    }
+
     #[cfg(feature = "serialize")]
     #[test]
     fn test_serialisation() {
@@ -247,7 +880,7 @@ mod tests {
             let d = Interval::bounded(lb, ub);
 
             assert_tokens(
-                &d,
+                &d.readable(),
                 &[
                     Token::Struct {
                         name: "Interval",
@@ -267,5 +900,117 @@ mod tests {
         check(0.0, 5.0);
         check(-5.0, 5.0);
         check(-5.0, 0.0);
+
+        assert_tokens(&Interval::left_bounded(1.0).readable(), &[
+            Token::Struct { name: "Interval", len: 1, },
+            Token::Str("lb"),
+            Token::Some,
+            Token::F64(1.0),
+            Token::StructEnd,
+        ]);
+        assert_tokens(&Interval::<i64>::unbounded().readable(), &[
+            Token::Struct { name: "Interval", len: 0, },
+            Token::StructEnd,
+        ]);
+        assert_tokens(&Interval::left_bounded(1.0).compact(), &[
+            Token::Struct { name: "Interval", len: 4, },
+            Token::Str("lb"),
+            Token::Some,
+            Token::F64(1.0),
+            Token::Str("ub"),
+            Token::None,
+            Token::Str("lb_kind"),
+            Token::UnitVariant { name: "BoundKind", variant: "closed", },
+            Token::Str("ub_kind"),
+            Token::UnitVariant { name: "BoundKind", variant: "closed", },
+            Token::StructEnd,
+        ]);
+        let open = Interval::left_bounded(1.0).with_kinds(BoundKind::Open, BoundKind::Open);
+
+        assert_tokens(&open.readable(), &[
+            Token::Struct { name: "Interval", len: 2, },
+            Token::Str("lb"),
+            Token::Some,
+            Token::F64(1.0),
+            Token::Str("lb_kind"),
+            Token::UnitVariant { name: "BoundKind", variant: "open", },
+            Token::StructEnd,
+        ]);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serialisation_round_trip() {
+        extern crate bincode;
+        extern crate serde_json;
+        extern crate serde_yaml;
+
+        fn check<T>(d: Interval<T>)
+        where
+            Interval<T>: ::serde::Serialize + ::serde::de::DeserializeOwned + PartialEq + fmt::Debug,
+        {
+            let json = serde_json::to_string(&d).unwrap();
+            let yaml = serde_yaml::to_string(&d).unwrap();
+            let bin = bincode::serialize(&d).unwrap();
+
+            assert!(!json.contains("null") && !json.contains("inf"));
+            assert_eq!(serde_json::from_str::<Interval<T>>(&json).unwrap(), d);
+            assert_eq!(serde_yaml::from_str::<Interval<T>>(&yaml).unwrap(), d);
+            assert_eq!(bincode::deserialize::<Interval<T>>(&bin).unwrap(), d);
+        }
+
+        check(Interval::bounded(0.1, 1.0 / 3.0));
+        check(Interval::bounded(-1e300, 5e-324));
+        check(Interval::left_bounded(-0.5));
+        check(Interval::right_bounded(2.5));
+        check(Interval::<f64>::unbounded());
+        check(Interval::bounded(i64::MIN, i64::MAX));
+        check(Interval::left_bounded(3i64));
+        check(Interval::<i64>::unbounded());
+        check(Interval::open(0.0, 1.0));
+        check(Interval::right_bounded(2.5).with_kinds(BoundKind::Closed, BoundKind::Open));
+        check(Interval::open(-3i64, 3));
+
+        let space = crate::ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(0.0)]);
+        let json = serde_json::to_string(&space).unwrap();
+
+        assert_eq!(json, r#"[{"lb":0.0,"ub":1.0},{"lb":0.0}]"#);
+        assert_eq!(serde_json::from_str::<crate::ProductSpace<Interval>>(&json).unwrap(), space);
+        assert_eq!(
+            bincode::deserialize::<crate::ProductSpace<Interval>>(
+                &bincode::serialize(&space).unwrap()
+            ).unwrap(),
+            space
+        );
+
+        assert_eq!(serde_json::to_string(&Interval::left_bounded(0.0)).unwrap(), r#"{"lb":0.0}"#);
+        assert_eq!(
+            serde_json::to_string(&Interval::bounded(f64::NEG_INFINITY, 1.0)).unwrap(),
+            r#"{"ub":1.0}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Interval>(r#"{"ub": 1.0}"#).unwrap(),
+            Interval::right_bounded(1.0)
+        );
+        assert_eq!(
+            bincode::deserialize::<Interval>(
+                &bincode::serialize(&Interval::bounded(0.0, f64::INFINITY)).unwrap()
+            ).unwrap(),
+            Interval::left_bounded(0.0)
+        );
+        let nan = (Some(f64::NAN), None::<f64>, BoundKind::Closed, BoundKind::Closed);
+
+        assert!(bincode::deserialize::<Interval>(&bincode::serialize(&nan).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Interval::bounded(-5.0, 5.0); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::bounded(1.0, 1.0); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::open(0.0, 1.0); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::left_bounded(0.0); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::<f64>::unbounded(); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::bounded(-5i64, 5); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Interval::open(-5i64, 5); sample, bounds, shape, fmt, serde);
     }
 }
@@ -0,0 +1,231 @@
+use crate::{prelude::*, ProductSpace, SampleError};
+use rand::{distributions::Distribution, RngCore};
+use std::{error::Error, fmt};
+
+type ComponentSampler<V> = Box<dyn Fn(&mut dyn RngCore) -> V>;
+
+/// Error returned when a component index is out of range for a product space, e.g. in a
+/// component sampler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComponentError {
+    /// Index of the offending component.
+    pub index: usize,
+    /// Number of components in the product space.
+    pub n_components: usize,
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "component {} is out of range for a product of {} spaces",
+            self.index, self.n_components
+        )
+    }
+}
+
+impl Error for ComponentError {}
+
+/// Builder for a `JointSampler`.
+pub struct JointSamplerBuilder<D: Space> {
+    space: ProductSpace<D>,
+    samplers: Vec<(usize, ComponentSampler<D::Value>)>,
+}
+
+impl<D: Space> JointSamplerBuilder<D> {
+    /// Sample component `index` using the closure `sampler`.
+    ///
+    /// Later calls for the same component replace earlier ones.
+    pub fn with_component<F>(mut self, index: usize, sampler: F) -> Self
+    where F: Fn(&mut dyn RngCore) -> D::Value + 'static {
+        self.samplers.push((index, Box::new(sampler)));
+        self
+    }
+
+    /// Sample component `index` from the distribution `dist`.
+    pub fn with_distribution<T>(self, index: usize, dist: T) -> Self
+    where T: Distribution<D::Value> + 'static {
+        self.with_component(index, move |rng| dist.sample(rng))
+    }
+
+    /// Validate the component indices and construct the sampler.
+    pub fn build(self) -> Result<JointSampler<D>, ComponentError> {
+        let n_components = self.space.iter().len();
+        let mut samplers: Vec<Option<ComponentSampler<D::Value>>> =
+            (0..n_components).map(|_| None).collect();
+
+        for (index, sampler) in self.samplers {
+            match samplers.get_mut(index) {
+                Some(slot) => *slot = Some(sampler),
+                None => return Err(ComponentError { index, n_components, }),
+            }
+        }
+
+        Ok(JointSampler { space: self.space, samplers, })
+    }
+}
+
+/// Sampler over a product space with an independent distribution per component.
+///
+/// Components without a custom sampler fall back to the uniform `sample` of the component
+/// space. All other behaviour is forwarded to the product space.
+/// ```
+/// use spaces::{discrete::Ordinal, JointSampler, ProductSpace, Sample};
+///
+/// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(5)]);
+/// let sampler = JointSampler::builder(space)
+///     .with_distribution(0, rand::distributions::WeightedIndex::new([0, 1, 0]).unwrap())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(sampler.sample(&mut rand::thread_rng())[0], 1);
+/// ```
+pub struct JointSampler<D: Space> {
+    space: ProductSpace<D>,
+    samplers: Vec<Option<ComponentSampler<D::Value>>>,
+}
+
+impl<D: Space> JointSampler<D> {
+    pub fn builder(space: ProductSpace<D>) -> JointSamplerBuilder<D> {
+        JointSamplerBuilder { space, samplers: vec![], }
+    }
+
+    #[inline]
+    pub fn space(&self) -> &ProductSpace<D> { &self.space }
+}
+
+impl<D: Sample> JointSampler<D> {
+    /// Draw a value into `out`, reusing its allocation.
+    pub fn sample_into<R: Rng>(&self, rng: &mut R, out: &mut Vec<D::Value>) {
+        out.clear();
+        out.extend(self.space.iter().zip(self.samplers.iter()).map(|(d, s)| match s {
+            Some(s) => s(rng),
+            None => d.sample(rng),
+        }));
+    }
+}
+
+impl<D: Space> Space for JointSampler<D> {
+    type Value = Vec<D::Value>;
+
+    fn dim(&self) -> Dim { self.space.dim() }
+
+    fn shape(&self) -> Shape { self.space.shape() }
+
+    fn card(&self) -> Card { self.space.card() }
+}
+
+impl<D: Sample> Sample for JointSampler<D> {
+    fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> Vec<D::Value> {
+        self.space.iter().zip(self.samplers.iter()).map(|(d, s)| match s {
+            Some(s) => s(&mut rng),
+            None => d.sample(rng),
+        }).collect()
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> Result<Vec<D::Value>, SampleError> {
+        self.space.iter().zip(self.samplers.iter()).map(|(d, s)| match s {
+            Some(s) => Ok(s(&mut rng)),
+            None => d.try_sample(rng),
+        }).collect()
+    }
+}
+
+impl<D: Space + fmt::Debug> fmt::Debug for JointSampler<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let custom: Vec<usize> = self.samplers.iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.as_ref().map(|_| i))
+            .collect();
+
+        f.debug_struct("JointSampler")
+            .field("space", &self.space)
+            .field("custom_components", &custom)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval, TruncatedNormal};
+    use rand::{distributions::WeightedIndex, rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_mixed_marginals() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4), Ordinal::new(2)]);
+        let sampler = JointSampler::builder(space)
+            .with_distribution(0, WeightedIndex::new([1, 2, 7]).unwrap())
+            .with_component(1, |rng| if rng.gen_bool(0.9) { 3 } else { 0 })
+            .build()
+            .unwrap();
+
+        let n = 20_000;
+        let mut counts = [[0usize; 4]; 3];
+        let mut val = vec![];
+
+        for _ in 0..n {
+            sampler.sample_into(&mut rng, &mut val);
+
+            assert!(sampler.space().iter().zip(val.iter()).all(|(d, &v)| d.contains(v)));
+
+            for (c, &v) in counts.iter_mut().zip(val.iter()) {
+                c[v] += 1;
+            }
+        }
+
+        let freq = |c: usize, v: usize| counts[c][v] as f64 / n as f64;
+
+        assert!((freq(0, 0) - 0.1).abs() < 0.02);
+        assert!((freq(0, 2) - 0.7).abs() < 0.02);
+        assert!((freq(1, 3) - 0.9).abs() < 0.02);
+        assert_eq!(counts[1][1] + counts[1][2], 0);
+        assert!((freq(2, 0) - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_truncated_normal_component() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]);
+        let tn = TruncatedNormal::new(Interval::bounded(0.0, 1.0), 0.0, 0.1);
+        let sampler = JointSampler::builder(space)
+            .with_component(0, move |rng| tn.sample(rng))
+            .build()
+            .unwrap();
+
+        let n = 10_000;
+        let means = (0..n).map(|_| sampler.sample(&mut rng)).fold(vec![0.0; 2], |acc, v| {
+            acc.iter().zip(v).map(|(a, x)| a + x / n as f64).collect()
+        });
+
+        // Half-normal mean of 0.1 * sqrt(2 / pi), and the uniform mean of 0.5.
+        assert!((means[0] - 0.0798).abs() < 0.005);
+        assert!((means[1] - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space =
+            ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::left_bounded(0.0)]);
+        let sampler = JointSampler::builder(space.clone()).build().unwrap();
+
+        assert_eq!(sampler.dim(), space.dim());
+        assert_eq!(sampler.try_sample(&mut rng), Err(SampleError::Unbounded));
+
+        let sampler = JointSampler::builder(space).with_component(1, |_| 5.0).build().unwrap();
+
+        assert_eq!(sampler.try_sample(&mut rng).map(|v| v[1]), Ok(5.0));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+
+        assert_eq!(
+            JointSampler::builder(space).with_component(2, |_| 0).build().err(),
+            Some(ComponentError { index: 2, n_components: 2, })
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{discrete::Ordinal, prelude::*, Interval, PairSpace, ProductSpace, TwoSpace};
+
+/// Trait for spaces whose values can be interpolated between without leaving the space, e.g. for
+/// smoothing trajectories.
+///
+/// Progress `t` outside of `[0, 1]` extrapolates beyond `a` or `b`, and the result is projected
+/// back onto the space; `t = 0` and `t = 1` reproduce `a` and `b` exactly.
+/// ```
+/// use spaces::{real::Interval, Lerp, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]);
+///
+/// assert_eq!(space.lerp(&vec![0.0, 1.0], &vec![1.0, 0.0], 0.25), vec![0.25, 0.75]);
+/// assert_eq!(space.lerp(&vec![0.0, 1.0], &vec![1.0, 0.0], 1.5), vec![1.0, 0.0]);
+/// ```
+pub trait Lerp: Space {
+    /// Returns the value at progress `t` from `a` (at zero) to `b` (at one), where both are
+    /// values of the space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t` is `NaN`.
+    fn lerp(&self, a: &Self::Value, b: &Self::Value, t: f64) -> Self::Value;
+}
+
+// Interpolates from the nearer endpoint, such that both endpoints are reproduced exactly. If the
+// difference of the endpoints overflows, their weighted sum is taken instead.
+fn lerp_f64(a: f64, b: f64, t: f64) -> f64 {
+    assert!(!t.is_nan(), "Cannot interpolate with a NaN progress.");
+
+    let d = b - a;
+
+    if !d.is_finite() && a.is_finite() && b.is_finite() {
+        a * (1.0 - t) + b * t
+    } else if t <= 0.5 {
+        a + t * d
+    } else {
+        b - (1.0 - t) * d
+    }
+}
+
+// As `lerp_f64`, rounding the offset from the nearer endpoint to the nearest integer.
+fn lerp_i128(a: i128, b: i128, t: f64) -> i128 {
+    assert!(!t.is_nan(), "Cannot interpolate with a NaN progress.");
+
+    let d = (b - a) as f64;
+
+    if t <= 0.5 { a + (t * d).round() as i128 } else { b - ((1.0 - t) * d).round() as i128 }
+}
+
+/// Values are interpolated linearly, and clamped onto the interval.
+impl Lerp for Interval<f64> {
+    fn lerp(&self, a: &f64, b: &f64, t: f64) -> f64 { self.map_onto(lerp_f64(*a, *b, t)) }
+}
+
+/// Values are interpolated linearly, rounded to the nearest integer and clamped onto the interval.
+impl Lerp for Interval<i64> {
+    fn lerp(&self, a: &i64, b: &i64, t: f64) -> i64 {
+        let val = lerp_i128(*a as i128, *b as i128, t);
+
+        self.map_onto(val.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+}
+
+/// Indices are interpolated linearly, rounded to the nearest index and clamped onto the space.
+impl Lerp for Ordinal {
+    fn lerp(&self, a: &usize, b: &usize, t: f64) -> usize {
+        let max = self.size().saturating_sub(1) as i128;
+
+        lerp_i128(*a as i128, *b as i128, t).clamp(0, max) as usize
+    }
+}
+
+/// Components are interpolated independently.
+///
+/// # Panics
+///
+/// Panics if either value does not have one entry per component.
+impl<D: Lerp> Lerp for ProductSpace<D> {
+    fn lerp(&self, a: &Vec<D::Value>, b: &Vec<D::Value>, t: f64) -> Vec<D::Value> {
+        assert_eq!(a.len(), self.iter().len(), "Value does not match the dimension of the space.");
+        assert_eq!(b.len(), self.iter().len(), "Value does not match the dimension of the space.");
+
+        self.iter().zip(a.iter().zip(b.iter())).map(|(d, (a, b))| d.lerp(a, b, t)).collect()
+    }
+}
+
+/// Components are interpolated independently.
+impl<D: Lerp> Lerp for TwoSpace<D> {
+    fn lerp(&self, a: &[D::Value; 2], b: &[D::Value; 2], t: f64) -> [D::Value; 2] {
+        [self[0].lerp(&a[0], &b[0], t), self[1].lerp(&a[1], &b[1], t)]
+    }
+}
+
+/// Components are interpolated independently.
+impl<D1: Lerp, D2: Lerp> Lerp for PairSpace<D1, D2> {
+    fn lerp(
+        &self,
+        a: &(D1::Value, D2::Value),
+        b: &(D1::Value, D2::Value),
+        t: f64,
+    ) -> (D1::Value, D2::Value) {
+        (self.0.lerp(&a.0, &b.0, t), self.1.lerp(&a.1, &b.1, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BoundKind;
+    use super::*;
+
+    const TS: [f64; 9] = [-1.0, -0.1, 0.0, 0.1, 0.3, 0.5, 0.9, 1.0, 2.0];
+
+    // Checks that the endpoints are reproduced exactly, and that every interpolant is contained
+    // in the space.
+    fn check<S: Lerp + BoundedSpace>(space: &S, a: S::Value, b: S::Value)
+    where S::Value: PartialOrd + std::fmt::Debug
+    {
+        assert_eq!(space.lerp(&a, &b, 0.0), a);
+        assert_eq!(space.lerp(&a, &b, 1.0), b);
+
+        for &t in TS.iter() {
+            let val = space.lerp(&a, &b, t);
+
+            assert!(space.contains(val.clone()), "{:?} (at t = {}) is not contained", val, t);
+        }
+    }
+
+    #[test]
+    fn test_interval_f64() {
+        let d = Interval::bounded(-1.0, 2.0);
+
+        check(&d, -1.0, 2.0);
+        check(&d, 0.1, 0.7);
+        check(&Interval::open(0.0, 1.0), 0.25, 0.75);
+        check(&Interval::left_bounded(0.0), 1e300, 0.0);
+
+        assert_eq!(d.lerp(&0.0, &2.0, 0.25), 0.5);
+        assert_eq!(d.lerp(&0.0, &2.0, 2.0), 2.0);
+        assert_eq!(d.lerp(&0.0, &2.0, -2.0), -1.0);
+        assert_eq!(Interval::<f64>::unbounded().lerp(&0.0, &2.0, 3.0), 6.0);
+
+        let open = Interval::open(0.0, 1.0);
+
+        assert!(open.lerp(&0.25, &0.75, -10.0) > 0.0);
+        assert!(open.lerp(&0.25, &0.75, 10.0) < 1.0);
+
+        let wide = Interval::bounded(-f64::MAX, f64::MAX);
+
+        check(&wide, -f64::MAX, f64::MAX);
+        check(&wide, f64::MAX, -1e308);
+        assert_eq!(wide.lerp(&-f64::MAX, &f64::MAX, 0.5), 0.0);
+        assert_eq!(wide.lerp(&-f64::MAX, &f64::MAX, 2.0), f64::MAX);
+    }
+
+    #[test]
+    fn test_interval_i64() {
+        let d = Interval::bounded(-5i64, 5);
+
+        check(&d, -5, 5);
+        check(&Interval::bounded(i64::MIN, i64::MAX), i64::MIN, i64::MAX);
+        check(&Interval::bounded(0i64, 10).with_kinds(BoundKind::Open, BoundKind::Open), 1, 9);
+
+        assert_eq!(d.lerp(&0, &5, 0.5), 3);
+        assert_eq!(d.lerp(&-5, &5, 0.26), -2);
+        assert_eq!(d.lerp(&0, &5, 10.0), 5);
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let d = Ordinal::new(10);
+
+        check(&d, 0, 9);
+        check(&d, 7, 2);
+        check(&Ordinal::new(usize::MAX), 1, usize::MAX - 1);
+
+        assert_eq!(d.lerp(&0, &9, 0.5), 5);
+        assert_eq!(d.lerp(&9, &0, 0.5), 4);
+        assert_eq!(d.lerp(&2, &4, -3.0), 0);
+        assert_eq!(d.lerp(&2, &4, 5.0), 9);
+    }
+
+    #[test]
+    fn test_products() {
+        let d = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(-1.0, 0.0)]);
+
+        assert_eq!(d.lerp(&vec![0.0, 0.0], &vec![1.0, -1.0], 0.5), vec![0.5, -0.5]);
+        assert_eq!(d.lerp(&vec![0.0, 0.0], &vec![1.0, -1.0], 4.0), vec![1.0, -1.0]);
+        assert_eq!(d.lerp(&vec![0.3, -0.3], &vec![0.9, -0.1], 1.0), vec![0.9, -0.1]);
+
+        let d = TwoSpace::new([Ordinal::new(3), Ordinal::new(5)]);
+
+        assert_eq!(d.lerp(&[0, 0], &[2, 4], 0.5), [1, 2]);
+
+        let d = PairSpace::new(Ordinal::new(4), Interval::bounded(0.0, 1.0));
+
+        assert_eq!(d.lerp(&(0, 1.0), &(3, 0.0), 0.75), (2, 0.25));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_product() {
+        ProductSpace::new(vec![Interval::<f64>::unit(); 2]).lerp(&vec![0.0], &vec![1.0], 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nan() {
+        Interval::<f64>::unit().lerp(&0.0, &1.0, f64::NAN);
+    }
+}
@@ -7,26 +7,190 @@
 //! streamline many common preprocessing and type conversion tasks.
 extern crate itertools;
 extern crate num_traits;
+extern crate rand;
+extern crate rand_chacha;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 #[cfg(feature = "serialize")]
 #[macro_use]
 extern crate serde;
 
+#[cfg(feature = "derive")]
+extern crate spaces_derive;
+
+#[cfg(feature = "serialize")]
+extern crate serde_json;
+
+use rand::Rng;
+
 mod macros;
 
 import_all!(dim);
+import_all!(shape);
 import_all!(card);
+import_all!(limits);
+import_all!(contains);
+import_all!(rows);
+import_all!(sample);
 
 pub mod discrete;
 pub mod real;
+pub mod compat;
+pub mod codec;
+pub mod parse;
+pub mod patch;
+pub mod registry;
+pub mod basis;
+pub mod errors;
+pub mod testing;
+
+#[cfg(feature = "serialize")]
+pub mod gym;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "interop")]
+pub mod interop;
+
+#[cfg(feature = "presets")]
+pub mod presets;
 
+pub use errors::Error;
+pub use real::{RealSpace, RealVectorSpace};
+
+/// Derive `Space`, `Sample` and `BoundedSpace` for a struct whose fields are all spaces.
+///
+/// The derive generates a companion struct, named by appending `Value` to the name of the
+/// deriving struct, that holds one value per field; it derives `Clone`, `Debug`, `PartialEq` and
+/// `PartialOrd`, and mirrors the visibility of the struct and its fields. The dimensionality is
+/// the sum over the fields and the cardinality is the product, as for `ProductSpace`; sampling
+/// fills each field independently, and a value is contained only if every field is. Every field
+/// must therefore implement `Space`, `Sample` and `BoundedSpace`, unless it is marked with one of
+/// the following attributes:
+///
+/// - `#[space(skip)]` excludes the field, e.g. for metadata, and it does not appear in the value;
+/// - `#[space(flatten)]` treats a field of type `Vec<S>` or `[S; N]` as a product of its
+///   elements, with a value of type `Vec<S::Value>`.
+///
+/// Generic structs and structs without named fields are not supported.
+/// ```
+/// use spaces::{discrete::Binary, real::Interval, BoundedSpace, Card, Dim, Sample, Space};
+///
+/// #[derive(Space)]
+/// struct Obs {
+///     position: Interval,
+///     velocity: Interval,
+///     on_ground: Binary,
+///     #[space(flatten)]
+///     sensors: [Binary; 3],
+///     #[space(skip)]
+///     label: &'static str,
+/// }
+///
+/// let obs = Obs {
+///     position: Interval::bounded(-1.0, 1.0),
+///     velocity: Interval::bounded(-5.0, 5.0),
+///     on_ground: Binary,
+///     sensors: [Binary; 3],
+///     label: "cart",
+/// };
+///
+/// assert_eq!(obs.dim(), Dim::Finite(6));
+/// assert_eq!(obs.card(), Card::Infinite);
+///
+/// let val: ObsValue = obs.sample(&mut rand::thread_rng());
+///
+/// assert!(val.position.abs() <= 1.0);
+/// assert_eq!(val.sensors.len(), 3);
+/// assert!(obs.contains(val));
+/// ```
+///
+/// Fields that are not spaces must be skipped:
+/// ```compile_fail
+/// use spaces::{real::Interval, Space};
+///
+/// #[derive(Space)]
+/// struct Obs {
+///     position: Interval,
+///     label: String,
+/// }
+/// ```
+///
+/// Only `Vec` and array fields can be flattened:
+/// ```compile_fail
+/// use spaces::{real::Interval, Space};
+///
+/// #[derive(Space)]
+/// struct Obs {
+///     #[space(flatten)]
+///     position: Interval,
+/// }
+/// ```
+///
+/// Unknown attributes are rejected:
+/// ```compile_fail
+/// use spaces::{real::Interval, Space};
+///
+/// #[derive(Space)]
+/// struct Obs {
+///     #[space(rename = "x")]
+///     position: Interval,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use spaces_derive::Space;
+
+// Items referred to by the code generated by `spaces-derive`; not part of the public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use rand::Rng;
+}
+
+import_all!(affine);
 import_all!(empty);
 import_all!(interval);
+import_all!(normal);
+import_all!(sentinel);
+import_all!(reward);
 import_all!(partition);
+import_all!(quantised);
+import_all!(rounding);
+import_all!(sample_with);
 
 import_all!(pair);
 import_all!(n_space);
 import_all!(product);
+import_all!(joint);
+import_all!(env);
+import_all!(stacked);
+import_all!(repeated);
+import_all!(scheduled);
+
+import_all!(table);
+import_all!(pmf);
+import_all!(description);
+import_all!(flatten);
+import_all!(flat);
+import_all!(approx);
+import_all!(corners);
+import_all!(lerp);
+import_all!(enumerated);
+import_all!(value_display);
+import_all!(antithetic);
+import_all!(density);
+import_all!(delta);
+import_all!(nominal);
+import_all!(spread);
+import_all!(map_fn);
+import_all!(product_map);
+import_all!(perturbed);
+import_all!(fingerprint);
+import_all!(episode);
+import_all!(mixture);
 
 /// Trait for defining geometric spaces.
 pub trait Space {
@@ -34,33 +198,73 @@ pub trait Space {
     type Value: Clone;
 
     /// Return the dimensionality of the space.
+    ///
+    /// For finite dimensionalities, this is the number of components of the shape of the space;
+    /// see `shape`.
     fn dim(&self) -> Dim;
 
     /// Return the number of elements in the set comprising the space.
     fn card(&self) -> Card;
+
+    /// Return the shape of the values of the space, for which `shape().len()` must equal the
+    /// (finite) dimensionality.
+    ///
+    /// By default, spaces of dimensionality one are scalars, and all others are flat vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics by default if the dimensionality is infinite.
+    fn shape(&self) -> Shape { Shape::from_dim(self.dim()) }
+
+    /// Returns true iff the space contains a finite number of elements; see `card`.
+    fn is_finite(&self) -> bool { matches!(self.card(), Card::Finite(_)) }
 }
 
-impl<D: Space> Space for Box<D> {
-    type Value = D::Value;
+/// Trait for spaces from which values can be drawn at random.
+pub trait Sample: Space {
+    /// Draw a value uniformly at random from the space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no uniform distribution over the space exists; see `try_sample`.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value;
 
-    fn dim(&self) -> Dim {
-        (**self).dim()
+    /// Draw a value uniformly at random from the space, or return an error if no uniform
+    /// distribution over the space exists.
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        Ok(self.sample(rng))
     }
 
-    fn card(&self) -> Card {
-        (**self).card()
-    }
-}
+    /// Draw `n` values in parallel, reproducibly for a given `seed`.
+    ///
+    /// The values are generated in fixed-size chunks, each with an independent `SmallRng`
+    /// seeded from `seed` and the chunk index. The output is therefore identical regardless of
+    /// the number of threads used by `rayon`.
+    #[cfg(feature = "rayon")]
+    fn par_sample_n(&self, n: usize, seed: u64) -> Vec<Self::Value>
+    where
+        Self: Sync,
+        Self::Value: Send,
+    {
+        use rand::{rngs::SmallRng, SeedableRng};
+        use rayon::prelude::*;
 
-impl<'a, D: Space> Space for &'a D {
-    type Value = D::Value;
+        use crate::episode::splitmix64;
 
-    fn dim(&self) -> Dim {
-        (**self).dim()
-    }
+        const CHUNK_SIZE: usize = 1024;
+
+        let n_chunks = n.div_ceil(CHUNK_SIZE);
+        let chunks: Vec<Vec<Self::Value>> = (0..n_chunks)
+            .into_par_iter()
+            .map(|i| {
+                let mut rng = SmallRng::seed_from_u64(splitmix64(seed ^ splitmix64(i as u64)));
+                let size = CHUNK_SIZE.min(n - i * CHUNK_SIZE);
 
-    fn card(&self) -> Card {
-        (**self).card()
+                (0..size).map(|_| self.sample(&mut rng)).collect()
+            })
+            .collect();
+
+        chunks.into_iter().flatten().collect()
     }
 }
 
@@ -69,34 +273,81 @@ impl<'a, D: Space> Space for &'a D {
 /// Note: If both `inf` and `sup` are well defined (i.e. are not None), then the interval is
 /// totally bounded and we have a compact space; this is true in `spaces` as bounds are treated as
 /// closed.
+///
+/// Bounds are always expressed in terms of `Self::Value`; there is no separate associated bound
+/// type, so generic code may compare the bounds of a space directly with its values:
+/// ```
+/// use spaces::{discrete::{Naturals, Ordinal}, BoundedSpace, Interval};
+///
+/// fn width<S: BoundedSpace<Value = u64>>(space: &S) -> Option<u64> {
+///     Some(space.sup()? - space.inf()?)
+/// }
+///
+/// assert_eq!(width(&Naturals), None);
+/// assert_eq!(Ordinal::new(4).sup(), Some(3usize));
+/// assert_eq!(Interval::left_bounded(0.0).inf(), Some(0.0));
+/// ```
 pub trait BoundedSpace: Space
 where
     Self::Value: PartialOrd,
 {
-    /// Returns the value of the dimension's infimum, if it exists.
+    /// Returns the value of the dimension's infimum.
+    ///
+    /// `None` means that the space is unbounded below, not that the bound is unknown.
     fn inf(&self) -> Option<Self::Value>;
 
-    /// Returns the value of the dimension's supremum, if it exists.
+    /// Returns the value of the dimension's supremum.
+    ///
+    /// `None` means that the space is unbounded above, not that the bound is unknown.
     fn sup(&self) -> Option<Self::Value>;
 
     /// Returns true iff `val` lies within the dimension's bounds (closed).
     fn contains(&self, val: Self::Value) -> bool;
 
+    /// Checks that every value in `vals` lies within the dimension's bounds.
+    ///
+    /// Stops at, and returns an error describing, the first value that does not.
+    fn contains_all<'a, I>(&self, vals: I) -> Result<(), ContainsError>
+    where
+        I: IntoIterator<Item = &'a Self::Value>,
+        Self::Value: 'a + std::fmt::Debug,
+    {
+        match vals.into_iter().enumerate().find(|(_, v)| !self.contains((*v).clone())) {
+            Some((index, v)) => Err(ContainsError::new(index, v)),
+            None => Ok(()),
+        }
+    }
+
     /// Returns true iff `self` has a finite infimum.
-    fn is_left_bounded(&self) -> bool {
+    fn is_lower_bounded(&self) -> bool {
         self.inf().is_some()
     }
 
     /// Returns true iff `self` has a finite supremum.
-    fn is_right_bounded(&self) -> bool {
+    fn is_upper_bounded(&self) -> bool {
         self.sup().is_some()
     }
 
     /// Returns true iff `self` has finite bounds in both directions.
+    fn is_bounded(&self) -> bool {
+        self.is_lower_bounded() && self.is_upper_bounded()
+    }
+
+    /// Alias of `is_lower_bounded`.
+    fn is_left_bounded(&self) -> bool {
+        self.is_lower_bounded()
+    }
+
+    /// Alias of `is_upper_bounded`.
+    fn is_right_bounded(&self) -> bool {
+        self.is_upper_bounded()
+    }
+
+    /// Returns true iff `self` has finite bounds in both directions; see `is_bounded`.
     ///
     /// Note: this trait assumed closedness, so compactness follows.
     fn is_compact(&self) -> bool {
-        self.is_left_bounded() && self.is_right_bounded()
+        self.is_bounded()
     }
 }
 
@@ -110,14 +361,130 @@ pub trait FiniteSpace: Space + IntoIterator<Item = <Self as Space>::Value> {
         self.card().into()
     }
 
+    /// Returns the number of elements in the set comprising the space.
+    fn len(&self) -> usize { self.card_finite() }
+
+    /// Returns true iff the space contains no elements.
+    fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Collect every element of the space into a `Vec`.
+    ///
+    /// Returns an error, without enumerating anything, if the space has more than
+    /// `MATERIALISE_LIMIT` elements.
+    fn try_collect_all(&self) -> Result<Vec<Self::Value>, SizeLimitError> {
+        SizeLimitError::check(self.len()).map(|_| self.iter().collect())
+    }
+
     fn iter(&self) -> Self::Iter;
+
+    /// Returns the position of `val` in the enumeration order of the space, if present.
+    fn to_index(&self, val: &Self::Value) -> Option<usize>
+    where Self::Value: PartialEq {
+        self.iter().position(|v| v == *val)
+    }
+
+    /// Returns the value at position `idx` in the enumeration order of the space, if present.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_index(&self, idx: usize) -> Option<Self::Value> { self.iter().nth(idx) }
+
+    /// Returns an iterator over every `stride`th value of the space in enumeration order,
+    /// starting from the first.
+    ///
+    /// Values are looked up lazily with `from_index`, such that skipped values are never
+    /// enumerated when `from_index` itself takes constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    /// ```
+    /// use spaces::{discrete::Ordinal, FiniteSpace};
+    ///
+    /// let d = Ordinal::new(10);
+    ///
+    /// assert_eq!(d.iter_every(4).collect::<Vec<_>>(), vec![0, 4, 8]);
+    /// ```
+    fn iter_every(&self, stride: usize) -> impl Iterator<Item = Self::Value> + '_
+    where Self: Sized {
+        (0..self.len()).step_by(stride).map_while(move |i| self.from_index(i))
+    }
+
+    /// Returns an iterator over `k` values spread evenly over the enumeration order of the
+    /// space, at the indices of `Spread`; every value is returned if the space has at most `k`.
+    ///
+    /// Unlike `k` independent samples, which tend to cluster, the values cover the whole
+    /// enumeration. As with `iter_every`, values are looked up lazily with `from_index`.
+    /// ```
+    /// use spaces::{discrete::Ordinal, FiniteSpace};
+    ///
+    /// let d = Ordinal::new(usize::MAX);
+    ///
+    /// assert_eq!(d.iter_spread(2).collect::<Vec<_>>(), vec![0, usize::MAX / 2]);
+    /// ```
+    fn iter_spread(&self, k: usize) -> impl Iterator<Item = Self::Value> + '_
+    where Self: Sized {
+        Spread::new(self.len(), k).map_while(move |i| self.from_index(i))
+    }
+
+    /// Returns the fraction of the values of the space that appear at least once in `samples`.
+    ///
+    /// Values not contained in the space are ignored, and the coverage of an empty batch (or an
+    /// empty space) is zero.
+    /// ```
+    /// use spaces::{discrete::Ordinal, FiniteSpace};
+    ///
+    /// assert_eq!(Ordinal::new(4).coverage(&[0, 2, 2, 9]), 0.5);
+    /// ```
+    fn coverage(&self, samples: &[Self::Value]) -> f64
+    where Self::Value: PartialEq {
+        index_coverage(self.len(), samples.iter().filter_map(|v| self.to_index(v)))
+    }
+
+    /// Precompute the enumeration of the space, for constant-time index lookups; see
+    /// `Enumerated`.
+    ///
+    /// Returns an error, without enumerating anything, if the space has more than
+    /// `MATERIALISE_LIMIT` elements.
+    fn enumerated(self) -> Result<Enumerated<Self>, SizeLimitError>
+    where Self: Sized, Self::Value: std::hash::Hash + Eq {
+        Enumerated::new(self)
+    }
+}
+
+/// Returns the fraction of `0..n` that appears in `indices`, or zero if either is empty.
+///
+/// Visited indices are tracked with a bitset, unless `n` exceeds `MATERIALISE_LIMIT`.
+pub(crate) fn index_coverage<I: Iterator<Item = usize>>(n: usize, indices: I) -> f64 {
+    let mut indices = indices.peekable();
+
+    if n == 0 || indices.peek().is_none() { return 0.0; }
+
+    let visited = if n <= MATERIALISE_LIMIT {
+        let mut bits = vec![0u64; n.div_ceil(64)];
+
+        indices.for_each(|i| bits[i / 64] |= 1 << (i % 64));
+        bits.iter().map(|b| b.count_ones() as usize).sum()
+    } else {
+        let mut indices: Vec<usize> = indices.collect();
+
+        indices.sort_unstable();
+        indices.dedup();
+        indices.len()
+    };
+
+    visited as f64 / n as f64
 }
 
 
 /// Trait for defining spaces containing a finite set of values.
 pub trait FiniteOrderedSpace: FiniteSpace where Self::Value: PartialOrd     {
-    /// Returns the finite range of values contained by this space.
-    fn range(&self) -> ::std::ops::Range<Self::Value>;
+    /// Returns the finite, inclusive range of values contained by this space.
+    ///
+    /// The range is inclusive such that it can end at the largest value of `Self::Value`; the
+    /// range of an empty space is `1..=0`.
+    ///
+    /// Note: before version 6.0, this returned an exclusive `Range`, which could not represent
+    /// spaces ending at the largest value of `Self::Value`.
+    fn range(&self) -> ::std::ops::RangeInclusive<Self::Value>;
 }
 
 /// Trait for types that implement a mapping from values of one set onto another.
@@ -129,6 +496,15 @@ pub trait Surjection<X, Y> {
     fn map_onto(&self, from: X) -> Y;
 }
 
+/// Trait for types that implement a randomised mapping from values of one set onto another.
+///
+/// This is the analogue of `Surjection` for maps that require a source of randomness, such as
+/// stochastic rounding; every element of the codomain should be reachable from the domain.
+pub trait StochasticSurjection<X, Y> {
+    /// Map value from domain onto codomain, drawing any randomness from `rng`.
+    fn map_with<R: Rng + ?Sized>(&self, from: X, rng: &mut R) -> Y;
+}
+
 /// Trait for types that can be combined in the form of a union.
 ///
 /// The union of a collection of sets is the set that contains all elements in the collection.
@@ -142,7 +518,7 @@ pub trait Union<S = Self> {
         Self: Sized,
     {
         other_spaces
-            .into_iter()
+            .iter()
             .fold(self, |acc, other_space| acc.union(other_space))
     }
 }
@@ -161,11 +537,274 @@ pub trait Intersection<S = Self> {
         Self: Sized,
     {
         other_spaces
-            .into_iter()
+            .iter()
             .fold(self, |acc, other_space| acc.intersect(other_space))
     }
 }
 
+// Forwarding implementations for smart pointers and references, such that a single (possibly
+// large) space can be shared between wrappers and threads without cloning it. `FiniteSpace` is
+// not forwarded, since it requires `IntoIterator`, which cannot be implemented for foreign
+// pointer types.
+macro_rules! impl_pointer_space {
+    ($($ptr:ty),+) => {$(
+        impl<D: Space> Space for $ptr {
+            type Value = D::Value;
+
+            fn dim(&self) -> Dim { (**self).dim() }
+
+            fn card(&self) -> Card { (**self).card() }
+
+            fn shape(&self) -> Shape { (**self).shape() }
+        }
+
+        impl<D: Sample> Sample for $ptr {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value { (**self).sample(rng) }
+
+            fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+                (**self).try_sample(rng)
+            }
+        }
+
+        impl<D: BoundedSpace> BoundedSpace for $ptr
+        where D::Value: PartialOrd
+        {
+            fn inf(&self) -> Option<D::Value> { (**self).inf() }
+
+            fn sup(&self) -> Option<D::Value> { (**self).sup() }
+
+            fn contains(&self, val: D::Value) -> bool { (**self).contains(val) }
+        }
+
+        impl<D: Surjection<X, Y>, X, Y> Surjection<X, Y> for $ptr {
+            fn map_onto(&self, from: X) -> Y { (**self).map_onto(from) }
+        }
+    )+};
+}
+
+impl_pointer_space!(Box<D>, &D, std::rc::Rc<D>, std::sync::Arc<D>);
+
 mod prelude {
-    pub use super::{BoundedSpace, Card, Dim, FiniteOrderedSpace, FiniteSpace, Intersection, Space, Surjection, Union};
+    pub use super::{BoundedSpace, Card, Dim, FiniteOrderedSpace, FiniteSpace, Intersection, RealSpace, RealVectorSpace, Sample, Shape, Space, StochasticSurjection, Surjection, Union};
+    pub use rand::Rng;
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate static_assertions;
+
+    use crate::{
+        codec::SinglePrecision,
+        discrete::{
+            Alphabet, Binary, Integers, Naturals, NonNegativeIntegers, NonZeroIntegers, Ordinal, Rescale,
+        },
+        real::{NonNegativeReals, PositiveReals, Reals},
+        *,
+    };
+
+    static_assertions::assert_impl_all!(Empty: Send, Sync);
+    static_assertions::assert_impl_all!(Binary: Send, Sync);
+    static_assertions::assert_impl_all!(Alphabet: Send, Sync);
+    static_assertions::assert_impl_all!(Ordinal: Send, Sync);
+    static_assertions::assert_impl_all!(Naturals: Send, Sync);
+    static_assertions::assert_impl_all!(Integers: Send, Sync);
+    static_assertions::assert_impl_all!(NonZeroIntegers: Send, Sync);
+    static_assertions::assert_impl_all!(NonNegativeIntegers: Send, Sync);
+    static_assertions::assert_impl_all!(Reals: Send, Sync);
+    static_assertions::assert_impl_all!(NonNegativeReals: Send, Sync);
+    static_assertions::assert_impl_all!(PositiveReals: Send, Sync);
+    static_assertions::assert_impl_all!(Interval<f64>: Send, Sync);
+    static_assertions::assert_impl_all!(Interval<i64>: Send, Sync);
+    static_assertions::assert_impl_all!(Equipartition: Send, Sync);
+    static_assertions::assert_impl_all!(LogPartition: Send, Sync);
+    static_assertions::assert_impl_all!(Quantised: Send, Sync);
+    static_assertions::assert_impl_all!(AffineMap: Send, Sync);
+    static_assertions::assert_impl_all!(SinglePrecision<Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(PairSpace<Ordinal, Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(TwoSpace<Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(ProductSpace<Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(Stacked<Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(TruncatedNormal<Interval>: Send, Sync);
+    static_assertions::assert_impl_all!(TableMap<Ordinal, Binary>: Send, Sync);
+    static_assertions::assert_impl_all!(Pmf<Ordinal>: Send, Sync);
+    static_assertions::assert_impl_all!(Rescale: Send, Sync);
+
+    #[test]
+    fn test_coverage_finite() {
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+        let all: Vec<Vec<usize>> = space.iter_order(EnumOrder::Lex).collect();
+
+        assert_eq!(space.coverage(&all), 1.0);
+        assert_eq!(space.coverage(&[]), 0.0);
+        assert_eq!(space.coverage(&vec![vec![1, 1]; 50]), 1.0 / 12.0);
+        assert_eq!(space.coverage(&[vec![1, 1], vec![5, 5]]), 1.0 / 12.0);
+
+        let scores: Vec<f64> = (0..=all.len()).map(|n| space.coverage(&all[..n])).collect();
+
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(Binary.coverage(&[true, true]), 0.5);
+        assert_eq!(Ordinal::new(200).coverage(&(0..100).collect::<Vec<_>>()), 0.5);
+    }
+
+    #[test]
+    fn test_bounded_predicates() {
+        fn predicates<S: BoundedSpace>(s: &S) -> (bool, bool, bool)
+        where S::Value: PartialOrd
+        {
+            (s.is_lower_bounded(), s.is_upper_bounded(), s.is_bounded())
+        }
+
+        assert_eq!(predicates(&Interval::bounded(0.0, 1.0)), (true, true, true));
+        assert_eq!(predicates(&Interval::left_bounded(0.0)), (true, false, false));
+        assert_eq!(predicates(&Interval::right_bounded(0.0)), (false, true, false));
+        assert_eq!(predicates(&Interval::<f64>::unbounded()), (false, false, false));
+        assert_eq!(predicates(&Ordinal::new(3)), (true, true, true));
+        assert_eq!(predicates(&Naturals), (true, false, false));
+        assert_eq!(predicates(&NonNegativeIntegers), (true, false, false));
+        assert_eq!(predicates(&Integers), (false, false, false));
+        assert_eq!(predicates(&Binary), (true, true, true));
+        assert_eq!(predicates(&Box::new(PositiveReals)), (true, false, false));
+
+        assert!(Interval::bounded(0.0, 1.0).is_compact());
+        assert!(!Naturals.is_compact());
+        assert!(Naturals.is_left_bounded() && !Naturals.is_right_bounded());
+    }
+
+    #[test]
+    fn test_is_finite() {
+        assert!(Binary.is_finite());
+        assert!(Ordinal::new(0).is_finite());
+        assert!(Alphabet::new("abc").is_finite());
+        assert!(!Naturals.is_finite());
+        assert!(!Interval::<f64>::unit().is_finite());
+        assert!(Interval::bounded(0i64, 10).is_finite());
+        assert!(ProductSpace::new(vec![Ordinal::new(2); 3]).is_finite());
+        assert!(!ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(usize::MAX)]).is_finite());
+    }
+
+    #[test]
+    fn test_shared_space() {
+        use std::{rc::Rc, sync::Arc, thread};
+
+        let space = Arc::new(Alphabet::sorted((0..100_000).filter_map(char::from_u32)));
+        let shared: Vec<_> = (0..2).map(|_| Arc::clone(&space)).collect();
+
+        assert_eq!(Arc::strong_count(&space), 3);
+
+        let handles: Vec<_> = shared.into_iter().map(|space| {
+            thread::spawn(move || {
+                let mut rng = rand::thread_rng();
+
+                (0..100).all(|_| {
+                    let c = space.sample(&mut rng);
+                    let i: usize = space.map_onto(c);
+
+                    space.map_onto(i) == c
+                })
+            })
+        }).collect();
+
+        assert!(handles.into_iter().all(|h| h.join().unwrap()));
+        assert_eq!(Arc::strong_count(&space), 1);
+        assert_eq!(space.card(), Card::Finite(space.len()));
+
+        fn clamp<S: BoundedSpace<Value = f64> + Surjection<f64, f64>>(s: S, x: f64) -> f64 {
+            assert!(s.contains(s.inf().unwrap()) && s.contains(s.sup().unwrap()));
+
+            s.map_onto(x)
+        }
+
+        let interval = Rc::new(Interval::bounded(0.0, 1.0));
+
+        assert_eq!(clamp(Rc::clone(&interval), 2.0), 1.0);
+        assert_eq!(clamp(&interval, -1.0), 0.0);
+        assert_eq!(clamp(Box::new(Interval::unit()), 0.5), 0.5);
+        assert_eq!(Rc::strong_count(&interval), 1);
+    }
+
+    // Ordinal space counting the calls to `from_index`, and failing on full enumeration.
+    struct Counted(Ordinal, std::cell::Cell<usize>);
+
+    impl Space for Counted {
+        type Value = usize;
+
+        fn dim(&self) -> Dim { Dim::one() }
+
+        fn card(&self) -> Card { self.0.card() }
+    }
+
+    impl FiniteSpace for Counted {
+        type Iter = std::iter::Empty<usize>;
+
+        fn iter(&self) -> Self::Iter { panic!("Space was enumerated.") }
+
+        fn from_index(&self, idx: usize) -> Option<usize> {
+            self.1.set(self.1.get() + 1);
+            self.0.from_index(idx)
+        }
+    }
+
+    impl IntoIterator for Counted {
+        type Item = usize;
+        type IntoIter = std::iter::Empty<usize>;
+
+        fn into_iter(self) -> Self::IntoIter { panic!("Space was enumerated.") }
+    }
+
+    #[test]
+    fn test_iter_every_spread() {
+        let d = Ordinal::new(10);
+
+        assert_eq!(d.iter_every(1).collect::<Vec<_>>(), d.iter().collect::<Vec<_>>());
+        assert_eq!(d.iter_every(3).collect::<Vec<_>>(), vec![0, 3, 6, 9]);
+        assert_eq!(d.iter_every(10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(d.iter_spread(4).collect::<Vec<_>>(), vec![0, 2, 5, 7]);
+        assert_eq!(d.iter_spread(10).collect::<Vec<_>>(), d.iter().collect::<Vec<_>>());
+        assert_eq!(d.iter_spread(11).collect::<Vec<_>>(), d.iter().collect::<Vec<_>>());
+        assert_eq!(Ordinal::new(0).iter_spread(3).count(), 0);
+        assert_eq!(Alphabet::new("abcdef").iter_every(2).collect::<String>(), "ace");
+        assert_eq!(Binary.iter_spread(1).collect::<Vec<_>>(), vec![false]);
+    }
+
+    #[test]
+    fn test_iter_every_spread_lazy() {
+        let space = Counted(Ordinal::new(usize::MAX), std::cell::Cell::new(0));
+        let mut every = space.iter_every(1 << 40);
+
+        assert_eq!(space.1.get(), 0);
+        assert_eq!(every.nth(2), Some(2 << 40));
+        assert_eq!(space.1.get(), 3);
+        assert_eq!(every.next(), Some(3 << 40));
+        assert_eq!(space.1.get(), 4);
+
+        space.1.set(0);
+
+        assert_eq!(space.iter_spread(5).last(), Some(usize::MAX / 5 * 4));
+        assert_eq!(space.1.get(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_every_zero_stride() { let _ = Ordinal::new(3).iter_every(0); }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_sample_n_determinism() {
+        let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::unit()]);
+        let sample_with = |n_threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(n_threads)
+                .build()
+                .unwrap()
+                .install(|| space.par_sample_n(5000, 42))
+        };
+
+        let reference = sample_with(1);
+
+        assert_eq!(reference.len(), 5000);
+        assert_eq!(reference, sample_with(2));
+        assert_eq!(reference, sample_with(7));
+        assert_ne!(reference, space.par_sample_n(5000, 43));
+        assert!(reference.iter().all(|v| space[0].contains(v[0]) && space[1].contains(v[1])));
+    }
 }
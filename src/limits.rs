@@ -0,0 +1,52 @@
+use std::{error::Error, fmt};
+
+/// Maximum number of elements materialised by methods such as `FiniteSpace::try_collect_all`.
+pub const MATERIALISE_LIMIT: usize = 1 << 24;
+
+/// Maximum number of elements printed by the alternate (`{:#?}`) `Debug` format of container
+/// spaces, such as `ProductSpace` and `Alphabet`; the plain format prints only a summary.
+pub const DEBUG_LIMIT: usize = 32;
+
+/// `Debug` adaptor listing at most `DEBUG_LIMIT` elements, followed by a count of the rest.
+pub(crate) struct DebugCapped<I>(pub(crate) I);
+
+impl<I> fmt::Debug for DebugCapped<I>
+where I: Clone + ExactSizeIterator, I::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.0.len();
+        let mut list = f.debug_list();
+
+        list.entries(self.0.clone().take(DEBUG_LIMIT));
+
+        if n > DEBUG_LIMIT { list.entry(&format_args!("... {} more", n - DEBUG_LIMIT)); }
+
+        list.finish()
+    }
+}
+
+/// Error returned when an operation would materialise more than `MATERIALISE_LIMIT` elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeLimitError {
+    pub size: usize,
+    pub limit: usize,
+}
+
+impl SizeLimitError {
+    /// Returns `Ok(size)` if `size` is within `MATERIALISE_LIMIT`, and an error otherwise.
+    pub fn check(size: usize) -> Result<usize, SizeLimitError> {
+        if size <= MATERIALISE_LIMIT {
+            Ok(size)
+        } else {
+            Err(SizeLimitError { size, limit: MATERIALISE_LIMIT, })
+        }
+    }
+}
+
+impl fmt::Display for SizeLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "refusing to materialise {} elements (limit is {})", self.size, self.limit)
+    }
+}
+
+impl Error for SizeLimitError {}
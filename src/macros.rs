@@ -7,6 +7,22 @@ macro_rules! clip {
     }};
 }
 
+macro_rules! impl_unbounded_sample {
+    ($type:ty) => {
+        /// Note: there is no uniform distribution over this space, so `sample` always panics and
+        /// `try_sample` always returns `SampleError::Unbounded`.
+        impl Sample for $type {
+            fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> Self::Value {
+                panic!("Cannot sample uniformly from an unbounded space.")
+            }
+
+            fn try_sample<R: Rng + ?Sized>(&self, _: &mut R) -> Result<Self::Value, crate::SampleError> {
+                Err(crate::SampleError::Unbounded)
+            }
+        }
+    };
+}
+
 macro_rules! import_all {
     ($module:ident) => {
         mod $module;
@@ -14,18 +30,203 @@ macro_rules! import_all {
     };
 }
 
+/// Implement `Union` and `Intersection` for spaces that are closed under both operations.
+///
+/// This is intended for spaces with a single possible instance (e.g. `Reals` or `Binary`), for
+/// which the union or intersection with another space of type `$other` is always `self`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate spaces;
+///
+/// use spaces::{Card, Dim, Intersection, Space, Union};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Unit;
+///
+/// impl Space for Unit {
+///     type Value = ();
+///
+///     fn dim(&self) -> Dim { Dim::one() }
+///
+///     fn card(&self) -> Card { Card::Finite(1) }
+/// }
+///
+/// impl_union_intersect!(Unit, Unit);
+///
+/// # fn main() {
+/// assert_eq!(Unit.union(&Unit), Unit);
+/// assert_eq!(Unit.intersect(&Unit), Unit);
+/// # }
+/// ```
+///
+/// The second argument may be any type, which yields a cross-type union and intersection, and
+/// generic spaces are supported by prefixing the invocation with the generic parameters:
+///
+/// ```
+/// #[macro_use]
+/// extern crate spaces;
+///
+/// use spaces::{discrete::Ordinal, Card, Dim, Intersection, Space, Union};
+/// use std::marker::PhantomData;
+///
+/// struct Everything<T>(PhantomData<T>);
+///
+/// impl<T: Clone> Space for Everything<T> {
+///     type Value = T;
+///
+///     fn dim(&self) -> Dim { Dim::one() }
+///
+///     fn card(&self) -> Card { Card::Infinite }
+/// }
+///
+/// impl_union_intersect!(impl<T: Clone> Everything<T>, Ordinal);
+///
+/// # fn main() {
+/// let s: Everything<usize> = Everything(PhantomData).union(&Ordinal::new(5));
+/// # }
+/// ```
+#[macro_export]
 macro_rules! impl_union_intersect {
-    ($type:ty, $build:expr) => {
-        impl Union for $type {
-            fn union(self, _: &$type) -> Self {
+    (impl<$($gen:ident $(: $bound:path)?),*> $type:ty, $other:ty) => {
+        impl<$($gen $(: $bound)?),*> $crate::Union<$other> for $type {
+            fn union(self, _: &$other) -> Self {
                 self
             }
         }
 
-        impl Intersection for $type {
-            fn intersect(self, _: &$type) -> Self {
+        impl<$($gen $(: $bound)?),*> $crate::Intersection<$other> for $type {
+            fn intersect(self, _: &$other) -> Self {
                 self
             }
         }
-    }
+    };
+    ($type:ty, $other:ty) => {
+        impl $crate::Union<$other> for $type {
+            fn union(self, _: &$other) -> Self {
+                self
+            }
+        }
+
+        impl $crate::Intersection<$other> for $type {
+            fn intersect(self, _: &$other) -> Self {
+                self
+            }
+        }
+    };
+}
+
+/// Assert the laws that every space should satisfy, using the functions of the `testing` module.
+///
+/// The laws to check are listed after the space, and default to `sample, bounds, shape, fmt`:
+///
+/// - `sample`: drawn values are contained in the space (`testing::check_samples`);
+/// - `bounds`: the infimum is no greater than the supremum (`testing::check_bounds`);
+/// - `shape`: the shape agrees with the dimensionality (`testing::check_shape`);
+/// - `finite`: enumeration agrees with the cardinality, and indices round-trip
+///   (`testing::check_enumeration`);
+/// - `fmt`: `Debug` and `Display` do not panic (`testing::check_fmt`);
+/// - `serde`: the space survives a round trip through JSON unchanged (`testing::check_round_trip`).
+///   This law requires `serde_json` to be available to the calling crate, and is skipped unless
+///   the `serialize` feature of `spaces` is enabled.
+///
+/// ```
+/// #[macro_use]
+/// extern crate spaces;
+///
+/// use spaces::{discrete::Ordinal, real::Interval, ProductSpace};
+///
+/// # fn main() {
+/// assert_space_laws!(Interval::bounded(-1.0, 1.0));
+/// assert_space_laws!(Ordinal::new(5); sample, bounds, shape, finite, fmt);
+/// assert_space_laws!(ProductSpace::new(vec![Interval::unit(); 3]); shape, fmt);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_space_laws {
+    ($space:expr) => {
+        $crate::assert_space_laws!($space; sample, bounds, shape, fmt)
+    };
+    ($space:expr; $($law:ident),+ $(,)?) => {{
+        let space = $space;
+
+        $($crate::__space_law!($law, &space);)+
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __space_law {
+    (sample, $space:expr) => {
+        $crate::testing::check_samples($space, $crate::testing::LAW_SAMPLES)
+    };
+    (bounds, $space:expr) => { $crate::testing::check_bounds($space) };
+    (shape, $space:expr) => { $crate::testing::check_shape($space) };
+    (finite, $space:expr) => { $crate::testing::check_enumeration($space) };
+    (fmt, $space:expr) => { $crate::testing::check_fmt($space) };
+    (serde, $space:expr) => { $crate::__serde_law!($space) };
+}
+
+/// Assert the laws that every space without values should satisfy, using the `check_empty_*`
+/// functions of the `testing` module.
+///
+/// As with `assert_space_laws!`, the laws to check are listed after the space, and default to
+/// `sample, bounds`:
+///
+/// - `sample`: the cardinality is zero, and `try_sample` returns `SampleError::Empty`
+///   (`testing::check_empty_samples`);
+/// - `bounds`: neither an infimum nor a supremum exists (`testing::check_empty_bounds`);
+/// - `finite`: every means of enumeration yields nothing, and the coverage is zero
+///   (`testing::check_empty_enumeration`).
+///
+/// ```
+/// #[macro_use]
+/// extern crate spaces;
+///
+/// use spaces::{discrete::Ordinal, real::Interval, PairSpace};
+///
+/// # fn main() {
+/// empty_space_laws!(Interval::bounded(1.0, 0.0));
+/// empty_space_laws!(Ordinal::new(0); sample, bounds, finite);
+/// empty_space_laws!(PairSpace::new(Ordinal::new(3), Ordinal::new(0)); sample);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! empty_space_laws {
+    ($space:expr) => {
+        $crate::empty_space_laws!($space; sample, bounds)
+    };
+    ($space:expr; $($law:ident),+ $(,)?) => {{
+        let space = $space;
+
+        $($crate::__empty_space_law!($law, &space);)+
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __empty_space_law {
+    (sample, $space:expr) => { $crate::testing::check_empty_samples($space) };
+    (bounds, $space:expr) => { $crate::testing::check_empty_bounds($space) };
+    (finite, $space:expr) => { $crate::testing::check_empty_enumeration($space) };
+}
+
+#[cfg(feature = "serialize")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __serde_law {
+    ($space:expr) => {
+        $crate::testing::check_round_trip($space, |space| {
+            let json = ::serde_json::to_string(space).expect("space could not be serialised");
+
+            ::serde_json::from_str(&json).expect("space could not be deserialised")
+        })
+    };
+}
+
+#[cfg(not(feature = "serialize"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __serde_law {
+    ($space:expr) => { let _ = $space; };
 }
@@ -0,0 +1,197 @@
+use crate::prelude::*;
+use std::{fmt, marker::PhantomData};
+
+/// Surjection backed by a closure, for one-off mappings that do not warrant a type of their own.
+///
+/// Closures cannot be serialised, so serialising a `MapFn`, or any composition containing one,
+/// fails with an error rather than silently dropping the mapping.
+/// ```
+/// use spaces::{Chain, Equipartition, Interval, MapFn, Surjection};
+///
+/// let log_bins = Chain::new(
+///     Chain::new(Interval::bounded(1.0, 100.0), MapFn::new(f64::ln)),
+///     Equipartition::new(0.0, 100f64.ln(), 2),
+/// );
+///
+/// assert_eq!(log_bins.map_onto(5.0), 0);
+/// assert_eq!(log_bins.map_onto(50.0), 1);
+/// assert_eq!(log_bins.map_onto(1e6), 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct MapFn<F>(F);
+
+impl<F> MapFn<F> {
+    pub fn new(f: F) -> MapFn<F> { MapFn(f) }
+
+    pub fn into_inner(self) -> F { self.0 }
+}
+
+impl<X, Y, F: Fn(X) -> Y> Surjection<X, Y> for MapFn<F> {
+    fn map_onto(&self, from: X) -> Y { (self.0)(from) }
+}
+
+/// Surjection backed by a fallible closure.
+///
+/// Errors are surfaced by `try_map_onto`; `map_onto` panics instead, such that a `TryMapFn` may
+/// still be composed with other surjections where failure is a bug. As with `MapFn`, serialising
+/// a `TryMapFn` fails with an error.
+/// ```
+/// use spaces::{Surjection, TryMapFn};
+///
+/// let parse = TryMapFn::new(|s: &str| s.trim().parse::<usize>());
+///
+/// assert_eq!(parse.try_map_onto(" 4 "), Ok(4));
+/// assert!(parse.try_map_onto("four").is_err());
+/// assert_eq!(parse.map_onto("7"), 7);
+/// ```
+#[derive(Clone, Copy)]
+pub struct TryMapFn<F>(F);
+
+impl<F> TryMapFn<F> {
+    pub fn new(f: F) -> TryMapFn<F> { TryMapFn(f) }
+
+    pub fn into_inner(self) -> F { self.0 }
+
+    /// Map value from domain onto codomain, or return the error of the closure.
+    pub fn try_map_onto<X, Y, E>(&self, from: X) -> Result<Y, E>
+    where F: Fn(X) -> Result<Y, E> {
+        (self.0)(from)
+    }
+}
+
+/// # Panics
+///
+/// Panics if the closure returns an error; see `try_map_onto`.
+impl<X, Y, E: fmt::Display, F: Fn(X) -> Result<Y, E>> Surjection<X, Y> for TryMapFn<F> {
+    fn map_onto(&self, from: X) -> Y {
+        (self.0)(from).unwrap_or_else(|e| panic!("Cannot map value: {}.", e))
+    }
+}
+
+macro_rules! impl_closure_traits {
+    ($($name:ident),+) => {$(
+        impl<F> fmt::Debug for $name<F> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!(stringify!($name), "(<closure>)"))
+            }
+        }
+
+        #[cfg(feature = "serialize")]
+        impl<F> serde::Serialize for $name<F> {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom(concat!(
+                    stringify!($name), " wraps a closure, which cannot be serialized"
+                )))
+            }
+        }
+    )+};
+}
+
+impl_closure_traits!(MapFn, TryMapFn);
+
+/// Composition of two surjections, mapping through `first` and then `second`, where `Y` is the
+/// type of the intermediate values.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Chain<F, G, Y> {
+    first: F,
+    second: G,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    intermediate: PhantomData<fn() -> Y>,
+}
+
+impl<F, G, Y> Chain<F, G, Y> {
+    pub fn new(first: F, second: G) -> Chain<F, G, Y> {
+        Chain { first, second, intermediate: PhantomData, }
+    }
+
+    #[inline]
+    pub fn first(&self) -> &F { &self.first }
+
+    #[inline]
+    pub fn second(&self) -> &G { &self.second }
+}
+
+impl<X, Y, Z, F: Surjection<X, Y>, G: Surjection<Y, Z>> Surjection<X, Z> for Chain<F, G, Y> {
+    fn map_onto(&self, from: X) -> Z { self.second.map_onto(self.first.map_onto(from)) }
+}
+
+impl<F: Clone, G: Clone, Y> Clone for Chain<F, G, Y> {
+    fn clone(&self) -> Chain<F, G, Y> { Chain::new(self.first.clone(), self.second.clone()) }
+}
+
+impl<F: fmt::Debug, G: fmt::Debug, Y> fmt::Debug for Chain<F, G, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Chain").field("first", &self.first).field("second", &self.second).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Equipartition, Interval};
+    use super::*;
+
+    #[test]
+    fn test_pipeline() {
+        let log_bins: Chain<_, _, f64> = Chain::new(
+            Chain::new(Interval::bounded(1.0, 1000.0), MapFn::new(f64::ln)),
+            Equipartition::new(0.0, 1000f64.ln(), 3),
+        );
+
+        assert_eq!(log_bins.map_onto(-5.0), 0);
+        assert_eq!(log_bins.map_onto(1.0), 0);
+        assert_eq!(log_bins.map_onto(9.0), 0);
+        assert_eq!(log_bins.map_onto(11.0), 1);
+        assert_eq!(log_bins.map_onto(99.0), 1);
+        assert_eq!(log_bins.map_onto(101.0), 2);
+        assert_eq!(log_bins.map_onto(1e9), 2);
+
+        let copy = log_bins.clone();
+
+        assert_eq!(copy.map_onto(50.0), log_bins.map_onto(50.0));
+        assert_eq!(copy.first().second().map_onto(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_try_map() {
+        let checked = TryMapFn::new(|x: usize| if x < 5 { Ok(x * 2) } else { Err("too large") });
+        let chain = Chain::new(checked, Ordinal::new(6));
+
+        assert_eq!(checked.try_map_onto(2), Ok(4));
+        assert_eq!(checked.try_map_onto(5), Err("too large"));
+        assert_eq!(chain.map_onto(4), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot map value: too large.")]
+    fn test_try_map_panics() {
+        TryMapFn::new(|_: usize| Err::<usize, _>("too large")).map_onto(0);
+    }
+
+    #[test]
+    fn test_debug() {
+        let chain: Chain<_, _, f64> = Chain::new(MapFn::new(|x: f64| x), Interval::bounded(0, 1));
+
+        assert_eq!(format!("{:?}", MapFn::new(f64::ln)), "MapFn(<closure>)");
+        assert_eq!(format!("{:?}", TryMapFn::new(|x: f64| Ok::<_, ()>(x))), "TryMapFn(<closure>)");
+        assert_eq!(
+            format!("{:?}", chain),
+            format!("Chain {{ first: MapFn(<closure>), second: {:?} }}", Interval::bounded(0, 1))
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serialize() {
+        let spaces: Chain<Interval, _, f64> =
+            Chain::new(Interval::unit(), Equipartition::new(0.0, 1.0, 2));
+        let json = serde_json::to_string(&spaces).unwrap();
+
+        assert!(serde_json::from_str::<Chain<Interval, Equipartition, f64>>(&json).is_ok());
+
+        let closure: Chain<Interval, _, f64> = Chain::new(Interval::unit(), MapFn::new(f64::ln));
+        let err = serde_json::to_string(&closure).unwrap_err();
+
+        assert_eq!(err.to_string(), "MapFn wraps a closure, which cannot be serialized");
+        assert!(serde_json::to_string(&TryMapFn::new(|x: f64| Ok::<_, ()>(x))).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{prelude::*, SampleError};
+use std::{cmp::Ordering, error::Error, fmt};
+
+/// Error type for invalid mixtures.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum MixtureError {
+    /// The mixture has no components.
+    Empty,
+    /// The weight at `index` is not a finite, positive number.
+    InvalidWeight { index: usize, value: f64 },
+}
+
+impl fmt::Display for MixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MixtureError::Empty => write!(f, "a mixture must have at least one component"),
+            MixtureError::InvalidWeight { index, value } =>
+                write!(f, "weight {} at index {} is not a finite, positive number", value, index),
+        }
+    }
+}
+
+impl Error for MixtureError {}
+
+/// Weighted union of spaces, sampled by first drawing a component according to its weight and
+/// then drawing a value from that component.
+///
+/// Membership and bounds are those of the union of the components: a value is contained if any
+/// component contains it, and the bounds are the extreme bounds over all components. The
+/// cardinality is the sum over the components, and so over-counts values shared by overlapping
+/// components. The dimensionality and shape are those of the first component.
+/// ```
+/// use spaces::{BoundedSpace, Interval, Mixture};
+///
+/// let scenarios = Mixture::new(vec![
+///     (0.8, Interval::bounded(-1.0, 1.0)),
+///     (0.2, Interval::bounded(5.0, 10.0)),
+/// ]).unwrap();
+///
+/// assert!(scenarios.contains(0.5) && scenarios.contains(7.0) && !scenarios.contains(2.0));
+/// assert_eq!((scenarios.inf(), scenarios.sup()), (Some(-1.0), Some(10.0)));
+/// assert_eq!(scenarios.components()[1].0, 0.2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "MixtureRepr<S>"))]
+pub struct Mixture<S> {
+    components: Vec<(f64, S)>,
+}
+
+// Deserialized mixtures are checked, and their weights normalised, as by `Mixture::new`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Mixture")]
+struct MixtureRepr<S> {
+    components: Vec<(f64, S)>,
+}
+
+#[cfg(feature = "serialize")]
+impl<S> std::convert::TryFrom<MixtureRepr<S>> for Mixture<S> {
+    type Error = MixtureError;
+
+    fn try_from(repr: MixtureRepr<S>) -> Result<Mixture<S>, MixtureError> {
+        Mixture::new(repr.components)
+    }
+}
+
+impl<S> Mixture<S> {
+    /// Construct a mixture from pairs of weights and components, normalising the weights by
+    /// their sum.
+    ///
+    /// The weights must be finite and positive; components that should never be drawn are
+    /// removed rather than given a weight of zero.
+    pub fn new(components: Vec<(f64, S)>) -> Result<Mixture<S>, MixtureError> {
+        if components.is_empty() { return Err(MixtureError::Empty); }
+
+        if let Some((index, &(value, _))) = components
+            .iter()
+            .enumerate()
+            .find(|(_, (w, _))| !(w.is_finite() && *w > 0.0))
+        {
+            return Err(MixtureError::InvalidWeight { index, value, });
+        }
+
+        // Weights are scaled by the largest first, such that the total cannot overflow.
+        let max = components.iter().fold(0.0f64, |m, (w, _)| m.max(*w));
+        let total: f64 = components.iter().map(|(w, _)| w / max).sum();
+        let components = components.into_iter().map(|(w, s)| (w / max / total, s)).collect();
+
+        Ok(Mixture { components, })
+    }
+
+    /// Construct a mixture over `components` with equal weights.
+    pub fn uniform(components: Vec<S>) -> Result<Mixture<S>, MixtureError> {
+        Mixture::new(components.into_iter().map(|s| (1.0, s)).collect())
+    }
+
+    /// Returns the pairs of normalised weights and components.
+    #[inline]
+    pub fn components(&self) -> &[(f64, S)] { &self.components }
+
+    /// Draw the index of a component according to the weights.
+    pub fn sample_component<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let mut u = rng.gen::<f64>();
+
+        // Fall back onto the last component, to absorb rounding error in the total.
+        self.components
+            .iter()
+            .position(|&(w, _)| { u -= w; u < 0.0 })
+            .unwrap_or(self.components.len() - 1)
+    }
+}
+
+// Returns the most extreme of the bounds in the given direction, or `None` if any is unbounded.
+fn extreme<V: PartialOrd, I>(bounds: I, keep: Ordering) -> Option<V>
+where I: IntoIterator<Item = Option<V>>
+{
+    bounds.into_iter().try_fold(None, |acc: Option<V>, b| b.map(|b| match acc {
+        Some(a) if b.partial_cmp(&a) != Some(keep) => Some(a),
+        _ => Some(b),
+    })).and_then(|b| b)
+}
+
+impl<S: Space> Space for Mixture<S> {
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.components[0].1.dim() }
+
+    fn shape(&self) -> Shape { self.components[0].1.shape() }
+
+    fn card(&self) -> Card { Card::sum(self.components.iter().map(|(_, s)| s.card())) }
+}
+
+impl<S: Sample> Sample for Mixture<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value {
+        self.components[self.sample_component(rng)].1.sample(rng)
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+        self.components[self.sample_component(rng)].1.try_sample(rng)
+    }
+}
+
+impl<S: BoundedSpace> BoundedSpace for Mixture<S>
+where S::Value: PartialOrd + Clone
+{
+    fn inf(&self) -> Option<S::Value> {
+        extreme(self.components.iter().map(|(_, s)| s.inf()), Ordering::Less)
+    }
+
+    fn sup(&self) -> Option<S::Value> {
+        extreme(self.components.iter().map(|(_, s)| s.sup()), Ordering::Greater)
+    }
+
+    fn contains(&self, val: S::Value) -> bool {
+        self.components.iter().any(|(_, s)| s.contains(val.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_frequencies() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = Mixture::new(vec![
+            (4.0, Interval::bounded(0.0, 1.0)),
+            (1.0, Interval::bounded(10.0, 11.0)),
+        ]).unwrap();
+        let n = 10_000;
+        let low = (0..n).filter(|_| space.sample(&mut rng) < 5.0).count();
+
+        assert_eq!(space.components()[0].0, 0.8);
+        assert!((low as f64 / n as f64 - 0.8).abs() < 0.02, "{} of {} were low", low, n);
+
+        let uniform = Mixture::uniform(vec![Ordinal::new(2), Ordinal::new(4)]).unwrap();
+        let first = (0..n).filter(|_| uniform.sample_component(&mut rng) == 0).count();
+
+        assert!((first as f64 / n as f64 - 0.5).abs() < 0.02, "{} of {} were first", first, n);
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let space = Mixture::uniform(vec![
+            Interval::bounded(0.0, 2.0),
+            Interval::bounded(1.0, 3.0),
+            Interval::bounded(5.0, 6.0),
+        ]).unwrap();
+
+        assert!(space.contains(0.5) && space.contains(1.5) && space.contains(2.5));
+        assert!(space.contains(5.0) && !space.contains(4.0) && !space.contains(-0.1));
+        assert_eq!((space.inf(), space.sup()), (Some(0.0), Some(6.0)));
+
+        let discrete = Mixture::uniform(vec![Ordinal::new(3), Ordinal::new(5)]).unwrap();
+
+        assert_eq!(discrete.card(), Card::Finite(8));
+        assert_eq!(discrete.dim(), Dim::one());
+
+        let unbounded = Mixture::uniform(vec![Interval::new(None, Some(0.0)), Interval::unit()]);
+
+        assert_eq!(unbounded.as_ref().map(|s| (s.inf(), s.sup())), Ok((None, Some(1.0))));
+    }
+
+    #[test]
+    fn test_invalid_weights() {
+        let d = Interval::bounded(0.0, 1.0);
+
+        assert_eq!(
+            Mixture::new(vec![(1.0, d), (0.0, d)]),
+            Err(MixtureError::InvalidWeight { index: 1, value: 0.0 })
+        );
+        assert_eq!(
+            Mixture::new(vec![(-0.5, d)]),
+            Err(MixtureError::InvalidWeight { index: 0, value: -0.5 })
+        );
+        assert!(matches!(
+            Mixture::new(vec![(f64::NAN, d)]),
+            Err(MixtureError::InvalidWeight { index: 0, .. })
+        ));
+        assert_eq!(Mixture::<Interval>::uniform(vec![]), Err(MixtureError::Empty));
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(
+            Mixture::uniform(vec![Interval::bounded(0.0, 1.0), Interval::bounded(2.0, 3.0)])
+                .unwrap();
+            sample, bounds, shape
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let d = Interval::bounded(0.0, 1.0);
+        let parse = |w: (f64, f64)| serde_json::from_value::<Mixture<Interval>>(
+            serde_json::json!({ "components": [[w.0, d], [w.1, d]] })
+        );
+
+        assert_eq!(parse((3.0, 1.0)).unwrap().components()[0].0, 0.75);
+        assert!(parse((1.0, 0.0)).is_err());
+        assert!(parse((1.0, -1.0)).is_err());
+        assert!(serde_json::from_str::<Mixture<Interval>>(r#"{"components":[]}"#).is_err());
+
+        let mixture = Mixture::new(vec![(0.1, d), (0.7, d), (0.2, d)]).unwrap();
+        let json = serde_json::to_string(&mixture).unwrap();
+
+        assert_eq!(serde_json::from_str::<Mixture<Interval>>(&json).unwrap(), mixture);
+    }
+}
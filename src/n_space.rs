@@ -1,4 +1,4 @@
-use crate::{Interval, Equipartition, prelude::*};
+use crate::{Interval, Equipartition, ProductCard, SampleError, prelude::*};
 use std::{
     fmt::{self, Display},
     ops::Index,
@@ -6,14 +6,40 @@ use std::{
 };
 
 /// 2-dimensional homogeneous space.
-#[derive(Clone, Copy, Debug)]
+///
+/// The default space pairs two copies of the component default.
+#[derive(Clone, Copy, Debug, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct TwoSpace<D>([D; 2]);
 
 impl<D> TwoSpace<D> {
     pub fn new(ds: [D; 2]) -> Self { TwoSpace(ds) }
 
-    pub fn iter(&self) -> SliceIter<D> { self.0.iter() }
+    pub fn iter(&self) -> SliceIter<'_, D> { self.0.iter() }
+}
+
+impl<D: Space> TwoSpace<D> {
+    /// Returns the cardinality of each component space.
+    pub fn component_cards(&self) -> Vec<Card> { self.iter().map(|d| d.card()).collect() }
+
+    /// Returns the dimensionality of each component space.
+    pub fn component_dims(&self) -> Vec<Dim> { self.iter().map(|d| d.dim()).collect() }
+
+    /// Returns the cardinality of the space, factorised into its components.
+    pub fn product_card(&self) -> ProductCard { ProductCard(self.component_cards()) }
+}
+
+impl<D: BoundedSpace> TwoSpace<D>
+where D::Value: PartialOrd
+{
+    /// Returns true iff both components have a finite infimum.
+    pub fn is_lower_bounded(&self) -> bool { self.iter().all(|d| d.is_lower_bounded()) }
+
+    /// Returns true iff both components have a finite supremum.
+    pub fn is_upper_bounded(&self) -> bool { self.iter().all(|d| d.is_upper_bounded()) }
+
+    /// Returns true iff both components have finite bounds in both directions.
+    pub fn is_bounded(&self) -> bool { self.iter().all(|d| d.is_bounded()) }
 }
 
 impl TwoSpace<Interval> {
@@ -30,7 +56,26 @@ impl<D: Space> Space for TwoSpace<D> {
 
     fn dim(&self) -> Dim { self[0].dim() + self[1].dim() }
 
-    fn card(&self) -> Card { self[0].card() * self[1].card() }
+    fn shape(&self) -> Shape { Shape::vector(self.dim().into()) }
+
+    /// Products with an empty component are empty.
+    fn card(&self) -> Card { self.product_card().total() }
+}
+
+impl<D: Sample> Sample for TwoSpace<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value {
+        if self.card() == Card::Finite(0) {
+            panic!("Cannot sample from space: {}.", SampleError::Empty)
+        }
+
+        [self[0].sample(rng), self[1].sample(rng)]
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        if self.card() == Card::Finite(0) { return Err(SampleError::Empty); }
+
+        Ok([self[0].try_sample(rng)?, self[1].try_sample(rng)?])
+    }
 }
 
 impl<D: Union + Clone> Union for TwoSpace<D> {
@@ -82,6 +127,17 @@ mod tests {
     use crate::discrete::Ordinal;
     use super::*;
 
+    #[test]
+    fn test_bounded() {
+        let space = TwoSpace::new([Interval::bounded(0.0, 1.0), Interval::right_bounded(0.0)]);
+
+        assert_eq!(
+            (space.is_lower_bounded(), space.is_upper_bounded(), space.is_bounded()),
+            (false, true, false)
+        );
+        assert!(TwoSpace::new([Ordinal::new(2), Ordinal::new(3)]).is_bounded());
+    }
+
     #[test]
     fn test_dim() {
         assert_eq!(TwoSpace::new([Ordinal::new(2), Ordinal::new(2)]).dim(), Dim::Finite(2));
@@ -95,6 +151,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_component_cards() {
+        let space = TwoSpace::new([Ordinal::new(3), Ordinal::new(5)]);
+
+        assert_eq!(space.component_cards(), vec![Card::Finite(3), Card::Finite(5)]);
+        assert_eq!(space.component_dims(), vec![Dim::Finite(1); 2]);
+        assert_eq!(space.product_card().total(), space.card());
+        assert_eq!(format!("{:#}", space.product_card()), "3 \u{d7} 5");
+    }
+
     #[test]
     fn test_partitioned() {
         let ps = TwoSpace::new([
@@ -117,4 +183,9 @@ mod tests {
         assert_eq!(ps.map_onto([2.5, 1.5]), [2.5, 1.5]);
         assert_eq!(ps.map_onto([-1.0, 10.0]), [0.0, 2.0]);
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(TwoSpace::new([Ordinal::new(2), Ordinal::new(3)]); shape, fmt);
+    }
 }
@@ -0,0 +1,283 @@
+use crate::{prelude::*, Lerp, SampleError, SizeLimitError};
+use std::fmt;
+
+/// Finite space whose values are unordered categories.
+///
+/// `Nominal` forwards the finite structure of the base space, but deliberately not its order:
+/// it implements neither `BoundedSpace` nor `Surjection` (i.e. clamping) nor `Lerp`, and does not
+/// dereference to the base space, such that categorical values cannot be treated as ordered
+/// integers by accident. Values may only be encoded by their index or one-hot; see `Ordered` for
+/// the ordered counterpart.
+/// ```
+/// use spaces::{discrete::Ordinal, FiniteSpace, Nominal};
+///
+/// let colour = Nominal::new(Ordinal::new(3));
+///
+/// assert!(colour.contains(2));
+/// assert_eq!(colour.to_index(&2), Some(2));
+/// assert_eq!(colour.one_hot(&1), Ok(vec![0.0, 1.0, 0.0]));
+/// ```
+///
+/// Interpolating between categories does not compile:
+/// ```compile_fail
+/// use spaces::{discrete::Ordinal, Lerp, Nominal};
+///
+/// let colour = Nominal::new(Ordinal::new(3));
+///
+/// colour.lerp(&0, &2, 0.5);
+/// ```
+///
+/// Nor does clamping onto the space:
+/// ```compile_fail
+/// use spaces::{discrete::Ordinal, Nominal, Surjection};
+///
+/// let colour = Nominal::new(Ordinal::new(3));
+/// let val: usize = colour.map_onto(7usize);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Nominal<S>(S);
+
+/// Finite space whose values are ordered, in the enumeration order of the base space.
+///
+/// `Ordered` forwards everything of the base space, including its bounds, clamping and
+/// interpolation, and additionally offers the thermometer encoding; see `Nominal` for the
+/// unordered counterpart.
+/// ```
+/// use spaces::{discrete::Ordinal, Lerp, Ordered};
+///
+/// let rating = Ordered::new(Ordinal::new(4));
+///
+/// assert_eq!(rating.thermometer(&2), Ok(vec![1.0, 1.0, 0.0]));
+/// assert_eq!(rating.lerp(&0, &3, 0.5), 2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Ordered<S>(S);
+
+// Returns the one-hot encoding of `val` in `space`.
+fn one_hot<S: FiniteSpace>(space: &S, val: &S::Value) -> Result<Vec<f64>, SizeLimitError>
+where S::Value: PartialEq
+{
+    let n = SizeLimitError::check(space.len())?;
+    let idx = space.to_index(val).expect("Value is not contained in the space.");
+    let mut encoding = vec![0.0; n];
+
+    encoding[idx] = 1.0;
+
+    Ok(encoding)
+}
+
+macro_rules! impl_marker {
+    ($name:ident) => {
+        impl<S: FiniteSpace> $name<S> {
+            pub fn new(space: S) -> $name<S> { $name(space) }
+
+            #[inline]
+            pub fn base(&self) -> &S { &self.0 }
+
+            pub fn into_base(self) -> S { self.0 }
+
+            /// Returns the one-hot encoding of `val`, with one entry per value of the space in
+            /// enumeration order.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `val` is not contained in the space.
+            pub fn one_hot(&self, val: &S::Value) -> Result<Vec<f64>, SizeLimitError>
+            where S::Value: PartialEq {
+                one_hot(&self.0, val)
+            }
+        }
+
+        impl<S: FiniteSpace> Space for $name<S> {
+            type Value = S::Value;
+
+            fn dim(&self) -> Dim { self.0.dim() }
+
+            fn shape(&self) -> Shape { self.0.shape() }
+
+            fn card(&self) -> Card { self.0.card() }
+        }
+
+        impl<S: FiniteSpace + Sample> Sample for $name<S> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value { self.0.sample(rng) }
+
+            fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+                self.0.try_sample(rng)
+            }
+        }
+
+        impl<S: FiniteSpace> FiniteSpace for $name<S> {
+            type Iter = S::Iter;
+
+            fn len(&self) -> usize { self.0.len() }
+
+            fn is_empty(&self) -> bool { self.0.is_empty() }
+
+            fn iter(&self) -> S::Iter { self.0.iter() }
+
+            fn to_index(&self, val: &S::Value) -> Option<usize>
+            where S::Value: PartialEq {
+                self.0.to_index(val)
+            }
+
+            fn from_index(&self, idx: usize) -> Option<S::Value> { self.0.from_index(idx) }
+        }
+
+        impl<S: FiniteSpace> IntoIterator for $name<S> {
+            type Item = S::Value;
+            type IntoIter = S::IntoIter;
+
+            fn into_iter(self) -> S::IntoIter { self.0.into_iter() }
+        }
+
+        impl<S: FiniteSpace + fmt::Display> fmt::Display for $name<S> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.0.fmt(f) }
+        }
+    };
+}
+
+impl_marker!(Nominal);
+impl_marker!(Ordered);
+
+impl<S: FiniteSpace + BoundedSpace> Nominal<S>
+where S::Value: PartialOrd
+{
+    /// Returns true iff `val` is contained in the space.
+    pub fn contains(&self, val: S::Value) -> bool { self.0.contains(val) }
+}
+
+impl<S: FiniteSpace> Ordered<S> {
+    /// Returns the thermometer (cumulative) encoding of `val`: one entry per value of the space
+    /// but the first, in enumeration order, which is one iff `val` lies above that value.
+    ///
+    /// Unlike the one-hot encoding, distances between encodings grow with the distance between
+    /// values; the first value, which `val` can never lie above, is omitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` is not contained in the space.
+    pub fn thermometer(&self, val: &S::Value) -> Result<Vec<f64>, SizeLimitError>
+    where S::Value: PartialEq {
+        let n = SizeLimitError::check(self.0.len())?;
+        let idx = self.0.to_index(val).expect("Value is not contained in the space.");
+
+        Ok((1..n).map(|i| if i <= idx { 1.0 } else { 0.0 }).collect())
+    }
+}
+
+impl<S: FiniteSpace + BoundedSpace> BoundedSpace for Ordered<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.0.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.0.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { self.0.contains(val) }
+}
+
+impl<S: FiniteOrderedSpace> FiniteOrderedSpace for Ordered<S>
+where S::Value: PartialOrd
+{
+    fn range(&self) -> ::std::ops::RangeInclusive<S::Value> { self.0.range() }
+}
+
+impl<X, Y, S: FiniteSpace + Surjection<X, Y>> Surjection<X, Y> for Ordered<S> {
+    fn map_onto(&self, from: X) -> Y { self.0.map_onto(from) }
+}
+
+impl<S: FiniteSpace + Lerp> Lerp for Ordered<S> {
+    fn lerp(&self, a: &S::Value, b: &S::Value, t: f64) -> S::Value { self.0.lerp(a, b, t) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        discrete::{Binary, Ordinal},
+        Equipartition,
+        MATERIALISE_LIMIT,
+    };
+    use super::*;
+
+    // Manhattan distance between two encodings.
+    fn distance(a: &[f64], b: &[f64]) -> f64 { a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum() }
+
+    #[test]
+    fn test_thermometer() {
+        let d = Ordered::new(Ordinal::new(5));
+
+        assert_eq!(d.thermometer(&0), Ok(vec![0.0; 4]));
+        assert_eq!(d.thermometer(&1), Ok(vec![1.0, 0.0, 0.0, 0.0]));
+        assert_eq!(d.thermometer(&3), Ok(vec![1.0, 1.0, 1.0, 0.0]));
+        assert_eq!(d.thermometer(&4), Ok(vec![1.0; 4]));
+
+        for a in d.iter() {
+            let ta = d.thermometer(&a).unwrap();
+
+            assert_eq!(ta.iter().sum::<f64>(), a as f64);
+            assert!(ta.windows(2).all(|w| w[0] >= w[1]), "{:?} is not cumulative", ta);
+
+            for b in d.iter() {
+                let tb = d.thermometer(&b).unwrap();
+
+                assert_eq!(distance(&ta, &tb), (a as f64 - b as f64).abs());
+            }
+        }
+
+        assert_eq!(Ordered::new(Ordinal::new(1)).thermometer(&0), Ok(vec![]));
+        assert_eq!(Ordered::new(Binary).thermometer(&true), Ok(vec![1.0]));
+
+        let d = Ordered::new(Equipartition::new(0.0, 1.0, 3));
+
+        assert_eq!(d.thermometer(&d.from_index(1).unwrap()), Ok(vec![1.0, 0.0]));
+        assert_eq!(
+            Ordered::new(Ordinal::new(usize::MAX)).thermometer(&0),
+            Err(SizeLimitError { size: usize::MAX, limit: MATERIALISE_LIMIT })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_thermometer_not_contained() {
+        let _ = Ordered::new(Ordinal::new(3)).thermometer(&3);
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let nominal = Nominal::new(Ordinal::new(3));
+        let ordered = Ordered::new(Ordinal::new(3));
+
+        for v in nominal.iter() {
+            assert_eq!(nominal.one_hot(&v), Ordinal::new(3).one_hot(v));
+            assert_eq!(ordered.one_hot(&v), Ordinal::new(3).one_hot(v));
+        }
+
+        assert_eq!(Nominal::new(Binary).one_hot(&false), Ok(vec![1.0, 0.0]));
+    }
+
+    #[test]
+    fn test_forwarding() {
+        let nominal = Nominal::new(Ordinal::new(4));
+        let ordered = Ordered::new(Ordinal::new(4));
+
+        assert_eq!(nominal.card(), Card::Finite(4));
+        assert!(nominal.contains(3) && !nominal.contains(4));
+        assert_eq!(nominal.from_index(2), Some(2));
+        assert_eq!(nominal.base(), &Ordinal::new(4));
+        assert_eq!(nominal.to_string(), "[0..3]");
+        assert_eq!(nominal.into_base(), Ordinal::new(4));
+
+        assert_eq!((ordered.inf(), ordered.sup()), (Some(0), Some(3)));
+        assert_eq!(ordered.map_onto(9usize), 3usize);
+        assert_eq!(ordered.range(), 0..=3);
+        assert_eq!(ordered.lerp(&0, &2, 0.5), 1);
+        assert_eq!(ordered.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Nominal::new(Ordinal::new(5)); shape, finite, fmt);
+        assert_space_laws!(Ordered::new(Ordinal::new(5)); sample, bounds, shape, finite, fmt);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    interval::{next_down, next_up},
+    prelude::*,
+    Describe, SampleError, SpaceDescription,
+};
+use std::{
+    error::Error,
+    f64::consts::{E, PI},
+    fmt,
+};
+
+/// Error returned when the parameters of a truncated normal distribution are invalid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalParamsError {
+    pub mean: f64,
+    pub std: f64,
+}
+
+impl fmt::Display for NormalParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "mean {} and std {} are not a finite mean and a finite, positive std",
+            self.mean, self.std
+        )
+    }
+}
+
+impl Error for NormalParamsError {}
+
+/// Draw a value from the standard normal distribution using the Box-Muller transform.
+pub(crate) fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// Draw a value from the standard normal distribution truncated to `[a, b]`, with `0 <= a`.
+///
+/// Uses the exponential rejection sampler of Robert (1995), falling back to a uniform proposal
+/// when the interval is narrow enough for that to be more efficient. Both samplers have an
+/// acceptance rate bounded away from zero, even far into the tail.
+fn standard_tail<R: Rng + ?Sized>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let r = (a * a + 4.0).sqrt();
+    let alpha = (a + r) / 2.0;
+    let threshold = a + 2.0 * E.sqrt() / (a + r) * ((a * a - a * r) / 4.0).exp();
+
+    if b < threshold {
+        loop {
+            let z = rng.gen_range(a..=b);
+
+            if rng.gen::<f64>() <= ((a * a - z * z) / 2.0).exp() { return z; }
+        }
+    }
+
+    loop {
+        let z = a - (1.0 - rng.gen::<f64>()).ln() / alpha;
+
+        if z <= b && rng.gen::<f64>() <= (-(z - alpha) * (z - alpha) / 2.0).exp() { return z; }
+    }
+}
+
+/// Draw a value from the standard normal distribution truncated to `[a, b]`.
+fn standard_truncated<R: Rng + ?Sized>(rng: &mut R, a: f64, b: f64) -> f64 {
+    if a >= 0.0 {
+        standard_tail(rng, a, b)
+    } else if b <= 0.0 {
+        -standard_tail(rng, -b, -a)
+    } else if b - a >= (2.0 * PI).sqrt() {
+        loop {
+            let z = standard_normal(rng);
+
+            if z >= a && z <= b { return z; }
+        }
+    } else {
+        loop {
+            let z = rng.gen_range(a..=b);
+
+            if rng.gen::<f64>() <= (-z * z / 2.0).exp() { return z; }
+        }
+    }
+}
+
+/// Complementary error function, with fractional error below 1.2e-7 everywhere (Press et al.,
+/// 1992).
+pub(crate) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23 + t * (1.000_023_68 + t * (0.374_091_96 + t * (0.096_784_18
+        + t * (-0.186_288_06 + t * (0.278_868_07 + t * (-1.135_203_98 + t * (1.488_515_87
+        + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+/// Returns the logarithm of the mass of the standard normal distribution over `[a, b]`.
+///
+/// The mass is computed from the tail on the side of the interval away from the mean, such that
+/// it retains its relative precision far into either tail.
+pub(crate) fn standard_log_normaliser(a: f64, b: f64) -> f64 {
+    let tail = |x: f64| erfc(x / std::f64::consts::SQRT_2) / 2.0;
+
+    if a >= 0.0 {
+        (tail(a) - tail(b)).ln()
+    } else if b <= 0.0 {
+        (tail(-b) - tail(-a)).ln()
+    } else {
+        (1.0 - tail(-a) - tail(b)).ln()
+    }
+}
+
+/// Draw a value from a normal distribution with the given `mean` and `std`, truncated to
+/// `[lb, ub]`; infinite bounds are permitted.
+pub(crate) fn truncated_normal<R: Rng + ?Sized>(
+    rng: &mut R,
+    lb: f64,
+    ub: f64,
+    mean: f64,
+    std: f64,
+) -> Result<f64, SampleError> {
+    if lb.is_nan() || ub.is_nan() || !mean.is_finite() || !std.is_finite() || std <= 0.0 {
+        return Err(SampleError::NoDistribution);
+    }
+
+    if lb > ub { return Err(SampleError::Empty); }
+
+    if lb == ub { return Ok(lb); }
+
+    let a = (lb - mean) / std;
+    let b = (ub - mean) / std;
+
+    Ok((mean + std * standard_truncated(rng, a, b)).max(lb).min(ub))
+}
+
+/// Space wrapper that samples from a truncated normal distribution over its base space.
+///
+/// All other behaviour is forwarded to the base space, such that this can be used wherever the
+/// base space is expected.
+/// ```
+/// use spaces::{real::Interval, BoundedSpace, Sample, TruncatedNormal};
+///
+/// let space = TruncatedNormal::new(Interval::bounded(-1.0, 1.0), 0.0, 0.5);
+/// let mut rng = rand::thread_rng();
+///
+/// assert!(space.contains(space.sample(&mut rng)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "TruncatedNormalRepr<S>"))]
+pub struct TruncatedNormal<S> {
+    base: S,
+    mean: f64,
+    std: f64,
+}
+
+// Deserialized distributions are checked as by `TruncatedNormal::try_new`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "TruncatedNormal")]
+struct TruncatedNormalRepr<S> {
+    base: S,
+    mean: f64,
+    std: f64,
+}
+
+#[cfg(feature = "serialize")]
+impl<S> std::convert::TryFrom<TruncatedNormalRepr<S>> for TruncatedNormal<S> {
+    type Error = NormalParamsError;
+
+    fn try_from(repr: TruncatedNormalRepr<S>) -> Result<TruncatedNormal<S>, NormalParamsError> {
+        TruncatedNormal::try_new(repr.base, repr.mean, repr.std)
+    }
+}
+
+impl<S> TruncatedNormal<S> {
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid; see `try_new`.
+    pub fn new(base: S, mean: f64, std: f64) -> TruncatedNormal<S> {
+        TruncatedNormal::try_new(base, mean, std)
+            .unwrap_or_else(|e| panic!("Invalid truncated normal: {}.", e))
+    }
+
+    /// Construct the distribution, or return an error unless `mean` is finite and `std` is
+    /// finite and strictly positive.
+    pub fn try_new(base: S, mean: f64, std: f64) -> Result<TruncatedNormal<S>, NormalParamsError> {
+        if !mean.is_finite() || !std.is_finite() || std <= 0.0 {
+            return Err(NormalParamsError { mean, std, });
+        }
+
+        Ok(TruncatedNormal { base, mean, std, })
+    }
+
+    #[inline]
+    pub fn base(&self) -> &S { &self.base }
+
+    #[inline]
+    pub fn mean(&self) -> f64 { self.mean }
+
+    #[inline]
+    pub fn std(&self) -> f64 { self.std }
+}
+
+impl<S: Space> Space for TruncatedNormal<S> {
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.base.dim() }
+
+    fn shape(&self) -> Shape { self.base.shape() }
+
+    fn card(&self) -> Card { self.base.card() }
+}
+
+impl<S: BoundedSpace<Value = f64>> Sample for TruncatedNormal<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.try_sample(rng)
+            .unwrap_or_else(|e| panic!("Cannot sample from truncated normal: {}.", e))
+    }
+
+    /// Bounds excluded from the base space, such as those of open intervals, are tightened to
+    /// the adjacent float inside, so they are never drawn. Draws outside the base space, as for
+    /// empty bases (which have no bounds), fail with `SampleError::Empty`.
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, SampleError> {
+        let lb = match self.base.inf() {
+            Some(lb) if lb.is_finite() && !self.base.contains(lb) => next_up(lb),
+            Some(lb) => lb,
+            None => f64::NEG_INFINITY,
+        };
+        let ub = match self.base.sup() {
+            Some(ub) if ub.is_finite() && !self.base.contains(ub) => next_down(ub),
+            Some(ub) => ub,
+            None => f64::INFINITY,
+        };
+
+        let val = truncated_normal(rng, lb, ub, self.mean, self.std)?;
+
+        if self.base.contains(val) { Ok(val) } else { Err(SampleError::Empty) }
+    }
+}
+
+impl<S: BoundedSpace> BoundedSpace for TruncatedNormal<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.base.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.base.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { self.base.contains(val) }
+}
+
+impl<S: Surjection<X, Y>, X, Y> Surjection<X, Y> for TruncatedNormal<S> {
+    fn map_onto(&self, val: X) -> Y { self.base.map_onto(val) }
+}
+
+impl<S: Describe> Describe for TruncatedNormal<S> {
+    fn describe(&self) -> SpaceDescription { self.base.describe() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interval;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    fn pdf(x: f64) -> f64 { (-x * x / 2.0).exp() / (2.0 * PI).sqrt() }
+
+    fn analytic(lb: f64, ub: f64, mean: f64, std: f64) -> (f64, f64) {
+        let (a, b) = ((lb - mean) / std, (ub - mean) / std);
+        let z = if a >= 0.0 {
+            (erfc(a / 2f64.sqrt()) - erfc(b / 2f64.sqrt())) / 2.0
+        } else {
+            (erfc(-b / 2f64.sqrt()) - erfc(-a / 2f64.sqrt())) / 2.0
+        };
+        let m = (pdf(a) - pdf(b)) / z;
+        let v = 1.0 + (a * pdf(a) - b * pdf(b)) / z - m * m;
+
+        (mean + std * m, std * std * v)
+    }
+
+    fn check(lb: f64, ub: f64, mean: f64, std: f64) {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let n = 50_000;
+        let d = Interval::bounded(lb, ub);
+        let xs: Vec<f64> = (0..n).map(|_| d.sample_normal(&mut rng, mean, std)).collect();
+
+        let emp_mean = xs.iter().sum::<f64>() / n as f64;
+        let emp_var = xs.iter().map(|x| (x - emp_mean) * (x - emp_mean)).sum::<f64>() / n as f64;
+        let (mean, var) = analytic(lb, ub, mean, std);
+
+        assert!(xs.iter().all(|&x| d.contains(x)));
+        assert!(
+            (emp_mean - mean).abs() < 5.0 * (var / n as f64).sqrt(),
+            "{} vs {}",
+            emp_mean,
+            mean
+        );
+        assert!((emp_var - var).abs() < 0.05 * var, "{} vs {}", emp_var, var);
+    }
+
+    #[test]
+    fn test_central() {
+        check(-1.0, 1.0, 0.0, 1.0);
+        check(-3.0, 5.0, 1.0, 2.0);
+        check(0.0, 1.0, 0.25, 0.1);
+    }
+
+    #[test]
+    fn test_one_sided() {
+        check(0.5, 3.0, 0.0, 1.0);
+        check(-3.0, -0.5, 0.0, 1.0);
+        check(1.0, 1.1, 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_far_tail() {
+        check(8.0, 9.0, 0.0, 1.0);
+        check(-9.0, -8.0, 0.0, 1.0);
+        check(4.0, 4.2, 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(truncated_normal(&mut rng, 1.0, 1.0, 0.0, 1.0), Ok(1.0));
+        assert_eq!(truncated_normal(&mut rng, 1.0, 0.0, 0.0, 1.0), Err(SampleError::Empty));
+        assert_eq!(
+            truncated_normal(&mut rng, 0.0, 1.0, 0.0, 0.0),
+            Err(SampleError::NoDistribution)
+        );
+        assert!(truncated_normal(&mut rng, f64::NEG_INFINITY, f64::INFINITY, 0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_open_bounds() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let tight = TruncatedNormal::new(Interval::open(0.0, next_up(next_up(0.0))), 0.0, 1.0);
+        let far = TruncatedNormal::new(Interval::open(0.0, 1.0), 100.0, 1.0);
+        let sliver = TruncatedNormal::new(Interval::open(1.0, next_up(1.0)), 0.0, 1.0);
+
+        assert_eq!(tight.try_sample(&mut rng), Ok(next_up(0.0)));
+        assert!((0..1_000).all(|_| far.contains(far.sample(&mut rng))));
+        assert_eq!(sliver.try_sample(&mut rng), Err(SampleError::Empty));
+        assert_eq!(
+            TruncatedNormal::new(Interval::bounded(1.0, 0.0), 0.0, 1.0).try_sample(&mut rng),
+            Err(SampleError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_wrapper() {
+        let space = TruncatedNormal::new(Interval::left_bounded(2.0), 0.0, 1.0);
+
+        assert_eq!(space.dim(), Dim::one());
+        assert_eq!(space.inf(), Some(2.0));
+        assert_eq!(space.map_onto(-1.0), 2.0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let d = Interval::<f64>::unit();
+
+        assert!(TruncatedNormal::try_new(d, 0.5, 0.1).is_ok());
+        assert_eq!(
+            TruncatedNormal::try_new(d, 0.5, 0.0),
+            Err(NormalParamsError { mean: 0.5, std: 0.0 })
+        );
+        assert!(TruncatedNormal::try_new(d, f64::INFINITY, 1.0).is_err());
+        assert!(TruncatedNormal::try_new(d, 0.0, f64::NAN).is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let space = TruncatedNormal::new(Interval::<f64>::unit(), 0.5, 0.1);
+        let mut json = serde_json::to_value(space).unwrap();
+
+        assert!(serde_json::from_value::<TruncatedNormal<Interval>>(json.clone()).is_ok());
+
+        json["std"] = (-1.0).into();
+
+        assert!(serde_json::from_value::<TruncatedNormal<Interval>>(json).is_err());
+    }
+
+    #[test]
+    fn test_laws() {
+        let left_bounded = TruncatedNormal::new(Interval::left_bounded(2.0), 0.0, 1.0);
+        let unit = TruncatedNormal::new(Interval::<f64>::unit(), 0.5, 0.1);
+
+        assert_space_laws!(left_bounded; sample, bounds, shape);
+        assert_space_laws!(unit; sample, bounds, serde);
+    }
+}
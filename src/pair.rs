@@ -1,8 +1,10 @@
-use crate::{Interval, Equipartition, prelude::*};
+use crate::{Interval, Equipartition, ProductCard, SampleError, prelude::*};
 use std::fmt::{self, Display};
 
 /// 2-dimensional heterogeneous space.
-#[derive(Clone, Copy, Debug)]
+///
+/// The default space pairs the defaults of each component.
+#[derive(Clone, Copy, Debug, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct PairSpace<D1, D2>(pub D1, pub D2);
 
@@ -10,6 +12,36 @@ impl<D1, D2> PairSpace<D1, D2> {
     pub fn new(d1: D1, d2: D2) -> Self { PairSpace(d1, d2) }
 }
 
+impl<D1: Space, D2: Space> PairSpace<D1, D2> {
+    /// Returns the cardinality of each component space.
+    pub fn component_cards(&self) -> Vec<Card> { vec![self.0.card(), self.1.card()] }
+
+    /// Returns the dimensionality of each component space.
+    pub fn component_dims(&self) -> Vec<Dim> { vec![self.0.dim(), self.1.dim()] }
+
+    /// Returns the cardinality of the space, factorised into its components.
+    pub fn product_card(&self) -> ProductCard { ProductCard(self.component_cards()) }
+}
+
+impl<D1: BoundedSpace, D2: BoundedSpace> PairSpace<D1, D2>
+where
+    D1::Value: PartialOrd,
+    D2::Value: PartialOrd,
+{
+    /// Returns true iff both components have a finite infimum.
+    pub fn is_lower_bounded(&self) -> bool {
+        self.0.is_lower_bounded() && self.1.is_lower_bounded()
+    }
+
+    /// Returns true iff both components have a finite supremum.
+    pub fn is_upper_bounded(&self) -> bool {
+        self.0.is_upper_bounded() && self.1.is_upper_bounded()
+    }
+
+    /// Returns true iff both components have finite bounds in both directions.
+    pub fn is_bounded(&self) -> bool { self.0.is_bounded() && self.1.is_bounded() }
+}
+
 impl PairSpace<Interval, Interval> {
     pub fn equipartitioned(self, density: usize) -> PairSpace<Equipartition, Equipartition> {
         PairSpace(
@@ -30,7 +62,26 @@ impl<D1: Space, D2: Space> Space for PairSpace<D1, D2> {
 
     fn dim(&self) -> Dim { self.0.dim() + self.1.dim() }
 
-    fn card(&self) -> Card { self.0.card() * self.1.card() }
+    fn shape(&self) -> Shape { Shape::vector(self.dim().into()) }
+
+    /// Products with an empty component are empty.
+    fn card(&self) -> Card { self.product_card().total() }
+}
+
+impl<D1: Sample, D2: Sample> Sample for PairSpace<D1, D2> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value {
+        if self.card() == Card::Finite(0) {
+            panic!("Cannot sample from space: {}.", SampleError::Empty)
+        }
+
+        (self.0.sample(rng), self.1.sample(rng))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        if self.card() == Card::Finite(0) { return Err(SampleError::Empty); }
+
+        Ok((self.0.try_sample(rng)?, self.1.try_sample(rng)?))
+    }
 }
 
 impl<D1: Union, D2: Union> Union for PairSpace<D1, D2> {
@@ -66,6 +117,17 @@ mod tests {
     use crate::discrete::Ordinal;
     use super::*;
 
+    #[test]
+    fn test_bounded() {
+        let space = PairSpace::new(Ordinal::new(4), Interval::left_bounded(0.0));
+
+        assert_eq!(
+            (space.is_lower_bounded(), space.is_upper_bounded(), space.is_bounded()),
+            (true, false, false)
+        );
+        assert!(PairSpace::new(Ordinal::new(4), Interval::<f64>::unit()).is_bounded());
+    }
+
     #[test]
     fn test_dim() {
         assert_eq!(PairSpace::new(Ordinal::new(2), Ordinal::new(2)).dim(), Dim::Finite(2));
@@ -79,6 +141,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_component_cards() {
+        let inner = PairSpace::new(Ordinal::new(5), Interval::<f64>::unit());
+        let space = PairSpace::new(Ordinal::new(3), inner);
+
+        assert_eq!(space.component_cards(), vec![Card::Finite(3), Card::Infinite]);
+        assert_eq!(space.component_dims(), vec![Dim::Finite(1), Dim::Finite(2)]);
+        assert_eq!(space.product_card().total(), space.card());
+        assert_eq!(format!("{:#}", space.1.product_card()), "5 \u{d7} \u{221e}");
+
+        let huge = PairSpace::new(Ordinal::new(usize::MAX), Ordinal::new(2));
+
+        assert_eq!(huge.product_card().total(), huge.card());
+        assert_eq!(huge.card(), Card::Infinite);
+    }
+
     #[test]
     fn test_partitioned() {
         let ps = PairSpace::new(Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0));
@@ -96,4 +174,9 @@ mod tests {
         assert_eq!(ps.map_onto((2.5, 1.5)), (2.5, 1.5));
         assert_eq!(ps.map_onto((-1.0, 10.0)), (0.0, 2.0));
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(PairSpace::new(Ordinal::new(2), Interval::<f64>::unit()); shape, fmt);
+    }
 }
@@ -0,0 +1,693 @@
+//! Parsing of spaces from strings.
+//!
+//! The grammar accepted by the `FromStr` implementations in this module is given below, where
+//! whitespace is permitted between any two tokens:
+//!
+//! ```text
+//! space    := factor ("x" factor)*
+//! factor   := "binary" | "{0, 1}"
+//!           | "discrete" "(" uint ")" | "[" "0" ".." uint "]"
+//!           | "interval" "(" bound "," bound ")" | "[" bound "," bound "]"
+//!           | "box" "(" bounds "," bounds ")"
+//!           | "[" space ("," space)* "]"
+//!           | "(" space ")"
+//!           | name ("(" number ("," number)* ")")?
+//! bounds   := "[" bound ("," bound)* "]"
+//! bound    := number | ("+" | "-")? ("inf" | "infinity")
+//! ```
+//!
+//! Numbers may be written in scientific notation, e.g. `1e-3` or `2.5E+2`, and the names of
+//! infinity are matched case-insensitively, such that the `Infinity` of JavaScript exports and the
+//! `inf` of Python exports are both accepted.
+//!
+//! The bracketed forms are exactly those produced by the `Display` implementations of `Ordinal`,
+//! `Binary`, `Interval` and `ProductSpace`, such that displayed spaces may be parsed back; the
+//! exception is intervals with open bounds, which the grammar cannot express. Note
+//! that `[0..k]` denotes the `k + 1` values `0, 1, ..., k`, while `discrete(n)` denotes `n` values.
+//!
+//! Here, `name` is any space registered with the `registry` module.
+//!
+//! Products written with `x` are flattened, so `a x b x c` has three components, whereas
+//! `(a x b) x c` has two. Arbitrary (heterogeneous) spaces are parsed into a `SpaceDescription`.
+//! ```
+//! use spaces::{discrete::Ordinal, ScalarKind, SpaceDescription};
+//!
+//! let d: Ordinal = "discrete(6)".parse().unwrap();
+//! let s: SpaceDescription = "discrete(3) x interval(0, 1)".parse().unwrap();
+//!
+//! assert_eq!(d, Ordinal::new(6));
+//! assert_eq!(s, SpaceDescription::Product(vec![
+//!     SpaceDescription::Scalar(ScalarKind::Discrete { n: 3 }),
+//!     SpaceDescription::Scalar(ScalarKind::real(Some(0.0), Some(1.0))),
+//! ]));
+//! ```
+use crate::{
+    discrete::{Binary, Ordinal},
+    registry,
+    Interval, ProductSpace, ScalarKind, SpaceDescription,
+};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Kind of error encountered while parsing a space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input ended while more was expected.
+    UnexpectedEnd,
+    /// A token other than the one described was found.
+    Expected(&'static str),
+    /// The named space is not known to the parser.
+    UnknownSpace(String),
+    /// A registered space rejected its arguments.
+    InvalidArguments(String),
+    /// A number could not be parsed.
+    InvalidNumber,
+    /// The bounds given do not define a valid space.
+    InvalidBounds,
+    /// The input was well-formed, but described a different kind of space.
+    WrongSpace { expected: &'static str },
+    /// The input contained unexpected characters after a complete space.
+    TrailingInput,
+}
+
+/// Error type for spaces that could not be parsed, pointing at the offending byte offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    fn new(offset: usize, kind: ParseErrorKind) -> ParseError { ParseError { offset, kind, } }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseErrorKind::Expected(what) => write!(f, "expected {}", what)?,
+            ParseErrorKind::UnknownSpace(name) => write!(f, "unknown space \"{}\"", name)?,
+            ParseErrorKind::InvalidArguments(name) =>
+                write!(f, "invalid arguments for space \"{}\"", name)?,
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ParseErrorKind::InvalidBounds => write!(f, "invalid bounds")?,
+            ParseErrorKind::WrongSpace { expected } => write!(f, "expected {}", expected)?,
+            ParseErrorKind::TrailingInput => write!(f, "unexpected trailing input")?,
+        }
+
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl Error for ParseError {}
+
+type Result<T> = ::std::result::Result<T, ParseError>;
+
+// Returns true iff `token` names a (signed) infinity, ignoring case.
+pub(crate) fn is_infinity(token: &str) -> bool {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+
+    unsigned.eq_ignore_ascii_case("inf") || unsigned.eq_ignore_ascii_case("infinity")
+}
+
+/// Recursive-descent parser over the grammar documented at the module level.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Parser<'a> { Parser { src, pos: 0, } }
+
+    fn rest(&self) -> &'a str { &self.src[self.pos..] }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error<T>(&self, kind: ParseErrorKind) -> Result<T> {
+        if self.pos == self.src.len() && kind != ParseErrorKind::TrailingInput {
+            Err(ParseError::new(self.pos, ParseErrorKind::UnexpectedEnd))
+        } else {
+            Err(ParseError::new(self.pos, kind))
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &'static str) -> Result<()> {
+        if self.eat(token) { Ok(()) } else { self.error(ParseErrorKind::Expected(token)) }
+    }
+
+    fn ident(&mut self) -> &'a str {
+        self.skip_whitespace();
+
+        let rest = self.rest();
+        let n = rest.find(|c: char| !registry::is_ident_char(c)).unwrap_or(rest.len());
+
+        self.pos += n;
+
+        &rest[..n]
+    }
+
+    /// Consumes an unsigned integer, returning it with the offset at which it started.
+    fn uint(&mut self) -> Result<(usize, usize)> {
+        self.skip_whitespace();
+
+        let start = self.pos;
+        let rest = self.rest();
+        let n = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+
+        if n == 0 { return self.error(ParseErrorKind::Expected("an integer")); }
+
+        self.pos += n;
+
+        rest[..n]
+            .parse()
+            .map(|v| (v, start))
+            .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidNumber))
+    }
+
+    fn starts_bound(&mut self) -> bool {
+        match self.peek() {
+            Some(c) => c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || {
+                let rest = self.rest();
+                let n = rest.find(|c: char| !registry::is_ident_char(c)).unwrap_or(rest.len());
+
+                is_infinity(&rest[..n])
+            },
+            None => false,
+        }
+    }
+
+    /// Consumes a (possibly infinite) bound, returning `None` for infinite values.
+    fn bound(&mut self) -> Result<Option<f64>> {
+        self.skip_whitespace();
+
+        let start = self.pos;
+        let rest = self.rest();
+        let mut n = rest.find(|c: char| !"+-0123456789.eE".contains(c) && !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+
+        // A "." is only part of the number if followed by a digit, so that "[0..5]" is not
+        // lexed as "0." followed by ".5".
+        if let Some(i) = rest[..n].find("..") { n = i; }
+
+        if n == 0 { return self.error(ParseErrorKind::Expected("a number")); }
+
+        self.pos += n;
+
+        match &rest[..n] {
+            token if is_infinity(token) => Ok(None),
+            token => match token.parse::<f64>() {
+                Ok(v) if v.is_finite() => Ok(Some(v)),
+                _ => Err(ParseError::new(start, ParseErrorKind::InvalidNumber)),
+            },
+        }
+    }
+
+    fn bounds(&mut self) -> Result<Vec<Option<f64>>> {
+        self.expect("[")?;
+
+        let mut bounds = vec![self.bound()?];
+
+        while self.eat(",") {
+            bounds.push(self.bound()?);
+        }
+
+        self.expect("]")?;
+
+        Ok(bounds)
+    }
+
+    fn real(&self, start: usize, lb: Option<f64>, ub: Option<f64>) -> Result<ScalarKind> {
+        match (lb, ub) {
+            (Some(lb), Some(ub)) if lb > ub =>
+                Err(ParseError::new(start, ParseErrorKind::InvalidBounds)),
+            _ => Ok(ScalarKind::real(lb, ub)),
+        }
+    }
+
+    fn space(&mut self) -> Result<SpaceDescription> {
+        let first = self.factor()?;
+
+        if self.peek() != Some('x') { return Ok(first); }
+
+        let mut components = vec![first];
+
+        while self.eat("x") {
+            components.push(self.factor()?);
+        }
+
+        Ok(SpaceDescription::Product(components))
+    }
+
+    fn factor(&mut self) -> Result<SpaceDescription> {
+        let start = match self.peek() {
+            Some(_) => self.pos,
+            None => return self.error(ParseErrorKind::UnexpectedEnd),
+        };
+
+        if self.eat("(") {
+            let space = self.space()?;
+
+            self.expect(")")?;
+
+            return Ok(space);
+        }
+
+        if self.eat("{") {
+            self.expect("0")?;
+            self.expect(",")?;
+            self.expect("1")?;
+            self.expect("}")?;
+
+            return Ok(SpaceDescription::Scalar(ScalarKind::Binary));
+        }
+
+        if self.eat("[") {
+            if !self.starts_bound() {
+                let mut components = vec![self.space()?];
+
+                while self.eat(",") {
+                    components.push(self.space()?);
+                }
+
+                self.expect("]")?;
+
+                return Ok(SpaceDescription::Product(components));
+            }
+
+            let lb = self.bound()?;
+
+            if self.eat("..") {
+                let (ub, ub_start) = self.uint()?;
+                let n = ub
+                    .checked_add(1)
+                    .ok_or_else(|| ParseError::new(ub_start, ParseErrorKind::InvalidNumber))?;
+
+                self.expect("]")?;
+
+                return match lb {
+                    Some(0.0) => Ok(SpaceDescription::Scalar(ScalarKind::Discrete { n })),
+                    _ => Err(ParseError::new(start, ParseErrorKind::InvalidBounds)),
+                };
+            }
+
+            self.expect(",")?;
+
+            let ub = self.bound()?;
+
+            self.expect("]")?;
+
+            return self.real(start, lb, ub).map(SpaceDescription::Scalar);
+        }
+
+        match self.ident() {
+            "binary" => Ok(SpaceDescription::Scalar(ScalarKind::Binary)),
+            "discrete" => {
+                self.expect("(")?;
+
+                let (n, _) = self.uint()?;
+
+                self.expect(")")?;
+
+                Ok(SpaceDescription::Scalar(ScalarKind::Discrete { n, }))
+            },
+            "interval" => {
+                self.expect("(")?;
+
+                let lb = self.bound()?;
+
+                self.expect(",")?;
+
+                let ub = self.bound()?;
+
+                self.expect(")")?;
+
+                self.real(start, lb, ub).map(SpaceDescription::Scalar)
+            },
+            "box" => {
+                self.expect("(")?;
+
+                let lbs = self.bounds()?;
+
+                self.expect(",")?;
+
+                self.skip_whitespace();
+
+                let ubs_start = self.pos;
+                let ubs = self.bounds()?;
+
+                self.expect(")")?;
+
+                if lbs.len() != ubs.len() {
+                    return Err(ParseError::new(ubs_start, ParseErrorKind::InvalidBounds));
+                }
+
+                lbs.into_iter()
+                    .zip(ubs)
+                    .map(|(lb, ub)| self.real(start, lb, ub).map(SpaceDescription::Scalar))
+                    .collect::<Result<_>>()
+                    .map(SpaceDescription::Product)
+            },
+            "" => self.error(ParseErrorKind::Expected("a space")),
+            name => match registry::lookup(name) {
+                Some(ctor) => self.registered(start, name, ctor),
+                None => Err(ParseError::new(start, ParseErrorKind::UnknownSpace(name.to_owned()))),
+            },
+        }
+    }
+
+    fn registered(
+        &mut self,
+        start: usize,
+        name: &str,
+        ctor: registry::SpaceCtor,
+    ) -> Result<SpaceDescription> {
+        let mut args = vec![];
+
+        if self.eat("(") {
+            loop {
+                self.skip_whitespace();
+
+                let arg_start = self.pos;
+
+                match self.bound()? {
+                    Some(v) => args.push(v),
+                    None => return Err(ParseError::new(arg_start, ParseErrorKind::InvalidNumber)),
+                }
+
+                if !self.eat(",") { break; }
+            }
+
+            self.expect(")")?;
+        }
+
+        ctor(&args).ok_or_else(|| {
+            ParseError::new(start, ParseErrorKind::InvalidArguments(name.to_owned()))
+        })
+    }
+
+    /// Parses the entire input as a single space.
+    fn parse(src: &'a str) -> Result<SpaceDescription> {
+        let mut parser = Parser::new(src);
+        let space = parser.space()?;
+
+        if parser.peek().is_some() { return parser.error(ParseErrorKind::TrailingInput); }
+
+        Ok(space)
+    }
+}
+
+/// Trait for spaces that can be constructed from a `SpaceDescription`.
+pub trait FromDescription: Sized {
+    /// Human-readable name of the kind of space, used in error messages.
+    const EXPECTED: &'static str;
+
+    /// Construct the space from `description`, if it is of the right kind.
+    fn from_description(description: &SpaceDescription) -> Option<Self>;
+}
+
+impl FromDescription for Binary {
+    const EXPECTED: &'static str = "a binary space";
+
+    fn from_description(description: &SpaceDescription) -> Option<Binary> {
+        match description {
+            SpaceDescription::Scalar(ScalarKind::Binary) => Some(Binary),
+            _ => None,
+        }
+    }
+}
+
+impl FromDescription for Ordinal {
+    const EXPECTED: &'static str = "a discrete space";
+
+    fn from_description(description: &SpaceDescription) -> Option<Ordinal> {
+        match description {
+            SpaceDescription::Scalar(ScalarKind::Discrete { n }) => Some(Ordinal::new(*n)),
+            _ => None,
+        }
+    }
+}
+
+impl FromDescription for Interval<f64> {
+    const EXPECTED: &'static str = "an interval";
+
+    fn from_description(description: &SpaceDescription) -> Option<Interval<f64>> {
+        match description {
+            SpaceDescription::Scalar(ScalarKind::Real { lb, ub, lb_kind, ub_kind }) =>
+                Some(Interval::new(*lb, *ub).with_kinds(*lb_kind, *ub_kind)),
+            _ => None,
+        }
+    }
+}
+
+impl FromDescription for Interval<i64> {
+    const EXPECTED: &'static str = "an integer interval";
+
+    fn from_description(description: &SpaceDescription) -> Option<Interval<i64>> {
+        fn integral(bound: Option<f64>) -> Option<Option<i64>> {
+            match bound {
+                Some(b) if b.fract() != 0.0 || b.abs() > i64::MAX as f64 => None,
+                _ => Some(bound.map(|b| b as i64)),
+            }
+        }
+
+        match description {
+            SpaceDescription::Scalar(ScalarKind::Real { lb, ub, lb_kind, ub_kind }) =>
+                Some(Interval::new(integral(*lb)?, integral(*ub)?).with_kinds(*lb_kind, *ub_kind)),
+            SpaceDescription::Scalar(ScalarKind::Integer { lb, ub, lb_kind, ub_kind, excluded })
+                if excluded.is_empty() =>
+                Some(Interval::new(*lb, *ub).with_kinds(*lb_kind, *ub_kind)),
+            _ => None,
+        }
+    }
+}
+
+impl<D: FromDescription> FromDescription for ProductSpace<D> {
+    const EXPECTED: &'static str = "a product space";
+
+    fn from_description(description: &SpaceDescription) -> Option<ProductSpace<D>> {
+        match description {
+            SpaceDescription::Product(components) => components
+                .iter()
+                .map(D::from_description)
+                .collect::<Option<Vec<D>>>()
+                .map(ProductSpace::new),
+            _ => None,
+        }
+    }
+}
+
+impl FromDescription for SpaceDescription {
+    const EXPECTED: &'static str = "a space";
+
+    fn from_description(description: &SpaceDescription) -> Option<SpaceDescription> {
+        Some(description.clone())
+    }
+}
+
+fn parse_as<S: FromDescription>(src: &str) -> Result<S> {
+    let description = Parser::parse(src)?;
+    let offset = src.len() - src.trim_start().len();
+
+    S::from_description(&description).ok_or_else(|| {
+        ParseError::new(offset, ParseErrorKind::WrongSpace { expected: S::EXPECTED, })
+    })
+}
+
+macro_rules! impl_from_str {
+    (impl<$gen:ident: $bound:path> $type:ty) => {
+        impl<$gen: $bound> FromStr for $type {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self> { parse_as(s) }
+        }
+    };
+    ($type:ty) => {
+        impl FromStr for $type {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self> { parse_as(s) }
+        }
+    };
+}
+
+impl_from_str!(Binary);
+impl_from_str!(Ordinal);
+impl_from_str!(Interval<f64>);
+impl_from_str!(Interval<i64>);
+impl_from_str!(impl<D: FromDescription> ProductSpace<D>);
+impl_from_str!(SpaceDescription);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary() -> SpaceDescription { SpaceDescription::Scalar(ScalarKind::Binary) }
+
+    fn discrete(n: usize) -> SpaceDescription {
+        SpaceDescription::Scalar(ScalarKind::Discrete { n, })
+    }
+
+    fn real(lb: f64, ub: f64) -> SpaceDescription {
+        SpaceDescription::Scalar(ScalarKind::real(Some(lb), Some(ub)))
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!("binary".parse(), Ok(Binary));
+        assert_eq!("discrete(6)".parse(), Ok(Ordinal::new(6)));
+        assert_eq!("interval(-1, 1.5)".parse(), Ok(Interval::bounded(-1.0, 1.5)));
+        assert_eq!("interval(0, inf)".parse(), Ok(Interval::left_bounded(0.0)));
+        assert_eq!("interval(-inf, 2)".parse(), Ok(Interval::right_bounded(2i64)));
+        assert_eq!("interval(1e-3, 2E2)".parse(), Ok(Interval::bounded(0.001, 200.0)));
+    }
+
+    #[test]
+    fn test_number_forms() {
+        assert_eq!("interval(-1.5e+2, .25E-1)".parse(), Ok(Interval::bounded(-150.0, 0.025)));
+        assert_eq!("interval(-Infinity, INF)".parse(), Ok(Interval::<f64>::unbounded()));
+        assert_eq!("interval(0, +infinity)".parse(), Ok(Interval::left_bounded(0.0)));
+        assert_eq!("[-Inf, 3]".parse(), Ok(Interval::right_bounded(3.0)));
+        assert_eq!(
+            " box( [ -Infinity ,0 ],\t[1e0, inf] ) ".parse(),
+            Ok(ProductSpace::new(vec![Interval::right_bounded(1.0), Interval::left_bounded(0.0)]))
+        );
+
+        assert_eq!(
+            "interval(0, infinite)".parse::<Interval>(),
+            Err(ParseError::new(12, ParseErrorKind::InvalidNumber))
+        );
+        assert_eq!(
+            "interval(0, 1e999)".parse::<Interval>(),
+            Err(ParseError::new(12, ParseErrorKind::InvalidNumber))
+        );
+    }
+
+    #[test]
+    fn test_box() {
+        assert_eq!(
+            "box([-1, -1], [1, 1])".parse(),
+            Ok(ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]))
+        );
+        assert_eq!(
+            "box([0, 0], [1])".parse::<SpaceDescription>(),
+            Err(ParseError::new(12, ParseErrorKind::InvalidBounds))
+        );
+    }
+
+    #[test]
+    fn test_products() {
+        assert_eq!(
+            "discrete(3) x interval(0, 1)".parse(),
+            Ok(SpaceDescription::Product(vec![discrete(3), real(0.0, 1.0)]))
+        );
+        assert_eq!(
+            "discrete(2) x discrete(3) x discrete(4)".parse(),
+            Ok(SpaceDescription::Product(vec![discrete(2), discrete(3), discrete(4)]))
+        );
+        assert_eq!(
+            "(discrete(2) x box([0], [1])) x [binary, (discrete(4))]".parse(),
+            Ok(SpaceDescription::Product(vec![
+                SpaceDescription::Product(vec![
+                    discrete(2),
+                    SpaceDescription::Product(vec![real(0.0, 1.0)]),
+                ]),
+                SpaceDescription::Product(vec![binary(), discrete(4)]),
+            ]))
+        );
+        assert_eq!(
+            "discrete(2) x discrete(3)".parse(),
+            Ok(ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]))
+        );
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(" \tdiscrete ( 6 )\n".parse(), Ok(Ordinal::new(6)));
+        assert_eq!(
+            "box([-1,-1],[1,1])".parse(),
+            "  box( [ -1 , -1 ] , [ 1 , 1 ] )  ".parse::<SpaceDescription>()
+        );
+        assert_eq!(
+            "discrete(3)x(binary)".parse(),
+            Ok(SpaceDescription::Product(vec![discrete(3), binary()]))
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        fn err(src: &str) -> ParseError { src.parse::<SpaceDescription>().unwrap_err() }
+
+        assert_eq!(err(""), ParseError::new(0, ParseErrorKind::UnexpectedEnd));
+        assert_eq!(err("discrete(6"), ParseError::new(10, ParseErrorKind::UnexpectedEnd));
+        assert_eq!(err("discrete[6]"), ParseError::new(8, ParseErrorKind::Expected("(")));
+        assert_eq!(err("discrete(-6)"), ParseError::new(9, ParseErrorKind::Expected("an integer")));
+        assert_eq!(
+            err("  discreet(6)"),
+            ParseError::new(2, ParseErrorKind::UnknownSpace("discreet".to_owned()))
+        );
+        assert_eq!(err("interval(0, one)"), ParseError::new(12, ParseErrorKind::InvalidNumber));
+        assert_eq!(err("interval(1, 0)"), ParseError::new(0, ParseErrorKind::InvalidBounds));
+        assert_eq!(err("[1..5]"), ParseError::new(0, ParseErrorKind::InvalidBounds));
+        assert_eq!(
+            err(&format!("[0..{}]", usize::MAX)),
+            ParseError::new(4, ParseErrorKind::InvalidNumber)
+        );
+        assert_eq!(err("binary binary"), ParseError::new(7, ParseErrorKind::TrailingInput));
+        assert_eq!(err("discrete(3) x"), ParseError::new(13, ParseErrorKind::UnexpectedEnd));
+        assert_eq!(err("[discrete(2), ]"), ParseError::new(14, ParseErrorKind::Expected("a space")));
+        assert_eq!(err("[binary, ]"), ParseError::new(9, ParseErrorKind::Expected("a space")));
+
+        assert_eq!(
+            "interval(0, 1)".parse::<Ordinal>(),
+            Err(ParseError::new(0, ParseErrorKind::WrongSpace { expected: "a discrete space" }))
+        );
+        assert_eq!(
+            "interval(0, 0.5)".parse::<Interval<i64>>(),
+            Err(ParseError::new(0, ParseErrorKind::WrongSpace { expected: "an integer interval" }))
+        );
+        assert_eq!(err("discrete(6").to_string(), "unexpected end of input at offset 10");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        fn round_trip<S>(space: S)
+        where S: FromStr<Err = ParseError> + fmt::Display + fmt::Debug + PartialEq {
+            assert_eq!(space.to_string().parse(), Ok(space));
+        }
+
+        round_trip(Binary);
+        round_trip(Ordinal::new(1));
+        round_trip(Ordinal::new(6));
+        round_trip(Interval::bounded(-1.5, 2.25));
+        round_trip(Interval::<f64>::unbounded());
+        round_trip(Interval::left_bounded(3i64));
+        round_trip(Interval::right_bounded(-3i64));
+        round_trip(ProductSpace::new(vec![
+            Interval::bounded(-1.0, 1.0),
+            Interval::left_bounded(0.0),
+        ]));
+        round_trip(ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]));
+        round_trip(ProductSpace::new(vec![Binary; 3]));
+    }
+}
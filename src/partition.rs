@@ -1,11 +1,36 @@
-use crate::{Interval, prelude::*};
-use std::{cmp, fmt, ops::Range};
+use crate::{Interval, SampleError, prelude::*};
+use std::{cmp, fmt, ops::{Range, RangeInclusive}};
+
+/// Partition of a bounded interval of the reals into finitely many contiguous buckets.
+///
+/// Buckets are indexed in increasing order, and values are mapped onto the bucket containing
+/// them, with values outside of the interval clamped onto the first or last bucket.
+pub trait Partition1d: Space<Value = usize> + Surjection<f64, usize> {
+    /// Returns the number of buckets.
+    fn n_partitions(&self) -> usize;
+
+    /// Returns the `n_partitions() + 1` edges of the buckets, in increasing order.
+    fn edges(&self) -> Vec<f64>;
+
+    /// Returns the centre of each bucket, in order.
+    fn centres(&self) -> Vec<f64>;
+
+    /// Returns the bounds `(lo, hi)` of the bucket at `idx`, if any.
+    fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)>;
+
+    /// Returns the index of the bucket containing `val`.
+    fn to_partition(&self, val: f64) -> usize { self.map_onto(val) }
+}
 
 /// Finite, uniformly partitioned interval.
+///
+/// Note: `Default` is deliberately not implemented; there is no canonical choice of bounds or
+/// resolution, and a silently chosen grid would skew any discretised learner built on it.
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Equipartition {
-    lb: f64, ub: f64,
+    pub(crate) lb: f64,
+    pub(crate) ub: f64,
     n_partitions: usize,
 }
 
@@ -43,12 +68,44 @@ impl Equipartition {
             .collect()
     }
 
-    pub fn edges(&self) -> Vec<f64> {
-        let w = self.partition_width();
+    pub fn edges(&self) -> Vec<f64> { (0..=self.n_partitions).map(|i| self.edge(i)).collect() }
 
-        (0..=self.n_partitions)
-            .map(|i| self.lb + w * (i as f64))
-            .collect()
+    // The outer edges are exact, and edges shared with a refinement coincide exactly, since
+    // `(i * k) / (n * k)` and `i / n` round to the same float.
+    fn edge(&self, i: usize) -> f64 {
+        if i == self.n_partitions {
+            self.ub
+        } else {
+            self.lb + (self.ub - self.lb) * (i as f64 / self.n_partitions as f64)
+        }
+    }
+
+    /// Returns the bounds `(lo, hi)` of the partition at `idx`, if any.
+    pub fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)> {
+        if idx < self.n_partitions { Some((self.edge(idx), self.edge(idx + 1))) } else { None }
+    }
+
+    /// Iterate over the bounds `(lo, hi)` of each partition, in order.
+    ///
+    /// Adjacent partitions share their edges exactly, and together tile `[lb, ub]`.
+    pub fn partitions(&self) -> impl Iterator<Item = (f64, f64)> {
+        let d = *self;
+
+        (0..self.n_partitions).map(move |i| (d.edge(i), d.edge(i + 1)))
+    }
+
+    /// Returns a finer partition of the same interval, with `factor` times as many partitions.
+    ///
+    /// Each partition of `self` is split into `factor` partitions of the result, such that the
+    /// index of a value in `self` is its index in the result divided by `factor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero, or the number of partitions overflows.
+    pub fn refine(&self, factor: usize) -> Equipartition {
+        let n = self.n_partitions.checked_mul(factor).expect("Refined partition is too large.");
+
+        Equipartition::new(self.lb, self.ub, n)
     }
 
     pub fn to_partition(&self, val: f64) -> usize {
@@ -57,9 +114,16 @@ impl Equipartition {
         let diff = clipped - self.lb;
         let range = self.ub - self.lb;
 
-        let i = ((self.n_partitions as f64) * diff / range).floor() as usize;
+        let mut i = ((self.n_partitions as f64) * diff / range).floor() as usize;
+
+        if i >= self.n_partitions { i = self.n_partitions - 1; }
+
+        // The estimate may be off by one ulp at an edge, so settle it against the edges
+        // themselves; this keeps indices consistent with `bucket_bounds` and `refine`.
+        while i > 0 && clipped < self.edge(i) { i -= 1; }
+        while i + 1 < self.n_partitions && clipped >= self.edge(i + 1) { i += 1; }
 
-        if i >= self.n_partitions { self.n_partitions - 1 } else { i }
+        i
     }
 }
 
@@ -71,6 +135,14 @@ impl Space for Equipartition {
     fn card(&self) -> Card { Card::Finite(self.n_partitions) }
 }
 
+impl Sample for Equipartition {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize { rng.gen_range(0..self.n_partitions) }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, SampleError> {
+        if self.n_partitions == 0 { Err(SampleError::Empty) } else { Ok(self.sample(rng)) }
+    }
+}
+
 impl BoundedSpace for Equipartition {
     fn inf(&self) -> Option<usize> { Some(0) }
 
@@ -83,7 +155,19 @@ impl FiniteSpace for Equipartition {
     type Iter = Self::IntoIter;
 
     fn iter(&self) -> Self::Iter {
-        self.clone().into_iter()
+        self.into_iter()
+    }
+
+    fn len(&self) -> usize { self.n_partitions }
+
+    fn is_empty(&self) -> bool { false }
+
+    fn to_index(&self, val: &usize) -> Option<usize> {
+        if *val < self.n_partitions { Some(*val) } else { None }
+    }
+
+    fn from_index(&self, idx: usize) -> Option<usize> {
+        if idx < self.n_partitions { Some(idx) } else { None }
     }
 }
 
@@ -92,12 +176,14 @@ impl IntoIterator for Equipartition {
     type IntoIter = std::ops::Range<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.range()
+        0..self.n_partitions
     }
 }
 
 impl FiniteOrderedSpace for Equipartition {
-    fn range(&self) -> Range<Self::Value> { 0..self.n_partitions }
+    fn range(&self) -> RangeInclusive<Self::Value> {
+        self.n_partitions.checked_sub(1).map_or(RangeInclusive::new(1, 0), |last| 0..=last)
+    }
 }
 
 impl Surjection<f64, usize> for Equipartition {
@@ -113,13 +199,201 @@ impl cmp::PartialEq for Equipartition {
 impl fmt::Display for Equipartition {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.n_partitions {
-            d if d == 1 => write!(f, "{{{} = x0, x1 = {}}}", self.lb, self.ub),
-            d if d == 2 => write!(f, "{{{} = x0, x1, x2 = {}}}", self.lb, self.ub),
+            1 => write!(f, "{{{} = x0, x1 = {}}}", self.lb, self.ub),
+            2 => write!(f, "{{{} = x0, x1, x2 = {}}}", self.lb, self.ub),
             d => write!(f, "{{{} = x0, x1, ..., x{} = {}}}", self.lb, d, self.ub),
         }
     }
 }
 
+impl Partition1d for Equipartition {
+    fn n_partitions(&self) -> usize { self.n_partitions }
+
+    fn edges(&self) -> Vec<f64> { Equipartition::edges(self) }
+
+    fn centres(&self) -> Vec<f64> { Equipartition::centres(self) }
+
+    fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)> {
+        Equipartition::bucket_bounds(self, idx)
+    }
+
+    fn to_partition(&self, val: f64) -> usize { Equipartition::to_partition(self, val) }
+}
+
+/// Finite interval of the positive reals, partitioned uniformly on a log scale.
+///
+/// The edges of the partitions form a geometric sequence from `lb` to `ub`, such that each
+/// partition spans the same ratio `(ub / lb)^(1 / n_partitions)`, and the centre of each is the
+/// geometric mean of its edges. This is the natural discretisation for quantities that span
+/// several orders of magnitude, such as timeouts or prices. As with `Equipartition`, values
+/// outside of the interval (including all non-positive values) are clamped onto the first or
+/// last partition.
+/// ```
+/// use spaces::{LogPartition, Surjection};
+///
+/// let d = LogPartition::new(1.0, 1000.0, 3);
+///
+/// assert_eq!(d.map_onto(5.0), 0);
+/// assert_eq!(d.map_onto(50.0), 1);
+/// assert_eq!(d.map_onto(500.0), 2);
+/// assert_eq!(d.map_onto(-1.0), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct LogPartition {
+    lb: f64,
+    ub: f64,
+    n_partitions: usize,
+}
+
+impl LogPartition {
+    /// # Panics
+    ///
+    /// Panics if `lb` is not positive, `ub` is not greater than `lb`, or `n_partitions` is zero.
+    pub fn new(lb: f64, ub: f64, n_partitions: usize) -> LogPartition {
+        if n_partitions == 0 {
+            panic!("A partition must have a number partitions of 1 or greater.")
+        }
+
+        if !(lb > 0.0 && ub > lb && ub.is_finite()) {
+            panic!("A log partition must have finite bounds with 0 < lb < ub.")
+        }
+
+        LogPartition { lb, ub, n_partitions, }
+    }
+
+    #[inline]
+    pub fn lb(&self) -> f64 { self.lb }
+
+    #[inline]
+    pub fn ub(&self) -> f64 { self.ub }
+
+    #[inline]
+    pub fn n_partitions(&self) -> usize { self.n_partitions }
+
+    /// Returns the ratio between the upper and lower edge of every partition.
+    #[inline]
+    pub fn partition_ratio(&self) -> f64 {
+        (self.ub / self.lb).powf(1.0 / self.n_partitions as f64)
+    }
+
+    pub fn centres(&self) -> Vec<f64> {
+        self.partitions().map(|(lo, hi)| (lo * hi).sqrt()).collect()
+    }
+
+    pub fn edges(&self) -> Vec<f64> { (0..=self.n_partitions).map(|i| self.edge(i)).collect() }
+
+    // As for `Equipartition`, the outer edges are exact.
+    fn edge(&self, i: usize) -> f64 {
+        match i {
+            0 => self.lb,
+            i if i == self.n_partitions => self.ub,
+            i => self.lb * (self.ub / self.lb).powf(i as f64 / self.n_partitions as f64),
+        }
+    }
+
+    /// Returns the bounds `(lo, hi)` of the partition at `idx`, if any.
+    pub fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)> {
+        if idx < self.n_partitions { Some((self.edge(idx), self.edge(idx + 1))) } else { None }
+    }
+
+    /// Iterate over the bounds `(lo, hi)` of each partition, in order.
+    pub fn partitions(&self) -> impl Iterator<Item = (f64, f64)> {
+        let d = *self;
+
+        (0..self.n_partitions).map(move |i| (d.edge(i), d.edge(i + 1)))
+    }
+
+    pub fn to_partition(&self, val: f64) -> usize {
+        // Also catches NaN, along with all non-positive values.
+        if val.is_nan() || val <= self.lb { return 0; }
+
+        let i = ((self.n_partitions as f64) * (val / self.lb).ln() / (self.ub / self.lb).ln())
+            .floor() as usize;
+
+        cmp::min(i, self.n_partitions - 1)
+    }
+}
+
+impl Space for LogPartition {
+    type Value = usize;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    fn card(&self) -> Card { Card::Finite(self.n_partitions) }
+}
+
+impl Sample for LogPartition {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize { rng.gen_range(0..self.n_partitions) }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, SampleError> {
+        Ok(self.sample(rng))
+    }
+}
+
+impl BoundedSpace for LogPartition {
+    fn inf(&self) -> Option<usize> { Some(0) }
+
+    fn sup(&self) -> Option<usize> { Some(self.n_partitions - 1) }
+
+    fn contains(&self, val: usize) -> bool { val < self.n_partitions }
+}
+
+impl FiniteSpace for LogPartition {
+    type Iter = Self::IntoIter;
+
+    fn iter(&self) -> Self::Iter { self.into_iter() }
+
+    fn len(&self) -> usize { self.n_partitions }
+
+    fn is_empty(&self) -> bool { false }
+
+    fn to_index(&self, val: &usize) -> Option<usize> {
+        if *val < self.n_partitions { Some(*val) } else { None }
+    }
+
+    fn from_index(&self, idx: usize) -> Option<usize> {
+        if idx < self.n_partitions { Some(idx) } else { None }
+    }
+}
+
+impl IntoIterator for LogPartition {
+    type Item = usize;
+    type IntoIter = Range<usize>;
+
+    fn into_iter(self) -> Self::IntoIter { 0..self.n_partitions }
+}
+
+impl FiniteOrderedSpace for LogPartition {
+    fn range(&self) -> RangeInclusive<usize> {
+        self.n_partitions.checked_sub(1).map_or(RangeInclusive::new(1, 0), |last| 0..=last)
+    }
+}
+
+impl Surjection<f64, usize> for LogPartition {
+    fn map_onto(&self, val: f64) -> usize { self.to_partition(val) }
+}
+
+impl fmt::Display for LogPartition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "log{{{} = x0, ..., x{} = {}}}", self.lb, self.n_partitions, self.ub)
+    }
+}
+
+impl Partition1d for LogPartition {
+    fn n_partitions(&self) -> usize { self.n_partitions }
+
+    fn edges(&self) -> Vec<f64> { LogPartition::edges(self) }
+
+    fn centres(&self) -> Vec<f64> { LogPartition::centres(self) }
+
+    fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)> {
+        LogPartition::bucket_bounds(self, idx)
+    }
+
+    fn to_partition(&self, val: f64) -> usize { LogPartition::to_partition(self, val) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,6 +438,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_partitions_tile() {
+        let d = Equipartition::new(-0.3, 0.7, 7);
+        let parts: Vec<(f64, f64)> = d.partitions().collect();
+
+        assert_eq!(parts.len(), 7);
+        assert_eq!(parts[0].0, -0.3);
+        assert_eq!(parts[6].1, 0.7);
+        assert!(parts.windows(2).all(|w| w[0].1 == w[1].0 && w[0].0 < w[0].1));
+        assert_eq!(d.bucket_bounds(3), Some(parts[3]));
+        assert_eq!(d.bucket_bounds(7), None);
+        assert_eq!(d.edges().len(), 8);
+    }
+
+    #[test]
+    fn test_refine() {
+        let mut rng = ::rand::thread_rng();
+        let d = Equipartition::new(-0.3, 0.7, 7);
+        let r = d.refine(3);
+
+        assert_eq!(r.n_partitions(), 21);
+        assert_eq!(r.partitions().next().unwrap().0, -0.3);
+        assert_eq!(r.partitions().last().unwrap().1, 0.7);
+
+        for (i, &(lo, hi)) in d.partitions().collect::<Vec<_>>().iter().enumerate() {
+            assert_eq!(r.bucket_bounds(3 * i).unwrap().0, lo);
+            assert_eq!(r.bucket_bounds(3 * i + 2).unwrap().1, hi);
+        }
+
+        for _ in 0..1000 {
+            let x = rng.gen_range(-0.5..0.9);
+
+            assert_eq!(r.to_partition(x) / 3, d.to_partition(x));
+        }
+    }
+
+    #[test]
+    fn test_to_partition_edges() {
+        let mut rng = ::rand::thread_rng();
+
+        for _ in 0..1000 {
+            let lb = rng.gen_range(-20.0..20.0);
+            let d = Equipartition::new(lb, lb + rng.gen_range(0.1..20.0), rng.gen_range(1..50));
+            let r = d.refine(rng.gen_range(1..20));
+
+            for i in 0..d.n_partitions() {
+                assert_eq!(d.to_partition(d.edge(i)), i);
+                assert_eq!(r.to_partition(d.edge(i)), i * r.n_partitions() / d.n_partitions());
+            }
+
+            for i in 0..r.n_partitions() {
+                assert_eq!(r.to_partition(r.edge(i)), i);
+            }
+        }
+    }
+
     #[test]
     fn test_to_partition() {
         let d = Equipartition::new(0.0, 5.0, 6);
@@ -198,6 +528,16 @@ mod tests {
         check(-5.0, 0.0, 5);
     }
 
+    #[test]
+    fn test_len() {
+        for &n in &[1, 5, 10] {
+            let d = Equipartition::new(0.0, 1.0, n);
+
+            assert_eq!(d.len(), n);
+            assert!(!d.is_empty());
+        }
+    }
+
     #[test]
     fn test_bounds() {
         fn check(lb: f64, ub: f64, n_partitions: usize) {
@@ -221,7 +561,7 @@ mod tests {
         fn check(lb: f64, ub: f64, n_partitions: usize) {
             let d = Equipartition::new(lb, ub, n_partitions);
 
-            assert_eq!(d.range(), 0..n_partitions);
+            assert_eq!(d.range(), 0..=n_partitions - 1);
         }
 
         check(0.0, 5.0, 5);
@@ -243,6 +583,79 @@ mod tests {
         assert_eq!(d.map_onto(6.0), 5);
     }
 
+    #[test]
+    fn test_log_edges_geometric() {
+        let d = LogPartition::new(0.01, 100.0, 8);
+        let edges = d.edges();
+        let ratio = d.partition_ratio();
+
+        assert_eq!(edges.len(), 9);
+        assert_eq!(edges[0], 0.01);
+        assert_eq!(edges[8], 100.0);
+        assert!((ratio - 10f64.sqrt()).abs() < 1e-12);
+        assert!(edges.windows(2).all(|w| (w[1] / w[0] - ratio).abs() < 1e-9));
+        assert_eq!(d.bucket_bounds(2), Some((edges[2], edges[3])));
+        assert_eq!(d.bucket_bounds(8), None);
+    }
+
+    #[test]
+    fn test_log_centres() {
+        let d = LogPartition::new(1.0, 10_000.0, 4);
+        let centres = d.centres();
+
+        for (i, (c, (lo, hi))) in centres.iter().zip(d.partitions()).enumerate() {
+            assert!((c - (lo * hi).sqrt()).abs() < 1e-9);
+            assert!(*c < (lo + hi) / 2.0);
+            assert_eq!(d.to_partition(*c), i);
+        }
+
+        assert!((centres[0] - 10f64.sqrt()).abs() < 1e-9);
+        assert!((centres[3] - 1000.0 * 10f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_log_to_partition() {
+        let d = LogPartition::new(1.0, 1000.0, 3);
+
+        assert_eq!(d.map_onto(f64::NAN), 0);
+        assert_eq!(d.map_onto(-5.0), 0);
+        assert_eq!(d.map_onto(0.0), 0);
+        assert_eq!(d.map_onto(1.0), 0);
+        assert_eq!(d.map_onto(9.9), 0);
+        assert_eq!(d.map_onto(10.1), 1);
+        assert_eq!(d.map_onto(999.0), 2);
+        assert_eq!(d.map_onto(1000.0), 2);
+        assert_eq!(d.map_onto(1e9), 2);
+        assert_eq!(d.card(), Card::Finite(3));
+        assert_eq!(d.range(), 0..=2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_log_non_positive() {
+        LogPartition::new(0.0, 1.0, 4);
+    }
+
+    #[test]
+    fn test_partition1d_generic() {
+        fn occupancy<P: Partition1d>(p: &P, xs: &[f64]) -> Vec<usize> {
+            let mut counts = vec![0; p.n_partitions()];
+
+            for &x in xs { counts[p.to_partition(x)] += 1; }
+
+            counts
+        }
+
+        let xs = [1.5, 15.0, 150.0, 1500.0];
+
+        assert_eq!(occupancy(&Equipartition::new(1.0, 10_000.0, 4), &xs), vec![4, 0, 0, 0]);
+        assert_eq!(occupancy(&LogPartition::new(1.0, 10_000.0, 4), &xs), vec![1, 1, 1, 1]);
+        assert_eq!(
+            Partition1d::centres(&Equipartition::new(0.0, 5.0, 5)),
+            vec![0.5, 1.5, 2.5, 3.5, 4.5]
+        );
+    }
+
     #[cfg(feature = "serialize")]
     #[test]
     fn test_serialisation() {
@@ -271,4 +684,17 @@ mod tests {
         check(-5.0, 5.0, 10);
         check(-5.0, 0.0, 5);
     }
+
+    #[test]
+    fn test_laws() {
+        for &(lb, ub, n) in &[(0.0, 5.0, 5), (-1.0, 1.0, 1)] {
+            let d = Equipartition::new(lb, ub, n);
+
+            assert_space_laws!(d; sample, bounds, shape, finite, fmt, serde);
+        }
+
+        let d = LogPartition::new(1e-3, 1e3, 6);
+
+        assert_space_laws!(d; sample, bounds, shape, finite, fmt, serde);
+    }
 }
@@ -0,0 +1,736 @@
+//! Targeted overrides of the parameters of a space description, e.g. to change a single bound of
+//! a nested space across the configurations of a sweep.
+//!
+//! A patch sets one field of the node at a path into a `SpaceDescription`. Paths follow the
+//! leaf paths of `FlattenIndex`: the dotted positions of components in the tree of products,
+//! where `[i]` may be written for `.i`, followed by the name of a field of the scalar there
+//! (`lb`, `ub`, `n` or `step`). A path without a field replaces the whole node, and so takes a
+//! description as its value. Patches are validated as they are applied, and `diff` produces the
+//! patches that transform one description into another, e.g. for logging the overrides of an
+//! experiment.
+//!
+//! ```
+//! use spaces::{
+//!     discrete::Ordinal, patch::{apply_patch, diff, Patch, PatchValue},
+//!     Describe, Interval, PairSpace, ProductSpace,
+//! };
+//!
+//! let torques = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 3]);
+//! let base = PairSpace::new(Ordinal::new(4), torques).describe();
+//! let mut desc = base.clone();
+//!
+//! apply_patch(&mut desc, "1[2].ub", PatchValue::Number(2.5)).unwrap();
+//!
+//! assert_eq!(diff(&base, &desc), vec![Patch::new("1.2.ub", 2.5)]);
+//! assert!(apply_patch(&mut desc, "1.2.ub", PatchValue::Number(-2.0)).is_err());
+//! ```
+//!
+//! Patches parse from strings of the form `path=value`, e.g. from environment variables or the
+//! command line, where values are `null`, booleans, numbers or bracketed arrays thereof:
+//! ```
+//! use spaces::patch::{Patch, PatchValue};
+//!
+//! let patch: Patch = "0.ub=null".parse().unwrap();
+//!
+//! assert_eq!(patch, Patch::new("0.ub", PatchValue::Null));
+//! assert_eq!("lb=[0, 1.5]".parse::<Patch>().unwrap().value, vec![0.0, 1.5].into());
+//! ```
+use crate::{BoundKind, ScalarKind, SpaceDescription};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Value of a patch.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(untagged))]
+#[non_exhaustive]
+pub enum PatchValue {
+    /// Removes an optional bound.
+    Null,
+    /// No field of a description is currently boolean, so boolean values are always rejected
+    /// on application; they are accepted for the sake of configuration formats.
+    Bool(bool),
+    Number(f64),
+    /// Sets the field of each component of a product, in order.
+    Array(Vec<PatchValue>),
+    /// Replaces a whole node.
+    Space(SpaceDescription),
+}
+
+impl From<f64> for PatchValue {
+    fn from(x: f64) -> PatchValue { PatchValue::Number(x) }
+}
+
+impl From<bool> for PatchValue {
+    fn from(b: bool) -> PatchValue { PatchValue::Bool(b) }
+}
+
+impl<T: Into<PatchValue>> From<Option<T>> for PatchValue {
+    fn from(x: Option<T>) -> PatchValue { x.map_or(PatchValue::Null, Into::into) }
+}
+
+impl<T: Into<PatchValue>> From<Vec<T>> for PatchValue {
+    fn from(xs: Vec<T>) -> PatchValue {
+        PatchValue::Array(xs.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<SpaceDescription> for PatchValue {
+    fn from(desc: SpaceDescription) -> PatchValue { PatchValue::Space(desc) }
+}
+
+impl PatchValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            PatchValue::Null => "null",
+            PatchValue::Bool(_) => "a boolean",
+            PatchValue::Number(_) => "a number",
+            PatchValue::Array(_) => "an array",
+            PatchValue::Space(_) => "a space",
+        }
+    }
+}
+
+// Splits `s` at the top-level commas, outside of any brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let (mut depth, mut start) = (0i32, 0);
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parses `null`, `true`, `false`, numbers (including infinities) and bracketed, comma-separated
+/// arrays of these; descriptions cannot be parsed as values.
+impl FromStr for PatchValue {
+    type Err = PatchError;
+
+    fn from_str(s: &str) -> Result<PatchValue, PatchError> {
+        let s = s.trim();
+        let invalid = || PatchError::new("", PatchErrorKind::InvalidValue(s.to_owned()));
+
+        match s {
+            "null" => Ok(PatchValue::Null),
+            "true" => Ok(PatchValue::Bool(true)),
+            "false" => Ok(PatchValue::Bool(false)),
+            _ if s.starts_with('[') && s.ends_with(']') => {
+                let inner = s[1..s.len() - 1].trim();
+
+                if inner.is_empty() { return Ok(PatchValue::Array(vec![])); }
+
+                split_top_level(inner)
+                    .into_iter()
+                    .map(|part| part.parse().map_err(|_| invalid()))
+                    .collect::<Result<_, _>>()
+                    .map(PatchValue::Array)
+            },
+            _ => s.parse::<f64>().map(PatchValue::Number).map_err(|_| invalid()),
+        }
+    }
+}
+
+/// Override of the node or field at `path`; see the module documentation for the syntax.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Patch {
+    pub path: String,
+    pub value: PatchValue,
+}
+
+impl Patch {
+    pub fn new<P: Into<String>, V: Into<PatchValue>>(path: P, value: V) -> Patch {
+        Patch { path: path.into(), value: value.into(), }
+    }
+
+    /// Apply the patch to `desc`; see `apply_patch`.
+    pub fn apply(&self, desc: &mut SpaceDescription) -> Result<(), PatchError> {
+        apply_patch(desc, &self.path, self.value.clone())
+    }
+}
+
+/// Parses `path=value`, with the value as for `PatchValue`.
+impl FromStr for Patch {
+    type Err = PatchError;
+
+    fn from_str(s: &str) -> Result<Patch, PatchError> {
+        let (path, value) = s.split_once('=').ok_or_else(|| {
+            PatchError::new(s.trim(), PatchErrorKind::InvalidValue(String::new()))
+        })?;
+        let path = path.trim();
+        let value = value
+            .parse::<PatchValue>()
+            .map_err(|e| PatchError { path: path.to_owned(), ..e })?;
+
+        Ok(Patch::new(path, value))
+    }
+}
+
+/// The reason that a patch could not be applied.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PatchErrorKind {
+    /// The path is malformed, or no node or field exists at it.
+    InvalidPath,
+    /// The value is not of the type of the field, or of a node.
+    WrongType { expected: &'static str, found: &'static str },
+    /// An array does not have one entry per component of the product.
+    LengthMismatch { expected: usize, found: usize },
+    /// A value could not be parsed.
+    InvalidValue(String),
+    /// The patched space is invalid, e.g. with the lower bound above the upper bound.
+    Invalid(ScalarError),
+}
+
+/// Violation of the invariants of a scalar, as enforced by the constructors of the spaces.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ScalarError {
+    /// The lower bound exceeds the upper bound.
+    UnorderedBounds { lb: f64, ub: f64 },
+    /// A partition has no parts.
+    NoParts,
+    /// The step of a quantised interval is not positive.
+    NonPositiveStep { step: f64 },
+}
+
+impl fmt::Display for ScalarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScalarError::UnorderedBounds { lb, ub } =>
+                write!(f, "lower bound {} is greater than upper bound {}", lb, ub),
+            ScalarError::NoParts => write!(f, "a partition must have at least one part"),
+            ScalarError::NonPositiveStep { step } => write!(f, "step {} is not positive", step),
+        }
+    }
+}
+
+impl Error for ScalarError {}
+
+/// Error returned when a patch cannot be applied, naming the path of the offending field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatchError {
+    pub path: String,
+    pub kind: PatchErrorKind,
+}
+
+impl PatchError {
+    fn new<P: Into<String>>(path: P, kind: PatchErrorKind) -> PatchError {
+        PatchError { path: path.into(), kind, }
+    }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at \"{}\": ", self.path)?;
+
+        match &self.kind {
+            PatchErrorKind::InvalidPath => write!(f, "no such node or field"),
+            PatchErrorKind::WrongType { expected, found } =>
+                write!(f, "expected {}, found {}", expected, found),
+            PatchErrorKind::LengthMismatch { expected, found } =>
+                write!(f, "expected {} values, found {}", expected, found),
+            PatchErrorKind::InvalidValue(v) => write!(f, "invalid value \"{}\"", v),
+            PatchErrorKind::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            PatchErrorKind::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Splits a path into the positions of the node and the name of the field, if any.
+fn parse_path(path: &str) -> Option<(Vec<usize>, Option<&str>)> {
+    let mut indices = vec![];
+    let mut field = None;
+
+    if path.is_empty() { return Some((indices, field)); }
+
+    for segment in path.split('.') {
+        let mut parts = segment.split('[');
+        let head = parts.next()?;
+
+        if field.is_some() || (head.is_empty() && !segment.starts_with('[')) { return None; }
+
+        if let Ok(i) = head.parse::<usize>() {
+            indices.push(i);
+        } else if !head.is_empty() && head.chars().all(|c| c.is_ascii_lowercase()) {
+            field = Some(head);
+        } else if !head.is_empty() {
+            return None;
+        }
+
+        for part in parts {
+            if field.is_some() { return None; }
+
+            indices.push(part.strip_suffix(']')?.parse().ok()?);
+        }
+    }
+
+    Some((indices, field))
+}
+
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() { segment.to_owned() } else { format!("{}.{}", path, segment) }
+}
+
+fn number(path: &str, expected: &'static str, value: &PatchValue) -> Result<f64, PatchError> {
+    match *value {
+        PatchValue::Number(x) if !x.is_nan() => Ok(x),
+        _ => Err(PatchError::new(path, PatchErrorKind::WrongType {
+            expected, found: value.type_name(),
+        })),
+    }
+}
+
+fn finite(path: &str, value: &PatchValue) -> Result<f64, PatchError> {
+    match number(path, "a finite number", value)? {
+        x if x.is_finite() => Ok(x),
+        _ => Err(PatchError::new(path, PatchErrorKind::WrongType {
+            expected: "a finite number", found: "an infinite number",
+        })),
+    }
+}
+
+// Converts to an optional real bound, with infinities and null denoting a missing bound.
+fn real_bound(path: &str, value: &PatchValue) -> Result<Option<f64>, PatchError> {
+    match value {
+        PatchValue::Null => Ok(None),
+        _ => number(path, "a number or null", value).map(|x| Some(x).filter(|x| x.is_finite())),
+    }
+}
+
+fn integer(path: &str, value: &PatchValue) -> Result<i64, PatchError> {
+    match number(path, "an integer", value)? {
+        x if x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64 => Ok(x as i64),
+        _ => Err(PatchError::new(path, PatchErrorKind::WrongType {
+            expected: "an integer", found: "a fractional or out of range number",
+        })),
+    }
+}
+
+fn count(path: &str, value: &PatchValue) -> Result<usize, PatchError> {
+    match integer(path, value)? {
+        n if n >= 0 => Ok(n as usize),
+        _ => Err(PatchError::new(path, PatchErrorKind::WrongType {
+            expected: "a non-negative integer", found: "a negative number",
+        })),
+    }
+}
+
+// Sets an optional bound, keeping its kind unless it is removed; missing bounds are closed, as
+// for `Interval`.
+fn set_bound<T>(bound: &mut Option<T>, kind: &mut BoundKind, value: Option<T>) {
+    if value.is_none() { *kind = BoundKind::Closed; }
+
+    *bound = value;
+}
+
+// Sets `field` of `kind`, where `path` names the field.
+fn set_field(
+    kind: &mut ScalarKind,
+    path: &str,
+    field: &str,
+    value: &PatchValue,
+) -> Result<(), PatchError> {
+    let optional_int = |value: &PatchValue| match value {
+        PatchValue::Null => Ok(None),
+        PatchValue::Number(x) if x.is_infinite() => Ok(None),
+        _ => integer(path, value).map(Some),
+    };
+
+    match (kind, field) {
+        (ScalarKind::Discrete { n }, "n") | (ScalarKind::Partition { n, .. }, "n") =>
+            *n = count(path, value)?,
+        (ScalarKind::Integer { lb, lb_kind, .. }, "lb") =>
+            set_bound(lb, lb_kind, optional_int(value)?),
+        (ScalarKind::Integer { ub, ub_kind, .. }, "ub") =>
+            set_bound(ub, ub_kind, optional_int(value)?),
+        (ScalarKind::Real { lb, lb_kind, .. }, "lb") =>
+            set_bound(lb, lb_kind, real_bound(path, value)?),
+        (ScalarKind::Real { ub, ub_kind, .. }, "ub") =>
+            set_bound(ub, ub_kind, real_bound(path, value)?),
+        (ScalarKind::Partition { lb, .. }, "lb") | (ScalarKind::Quantised { lb, .. }, "lb") =>
+            *lb = finite(path, value)?,
+        (ScalarKind::Partition { ub, .. }, "ub") | (ScalarKind::Quantised { ub, .. }, "ub") =>
+            *ub = finite(path, value)?,
+        (ScalarKind::Quantised { step, .. }, "step") => *step = finite(path, value)?,
+        _ => return Err(PatchError::new(path, PatchErrorKind::InvalidPath)),
+    }
+
+    Ok(())
+}
+
+// Checks the invariants of a scalar, as enforced by the constructors of the spaces.
+fn validate(kind: &ScalarKind) -> Result<(), ScalarError> {
+    let ordered = |lb: f64, ub: f64| if lb > ub {
+        Err(ScalarError::UnorderedBounds { lb, ub, })
+    } else {
+        Ok(())
+    };
+
+    match *kind {
+        ScalarKind::Integer { lb: Some(lb), ub: Some(ub), .. } => ordered(lb as f64, ub as f64),
+        ScalarKind::Real { lb: Some(lb), ub: Some(ub), .. } => ordered(lb, ub),
+        ScalarKind::Partition { n: 0, .. } => Err(ScalarError::NoParts),
+        ScalarKind::Partition { lb, ub, .. } => ordered(lb, ub),
+        ScalarKind::Quantised { step, .. } if step <= 0.0 || step.is_nan() =>
+            Err(ScalarError::NonPositiveStep { step, }),
+        ScalarKind::Quantised { lb, ub, .. } => ordered(lb, ub),
+        _ => Ok(()),
+    }
+}
+
+// Checks every leaf of a replacement node, naming the path of the first invalid one.
+fn validate_all(desc: &SpaceDescription, path: &str) -> Result<(), PatchError> {
+    let mut result = Ok(());
+
+    desc.visit_leaves(&mut String::new(), &mut |leaf, kind| {
+        if result.is_ok() {
+            result = validate(kind)
+                .map_err(|e| PatchError::new(join(path, leaf), PatchErrorKind::Invalid(e)));
+        }
+    });
+
+    result
+}
+
+fn apply_at(
+    node: &mut SpaceDescription,
+    path: &str,
+    field: Option<&str>,
+    value: &PatchValue,
+) -> Result<(), PatchError> {
+    match (node, field, value) {
+        (node, None, PatchValue::Space(desc)) => {
+            validate_all(desc, path)?;
+
+            *node = desc.clone();
+        },
+        (_, None, value) => return Err(PatchError::new(path, PatchErrorKind::WrongType {
+            expected: "a space", found: value.type_name(),
+        })),
+        (SpaceDescription::Scalar(kind), Some(field), value) => {
+            set_field(kind, &join(path, field), field, value)?;
+            validate(kind).map_err(|e| {
+                PatchError::new(join(path, field), PatchErrorKind::Invalid(e))
+            })?;
+        },
+        (SpaceDescription::Product(components), Some(field), PatchValue::Array(values)) => {
+            if values.len() != components.len() {
+                return Err(PatchError::new(join(path, field), PatchErrorKind::LengthMismatch {
+                    expected: components.len(), found: values.len(),
+                }));
+            }
+
+            for (i, (c, v)) in components.iter_mut().zip(values.iter()).enumerate() {
+                apply_at(c, &join(path, &i.to_string()), Some(field), v)?;
+            }
+        },
+        (SpaceDescription::Product(_), Some(field), value) =>
+            return Err(PatchError::new(join(path, field), PatchErrorKind::WrongType {
+                expected: "an array", found: value.type_name(),
+            })),
+    }
+
+    Ok(())
+}
+
+/// Apply `value` to the node or field of `desc` at `path`.
+///
+/// Numbers patch the field of a scalar, with `null` (or an infinity) removing an optional bound;
+/// arrays patch the field of each component of a product in turn; and descriptions replace the
+/// node at a path without a field. The patched scalar, or every leaf of a replacement node, is
+/// validated as by the constructors of the spaces, e.g. requiring that the lower bound not
+/// exceed the upper bound. If the patch fails, `desc` is left unchanged, and the error names the
+/// path of the offending field.
+pub fn apply_patch(
+    desc: &mut SpaceDescription,
+    path: &str,
+    value: PatchValue,
+) -> Result<(), PatchError> {
+    let invalid = || PatchError::new(path, PatchErrorKind::InvalidPath);
+    let (indices, field) = parse_path(path).ok_or_else(invalid)?;
+    let mut node = &mut *desc;
+    let mut at = String::new();
+
+    for i in indices {
+        node = match node {
+            SpaceDescription::Product(components) => components.get_mut(i).ok_or_else(invalid)?,
+            SpaceDescription::Scalar(_) => return Err(invalid()),
+        };
+        at = join(&at, &i.to_string());
+    }
+
+    let mut patched = node.clone();
+
+    apply_at(&mut patched, &at, field, &value)?;
+    *node = patched;
+
+    Ok(())
+}
+
+fn diff_kinds(path: &str, a: &ScalarKind, b: &ScalarKind, patches: &mut Vec<Patch>) -> bool {
+    let mut push =
+        |field: &str, value: PatchValue| patches.push(Patch::new(join(path, field), value));
+
+    match (a, b) {
+        (ScalarKind::Discrete { n: n1 }, ScalarKind::Discrete { n: n2 }) =>
+            if n1 != n2 { push("n", (*n2 as f64).into()); },
+        // Bound kinds and exclusions cannot be patched, so differing nodes are replaced whole.
+        (
+            ScalarKind::Integer { lb: l1, ub: u1, lb_kind: lk1, ub_kind: uk1, excluded: e1 },
+            ScalarKind::Integer { lb: l2, ub: u2, lb_kind: lk2, ub_kind: uk2, excluded: e2 },
+        ) if (lk1, uk1, e1) == (lk2, uk2, e2) => {
+            if l1 != l2 { push("lb", l2.map(|x| x as f64).into()); }
+            if u1 != u2 { push("ub", u2.map(|x| x as f64).into()); }
+        },
+        (
+            ScalarKind::Real { lb: l1, ub: u1, lb_kind: lk1, ub_kind: uk1 },
+            ScalarKind::Real { lb: l2, ub: u2, lb_kind: lk2, ub_kind: uk2 },
+        ) if (lk1, uk1) == (lk2, uk2) => {
+            if l1 != l2 { push("lb", (*l2).into()); }
+            if u1 != u2 { push("ub", (*u2).into()); }
+        },
+        (
+            ScalarKind::Partition { lb: l1, ub: u1, n: n1 },
+            ScalarKind::Partition { lb: l2, ub: u2, n: n2 },
+        ) => {
+            if l1 != l2 { push("lb", (*l2).into()); }
+            if u1 != u2 { push("ub", (*u2).into()); }
+            if n1 != n2 { push("n", (*n2 as f64).into()); }
+        },
+        (
+            ScalarKind::Quantised { lb: l1, ub: u1, step: s1 },
+            ScalarKind::Quantised { lb: l2, ub: u2, step: s2 },
+        ) => {
+            if l1 != l2 { push("lb", (*l2).into()); }
+            if u1 != u2 { push("ub", (*u2).into()); }
+            if s1 != s2 { push("step", (*s2).into()); }
+        },
+        _ => return a == b,
+    }
+
+    true
+}
+
+fn diff_at(path: &str, a: &SpaceDescription, b: &SpaceDescription, patches: &mut Vec<Patch>) {
+    let same_shape = match (a, b) {
+        (SpaceDescription::Scalar(ka), SpaceDescription::Scalar(kb)) =>
+            diff_kinds(path, ka, kb, patches),
+        (SpaceDescription::Product(ca), SpaceDescription::Product(cb)) if ca.len() == cb.len() => {
+            for (i, (ca, cb)) in ca.iter().zip(cb.iter()).enumerate() {
+                diff_at(&join(path, &i.to_string()), ca, cb, patches);
+            }
+
+            true
+        },
+        _ => false,
+    };
+
+    if !same_shape { patches.push(Patch::new(path, b.clone())); }
+}
+
+/// Returns the patches that transform `a` into `b`, in depth-first order.
+///
+/// Differing parameters of scalars of the same kind produce one patch per field, and nodes whose
+/// kind or number of components differ are replaced whole; the result is empty iff `a == b`.
+/// ```
+/// use spaces::{patch::{diff, Patch}, ScalarKind, SpaceDescription};
+///
+/// let a = SpaceDescription::Scalar(ScalarKind::real(Some(0.0), Some(1.0)));
+/// let b = SpaceDescription::Scalar(ScalarKind::real(None, Some(2.0)));
+/// let mut patched = a.clone();
+///
+/// for patch in diff(&a, &b) {
+///     patch.apply(&mut patched).unwrap();
+/// }
+///
+/// assert_eq!(diff(&a, &b), vec![Patch::new("lb", None::<f64>), Patch::new("ub", 2.0)]);
+/// assert_eq!(patched, b);
+/// ```
+pub fn diff(a: &SpaceDescription, b: &SpaceDescription) -> Vec<Patch> {
+    let mut patches = vec![];
+
+    diff_at("", a, b, &mut patches);
+
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn real(lb: f64, ub: f64) -> SpaceDescription {
+        SpaceDescription::Scalar(ScalarKind::real(Some(lb), Some(ub)))
+    }
+
+    fn config() -> SpaceDescription {
+        SpaceDescription::Product(vec![
+            SpaceDescription::Scalar(ScalarKind::Discrete { n: 4 }),
+            SpaceDescription::Product(vec![real(-1.0, 1.0), real(-2.0, 2.0), real(0.0, 5.0)]),
+        ])
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let base = config();
+        let patches = [
+            Patch::new("1.2.ub", 10.0),
+            Patch::new("[1][0].lb", None::<f64>),
+            Patch::new("0.n", 6.0),
+        ];
+
+        for patch in patches.iter() {
+            let mut desc = base.clone();
+
+            patch.apply(&mut desc).unwrap();
+
+            let found = diff(&base, &desc);
+
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].value, patch.value);
+            assert_eq!(diff(&desc, &base).len(), 1);
+
+            let mut patched = base.clone();
+
+            found[0].apply(&mut patched).unwrap();
+
+            assert_eq!(patched, desc);
+        }
+
+        assert_eq!(diff(&base, &base), vec![]);
+    }
+
+    #[test]
+    fn test_arrays_and_nodes() {
+        let mut desc = config();
+
+        apply_patch(&mut desc, "1.ub", vec![3.0, 4.0, 6.0].into()).unwrap();
+        apply_patch(&mut desc, "0", real(0.0, 1.0).into()).unwrap();
+
+        assert_eq!(desc, SpaceDescription::Product(vec![
+            real(0.0, 1.0),
+            SpaceDescription::Product(vec![real(-1.0, 3.0), real(-2.0, 4.0), real(0.0, 6.0)]),
+        ]));
+        assert_eq!(diff(&config(), &desc), vec![
+            Patch::new("0", real(0.0, 1.0)),
+            Patch::new("1.0.ub", 3.0),
+            Patch::new("1.1.ub", 4.0),
+            Patch::new("1.2.ub", 6.0),
+        ]);
+        assert_eq!(
+            apply_patch(&mut desc, "1.ub", vec![1.0].into()).unwrap_err().kind,
+            PatchErrorKind::LengthMismatch { expected: 3, found: 1 }
+        );
+    }
+
+    #[test]
+    fn test_bound_kinds() {
+        let mut open = SpaceDescription::Scalar(ScalarKind::Real {
+            lb: Some(0.0), ub: Some(1.0), lb_kind: BoundKind::Open, ub_kind: BoundKind::Closed,
+        });
+
+        assert_eq!(diff(&real(0.0, 1.0), &open), vec![Patch::new("", open.clone())]);
+
+        apply_patch(&mut open, "lb", PatchValue::Null).unwrap();
+
+        assert_eq!(open, SpaceDescription::Scalar(ScalarKind::real(None, Some(1.0))));
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let mut desc = config();
+
+        for path in ["2.ub", "0.0.n", "1.0.hi", "1..x.ub", "1[0", "0.n.n", "1.Ub"].iter() {
+            let err = apply_patch(&mut desc, path, 1.0.into()).unwrap_err();
+
+            assert!(!matches!(err.kind, PatchErrorKind::Invalid(_)), "{}: {}", path, err);
+        }
+
+        assert_eq!(
+            apply_patch(&mut desc, "2.ub", 1.0.into()),
+            Err(PatchError::new("2.ub", PatchErrorKind::InvalidPath))
+        );
+        assert!(matches!(
+            apply_patch(&mut desc, "0.n", true.into()).unwrap_err().kind,
+            PatchErrorKind::WrongType { found: "a boolean", .. }
+        ));
+        assert_eq!(desc, config());
+    }
+
+    #[test]
+    fn test_validation_names_path() {
+        let mut desc = config();
+        let err = apply_patch(&mut desc, "1[2].ub", PatchValue::Number(-1.0)).unwrap_err();
+
+        assert_eq!(err.path, "1.2.ub");
+        assert_eq!(err.to_string(), "at \"1.2.ub\": lower bound 0 is greater than upper bound -1");
+        assert_eq!(desc, config());
+
+        let mut quantised =
+            SpaceDescription::Scalar(ScalarKind::Quantised { lb: 0.0, ub: 1.0, step: 0.1 });
+
+        assert_eq!(
+            apply_patch(&mut quantised, "step", PatchValue::Number(0.0)).unwrap_err().kind,
+            PatchErrorKind::Invalid(ScalarError::NonPositiveStep { step: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_validation_of_nodes() {
+        let mut desc = config();
+        let bad = SpaceDescription::Product(vec![
+            real(0.0, 1.0),
+            SpaceDescription::Scalar(ScalarKind::Partition { lb: 0.0, ub: 1.0, n: 0 }),
+        ]);
+        let err = apply_patch(&mut desc, "1", bad.into()).unwrap_err();
+
+        assert_eq!(err, PatchError::new("1.1", PatchErrorKind::Invalid(ScalarError::NoParts)));
+        assert_eq!(desc, config());
+
+        let err = apply_patch(&mut desc, "", real(1.0, 0.0).into()).unwrap_err();
+
+        assert_eq!(err.path, "");
+        assert_eq!(err.kind, PatchErrorKind::Invalid(ScalarError::UnorderedBounds {
+            lb: 1.0, ub: 0.0,
+        }));
+        assert!(err.source().is_some());
+        assert_eq!(desc, config());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1.2.ub = 2.5".parse(), Ok(Patch::new("1.2.ub", 2.5)));
+        assert_eq!("ub=inf".parse(), Ok(Patch::new("ub", f64::INFINITY)));
+        assert_eq!(
+            "ub=[1, [2, null], true]".parse::<Patch>().map(|p| p.value),
+            Ok(PatchValue::Array(vec![
+                1.0.into(),
+                PatchValue::Array(vec![2.0.into(), PatchValue::Null]),
+                true.into(),
+            ]))
+        );
+        assert_eq!(
+            "0.ub=high".parse::<Patch>(),
+            Err(PatchError::new("0.ub", PatchErrorKind::InvalidValue("high".into())))
+        );
+        assert!("0.ub".parse::<Patch>().is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{normal::standard_normal, prelude::*};
+
+/// Noise process used by `Perturbed`, with one entry per dimension of the action.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Noise {
+    /// Independent zero-mean Gaussian noise with standard deviation `std`.
+    Gaussian { std: Vec<f64> },
+    /// Ornstein-Uhlenbeck process reverting to zero at rate `theta`, with volatility `sigma`,
+    /// integrated with time step `dt` by the Euler-Maruyama method.
+    OrnsteinUhlenbeck { theta: f64, sigma: Vec<f64>, dt: f64 },
+}
+
+impl Noise {
+    /// Returns the number of dimensions of the noise.
+    pub fn dim(&self) -> usize {
+        match self {
+            Noise::Gaussian { std } => std.len(),
+            Noise::OrnsteinUhlenbeck { sigma, .. } => sigma.len(),
+        }
+    }
+}
+
+/// Exploration noise over a bounded real vector space, e.g. "action plus clipped Gaussian".
+///
+/// Each call to `perturb` adds a draw of the noise, multiplied by the current scale, to an action
+/// and projects the result back onto the space with `map_onto`. The Ornstein-Uhlenbeck process
+/// carries state from one call to the next, so `Perturbed` is deliberately not a `Space` itself;
+/// the state is cleared with `reset`, e.g. between episodes, and the scale may be decayed over
+/// training with `set_scale`.
+/// ```
+/// extern crate rand;
+///
+/// use spaces::{real::Interval, BoundedSpace, Perturbed, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]);
+/// let mut noisy = Perturbed::gaussian(space.clone(), vec![0.5, 0.1]);
+/// let mut rng = rand::thread_rng();
+///
+/// assert!(space.contains(&noisy.perturb(&[0.9, -0.2], &mut rng)));
+///
+/// noisy.set_scale(0.0);
+///
+/// assert_eq!(noisy.perturb(&[0.9, -0.2], &mut rng), vec![0.9, -0.2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Perturbed<S> {
+    space: S,
+    noise: Noise,
+    scale: f64,
+    state: Vec<f64>,
+}
+
+// Panics unless every parameter is finite and non-negative.
+fn check_params(name: &str, params: &[f64]) {
+    assert!(
+        params.iter().all(|p| p.is_finite() && *p >= 0.0),
+        "The {} of the noise must be finite and non-negative.", name
+    );
+}
+
+impl<S> Perturbed<S> {
+    /// Construct a wrapper adding Gaussian noise with standard deviation `std`, at unit scale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any deviation is negative or not finite.
+    pub fn gaussian(space: S, std: Vec<f64>) -> Perturbed<S> {
+        check_params("standard deviation", &std);
+
+        Perturbed::new(space, Noise::Gaussian { std, })
+    }
+
+    /// Construct a wrapper adding Ornstein-Uhlenbeck noise, at unit scale and from zero state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `theta`, `dt` or any volatility is negative or not finite.
+    pub fn ornstein_uhlenbeck(space: S, theta: f64, sigma: Vec<f64>, dt: f64) -> Perturbed<S> {
+        check_params("rate", &[theta]);
+        check_params("time step", &[dt]);
+        check_params("volatility", &sigma);
+
+        Perturbed::new(space, Noise::OrnsteinUhlenbeck { theta, sigma, dt, })
+    }
+
+    fn new(space: S, noise: Noise) -> Perturbed<S> {
+        let state = vec![0.0; noise.dim()];
+
+        Perturbed { space, noise, scale: 1.0, state, }
+    }
+
+    #[inline]
+    pub fn space(&self) -> &S { &self.space }
+
+    #[inline]
+    pub fn noise(&self) -> &Noise { &self.noise }
+
+    /// Returns the current scale of the noise.
+    #[inline]
+    pub fn scale(&self) -> f64 { self.scale }
+
+    /// Set the factor by which draws of the noise are multiplied; negative values and `NaN` are
+    /// treated as zero.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = if scale.is_nan() { 0.0 } else { scale.max(0.0) };
+    }
+
+    /// Clear the state of the Ornstein-Uhlenbeck process, which has no effect on Gaussian noise.
+    pub fn reset(&mut self) { self.state.iter_mut().for_each(|x| *x = 0.0); }
+
+    // Advances the noise process by one step, returning the (unscaled) noise.
+    fn step<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &[f64] {
+        match &self.noise {
+            Noise::Gaussian { std } => {
+                for (x, s) in self.state.iter_mut().zip(std.iter()) {
+                    *x = s * standard_normal(rng);
+                }
+            },
+            Noise::OrnsteinUhlenbeck { theta, sigma, dt } => {
+                for (x, s) in self.state.iter_mut().zip(sigma.iter()) {
+                    *x += -theta * *x * dt + s * dt.sqrt() * standard_normal(rng);
+                }
+            },
+        }
+
+        &self.state
+    }
+}
+
+impl<S: Space<Value = Vec<f64>> + Surjection<Vec<f64>, Vec<f64>>> Perturbed<S> {
+    /// Returns `action` plus a draw of the scaled noise, projected onto the space.
+    ///
+    /// At zero scale the noise process still advances, but `action` is returned unchanged if it
+    /// is contained in the space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `action` does not have one entry per dimension of the noise.
+    pub fn perturb<R: Rng + ?Sized>(&mut self, action: &[f64], rng: &mut R) -> Vec<f64> {
+        assert_eq!(action.len(), self.noise.dim(), "Action does not match the dimension of noise.");
+
+        let scale = self.scale;
+        let noise = self.step(rng);
+
+        if scale == 0.0 { return self.space.map_onto(action.to_vec()); }
+
+        let noisy = action.iter().zip(noise.iter()).map(|(a, n)| a + scale * n).collect();
+
+        self.space.map_onto(noisy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, ProductSpace};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    fn space() -> ProductSpace<Interval> {
+        ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::bounded(0.0, 0.1)])
+    }
+
+    #[test]
+    fn test_within_bounds() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut gaussian = Perturbed::gaussian(space(), vec![2.0, 1.0]);
+        let mut ou = Perturbed::ornstein_uhlenbeck(space(), 0.15, vec![3.0, 3.0], 0.1);
+
+        for _ in 0..1000 {
+            let action = space().sample(&mut rng);
+
+            assert!(space().contains(&gaussian.perturb(&action, &mut rng)));
+            assert!(space().contains(&ou.perturb(&action, &mut rng)));
+        }
+    }
+
+    #[test]
+    fn test_zero_scale() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut gaussian = Perturbed::gaussian(space(), vec![2.0, 1.0]);
+        let mut ou = Perturbed::ornstein_uhlenbeck(space(), 0.15, vec![0.2, 0.2], 0.01);
+
+        gaussian.set_scale(0.0);
+        ou.set_scale(-1.0);
+
+        assert_eq!(ou.scale(), 0.0);
+
+        for _ in 0..100 {
+            let action = space().sample(&mut rng);
+
+            assert_eq!(gaussian.perturb(&action, &mut rng), action);
+            assert_eq!(ou.perturb(&action, &mut rng), action);
+        }
+
+        assert_eq!(gaussian.perturb(&[3.0, 0.05], &mut rng), vec![1.0, 0.05]);
+
+        gaussian.set_scale(f64::NAN);
+
+        assert_eq!(gaussian.scale(), 0.0);
+    }
+
+    #[test]
+    fn test_ou_autocorrelation() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let unbounded = ProductSpace::new(vec![Interval::unbounded()]);
+        let mut ou = Perturbed::ornstein_uhlenbeck(unbounded.clone(), 0.15, vec![0.2], 0.01);
+        let mut gaussian = Perturbed::gaussian(unbounded, vec![0.2]);
+
+        // Lag-one autocorrelation of the noise added over consecutive calls.
+        let mut autocorr = |p: &mut Perturbed<ProductSpace<Interval>>| {
+            let xs: Vec<f64> = (0..5000).map(|_| p.perturb(&[0.0], &mut rng)[0]).collect();
+            let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+            let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+
+            xs.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum::<f64>() / var
+        };
+
+        assert!(autocorr(&mut ou) > 0.5);
+        assert!(autocorr(&mut gaussian).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut ou = Perturbed::ornstein_uhlenbeck(space(), 0.15, vec![0.5, 0.5], 0.1);
+
+        for _ in 0..10 {
+            ou.perturb(&[0.0, 0.05], &mut rng);
+        }
+
+        assert!(ou.state.iter().all(|&x| x != 0.0));
+
+        ou.reset();
+
+        assert_eq!(ou.state, vec![0.0; 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_std() { Perturbed::gaussian(space(), vec![1.0, -1.0]); }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_action() {
+        Perturbed::gaussian(space(), vec![1.0, 1.0])
+            .perturb(&[0.0], &mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::prelude::*;
+use rand::distributions::Distribution;
+use std::{error::Error, fmt};
+
+/// Tolerance on the total mass of the probabilities passed to `Pmf::new`.
+pub const PMF_TOLERANCE: f64 = 1e-6;
+
+/// Error type for invalid probability mass functions.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PmfError {
+    /// The pmf does not have exactly one probability per element of the space.
+    LengthMismatch { expected: usize, found: usize },
+    /// The probability at `index` is negative or not finite.
+    InvalidProbability { index: usize, value: f64 },
+    /// The probabilities sum to `total`, rather than one.
+    NotNormalised { total: f64 },
+}
+
+impl fmt::Display for PmfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PmfError::LengthMismatch { expected, found } =>
+                write!(f, "expected {} probabilities, found {}", expected, found),
+            PmfError::InvalidProbability { index, value } =>
+                write!(f, "probability {} at index {} is not a finite, non-negative number",
+                       value, index),
+            PmfError::NotNormalised { total } =>
+                write!(f, "probabilities sum to {}, not 1", total),
+        }
+    }
+}
+
+impl Error for PmfError {}
+
+/// Probability mass function over a finite space.
+///
+/// Entry `i` of the probability vector holds the probability of the `i`th value of the space
+/// (see `FiniteSpace::to_index`).
+/// ```
+/// use spaces::{discrete::Ordinal, Pmf};
+///
+/// let pmf = Pmf::new(Ordinal::new(3), vec![0.5, 0.25, 0.25]).unwrap();
+///
+/// assert_eq!(pmf.prob(&1), 0.25);
+/// assert_eq!(pmf.prob(&3), 0.0);
+/// assert!((pmf.entropy() - 1.5 * 2f64.ln()).abs() < 1e-12);
+/// assert!(pmf.sample(&mut rand::thread_rng()) < 3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(
+    try_from = "PmfRepr<S>",
+    bound(deserialize = "S: FiniteSpace + serde::Deserialize<'de>, S::Value: PartialEq")
+))]
+pub struct Pmf<S> {
+    space: S,
+    probs: Vec<f64>,
+}
+
+// Deserialized pmfs are checked as by `Pmf::new`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Pmf")]
+struct PmfRepr<S> {
+    space: S,
+    probs: Vec<f64>,
+}
+
+#[cfg(feature = "serialize")]
+impl<S: FiniteSpace> std::convert::TryFrom<PmfRepr<S>> for Pmf<S>
+where S::Value: PartialEq
+{
+    type Error = PmfError;
+
+    fn try_from(repr: PmfRepr<S>) -> Result<Pmf<S>, PmfError> { Pmf::new(repr.space, repr.probs) }
+}
+
+impl<S: FiniteSpace> Pmf<S>
+where S::Value: PartialEq
+{
+    /// Construct a pmf from one probability per element of `space`.
+    ///
+    /// The probabilities must be finite and non-negative, and sum to one within
+    /// `PMF_TOLERANCE`.
+    pub fn new(space: S, probs: Vec<f64>) -> Result<Pmf<S>, PmfError> {
+        let total = Pmf::check(&space, &probs)?;
+
+        if (total - 1.0).abs() > PMF_TOLERANCE {
+            return Err(PmfError::NotNormalised { total, });
+        }
+
+        Ok(Pmf { space, probs, })
+    }
+
+    /// Construct a pmf from one non-negative weight per element of `space`, normalising the
+    /// weights by their sum.
+    pub fn normalised(space: S, weights: Vec<f64>) -> Result<Pmf<S>, PmfError> {
+        let total = Pmf::check(&space, &weights)?;
+
+        if total <= 0.0 || !total.is_finite() {
+            return Err(PmfError::NotNormalised { total, });
+        }
+
+        Ok(Pmf { space, probs: weights.into_iter().map(|w| w / total).collect(), })
+    }
+
+    // Returns the sum of the probabilities, if they are valid for the space.
+    fn check(space: &S, probs: &[f64]) -> Result<f64, PmfError> {
+        let expected = space.len();
+
+        if probs.len() != expected {
+            return Err(PmfError::LengthMismatch { expected, found: probs.len(), });
+        }
+
+        match probs.iter().enumerate().find(|(_, p)| !(p.is_finite() && **p >= 0.0)) {
+            Some((index, &value)) => Err(PmfError::InvalidProbability { index, value, }),
+            None => Ok(probs.iter().sum()),
+        }
+    }
+
+    #[inline]
+    pub fn space(&self) -> &S { &self.space }
+
+    /// Returns the probabilities, in the enumeration order of the space.
+    #[inline]
+    pub fn probs(&self) -> &[f64] { &self.probs }
+
+    /// Returns the probability of `val`, which is zero if `val` is not contained in the space.
+    pub fn prob(&self, val: &S::Value) -> f64 {
+        self.space.to_index(val).map_or(0.0, |i| self.probs[i])
+    }
+
+    /// Returns the natural logarithm of the probability of `val`.
+    pub fn log_prob(&self, val: &S::Value) -> f64 { self.prob(val).ln() }
+
+    /// Returns the entropy of the pmf, in nats.
+    pub fn entropy(&self) -> f64 {
+        -self.probs.iter().filter(|&&p| p > 0.0).map(|&p| p * p.ln()).sum::<f64>()
+    }
+
+    /// Returns the Kullback-Leibler divergence of `other` from `self`, in nats.
+    ///
+    /// The divergence is infinite if `other` assigns zero probability to a value that `self`
+    /// does not.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pmfs are not defined over spaces of the same size.
+    pub fn kl_divergence(&self, other: &Pmf<S>) -> f64 {
+        assert_eq!(self.probs.len(), other.probs.len(), "Pmfs must be over the same space.");
+
+        self.probs
+            .iter()
+            .zip(other.probs.iter())
+            .filter(|&(&p, _)| p > 0.0)
+            .map(|(&p, &q)| p * (p / q).ln())
+            .sum()
+    }
+
+    /// Draw a value from the space according to the pmf.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value {
+        let n = self.probs.len();
+        let mut u = rng.gen::<f64>();
+
+        // Fall back onto the last value of positive mass, to absorb rounding error in the total.
+        let idx = self.probs.iter().position(|&p| { u -= p; u < 0.0 }).unwrap_or_else(|| {
+            self.probs.iter().rposition(|&p| p > 0.0).unwrap_or(n - 1)
+        });
+
+        self.space.from_index(idx).expect("Pmf index must be contained in the space.")
+    }
+}
+
+/// Allows drawing from a pmf wherever a distribution is expected, e.g. by `SampleWith`.
+impl<S: FiniteSpace> Distribution<S::Value> for Pmf<S>
+where S::Value: PartialEq
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value { Pmf::sample(self, rng) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discrete::{Binary, Ordinal};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let d = Ordinal::new(3);
+
+        assert!(Pmf::new(d, vec![0.2, 0.3, 0.5]).is_ok());
+        assert_eq!(
+            Pmf::new(d, vec![0.5, 0.5]),
+            Err(PmfError::LengthMismatch { expected: 3, found: 2 })
+        );
+        assert_eq!(
+            Pmf::new(d, vec![0.5, -0.5, 1.0]),
+            Err(PmfError::InvalidProbability { index: 1, value: -0.5 })
+        );
+        assert_eq!(
+            Pmf::new(d, vec![0.5, 0.5, 0.5]),
+            Err(PmfError::NotNormalised { total: 1.5 })
+        );
+        assert!(matches!(
+            Pmf::new(d, vec![0.5, f64::NAN, 0.5]),
+            Err(PmfError::InvalidProbability { index: 1, .. })
+        ));
+
+        let pmf = Pmf::normalised(d, vec![1.0, 1.0, 2.0]).unwrap();
+
+        assert_eq!(pmf.probs(), &[0.25, 0.25, 0.5]);
+        assert_eq!(
+            Pmf::normalised(d, vec![0.0; 3]),
+            Err(PmfError::NotNormalised { total: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_entropy() {
+        for n in 1..10 {
+            let pmf = Pmf::normalised(Ordinal::new(n), vec![1.0; n]).unwrap();
+
+            assert!((pmf.entropy() - (n as f64).ln()).abs() < 1e-12);
+        }
+
+        let pmf = Pmf::new(Binary, vec![0.0, 1.0]).unwrap();
+
+        assert_eq!(pmf.entropy(), 0.0);
+        assert_eq!(pmf.prob(&true), 1.0);
+        assert_eq!(pmf.log_prob(&false), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_kl_divergence() {
+        let p = Pmf::new(Ordinal::new(2), vec![0.5, 0.5]).unwrap();
+        let q = Pmf::new(Ordinal::new(2), vec![0.25, 0.75]).unwrap();
+        let r = Pmf::new(Ordinal::new(2), vec![1.0, 0.0]).unwrap();
+
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert_eq!(q.kl_divergence(&q), 0.0);
+        assert!((p.kl_divergence(&q) - 0.5 * (4.0f64 / 3.0).ln()).abs() < 1e-12);
+        assert!(p.kl_divergence(&q) > 0.0 && q.kl_divergence(&p) > 0.0);
+        assert_eq!(p.kl_divergence(&r), f64::INFINITY);
+        assert_eq!(r.kl_divergence(&p), 2f64.ln());
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let probs = vec![0.1, 0.0, 0.6, 0.3];
+        let pmf = Pmf::new(Ordinal::new(4), probs.clone()).unwrap();
+        let mut counts = [0usize; 4];
+
+        for _ in 0..100_000 {
+            counts[pmf.sample(&mut rng)] += 1;
+        }
+
+        for (&c, &p) in counts.iter().zip(probs.iter()) {
+            assert!((c as f64 / 100_000.0 - p).abs() < 0.01);
+        }
+
+        assert_eq!(counts[1], 0);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let pmf: Pmf<Ordinal> = serde_json::from_str(r#"{"space":2,"probs":[0.25,0.75]}"#).unwrap();
+
+        assert_eq!(pmf.prob(&1), 0.75);
+
+        let err = |json: &str| serde_json::from_str::<Pmf<Ordinal>>(json).is_err();
+
+        assert!(err(r#"{"space":3,"probs":[0.5,0.5]}"#));
+        assert!(err(r#"{"space":2,"probs":[1.5,-0.5]}"#));
+        assert!(err(r#"{"space":2,"probs":[0.5,0.25]}"#));
+    }
+}
@@ -0,0 +1,162 @@
+//! Spaces of common reinforcement learning benchmarks, with the canonical bounds of gym.
+//!
+//! Bounds that gym gives as the largest finite `float32` are unbounded here. Observations of the
+//! Atari environments are flattened, such that an image is a vector of pixel intensities in
+//! row-major `(height, width, channel)` order; see `ATARI_IMAGE_SHAPE`.
+//! ```
+//! use spaces::{presets, BoundedSpace, Dim, Space};
+//!
+//! let spec = presets::cartpole();
+//!
+//! assert_eq!(spec.observation.dim(), Dim::Finite(4));
+//! assert!(spec.observation.contains(&[0.0, 100.0, 0.1, -100.0]));
+//! assert!(!spec.observation.contains(&[5.0, 0.0, 0.0, 0.0]));
+//! assert!(spec.action.contains(1));
+//! ```
+use crate::{discrete::Ordinal, EnvSpec, Interval, ProductSpace};
+
+/// Extents of an Atari image observation, as `(height, width, channel)`.
+pub const ATARI_IMAGE_SHAPE: [usize; 3] = [210, 160, 3];
+
+/// Number of bytes in the RAM of the Atari 2600.
+pub const ATARI_RAM_SIZE: usize = 128;
+
+// Pole angle past which an episode of CartPole terminates, 12 degrees, in radians.
+const CARTPOLE_THETA_THRESHOLD: f64 = 12.0 * 2.0 * ::std::f64::consts::PI / 360.0;
+
+// Cart position past which an episode of CartPole terminates.
+const CARTPOLE_X_THRESHOLD: f64 = 2.4;
+
+fn bytes(n: usize) -> ProductSpace<Ordinal<u8>> {
+    ProductSpace::new(vec![Ordinal::<u8>::try_new(256).unwrap(); n])
+}
+
+/// Observation space of CartPole: cart position and velocity, and pole angle and angular
+/// velocity.
+///
+/// The position and angle are bounded by twice their termination thresholds.
+pub fn cartpole_observation() -> ProductSpace<Interval> {
+    let x = 2.0 * CARTPOLE_X_THRESHOLD;
+    let theta = 2.0 * CARTPOLE_THETA_THRESHOLD;
+
+    ProductSpace::new(vec![
+        Interval::bounded(-x, x),
+        Interval::unbounded(),
+        Interval::bounded(-theta, theta),
+        Interval::unbounded(),
+    ])
+}
+
+/// Action space of CartPole: push the cart to the left (0) or to the right (1).
+pub fn cartpole_action() -> Ordinal { Ordinal::new(2) }
+
+/// Spaces of CartPole; see `cartpole_observation` and `cartpole_action`.
+pub fn cartpole() -> EnvSpec<ProductSpace<Interval>, Ordinal> {
+    EnvSpec::new(cartpole_observation(), cartpole_action())
+}
+
+/// Spaces of MountainCar: the position and velocity of the car, and an acceleration to the left
+/// (0), none (1) or to the right (2).
+pub fn mountain_car() -> EnvSpec<ProductSpace<Interval>, Ordinal> {
+    EnvSpec::new(
+        ProductSpace::new(vec![Interval::bounded(-1.2, 0.6), Interval::bounded(-0.07, 0.07)]),
+        Ordinal::new(3),
+    )
+}
+
+/// Spaces of Pendulum: the cosine and sine of the angle and the angular velocity of the
+/// pendulum, and the torque applied to it.
+pub fn pendulum() -> EnvSpec<ProductSpace<Interval>, ProductSpace<Interval>> {
+    EnvSpec::new(
+        ProductSpace::new(vec![
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(-8.0, 8.0),
+        ]),
+        ProductSpace::new(vec![Interval::bounded(-2.0, 2.0)]),
+    )
+}
+
+/// Observation space of the RAM variants of the Atari environments: `ATARI_RAM_SIZE` bytes.
+pub fn atari_ram() -> ProductSpace<Ordinal<u8>> { bytes(ATARI_RAM_SIZE) }
+
+/// Observation space of the image variants of the Atari environments: one byte per channel of
+/// each pixel, flattened from `ATARI_IMAGE_SHAPE` in row-major order.
+pub fn atari_image() -> ProductSpace<Ordinal<u8>> { bytes(ATARI_IMAGE_SHAPE.iter().product()) }
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoundedSpace, Card, Dim, FiniteSpace, Space};
+    use super::*;
+
+    // Bounds of the boxes, transcribed from the gym source; `f32::MAX` is written as infinity.
+    const BOXES: &[(&str, &[f64], &[f64])] = &[
+        (
+            "cartpole",
+            &[-4.8, -f64::INFINITY, -0.41887903, -f64::INFINITY],
+            &[4.8, f64::INFINITY, 0.41887903, f64::INFINITY],
+        ),
+        ("mountain_car", &[-1.2, -0.07], &[0.6, 0.07]),
+        ("pendulum", &[-1.0, -1.0, -8.0], &[1.0, 1.0, 8.0]),
+        ("pendulum_action", &[-2.0], &[2.0]),
+    ];
+
+    fn bounds(space: &ProductSpace<Interval>) -> (Vec<f64>, Vec<f64>) {
+        space.iter()
+            .map(|d| (d.inf().unwrap_or(-f64::INFINITY), d.sup().unwrap_or(f64::INFINITY)))
+            .unzip()
+    }
+
+    #[test]
+    fn test_boxes() {
+        for &(name, lb, ub) in BOXES {
+            let space = match name {
+                "cartpole" => cartpole_observation(),
+                "mountain_car" => mountain_car().observation,
+                "pendulum" => pendulum().observation,
+                "pendulum_action" => pendulum().action,
+                _ => unreachable!(),
+            };
+            let (inf, sup) = bounds(&space);
+
+            assert_eq!(space.dim(), Dim::Finite(lb.len()), "{}", name);
+
+            for (actual, expected) in inf.iter().chain(sup.iter()).zip(lb.iter().chain(ub)) {
+                assert!(
+                    actual == expected || (actual - expected).abs() < 1e-7,
+                    "{}: bound {} != {}", name, actual, expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete() {
+        assert_eq!(cartpole_action().card(), Card::Finite(2));
+        assert_eq!(cartpole().action, cartpole_action());
+        assert_eq!(mountain_car().action.card(), Card::Finite(3));
+        assert_eq!(mountain_car().action.sup(), Some(2));
+    }
+
+    #[test]
+    fn test_atari() {
+        let ram = atari_ram();
+        let image = atari_image();
+
+        assert_eq!(ram.dim(), Dim::Finite(128));
+        assert_eq!(image.dim(), Dim::Finite(210 * 160 * 3));
+        assert_eq!(image.card(), Card::Infinite);
+
+        for d in ram.iter().chain(image.iter()) {
+            assert_eq!((d.inf(), d.sup(), d.len()), (Some(0), Some(255), 256));
+        }
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(cartpole_observation(); shape, fmt);
+        assert_space_laws!(mountain_car().observation; shape, fmt);
+        assert_space_laws!(pendulum().action; shape, fmt);
+        assert_space_laws!(atari_ram(); shape, fmt);
+    }
+}
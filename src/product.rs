@@ -1,17 +1,26 @@
-use crate::{Equipartition, Interval, prelude::*};
+use crate::{
+    discrete::Ordinal, limits::DebugCapped, AffineMap, ComponentError, ContainsError,
+    Equipartition, Interval, Layout, ProductCard, RowsError, SampleError, Spread, index_coverage,
+    prelude::*, rows::check_input,
+};
 use itertools::{Itertools, EitherOrBoth};
 use std::{
+    collections::HashSet,
+    error::Error,
     fmt::{self, Display},
-    iter::FromIterator,
-    ops::{Add, Index},
+    iter::{FromIterator, FusedIterator},
+    ops::{Add, Index, Mul, Neg, Sub},
     slice::{Iter as SliceIter},
-    vec::{IntoIter as VecIntoIter},
 };
 
 /// N-dimensional homogeneous space.
 ///
 /// Represention of the Cartesian product of N topological spaces.
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// The default space is the empty product, for any component type. In particular, the default
+/// box, `ProductSpace<Interval>`, has no dimensions; the 1-dimensional unit box is given by
+/// `ProductSpace::new(vec![Interval::unit()])`.
+#[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct ProductSpace<D>(Vec<D>);
 
@@ -20,12 +29,341 @@ impl<D> ProductSpace<D> {
 
     pub fn empty() -> Self { ProductSpace(vec![]) }
 
-    pub fn iter(&self) -> SliceIter<D> { self.0.iter() }
+    pub fn iter(&self) -> SliceIter<'_, D> { self.0.iter() }
+
+    fn check_component(&self, index: usize) -> Result<usize, ComponentError> {
+        if index < self.0.len() {
+            Ok(index)
+        } else {
+            Err(ComponentError { index, n_components: self.0.len(), })
+        }
+    }
+
+    /// Returns the component space at `index`.
+    pub fn component(&self, index: usize) -> Result<&D, ComponentError> {
+        self.check_component(index).map(|i| &self.0[i])
+    }
+
+    /// Returns the component space at `index`, for modification in place.
+    pub fn component_mut(&mut self, index: usize) -> Result<&mut D, ComponentError> {
+        self.check_component(index).map(move |i| &mut self.0[i])
+    }
+
+    /// Returns a copy of the product with the component space at `index` replaced by `new`.
+    ///
+    /// Components of a product may differ in dimensionality, so any `new` component of the right
+    /// type is accepted.
+    /// ```
+    /// use spaces::{real::Interval, Dim, ProductSpace, Space};
+    ///
+    /// let space = ProductSpace::new(vec![Interval::bounded(-0.5, 0.5), Interval::unit()]);
+    /// let wider = space.replace_component(0, Interval::bounded(-1.0, 1.0)).unwrap();
+    ///
+    /// assert_eq!(wider.component(0), Ok(&Interval::bounded(-1.0, 1.0)));
+    /// assert_eq!(wider.component(1), space.component(1));
+    /// assert!(space.replace_component(2, Interval::unit()).is_err());
+    /// ```
+    pub fn replace_component(&self, index: usize, new: D) -> Result<ProductSpace<D>, ComponentError>
+    where D: Clone {
+        let mut space = self.clone();
+
+        *space.component_mut(index)? = new;
+
+        Ok(space)
+    }
+
+    /// Returns the product of the components at `indices`, in the order given.
+    ///
+    /// Indices may repeat, and the first that is out of range is reported as an error.
+    /// ```
+    /// use spaces::{discrete::Ordinal, Card, ProductSpace, Space};
+    ///
+    /// let space = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3), Ordinal::new(5)]);
+    /// let view = space.subset(&[2, 0]).unwrap();
+    ///
+    /// assert_eq!(view, ProductSpace::new(vec![Ordinal::new(5), Ordinal::new(2)]));
+    /// assert_eq!(view.card(), Card::Finite(10));
+    /// assert!(space.subset(&[0, 3]).is_err());
+    /// ```
+    pub fn subset(&self, indices: &[usize]) -> Result<ProductSpace<D>, ComponentError>
+    where D: Clone {
+        indices.iter().map(|&i| self.component(i).cloned()).collect()
+    }
+}
+
+impl<D> Default for ProductSpace<D> {
+    fn default() -> Self { ProductSpace::empty() }
+}
+
+impl<D: Space> ProductSpace<D> {
+    /// Returns the cardinality of each component space.
+    pub fn component_cards(&self) -> Vec<Card> { self.iter().map(|d| d.card()).collect() }
+
+    /// Returns the dimensionality of each component space.
+    pub fn component_dims(&self) -> Vec<Dim> { self.iter().map(|d| d.dim()).collect() }
+
+    /// Returns the cardinality of the space, factorised into its components.
+    pub fn product_card(&self) -> ProductCard { ProductCard(self.component_cards()) }
+
+    /// Returns true iff some component contains no values, in which case neither does the
+    /// product.
+    ///
+    /// Such products have a cardinality of zero, contain no values, enumerate nothing, and fail
+    /// to sample before drawing from any component.
+    /// ```
+    /// use spaces::{discrete::Ordinal, Card, ProductSpace, Space};
+    ///
+    /// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(0)]);
+    ///
+    /// assert!(space.has_empty_component());
+    /// assert_eq!(space.card(), Card::Finite(0));
+    /// assert!(!space.contains(&[1, 0]));
+    /// ```
+    pub fn has_empty_component(&self) -> bool {
+        self.iter().any(|d| d.card() == Card::Finite(0))
+    }
+
+    /// Returns the entries of `full` at `indices`, in the order given, such that the result is a
+    /// value of `subset(indices)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `full` does not have one entry per component.
+    pub fn project_value(
+        &self,
+        full: &[D::Value],
+        indices: &[usize],
+    ) -> Result<Vec<D::Value>, ComponentError> {
+        assert_eq!(full.len(), self.0.len(), "Value does not match the dimension of the space.");
 
-    pub fn into_iter(self) -> VecIntoIter<D> { self.0.into_iter() }
+        indices.iter().map(|&i| self.check_component(i).map(|i| full[i].clone())).collect()
+    }
+}
+
+/// Bounds of a product aggregate over its components; the empty product is bounded.
+impl<D: BoundedSpace> ProductSpace<D>
+where D::Value: PartialOrd
+{
+    /// Returns true iff every component has a finite infimum.
+    pub fn is_lower_bounded(&self) -> bool { self.iter().all(|d| d.is_lower_bounded()) }
+
+    /// Returns true iff every component has a finite supremum.
+    pub fn is_upper_bounded(&self) -> bool { self.iter().all(|d| d.is_upper_bounded()) }
+
+    /// Returns true iff every component has finite bounds in both directions.
+    pub fn is_bounded(&self) -> bool { self.iter().all(|d| d.is_bounded()) }
+
+    /// Returns true iff `val` has one entry per component, each lying within its bounds.
+    pub fn contains(&self, val: &[D::Value]) -> bool {
+        val.len() == self.0.len() && self.iter().zip(val).all(|(d, v)| d.contains(v.clone()))
+    }
 }
 
 impl ProductSpace<Interval> {
+    /// Write the clamp of `val` onto the box into `out`, reusing its allocation.
+    ///
+    /// This is the buffered counterpart of `map_onto`; `out` is resized to the dimension of the
+    /// box, and its previous contents are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` does not have one component per dimension.
+    pub fn map_into(&self, val: &[f64], out: &mut Vec<f64>) {
+        assert_eq!(val.len(), self.0.len(), "Value does not match the dimension of the space.");
+
+        out.clear();
+        out.extend(self.iter().zip(val).map(|(d, &v)| d.map_onto(v)));
+    }
+
+    /// Checks that every value in `vals` is contained in the space, identifying the first that
+    /// is not.
+    pub fn contains_all<'a, I>(&self, vals: I) -> Result<(), ContainsError>
+    where I: IntoIterator<Item = &'a Vec<f64>> {
+        match vals.into_iter().enumerate().find(|(_, v)| !self.contains(v)) {
+            Some((index, v)) => Err(ContainsError::new(index, v)),
+            None => Ok(()),
+        }
+    }
+
+    /// As `contains_all`, but for a row-major matrix of values with one row per sample; see
+    /// `contains_all_matrix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` is not a multiple of the number of dimensions.
+    pub fn contains_all_rows(&self, data: &[f64]) -> Result<(), ContainsError> {
+        self.contains_all_matrix(data, Layout::RowMajor)
+    }
+
+    /// As `contains_all`, but for a matrix of values with one row per sample, stored in `data`
+    /// with the given layout.
+    ///
+    /// The bounds are checked one column at a time, which avoids per-row dispatch over the
+    /// dimensions, and `data` is read in place for either layout. The error reports the first
+    /// violating row.
+    /// ```
+    /// use spaces::{real::Interval, Layout, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(0.0)]);
+    ///
+    /// // The rows [0.5, 2.0], [1.0, 0.0] and [0.0, -1.0], one column after another.
+    /// let data = [0.5, 1.0, 0.0, 2.0, 0.0, -1.0];
+    ///
+    /// assert_eq!(space.contains_all_matrix(&data, Layout::ColumnMajor).unwrap_err().index, 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` is not a multiple of the number of dimensions.
+    pub fn contains_all_matrix(&self, data: &[f64], layout: Layout) -> Result<(), ContainsError> {
+        let n_cols = self.0.len();
+
+        if n_cols == 0 {
+            assert!(data.is_empty(), "Data does not match the dimensionality of the space.");
+
+            return Ok(());
+        }
+
+        assert!(
+            data.len() % n_cols == 0,
+            "Data does not match the dimensionality of the space."
+        );
+
+        let n_rows = data.len() / n_cols;
+        let at = |i, j| data[layout.offset(n_rows, n_cols, i, j)];
+        let first = self.iter().enumerate().filter_map(|(j, d)| {
+            (0..n_rows).find(|&i| {
+                let v = at(i, j);
+
+                v.is_nan() || !d.contains(v)
+            })
+        }).min();
+
+        match first {
+            Some(i) => {
+                let row: Vec<f64> = (0..n_cols).map(|j| at(i, j)).collect();
+
+                Err(ContainsError::new(i, &row))
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Clamp each row of a row-major batch of values into the box, returning the clamped batch
+    /// and the number of rows that were modified.
+    ///
+    /// Rows must have one value per dimension of the box; `NaN` values are treated as modified
+    /// if, and only if, they are clamped onto a bound.
+    /// ```
+    /// use spaces::{real::Interval, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]);
+    /// let actions = [0.5, 0.5, 2.0, 0.0, -0.5, -3.0];
+    ///
+    /// assert_eq!(space.clamp_rows(&actions), Ok((vec![0.5, 0.5, 1.0, 0.0, -0.5, -1.0], 2)));
+    /// ```
+    pub fn clamp_rows(&self, data: &[f64]) -> Result<(Vec<f64>, usize), RowsError> {
+        self.clamp_matrix(data, Layout::RowMajor)
+    }
+
+    /// As `clamp_rows`, but clamps the batch in place and returns only the number of rows that
+    /// were modified.
+    ///
+    /// No values are modified when an error is returned.
+    pub fn clamp_rows_in_place(&self, data: &mut [f64]) -> Result<usize, RowsError> {
+        self.clamp_matrix_in_place(data, Layout::RowMajor)
+    }
+
+    /// As `clamp_rows`, but for a matrix of values with one row per sample, stored in `data`
+    /// with the given layout; the clamped matrix has the same layout.
+    pub fn clamp_matrix(
+        &self,
+        data: &[f64],
+        layout: Layout,
+    ) -> Result<(Vec<f64>, usize), RowsError> {
+        let mut out = data.to_vec();
+
+        self.clamp_matrix_in_place(&mut out, layout).map(|n| (out, n))
+    }
+
+    /// As `clamp_matrix`, but clamps the matrix in place and returns only the number of rows
+    /// that were modified.
+    ///
+    /// Either layout is traversed contiguously, without copying `data`. No values are modified
+    /// when an error is returned.
+    pub fn clamp_matrix_in_place(
+        &self,
+        data: &mut [f64],
+        layout: Layout,
+    ) -> Result<usize, RowsError> {
+        let n_cols = self.0.len();
+        let n_rows = check_input(data, n_cols)?;
+
+        if n_rows == 0 { return Ok(0); }
+
+        fn clamp(d: &Interval, v: &mut f64) -> bool {
+            let clamped = d.map_onto(*v);
+            let changed = clamped != *v && !(clamped.is_nan() && v.is_nan());
+
+            *v = clamped;
+
+            changed
+        }
+
+        match layout {
+            Layout::RowMajor => Ok(data.chunks_mut(n_cols).map(|row| {
+                row.iter_mut().zip(self.iter()).fold(false, |modified, (v, d)| {
+                    clamp(d, v) || modified
+                })
+            }).filter(|&modified| modified).count()),
+            Layout::ColumnMajor => {
+                let mut modified = vec![false; n_rows];
+
+                for (col, d) in data.chunks_mut(n_rows).zip(self.iter()) {
+                    for (v, m) in col.iter_mut().zip(modified.iter_mut()) {
+                        *m |= clamp(d, v);
+                    }
+                }
+
+                Ok(modified.into_iter().filter(|&m| m).count())
+            },
+        }
+    }
+
+    /// Returns the fraction of the cells of a uniform grid over the box that contain at least
+    /// one of `samples`.
+    ///
+    /// Each dimension is split into `resolution` cells, so the score of `n` distinct samples is
+    /// at most `n / resolution^d`; samples outside of the box are clamped onto its boundary
+    /// cells. The coverage of an empty batch, or of an empty box, is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the box is unbounded, or `resolution` is zero.
+    /// ```
+    /// use spaces::{real::Interval, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]);
+    ///
+    /// assert_eq!(space.coverage(&[vec![0.1, 0.1], vec![0.9, 0.9], vec![0.8, 0.7]], 2), 0.5);
+    /// ```
+    pub fn coverage(&self, samples: &[Vec<f64>], resolution: usize) -> f64 {
+        assert!(resolution > 0, "Coverage grid must have a resolution of 1 or greater.");
+
+        let grid = self.clone().equipartitioned(resolution);
+
+        if self.0.is_empty() || samples.is_empty() || self.iter().any(|d| d.is_empty()) {
+            return 0.0;
+        }
+
+        let cells: HashSet<Vec<usize>> = samples
+            .iter()
+            .map(|s| Surjection::<_, Vec<usize>>::map_onto(&grid, s.clone()))
+            .collect();
+
+        cells.len() as f64 / (resolution as f64).powi(self.0.len() as i32)
+    }
+
     pub fn equipartitioned(self, density: usize) -> ProductSpace<Equipartition> {
         self.into_iter()
             .map(|d| Equipartition::from_interval(d, density))
@@ -33,6 +371,74 @@ impl ProductSpace<Interval> {
     }
 }
 
+impl ProductSpace<Interval> {
+    /// Return the image of `self` under `map`, applied to every dimension, along with the map
+    /// itself.
+    pub fn affine(self, map: AffineMap) -> (ProductSpace<Interval>, AffineMap) {
+        (self.into_iter().map(|d| map.apply_interval(d)).collect(), map)
+    }
+}
+
+impl Add<f64> for ProductSpace<Interval> {
+    type Output = Self;
+
+    fn add(self, rhs: f64) -> Self { self.affine(AffineMap::shifting(rhs)).0 }
+}
+
+impl Sub<f64> for ProductSpace<Interval> {
+    type Output = Self;
+
+    fn sub(self, rhs: f64) -> Self { self.affine(AffineMap::shifting(-rhs)).0 }
+}
+
+impl Mul<f64> for ProductSpace<Interval> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self { self.affine(AffineMap::scaling(rhs)).0 }
+}
+
+impl Neg for ProductSpace<Interval> {
+    type Output = Self;
+
+    fn neg(self) -> Self { self.affine(AffineMap::scaling(-1.0)).0 }
+}
+
+/// Error type for invalid cells and indices of a grid, `ProductSpace<Equipartition>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GridError {
+    /// The grid has no dimensions, and therefore no cells.
+    Empty,
+    /// The number of cells in the grid overflows `usize`.
+    Overflow,
+    /// The cell does not have one coordinate per dimension of the grid.
+    DimensionMismatch { expected: usize, found: usize },
+    /// The coordinate of the cell along dimension `dim` is out of range.
+    CellOutOfRange { dim: usize, coord: usize, n_cells: usize },
+    /// The flat index is out of range.
+    IndexOutOfRange { index: usize, n_cells: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no dimensions"),
+            GridError::Overflow => write!(f, "number of grid cells overflows usize"),
+            GridError::DimensionMismatch { expected, found } =>
+                write!(f, "expected a cell with {} coordinates, found {}", expected, found),
+            GridError::CellOutOfRange { dim, coord, n_cells } => write!(
+                f,
+                "coordinate {} along dimension {} is out of range for {} cells",
+                coord, dim, n_cells
+            ),
+            GridError::IndexOutOfRange { index, n_cells } =>
+                write!(f, "index {} is out of range for a grid of {} cells", index, n_cells),
+        }
+    }
+}
+
+impl Error for GridError {}
+
 impl ProductSpace<Equipartition> {
     pub fn centres(&self) -> Vec<Vec<f64>> {
         self.iter().map(|d| d.centres()).collect()
@@ -41,14 +447,406 @@ impl ProductSpace<Equipartition> {
     pub fn edges(&self) -> Vec<Vec<f64>> {
         self.iter().map(|d| d.edges()).collect()
     }
+
+    /// Returns the total number of cells in the grid.
+    pub fn n_cells(&self) -> Result<usize, GridError> {
+        if self.0.is_empty() { return Err(GridError::Empty); }
+
+        self.n_values().ok_or(GridError::Overflow)
+    }
+
+    /// Returns the flat index of `cell`, under row-major (lexicographic) order; the last
+    /// dimension varies fastest.
+    /// ```
+    /// use spaces::{Equipartition, ProductSpace};
+    ///
+    /// let grid = ProductSpace::new(vec![Equipartition::new(0.0, 1.0, 3); 2]);
+    ///
+    /// assert_eq!(grid.flat_index(&[1, 2]), Ok(5));
+    /// assert_eq!(grid.cell_of(5), Ok(vec![1, 2]));
+    /// ```
+    pub fn flat_index(&self, cell: &[usize]) -> Result<usize, GridError> {
+        let n_cells = self.n_cells()?;
+
+        if cell.len() != self.0.len() {
+            return Err(GridError::DimensionMismatch { expected: self.0.len(), found: cell.len(), });
+        }
+
+        if let Some((dim, (&coord, d))) =
+            cell.iter().zip(self.iter()).enumerate().find(|(_, (&c, d))| c >= d.n_partitions())
+        {
+            return Err(GridError::CellOutOfRange { dim, coord, n_cells: d.n_partitions(), });
+        }
+
+        let idx = cell.iter().zip(self.iter()).fold(0, |idx, (&c, d)| idx * d.n_partitions() + c);
+
+        debug_assert!(idx < n_cells);
+
+        Ok(idx)
+    }
+
+    /// Returns the cell at flat index `idx`; the inverse of `flat_index`.
+    pub fn cell_of(&self, idx: usize) -> Result<Vec<usize>, GridError> {
+        let n_cells = self.n_cells()?;
+
+        if idx >= n_cells { return Err(GridError::IndexOutOfRange { index: idx, n_cells, }); }
+
+        let mut rem = idx;
+        let mut cell: Vec<usize> = self.iter().rev().map(|d| {
+            let n = d.n_partitions();
+            let coord = rem % n;
+
+            rem /= n;
+
+            coord
+        }).collect();
+
+        cell.reverse();
+
+        Ok(cell)
+    }
+
+    /// Returns the discrete space of flat cell indices.
+    pub fn to_discrete(&self) -> Result<Ordinal, GridError> { self.n_cells().map(Ordinal::new) }
+}
+
+/// Maps a point onto the flat index of the cell containing it, clamping it onto the grid first.
+///
+/// # Panics
+///
+/// Panics if the point does not have one component per dimension, or the grid has no cells
+/// or too many to index.
+impl Surjection<Vec<f64>, usize> for ProductSpace<Equipartition> {
+    fn map_onto(&self, val: Vec<f64>) -> usize {
+        let cell: Vec<usize> = self.map_onto(val);
+
+        self.flat_index(&cell).unwrap_or_else(|e| panic!("Cannot index grid: {}.", e))
+    }
+}
+
+/// Order in which the values of a finite `ProductSpace` are enumerated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnumOrder {
+    /// Lexicographic order; the last component varies fastest.
+    #[default]
+    Lex,
+    /// Reflected mixed-radix Gray code; successive values differ in exactly one component.
+    Gray,
+}
+
+impl<D: FiniteSpace> ProductSpace<D> {
+    fn radices(&self) -> Vec<usize> { self.iter().map(|d| d.card_finite()).collect() }
+
+    /// Returns the number of values in the space, or `None` if it overflows `usize`.
+    fn n_values(&self) -> Option<usize> {
+        self.iter().try_fold(1usize, |acc, d| acc.checked_mul(d.card_finite()))
+    }
+
+    /// Returns an iterator over all values of the space in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values in the space overflows `usize`.
+    pub fn iter_order(&self, order: EnumOrder) -> ProductIter<'_, D> {
+        ProductIter {
+            space: self,
+            order,
+            idx: 0,
+            len: self.n_values().expect("Product space is too large to enumerate."),
+        }
+    }
+
+    /// Returns the position of `val` in the enumeration of the space under `order`, if present.
+    pub fn to_index(&self, val: &[D::Value], order: EnumOrder) -> Option<usize>
+    where D::Value: PartialEq {
+        if val.len() != self.0.len() { return None; }
+
+        let digits = self
+            .iter()
+            .zip(val.iter())
+            .map(|(d, v)| d.to_index(v))
+            .collect::<Option<Vec<usize>>>()?;
+
+        let mut idx = 0usize;
+        let mut stride = 1usize;
+
+        for (&digit, radix) in digits.iter().zip(self.radices()).rev() {
+            idx = match order {
+                EnumOrder::Lex => digit * stride + idx,
+                EnumOrder::Gray if digit % 2 == 1 => digit * stride + (stride - 1 - idx),
+                EnumOrder::Gray => digit * stride + idx,
+            };
+            stride *= radix;
+        }
+
+        Some(idx)
+    }
+
+    /// Returns the fraction of the values of the space that appear at least once in `samples`;
+    /// see `FiniteSpace::coverage`.
+    ///
+    /// Spaces with more values than fit in a `usize` have zero coverage.
+    pub fn coverage(&self, samples: &[Vec<D::Value>]) -> f64
+    where D::Value: PartialEq {
+        let n = self.n_values().unwrap_or(0);
+
+        index_coverage(n, samples.iter().filter_map(|v| self.to_index(v, EnumOrder::Lex)))
+    }
+
+    /// Returns the value at position `idx` in the enumeration of the space under `order`, if
+    /// present.
+    pub fn from_index(&self, idx: usize, order: EnumOrder) -> Option<Vec<D::Value>> {
+        if idx >= self.n_values()? { return None; }
+
+        let radices = self.radices();
+        let mut stride: usize = radices.iter().product();
+        let mut rem = idx;
+
+        self.iter()
+            .zip(radices)
+            .map(|(d, radix)| {
+                stride /= radix;
+
+                let digit = rem / stride;
+
+                rem %= stride;
+
+                if order == EnumOrder::Gray && digit % 2 == 1 {
+                    rem = stride - 1 - rem;
+                }
+
+                d.from_index(digit)
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over every `stride`th value of the space under `order`, looked up
+    /// lazily with `from_index`; see `FiniteSpace::iter_every`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero, or if the number of values in the space overflows `usize`.
+    pub fn iter_every(
+        &self,
+        stride: usize,
+        order: EnumOrder,
+    ) -> impl Iterator<Item = Vec<D::Value>> + '_ {
+        let n = self.n_values().expect("Product space is too large to enumerate.");
+
+        (0..n).step_by(stride).map_while(move |i| self.from_index(i, order))
+    }
+
+    /// Returns an iterator over `k` values spread evenly over the enumeration of the space under
+    /// `order`, looked up lazily with `from_index`; see `FiniteSpace::iter_spread`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values in the space overflows `usize`.
+    /// ```
+    /// use spaces::{discrete::Ordinal, EnumOrder, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+    /// let spread: Vec<_> = space.iter_spread(3, EnumOrder::Lex).collect();
+    ///
+    /// assert_eq!(spread, vec![vec![0, 0], vec![1, 0], vec![2, 0]]);
+    /// ```
+    pub fn iter_spread(
+        &self,
+        k: usize,
+        order: EnumOrder,
+    ) -> impl Iterator<Item = Vec<D::Value>> + '_ {
+        let n = self.n_values().expect("Product space is too large to enumerate.");
+
+        Spread::new(n, k).map_while(move |i| self.from_index(i, order))
+    }
+}
+
+/// Iterator over the values of a finite `ProductSpace`.
+pub struct ProductIter<'a, D> {
+    space: &'a ProductSpace<D>,
+    order: EnumOrder,
+    idx: usize,
+    len: usize,
+}
+
+impl<'a, D> Clone for ProductIter<'a, D> {
+    fn clone(&self) -> Self {
+        ProductIter { space: self.space, order: self.order, idx: self.idx, len: self.len, }
+    }
+}
+
+impl<'a, D: Space + fmt::Debug> fmt::Debug for ProductIter<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProductIter")
+            .field("space", self.space)
+            .field("order", &self.order)
+            .field("idx", &self.idx)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<'a, D: FiniteSpace> Iterator for ProductIter<'a, D> {
+    type Item = Vec<D::Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.len { return None; }
+
+        let val = self.space.from_index(self.idx, self.order);
+
+        self.idx += 1;
+
+        val
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.len - self.idx;
+
+        (n, Some(n))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.idx = self.idx.saturating_add(n).min(self.len);
+        self.next()
+    }
 }
 
+impl<'a, D: FiniteSpace> ExactSizeIterator for ProductIter<'a, D> {}
+
+impl<'a, D: FiniteSpace> FusedIterator for ProductIter<'a, D> {}
+
 impl<D: Space> Space for ProductSpace<D> {
     type Value = Vec<D::Value>;
 
     fn dim(&self) -> Dim { self.iter().fold(Dim::Finite(0), |acc, d| acc + d.dim()) }
 
-    fn card(&self) -> Card { self.iter().fold(Card::Finite(0), |acc, d| acc * d.card()) }
+    /// Products are flat vectors of their components, of any dimensionality.
+    fn shape(&self) -> Shape { Shape::vector(self.dim().into()) }
+
+    /// Products with an empty component are empty; see `has_empty_component`.
+    fn card(&self) -> Card { self.product_card().total() }
+}
+
+/// Prints a summary of the product, `ProductSpace { n, dim, card }`; the alternate format,
+/// `{:#?}`, additionally lists the first `DEBUG_LIMIT` components.
+impl<D: Space + fmt::Debug> fmt::Debug for ProductSpace<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut s = f.debug_struct("ProductSpace");
+
+        s.field("n", &self.0.len()).field("dim", &self.dim()).field("card", &self.card());
+
+        if alternate { s.field("components", &DebugCapped(self.iter())); }
+
+        s.finish()
+    }
+}
+
+/// Products with an empty component fail to sample up front, rather than in that component.
+impl<D: Sample> Sample for ProductSpace<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value {
+        if self.has_empty_component() {
+            panic!("Cannot sample from space: {}.", SampleError::Empty)
+        }
+
+        self.iter().map(|d| d.sample(rng)).collect()
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        if self.has_empty_component() { return Err(SampleError::Empty); }
+
+        self.iter().map(|d| d.try_sample(rng)).collect()
+    }
+}
+
+/// Error type for invalid fixed components in `ProductSpace::sample_partial`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixError {
+    /// The index does not refer to a component of the product.
+    OutOfRange { index: usize, n_components: usize },
+    /// The component was fixed more than once.
+    Duplicate { index: usize },
+    /// The fixed value is not contained in the component space.
+    NotContained { index: usize },
+    /// A free component could not be sampled.
+    Sample { index: usize, error: SampleError },
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FixError::OutOfRange { index, n_components } => write!(
+                f,
+                "component {} is out of range for a product of {} spaces",
+                index, n_components
+            ),
+            FixError::Duplicate { index } => write!(f, "component {} is fixed twice", index),
+            FixError::NotContained { index } =>
+                write!(f, "fixed value of component {} is not contained in its space", index),
+            FixError::Sample { index, error } =>
+                write!(f, "cannot sample component {}: {}", index, error),
+        }
+    }
+}
+
+impl Error for FixError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FixError::Sample { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<D: Sample + BoundedSpace> ProductSpace<D>
+where D::Value: PartialOrd
+{
+    /// Draw a value with the components in `fixed` held at the given values, sampling the rest
+    /// uniformly from their spaces.
+    ///
+    /// Fixed values appear verbatim in the output, and are validated against their component
+    /// spaces before any sampling takes place; free components that cannot be sampled (e.g.
+    /// empty spaces) yield `FixError::Sample`.
+    /// ```
+    /// use spaces::{discrete::Ordinal, ProductSpace};
+    ///
+    /// let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4), Ordinal::new(5)]);
+    /// let val = space.sample_partial(&mut rand::thread_rng(), &[(2, 4), (0, 1)]).unwrap();
+    ///
+    /// assert_eq!((val[0], val[2]), (1, 4));
+    /// ```
+    pub fn sample_partial<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        fixed: &[(usize, D::Value)],
+    ) -> Result<Vec<D::Value>, FixError> {
+        let n_components = self.0.len();
+        let mut values: Vec<Option<D::Value>> = vec![None; n_components];
+
+        for (index, val) in fixed.iter().cloned() {
+            let slot = values
+                .get_mut(index)
+                .ok_or(FixError::OutOfRange { index, n_components, })?;
+
+            if slot.is_some() { return Err(FixError::Duplicate { index, }); }
+
+            if !self.0[index].contains(val.clone()) {
+                return Err(FixError::NotContained { index, });
+            }
+
+            *slot = Some(val);
+        }
+
+        self.iter()
+            .zip(values)
+            .enumerate()
+            .map(|(index, (d, v))| match v {
+                Some(v) => Ok(v),
+                None => d.try_sample(rng).map_err(|error| FixError::Sample { index, error }),
+            })
+            .collect()
+    }
 }
 
 impl<D: Space + Union + Clone> Union for ProductSpace<D> {
@@ -86,7 +884,7 @@ where D: Space + Surjection<X, <D as Space>::Value>
 {
     fn map_onto(&self, val: Vec<X>) -> Vec<D::Value> {
         self.iter()
-            .zip(val.into_iter())
+            .zip(val)
             .map(|(d, v)| d.map_onto(v))
             .collect()
     }
@@ -104,11 +902,11 @@ impl<D> FromIterator<D> for ProductSpace<D> {
     }
 }
 
-impl<D: Space> IntoIterator for ProductSpace<D> {
+impl<D> IntoIterator for ProductSpace<D> {
     type Item = D;
     type IntoIter = ::std::vec::IntoIter<D>;
 
-    fn into_iter(self) -> Self::IntoIter { self.into_iter() }
+    fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
 }
 
 impl<D: Space> Add<D> for ProductSpace<D> {
@@ -121,7 +919,7 @@ impl<D: Space> Add<ProductSpace<D>> for ProductSpace<D> {
     type Output = Self;
 
     fn add(self, rhs: ProductSpace<D>) -> Self::Output {
-        FromIterator::from_iter(self.into_iter().chain(rhs.into_iter()))
+        FromIterator::from_iter(self.into_iter().chain(rhs))
     }
 }
 
@@ -141,10 +939,61 @@ impl<D: Space + Display> fmt::Display for ProductSpace<D> {
 
 #[cfg(test)]
 mod tests {
-    use crate::discrete::Ordinal;
+    use crate::{discrete::{Binary, Naturals}, Empty, PairSpace, TwoSpace, DEBUG_LIMIT};
     use std::iter::FromIterator;
     use super::*;
 
+    #[test]
+    fn test_bounded() {
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::unit()]);
+
+        assert!(space.is_lower_bounded() && space.is_upper_bounded() && space.is_bounded());
+
+        let space = space.replace_component(1, Interval::left_bounded(0.0)).unwrap();
+
+        assert!(space.is_lower_bounded());
+        assert!(!space.is_upper_bounded());
+        assert!(!space.is_bounded());
+
+        let space = ProductSpace::new(vec![Naturals, Naturals]);
+
+        assert_eq!((space.is_lower_bounded(), space.is_bounded()), (true, false));
+        assert!(ProductSpace::<Interval>::empty().is_bounded());
+    }
+
+    #[test]
+    fn test_debug() {
+        let space = ProductSpace::new(vec![Binary; 1000]);
+
+        assert_eq!(
+            format!("{:?}", space),
+            "ProductSpace { n: 1000, dim: Finite(1000), card: Infinite }"
+        );
+        assert_eq!(
+            format!("{:?}", ProductSpace::new(vec![Ordinal::new(3); 2])),
+            "ProductSpace { n: 2, dim: Finite(2), card: Finite(9) }"
+        );
+        assert_eq!(
+            format!("{:#?}", space),
+            format!(
+                "ProductSpace {{\n    n: 1000,\n    dim: Finite(\n        1000,\n    ),\n    \
+                 card: Infinite,\n    components: [\n{}        ... 968 more,\n    ],\n}}",
+                "        Binary,\n".repeat(DEBUG_LIMIT)
+            )
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(ProductSpace::<Interval>::default(), ProductSpace::empty());
+        assert_eq!(ProductSpace::<Ordinal>::default().dim(), Dim::Finite(0));
+        assert_eq!(
+            PairSpace::<Binary, Ordinal>::default().component_cards(),
+            vec![Card::Finite(2), Card::Finite(2)]
+        );
+        assert_eq!(TwoSpace::<Interval>::default()[1], Interval::unit());
+    }
+
     #[test]
     fn test_dim() {
         assert_eq!(ProductSpace::new(vec![Ordinal::new(2); 2]).dim(), Dim::Finite(2));
@@ -158,6 +1007,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_empty_product() {
+        let space = ProductSpace::<Ordinal>::empty();
+        let values: Vec<_> = space.iter_order(EnumOrder::Lex).collect();
+
+        assert_eq!(space.card(), Card::Finite(1));
+        assert_eq!(values, vec![Vec::<usize>::new()]);
+        assert!(space.contains(&[]));
+        assert_eq!(space.to_index(&[], EnumOrder::Lex), Some(0));
+        assert_eq!(space.from_index(0, EnumOrder::Gray), Some(vec![]));
+        assert_eq!(space.from_index(1, EnumOrder::Gray), None);
+    }
+
+    #[test]
+    fn test_component_cards() {
+        let space = ProductSpace::new(vec![
+            Ordinal::new(3), Ordinal::new(5), Ordinal::new(2), Ordinal::new(7), Ordinal::new(11),
+            Ordinal::new(13), Ordinal::new(17), Ordinal::new(19),
+        ]);
+
+        assert_eq!(space.component_cards().len(), 8);
+        assert_eq!(space.component_dims(), vec![Dim::Finite(1); 8]);
+        assert_eq!(space.product_card().total(), space.card());
+        assert_eq!(space.card(), Card::Finite(9699690));
+        assert_eq!(format!("{}", space.product_card()), "9699690");
+        assert_eq!(
+            format!("{:#}", space.product_card()),
+            "3 \u{d7} 5 \u{d7} 2 \u{d7} 7 \u{d7} 11 \u{d7} 13 \u{d7} 17 \u{d7} 19"
+        );
+
+        let huge = ProductSpace::new(vec![Ordinal::new(1 << 40); 2]);
+
+        assert_eq!(huge.product_card().total(), huge.card());
+        assert_eq!(huge.card(), Card::Infinite);
+        assert_eq!(format!("{:#}", huge.product_card()), "1099511627776 \u{d7} 1099511627776");
+    }
+
     #[test]
     fn test_union() {
         let s1 = ProductSpace::new(vec![Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 3.0)]);
@@ -169,6 +1055,45 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0)]);
+
+        for _ in 0..100 {
+            let v = space.sample(&mut rng);
+
+            assert_eq!(v.len(), 2);
+            assert!(space[0].contains(v[0]) && space[1].contains(v[1]));
+        }
+
+        let space = ProductSpace::new(vec![
+            Interval::bounded(-f64::MAX, f64::MAX),
+            Interval::bounded(1e308, 1.7e308),
+            Interval::bounded(0.0, f64::from_bits(3)),
+        ]);
+
+        assert!((0..100).all(|_| space.contains(&space.sample(&mut rng))));
+    }
+
+    #[test]
+    fn test_try_sample() {
+        let mut rng = ::rand::thread_rng();
+        let good = ProductSpace::new(vec![Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0)]);
+        let bad = good.clone() + Interval::left_bounded(0.0);
+
+        assert!(good.try_sample(&mut rng).is_ok());
+        assert_eq!(bad.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(
+            PairSpace::new(Ordinal::new(3), Ordinal::new(0)).try_sample(&mut rng),
+            Err(SampleError::Empty)
+        );
+        assert_eq!(
+            TwoSpace::new([Interval::unit(), Interval::bounded(0.0, f64::NAN)]).try_sample(&mut rng),
+            Err(SampleError::NoDistribution)
+        );
+    }
+
     #[test]
     fn test_surjection() {
         let space = ProductSpace::new(vec![Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0)]);
@@ -180,7 +1105,7 @@ mod tests {
 
     #[test]
     fn test_indexing() {
-        let dimensions = vec![Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0)];
+        let dimensions = [Interval::bounded(0.0, 5.0), Interval::bounded(1.0, 2.0)];
         let space = ProductSpace::from_iter(dimensions.iter().cloned());
 
         assert_eq!(space[0], dimensions[0]);
@@ -195,6 +1120,460 @@ mod tests {
         assert_eq!(space.into_iter().collect::<Vec<Interval>>(), dimensions);
     }
 
+    #[test]
+    fn test_contains_all() {
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::left_bounded(0.0)]);
+        let rows = vec![vec![0.5, 10.0], vec![1.0, 0.0], vec![0.0, 3.0]];
+
+        assert_eq!(space.contains_all(&[]), Ok(()));
+        assert_eq!(space.contains_all(&rows), Ok(()));
+        assert_eq!(space.contains_all_rows(&[]), Ok(()));
+
+        let mut first = rows.clone();
+        first[0][1] = -1.0;
+
+        let mut last = rows.clone();
+        last[2][0] = 1.5;
+
+        let mut both = rows.clone();
+        both[1][1] = -1.0;
+        both[2][0] = f64::NAN;
+
+        for (vals, index) in [(first, 0), (last, 2), (both, 1)] {
+            let flat: Vec<f64> = vals.iter().flatten().cloned().collect();
+            let err = space.contains_all(&vals).unwrap_err();
+
+            assert_eq!(err.index, index);
+            assert_eq!(err.value, format!("{:?}", vals[index]));
+            assert_eq!(space.contains_all_rows(&flat), Err(err));
+        }
+
+        assert!(space.contains_all(&[vec![0.5]]).is_err());
+    }
+
+    #[test]
+    fn test_sample_partial() {
+        let mut rng = ::rand::thread_rng();
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(5.0, 6.0),
+        ]);
+
+        for _ in 0..100 {
+            let val = space.sample_partial(&mut rng, &[(2, 5.25), (0, 1.0)]).unwrap();
+
+            assert_eq!(val[0], 1.0);
+            assert_eq!(val[2], 5.25);
+            assert!(space.contains(&val));
+        }
+
+        assert_eq!(space.sample_partial(&mut rng, &[]).map(|v| v.len()), Ok(3));
+    }
+
+    #[test]
+    fn test_sample_partial_invalid() {
+        let mut rng = ::rand::thread_rng();
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4)]);
+
+        assert_eq!(
+            space.sample_partial(&mut rng, &[(0, 1), (2, 0)]),
+            Err(FixError::OutOfRange { index: 2, n_components: 2 })
+        );
+        assert_eq!(
+            space.sample_partial(&mut rng, &[(1, 1), (1, 2)]),
+            Err(FixError::Duplicate { index: 1 })
+        );
+        assert_eq!(
+            space.sample_partial(&mut rng, &[(0, 1), (1, 4)]),
+            Err(FixError::NotContained { index: 1 })
+        );
+
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(0)]);
+
+        assert_eq!(
+            space.sample_partial(&mut rng, &[(0, 1)]),
+            Err(FixError::Sample { index: 1, error: SampleError::Empty })
+        );
+    }
+
+    #[test]
+    fn test_clamp_rows() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::left_bounded(0.0),
+        ]);
+        let data = [
+            0.5, 0.0, 3.0,
+            2.0, 0.0, 3.0,
+            0.5, -5.0, -1.0,
+            1.0, 1.0, f64::NAN,
+            f64::NAN, 0.5, 10.0,
+        ];
+        let (clamped, n) = space.clamp_rows(&data).unwrap();
+
+        assert_eq!(n, 4);
+        assert_eq!(clamped, vec![
+            0.5, 0.0, 3.0,
+            1.0, 0.0, 3.0,
+            0.5, -1.0, 0.0,
+            1.0, 1.0, 0.0,
+            0.0, 0.5, 10.0,
+        ]);
+
+        let mut data = data;
+
+        assert_eq!(space.clamp_rows_in_place(&mut data), Ok(4));
+        assert_eq!(data.to_vec(), clamped);
+        assert_eq!(space.clamp_rows_in_place(&mut data), Ok(0));
+
+        // NaN values are only left in place by unbounded dimensions.
+        let space = ProductSpace::new(vec![Interval::unbounded(), Interval::bounded(0.0, 1.0)]);
+        let (clamped, n) = space.clamp_rows(&[f64::NAN, 0.5, f64::NAN, 2.0]).unwrap();
+
+        assert_eq!(n, 1);
+        assert!(clamped[0].is_nan() && clamped[2].is_nan());
+    }
+
+    // Transposes a row-major matrix with `n_cols` columns into column-major order.
+    fn to_column_major(data: &[f64], n_cols: usize) -> Vec<f64> {
+        let n_rows = data.len() / n_cols;
+
+        (0..n_cols).flat_map(|j| (0..n_rows).map(move |i| data[i * n_cols + j])).collect()
+    }
+
+    #[test]
+    fn test_matrix_layouts() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::left_bounded(0.0),
+        ]);
+        let rows = [
+            0.5, 0.0, 3.0,
+            2.0, 0.0, 3.0,
+            0.5, -5.0, -1.0,
+            1.0, 1.0, f64::NAN,
+        ];
+        let cols = to_column_major(&rows, 3);
+
+        assert_eq!(cols[..4], [0.5, 2.0, 0.5, 1.0]);
+        assert_eq!(
+            space.contains_all_matrix(&cols, Layout::ColumnMajor),
+            space.contains_all_rows(&rows)
+        );
+        assert_eq!(space.contains_all_matrix(&cols, Layout::ColumnMajor).unwrap_err().index, 1);
+        assert_eq!(
+            space.contains_all_matrix(&[0.5, 0.5, 0.0, 0.0, 3.0, -1.0], Layout::ColumnMajor),
+            Err(ContainsError::new(1, &[0.5, 0.0, -1.0]))
+        );
+
+        let (clamped_rows, n_rows) = space.clamp_rows(&rows).unwrap();
+        let (clamped_cols, n_cols) = space.clamp_matrix(&cols, Layout::ColumnMajor).unwrap();
+
+        assert_eq!(n_rows, 3);
+        assert_eq!(n_cols, 3);
+        assert_eq!(clamped_cols, to_column_major(&clamped_rows, 3));
+        assert_eq!(space.contains_all_matrix(&clamped_cols, Layout::ColumnMajor), Ok(()));
+
+        // Both layouts are clamped without reallocating.
+        for (layout, mut data) in [(Layout::RowMajor, rows.to_vec()), (Layout::ColumnMajor, cols)] {
+            let ptr = data.as_ptr();
+
+            assert_eq!(space.clamp_matrix_in_place(&mut data, layout), Ok(3));
+            assert_eq!(data.as_ptr(), ptr);
+            assert_eq!(space.clamp_matrix_in_place(&mut data, layout), Ok(0));
+        }
+
+        assert_eq!(space.clamp_matrix(&[], Layout::ColumnMajor), Ok((vec![], 0)));
+        assert_eq!(
+            space.clamp_matrix(&[0.0; 4], Layout::ColumnMajor),
+            Err(RowsError { row: 1, expected: 3, found: 1, })
+        );
+    }
+
+    #[test]
+    fn test_clamp_rows_width() {
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 3]);
+        let mut data = [2.0, 0.0, 0.0, 2.0];
+
+        assert_eq!(
+            space.clamp_rows_in_place(&mut data),
+            Err(RowsError { row: 1, expected: 3, found: 1, })
+        );
+        assert_eq!(data, [2.0, 0.0, 0.0, 2.0]);
+        assert_eq!(space.clamp_rows(&[]), Ok((vec![], 0)));
+    }
+
+    #[test]
+    fn test_map_into() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::left_bounded(0.0),
+        ]);
+        let mut out = vec![9.0; 5];
+
+        for val in [vec![0.5, 2.0], vec![-1.0, -1.0], vec![3.0, f64::NAN]] {
+            space.map_into(&val, &mut out);
+
+            assert_eq!(out, space.map_onto(val));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_into_wrong_dim() {
+        ProductSpace::new(vec![Interval::unbounded(); 2]).map_into(&[0.0], &mut vec![]);
+    }
+
+    fn grid() -> ProductSpace<Equipartition> {
+        ProductSpace::new(vec![
+            Equipartition::new(0.0, 3.0, 3),
+            Equipartition::new(-1.0, 1.0, 4),
+            Equipartition::new(0.0, 1.0, 2),
+        ])
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let grid = grid();
+
+        assert_eq!(grid.n_cells(), Ok(24));
+        assert_eq!(grid.to_discrete(), Ok(Ordinal::new(24)));
+
+        for (i, cell) in grid.iter_order(EnumOrder::Lex).enumerate() {
+            assert_eq!(grid.flat_index(&cell), Ok(i));
+            assert_eq!(grid.cell_of(i), Ok(cell));
+        }
+
+        assert_eq!(grid.cell_of(23), Ok(vec![2, 3, 1]));
+        assert_eq!(grid.cell_of(24), Err(GridError::IndexOutOfRange { index: 24, n_cells: 24 }));
+        assert_eq!(
+            grid.flat_index(&[0, 4, 0]),
+            Err(GridError::CellOutOfRange { dim: 1, coord: 4, n_cells: 4 })
+        );
+        assert_eq!(
+            grid.flat_index(&[0, 0]),
+            Err(GridError::DimensionMismatch { expected: 3, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_grid_errors() {
+        assert_eq!(ProductSpace::<Equipartition>::empty().n_cells(), Err(GridError::Empty));
+
+        let huge = ProductSpace::new(vec![Equipartition::new(0.0, 1.0, usize::MAX); 2]);
+
+        assert_eq!(huge.n_cells(), Err(GridError::Overflow));
+        assert_eq!(huge.flat_index(&[0, 0]), Err(GridError::Overflow));
+        assert_eq!(huge.to_discrete(), Err(GridError::Overflow));
+    }
+
+    #[test]
+    fn test_grid_surjection() {
+        let grid = grid();
+        let flat = |x: Vec<f64>| -> usize { grid.map_onto(x) };
+
+        assert_eq!(flat(vec![0.0, -1.0, 0.0]), 0);
+        assert_eq!(flat(vec![3.0, 1.0, 1.0]), 23);
+        assert_eq!(flat(vec![-5.0, -5.0, -5.0]), 0);
+        assert_eq!(flat(vec![5.0, 5.0, 5.0]), 23);
+
+        // Interior edges belong to the upper cell.
+        assert_eq!(flat(vec![1.0, 0.0, 0.5]), grid.flat_index(&[1, 2, 1]).unwrap());
+        assert_eq!(flat(vec![2.0, -0.5, 0.0]), grid.flat_index(&[2, 1, 0]).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_surjection_wrong_dim() {
+        let _: usize = grid().map_onto(vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_coverage_box() {
+        let mut rng = ::rand::thread_rng();
+        let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::unit()]);
+
+        assert_eq!(space.coverage(&[], 4), 0.0);
+        assert_eq!(space.coverage(&vec![vec![0.3, 0.3]; 100], 4), 1.0 / 16.0);
+        assert_eq!(space.coverage(&[vec![-5.0, 5.0]], 4), 1.0 / 16.0);
+        assert_eq!(
+            ProductSpace::new(vec![Interval::unit(), Interval::open(0.0, 0.0)])
+                .coverage(&[vec![0.5, 0.0]], 4),
+            0.0
+        );
+
+        let centres: Vec<Vec<f64>> = space
+            .clone()
+            .equipartitioned(4)
+            .iter_order(EnumOrder::Lex)
+            .map(|cell| vec![-0.75 + 0.5 * cell[0] as f64, 0.125 + 0.25 * cell[1] as f64])
+            .collect();
+
+        assert_eq!(space.coverage(&centres, 4), 1.0);
+
+        let samples: Vec<Vec<f64>> = (0..200).map(|_| space.sample(&mut rng)).collect();
+        let scores: Vec<f64> = (0..=200).map(|n| space.coverage(&samples[..n], 8)).collect();
+
+        assert!(scores.windows(2).all(|w| w[0] <= w[1]));
+        assert!(scores[200] > 0.5);
+    }
+
+    #[test]
+    fn test_affine_ops() {
+        let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::bounded(-1.0, 2.0)]);
+
+        assert_eq!(space.clone() * 2.0 - 1.0, ProductSpace::new(vec![
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(-3.0, 3.0),
+        ]));
+        assert_eq!(-space.clone(), ProductSpace::new(vec![
+            Interval::bounded(-1.0, 0.0),
+            Interval::bounded(-2.0, 1.0),
+        ]));
+
+        let (t, map) = space.affine(AffineMap::new(-2.0, 1.0));
+        let x = map.map_onto(vec![0.5, 2.0]);
+
+        assert_eq!(x, vec![0.0, -3.0]);
+        assert!(t[0].contains(x[0]));
+        assert!(t[1].contains(x[1]));
+    }
+
+    #[test]
+    fn test_iter_lex() {
+        let space = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]);
+
+        assert_eq!(space.iter_order(EnumOrder::Lex).collect::<Vec<_>>(), vec![
+            vec![0, 0], vec![0, 1], vec![0, 2],
+            vec![1, 0], vec![1, 1], vec![1, 2],
+        ]);
+    }
+
+    #[test]
+    fn test_iter_gray() {
+        let space = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]);
+
+        assert_eq!(space.iter_order(EnumOrder::Gray).collect::<Vec<_>>(), vec![
+            vec![0, 0], vec![0, 1], vec![0, 2],
+            vec![1, 2], vec![1, 1], vec![1, 0],
+        ]);
+    }
+
+    #[test]
+    fn test_iter_size_hint() {
+        let space = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]);
+
+        for &order in &[EnumOrder::Lex, EnumOrder::Gray] {
+            let mut it = space.iter_order(order);
+
+            for remaining in (0..=6).rev() {
+                assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+                assert_eq!(it.len(), remaining);
+                assert_eq!(it.clone().count(), remaining);
+
+                it.next();
+            }
+
+            assert_eq!(it.next(), None);
+            assert_eq!(it.size_hint(), (0, Some(0)));
+
+            let mut it = space.iter_order(order);
+
+            assert_eq!(it.nth(4), space.from_index(4, order));
+            assert_eq!(it.len(), 1);
+            assert_eq!(it.nth(3), None);
+            assert_eq!(it.len(), 0);
+        }
+
+        assert!(format!("{:?}", space.iter_order(EnumOrder::Gray)).starts_with("ProductIter"));
+    }
+
+    #[test]
+    fn test_gray_adjacency() {
+        fn check<D: FiniteSpace>(space: ProductSpace<D>) where D::Value: PartialEq {
+            let vals: Vec<_> = space.iter_order(EnumOrder::Gray).collect();
+
+            assert_eq!(Card::Finite(vals.len()), space.card());
+
+            for w in vals.windows(2) {
+                let n_diff = w[0].iter().zip(w[1].iter()).filter(|(a, b)| a != b).count();
+
+                assert_eq!(n_diff, 1);
+            }
+        }
+
+        check(ProductSpace::new(vec![Binary; 5]));
+        check(ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(2), Ordinal::new(4)]));
+        check(ProductSpace::new(vec![Ordinal::new(3); 3]));
+    }
+
+    #[test]
+    fn test_iter_every_spread() {
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(4), Ordinal::new(5)]);
+        let lex = |i: usize| vec![i / 20, i / 5 % 4, i % 5];
+
+        assert_eq!(
+            space.iter_every(7, EnumOrder::Lex).collect::<Vec<_>>(),
+            [0, 7, 14, 21, 28, 35, 42, 49, 56].iter().map(|&i| lex(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            space.iter_spread(4, EnumOrder::Lex).collect::<Vec<_>>(),
+            vec![vec![0, 0, 0], vec![0, 3, 0], vec![1, 2, 0], vec![2, 1, 0]]
+        );
+        assert_eq!(
+            space.iter_spread(7, EnumOrder::Lex).collect::<Vec<_>>(),
+            [0, 8, 17, 25, 34, 42, 51].iter().map(|&i| lex(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            space.iter_spread(100, EnumOrder::Gray).collect::<Vec<_>>(),
+            space.iter_order(EnumOrder::Gray).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            space.iter_every(1, EnumOrder::Lex).collect::<Vec<_>>(),
+            space.iter_order(EnumOrder::Lex).collect::<Vec<_>>()
+        );
+        assert_eq!(space.iter_every(60, EnumOrder::Gray).count(), 1);
+        assert_eq!(space.iter_spread(0, EnumOrder::Lex).count(), 0);
+        assert_eq!(
+            ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(0)])
+                .iter_spread(3, EnumOrder::Lex)
+                .count(),
+            0
+        );
+
+        let huge = ProductSpace::new(vec![Ordinal::new(1 << 20); 3]);
+        let spread: Vec<_> = huge.iter_spread(4, EnumOrder::Lex).collect();
+
+        assert_eq!(spread, vec![vec![0, 0, 0], vec![1 << 18, 0, 0], vec![1 << 19, 0, 0],
+            vec![3 << 18, 0, 0]]);
+        assert_eq!(huge.iter_every(1 << 59, EnumOrder::Lex).count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_every_zero_stride() {
+        let _ = ProductSpace::new(vec![Ordinal::new(3)]).iter_every(0, EnumOrder::Lex);
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(2), Ordinal::new(4)]);
+
+        for &order in &[EnumOrder::Lex, EnumOrder::Gray] {
+            for (i, v) in space.iter_order(order).enumerate() {
+                assert_eq!(space.to_index(&v, order), Some(i));
+                assert_eq!(space.from_index(i, order), Some(v));
+            }
+
+            assert_eq!(space.from_index(24, order), None);
+            assert_eq!(space.to_index(&[0, 2, 0], order), None);
+            assert_eq!(space.to_index(&[0, 0], order), None);
+        }
+    }
+
     #[test]
     fn test_add_op() {
         let mut sa = ProductSpace::new(vec![Ordinal::new(2); 2]);
@@ -217,4 +1596,130 @@ mod tests {
         assert_eq!(sc.dim(), Dim::Finite(6));
         assert_eq!(sc.card(), Card::Finite(144));
     }
+
+    #[test]
+    fn test_components() {
+        let mut space = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]);
+        let oob = ComponentError { index: 2, n_components: 2, };
+
+        assert_eq!(space.component(1), Ok(&Ordinal::new(3)));
+        assert_eq!(space.component(2), Err(oob));
+        assert_eq!(space.component_mut(2), Err(oob));
+        assert_eq!(ProductSpace::<Ordinal>::empty().component(0).unwrap_err().n_components, 0);
+
+        let wider = space.replace_component(0, Ordinal::new(5)).unwrap();
+
+        assert_eq!(wider.card(), Card::Finite(15));
+        assert_eq!(space.card(), Card::Finite(6));
+        assert_eq!(space.replace_component(2, Ordinal::new(5)), Err(oob));
+
+        *space.component_mut(1).unwrap() = Ordinal::new(7);
+
+        assert_eq!(space.card(), Card::Finite(14));
+
+        let unit_square = ProductSpace::new(vec![Interval::<f64>::unit(); 2]);
+        let nested = ProductSpace::new(vec![unit_square; 2]);
+        let replaced = nested.replace_component(1, ProductSpace::new(vec![Interval::unit(); 5]))
+            .unwrap();
+
+        assert_eq!(nested.dim(), Dim::Finite(4));
+        assert_eq!(replaced.dim(), Dim::Finite(7));
+        assert_eq!(replaced.component(0), nested.component(0));
+    }
+
+    #[test]
+    fn test_empty_component() {
+        let mut rng = ::rand::thread_rng();
+        let space = ProductSpace::new(vec![Empty; 2]);
+
+        assert!(space.has_empty_component());
+        assert_eq!(space.card(), Card::Finite(0));
+        assert!(!space.contains(&[(), ()]));
+        assert_eq!(space.iter_order(EnumOrder::Lex).count(), 0);
+        assert_eq!(space.try_sample(&mut rng), Err(SampleError::Empty));
+
+        let space = ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(0), Ordinal::new(2)]);
+
+        assert!(space.has_empty_component());
+        assert_eq!(space.card(), Card::Finite(0));
+        assert_eq!(space.product_card().0[1], Card::Finite(0));
+        assert!((0..3).all(|i| !space.contains(&[i, 0, 1])));
+        assert_eq!(space.iter_order(EnumOrder::Gray).next(), None);
+        assert_eq!(space.try_sample(&mut rng), Err(SampleError::Empty));
+
+        let space = ProductSpace::new(vec![Interval::bounded(0i64, 3), Interval::open(0, 1)]);
+
+        assert_eq!(space.card(), Card::Finite(0));
+        assert_eq!(space.try_sample(&mut rng), Err(SampleError::Empty));
+        assert!(!ProductSpace::new(vec![Ordinal::new(3); 2]).has_empty_component());
+        assert!(!ProductSpace::<Ordinal>::empty().has_empty_component());
+
+        let pair = PairSpace::new(Empty, Ordinal::new(3));
+
+        assert_eq!(pair.card(), Card::Finite(0));
+        assert_eq!(pair.try_sample(&mut rng), Err(SampleError::Empty));
+
+        let two = TwoSpace::new([Ordinal::new(2), Ordinal::new(0)]);
+
+        assert_eq!(two.card(), Card::Finite(0));
+        assert_eq!(two.try_sample(&mut rng), Err(SampleError::Empty));
+    }
+
+    #[test]
+    #[should_panic(expected = "space is empty")]
+    fn test_sample_empty_component() {
+        ProductSpace::new(vec![Empty; 3]).sample(&mut ::rand::thread_rng());
+    }
+
+    #[test]
+    #[should_panic(expected = "space is empty")]
+    fn test_sample_empty_pair() {
+        PairSpace::new(Ordinal::new(2), Empty).sample(&mut ::rand::thread_rng());
+    }
+
+    #[test]
+    fn test_subset() {
+        let space = ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-1.0, 0.0),
+            Interval::bounded(5.0, 6.0),
+        ]);
+        let full = vec![0.5, -0.5, 5.5];
+        let view = space.subset(&[2, 0]).unwrap();
+
+        assert_eq!(view, ProductSpace::new(vec![space[2], space[0]]));
+        assert_eq!(view.dim(), Dim::Finite(2));
+        assert_eq!(space.project_value(&full, &[2, 0]), Ok(vec![5.5, 0.5]));
+        assert!(view.contains(&space.project_value(&full, &[2, 0]).unwrap()));
+        assert_eq!(space.subset(&[]), Ok(ProductSpace::empty()));
+        assert_eq!(space.subset(&[1, 1]).unwrap().dim(), Dim::Finite(2));
+
+        let oob = ComponentError { index: 4, n_components: 3, };
+
+        assert_eq!(space.subset(&[0, 4, 5]), Err(oob));
+        assert_eq!(space.project_value(&full, &[4]), Err(oob));
+
+        let discrete = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3), Ordinal::new(4)]);
+
+        assert_eq!(discrete.subset(&[1, 2]).unwrap().card(), Card::Finite(12));
+
+        // Nested products are viewed one level at a time.
+        let nested = ProductSpace::new(vec![discrete.clone(), discrete.subset(&[0]).unwrap()]);
+        let inner = nested.subset(&[0]).unwrap()[0].subset(&[2]).unwrap();
+
+        assert_eq!(inner, ProductSpace::new(vec![Ordinal::new(4)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_project_wrong_dim() {
+        ProductSpace::new(vec![Ordinal::new(2); 3]).project_value(&[0, 1], &[0]).unwrap();
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(ProductSpace::new(vec![Interval::<f64>::unit(); 3]); shape, fmt, serde);
+        assert_space_laws!(ProductSpace::<Interval>::empty(); shape, fmt, serde);
+        assert_space_laws!(ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]); shape, serde);
+    }
 }
@@ -0,0 +1,186 @@
+use crate::prelude::*;
+use std::{error::Error, fmt};
+
+/// Error returned when a vector of values does not have one value per map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapLengthError {
+    /// Number of maps, and so of values required.
+    pub expected: usize,
+    /// Number of values given.
+    pub found: usize,
+}
+
+impl fmt::Display for MapLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} values, one per map, but found {}", self.expected, self.found)
+    }
+}
+
+impl Error for MapLengthError {}
+
+/// Product of two surjections, mapping the components of pairs independently.
+///
+/// Tuples of up to eight surjections, and vectors of surjections of the same type, are products
+/// in the same way; together with `Chain`, this allows structured preprocessing pipelines to be
+/// written declaratively.
+/// ```
+/// use spaces::{discrete::Ordinal, Equipartition, Interval, ProductMap, Surjection};
+///
+/// let map = ProductMap::new(Interval::bounded(0.0, 1.0), Equipartition::new(0.0, 1.0, 4));
+///
+/// assert_eq!(map.map_onto((1.5, 0.3)), (1.0, 1));
+/// assert_eq!((Ordinal::new(3), Interval::bounded(0i64, 5)).map_onto((7usize, -2)), (2, 0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ProductMap<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> ProductMap<A, B> {
+    pub fn new(first: A, second: B) -> ProductMap<A, B> { ProductMap { first, second, } }
+
+    #[inline]
+    pub fn first(&self) -> &A { &self.first }
+
+    #[inline]
+    pub fn second(&self) -> &B { &self.second }
+}
+
+impl<X1, X2, Y1, Y2, A, B> Surjection<(X1, X2), (Y1, Y2)> for ProductMap<A, B>
+where
+    A: Surjection<X1, Y1>,
+    B: Surjection<X2, Y2>,
+{
+    fn map_onto(&self, from: (X1, X2)) -> (Y1, Y2) {
+        (self.first.map_onto(from.0), self.second.map_onto(from.1))
+    }
+}
+
+/// Extension methods for combining surjections.
+pub trait SurjectionExt: Sized {
+    /// Returns the product of `self` and `other`; see `ProductMap`.
+    fn pair<B>(self, other: B) -> ProductMap<Self, B> { ProductMap::new(self, other) }
+}
+
+impl<M> SurjectionExt for M {}
+
+macro_rules! impl_tuple_map {
+    ($(($($m:ident $x:ident $y:ident $i:tt),+)),+ $(,)?) => {$(
+        impl<$($m, $x, $y),+> Surjection<($($x,)+), ($($y,)+)> for ($($m,)+)
+        where $($m: Surjection<$x, $y>),+
+        {
+            fn map_onto(&self, from: ($($x,)+)) -> ($($y,)+) { ($(self.$i.map_onto(from.$i),)+) }
+        }
+    )+};
+}
+
+impl_tuple_map!(
+    (M0 X0 Y0 0, M1 X1 Y1 1),
+    (M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2),
+    (M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2, M3 X3 Y3 3),
+    (M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2, M3 X3 Y3 3, M4 X4 Y4 4),
+    (M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2, M3 X3 Y3 3, M4 X4 Y4 4, M5 X5 Y5 5),
+    (M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2, M3 X3 Y3 3, M4 X4 Y4 4, M5 X5 Y5 5, M6 X6 Y6 6),
+    (
+        M0 X0 Y0 0, M1 X1 Y1 1, M2 X2 Y2 2, M3 X3 Y3 3,
+        M4 X4 Y4 4, M5 X5 Y5 5, M6 X6 Y6 6, M7 X7 Y7 7
+    ),
+);
+
+/// Map each of `from` through the map at the same position, or return an error if there is not
+/// exactly one value per map.
+pub fn try_map_each<X, Y, M>(maps: &[M], from: Vec<X>) -> Result<Vec<Y>, MapLengthError>
+where M: Surjection<X, Y>
+{
+    if from.len() != maps.len() {
+        return Err(MapLengthError { expected: maps.len(), found: from.len(), });
+    }
+
+    Ok(maps.iter().zip(from).map(|(m, x)| m.map_onto(x)).collect())
+}
+
+/// # Panics
+///
+/// Panics if there is not exactly one value per map; see `try_map_each`.
+impl<X, Y, M: Surjection<X, Y>> Surjection<Vec<X>, Vec<Y>> for Vec<M> {
+    fn map_onto(&self, from: Vec<X>) -> Vec<Y> {
+        try_map_each(self, from).unwrap_or_else(|e| panic!("Cannot map value: {}.", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Chain, Equipartition, Interval, MapFn, PairSpace};
+    use super::*;
+
+    #[test]
+    fn test_pipeline() {
+        // Raw observations of a position, a velocity and a gear, mapped onto a grid cell of the
+        // position and gear, paired with the clamped velocity.
+        let target = PairSpace::new(
+            PairSpace::new(Ordinal::new(4), Ordinal::new(3)),
+            Interval::bounded(-1.0, 1.0),
+        );
+        let raw = (
+            Chain::new(Interval::bounded(-2.0, 2.0), Equipartition::new(-2.0, 2.0, 4)),
+            Interval::bounded(-1.0, 1.0),
+            Ordinal::new(3),
+        );
+        let pipeline = Chain::new(
+            raw,
+            Chain::new(MapFn::new(|(x, v, g)| ((x, g), v)), target),
+        );
+
+        let cases = [
+            ((0.3, 0.5, 1usize), ((2, 1), 0.5)),
+            ((-5.0, 3.0, 0), ((0, 0), 1.0)),
+            ((1.99, -1.5, 9), ((3, 2), -1.0)),
+        ];
+
+        for &(obs, expected) in cases.iter() {
+            let out: ((usize, usize), f64) = pipeline.map_onto(obs);
+
+            assert_eq!(out, expected);
+            assert!(target.0 .0.contains(out.0 .0) && target.0 .1.contains(out.0 .1));
+            assert!(target.1.contains(out.1));
+        }
+    }
+
+    #[test]
+    fn test_pair() {
+        let map = Interval::bounded(0i64, 10).pair(Ordinal::new(2).pair(MapFn::new(f64::abs)));
+
+        assert_eq!(map.map_onto((12, (5usize, -2.5))), (10, (1, 2.5)));
+        assert_eq!(map.second().first(), &Ordinal::new(2));
+    }
+
+    #[test]
+    fn test_tuples() {
+        let unit = Interval::bounded(0.0, 1.0);
+        let eight = (unit, unit, unit, unit, unit, unit, unit, Ordinal::new(2));
+
+        assert_eq!(
+            eight.map_onto((-1.0, 0.5, 2.0, 0.0, 1.0, 0.25, 3.0, 7usize)),
+            (0.0, 0.5, 1.0, 0.0, 1.0, 0.25, 1.0, 1)
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        let maps = vec![Interval::bounded(0.0, 1.0), Interval::bounded(-1.0, 0.0)];
+
+        assert_eq!(maps.map_onto(vec![2.0, 2.0]), vec![1.0, 0.0]);
+        assert_eq!(
+            try_map_each(&maps, vec![0.5]),
+            Err(MapLengthError { expected: 2, found: 1 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot map value: expected 2 values, one per map, but found 3.")]
+    fn test_vec_length() {
+        vec![Ordinal::new(2); 2].map_onto(vec![0usize, 1, 2]);
+    }
+}
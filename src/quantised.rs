@@ -0,0 +1,471 @@
+use crate::prelude::*;
+use std::{cmp, error::Error, fmt, iter::FusedIterator, ops::Range};
+
+/// Relative tolerance, in units of `step`, used to absorb floating-point error.
+const TOLERANCE: f64 = 1e-9;
+
+/// Error type for invalid quantised intervals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum QuantisedError {
+    /// The step size is not finite and strictly positive.
+    InvalidStep { step: f64 },
+    /// The bounds are not finite, or `lb > ub`.
+    InvalidBounds { lb: f64, ub: f64 },
+    /// The grid has more points than can be counted by a `usize`.
+    TooManyPoints { lb: f64, ub: f64, step: f64 },
+}
+
+impl fmt::Display for QuantisedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuantisedError::InvalidStep { step } =>
+                write!(f, "step size {} is not finite and strictly positive", step),
+            QuantisedError::InvalidBounds { lb, ub } =>
+                write!(f, "bounds [{}, {}] are not finite with lb <= ub", lb, ub),
+            QuantisedError::TooManyPoints { lb, ub, step } =>
+                write!(f, "a grid over [{}, {}] with step {} has too many points", lb, ub, step),
+        }
+    }
+}
+
+impl Error for QuantisedError {}
+
+/// Bounded interval of reals restricted to a uniform grid of points.
+///
+/// The values of the space are `lb + i * step` for `i = 0, 1, ..., n - 1`, where `n` is the
+/// number of grid points lying in `[lb, ub]`. Values within a small tolerance of the upper bound
+/// are counted, such that, for example, `[0, 1]` with a step of `0.1` has exactly 11 points.
+///
+/// Note: as with `Equipartition`, `Default` is deliberately not implemented, since there is no
+/// canonical step size.
+/// ```
+/// use spaces::{Card, Quantised, Space, Surjection};
+///
+/// let d = Quantised::new(0.0, 1.0, 0.1);
+///
+/// assert_eq!(d.card(), Card::Finite(11));
+/// assert_eq!(d.map_onto(0.26), 0.30000000000000004);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "QuantisedRepr"))]
+pub struct Quantised {
+    pub(crate) lb: f64,
+    pub(crate) ub: f64,
+    pub(crate) step: f64,
+}
+
+// Deserialized intervals are checked as by `Quantised::try_new`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Quantised")]
+struct QuantisedRepr {
+    lb: f64,
+    ub: f64,
+    step: f64,
+}
+
+#[cfg(feature = "serialize")]
+impl std::convert::TryFrom<QuantisedRepr> for Quantised {
+    type Error = QuantisedError;
+
+    fn try_from(repr: QuantisedRepr) -> Result<Quantised, QuantisedError> {
+        Quantised::try_new(repr.lb, repr.ub, repr.step)
+    }
+}
+
+impl Quantised {
+    /// # Panics
+    ///
+    /// Panics if the interval is invalid; see `try_new`.
+    pub fn new(lb: f64, ub: f64, step: f64) -> Quantised {
+        Quantised::try_new(lb, ub, step)
+            .unwrap_or_else(|e| panic!("Invalid quantised interval: {}.", e))
+    }
+
+    /// Construct a quantised interval, or return an error if the step is not finite and
+    /// strictly positive, the bounds are not finite with `lb <= ub`, or the number of grid
+    /// points does not fit in a `usize`.
+    pub fn try_new(lb: f64, ub: f64, step: f64) -> Result<Quantised, QuantisedError> {
+        if step <= 0.0 || !step.is_finite() {
+            return Err(QuantisedError::InvalidStep { step, });
+        }
+
+        if lb > ub || !lb.is_finite() || !ub.is_finite() {
+            return Err(QuantisedError::InvalidBounds { lb, ub, });
+        }
+
+        // The largest `f64` below `usize::MAX as f64` leaves room for the point at `lb`; this
+        // also rejects spans that overflow to infinity.
+        let n_steps = (ub - lb) / step;
+
+        if n_steps.is_nan() || n_steps >= usize::MAX as f64 {
+            return Err(QuantisedError::TooManyPoints { lb, ub, step, });
+        }
+
+        Ok(Quantised { lb, ub, step, })
+    }
+
+    #[inline]
+    pub fn step(&self) -> f64 { self.step }
+
+    /// Returns the number of grid points in the space.
+    pub fn n_points(&self) -> usize {
+        let r = (self.ub - self.lb) / self.step;
+        let n = r.round();
+
+        if (r - n).abs() <= TOLERANCE * n.max(1.0) {
+            n as usize + 1
+        } else {
+            r.floor() as usize + 1
+        }
+    }
+
+    /// Returns the `i`th grid point, computed directly (i.e. without accumulation).
+    #[inline]
+    pub fn point(&self, i: usize) -> f64 { (self.lb + i as f64 * self.step).min(self.ub) }
+
+    /// Returns the bounds `(lo, hi)` of the cell of values nearest to the grid point at `idx`,
+    /// if any.
+    ///
+    /// Cells meet at the midpoints between adjacent grid points, with the outer cells extending
+    /// to `lb` and `ub`, respectively; these are exactly the preimages of `nearest_index`.
+    pub fn bucket_bounds(&self, idx: usize) -> Option<(f64, f64)> {
+        let n = self.n_points();
+
+        if idx >= n { return None; }
+
+        let lo = if idx == 0 { self.lb } else { self.midpoint(idx - 1) };
+        let hi = if idx + 1 == n { self.ub } else { self.midpoint(idx) };
+
+        Some((lo, hi))
+    }
+
+    /// Iterate over the bounds `(lo, hi)` of the cell of each grid point, in order.
+    ///
+    /// Adjacent cells share their edges exactly, and together tile `[lb, ub]`.
+    pub fn partitions(&self) -> impl Iterator<Item = (f64, f64)> {
+        let d = *self;
+
+        (0..self.n_points()).map(move |i| d.bucket_bounds(i).unwrap())
+    }
+
+    /// Returns a finer grid over the same interval, with a step `factor` times smaller.
+    ///
+    /// Index `i` of `self` maps to index `i * factor` in the result. The corresponding points
+    /// agree up to floating-point rounding, but may differ in the last place, since the finer
+    /// step is itself rounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero.
+    pub fn refine(&self, factor: usize) -> Quantised {
+        if factor == 0 { panic!("Cannot refine a grid by a factor of zero.") }
+
+        Quantised::new(self.lb, self.ub, self.step / factor as f64)
+    }
+
+    fn midpoint(&self, i: usize) -> f64 { self.lb + (i as f64 + 0.5) * self.step }
+
+    /// Returns the index of the grid point nearest to `val`, with ties rounding toward `lb`.
+    pub fn nearest_index(&self, val: f64) -> usize {
+        let clipped = clip!(self.lb, val, self.ub);
+        let t = (clipped - self.lb) / self.step;
+        let i = (t - 0.5).ceil().max(0.0) as usize;
+
+        i.min(self.n_points() - 1)
+    }
+}
+
+impl Space for Quantised {
+    type Value = f64;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    fn card(&self) -> Card { Card::Finite(self.n_points()) }
+}
+
+impl Sample for Quantised {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.point(rng.gen_range(0..self.n_points()))
+    }
+}
+
+impl BoundedSpace for Quantised {
+    fn inf(&self) -> Option<f64> { Some(self.lb) }
+
+    fn sup(&self) -> Option<f64> { Some(self.point(self.n_points() - 1)) }
+
+    fn contains(&self, val: f64) -> bool {
+        let tol = TOLERANCE * self.step;
+
+        val >= self.lb - tol
+            && val <= self.ub + tol
+            && (self.point(self.nearest_index(val)) - val).abs() <= tol
+    }
+}
+
+impl FiniteSpace for Quantised {
+    type Iter = Self::IntoIter;
+
+    fn iter(&self) -> Self::Iter { self.into_iter() }
+
+    fn len(&self) -> usize { self.n_points() }
+
+    fn is_empty(&self) -> bool { false }
+
+    fn to_index(&self, val: &f64) -> Option<usize> {
+        if self.contains(*val) { Some(self.nearest_index(*val)) } else { None }
+    }
+
+    fn from_index(&self, idx: usize) -> Option<f64> {
+        if idx < self.n_points() { Some(self.point(idx)) } else { None }
+    }
+}
+
+/// Iterator over the grid points of a `Quantised` space.
+#[derive(Debug, Clone)]
+pub struct QuantisedIter {
+    space: Quantised,
+    range: Range<usize>,
+}
+
+impl Iterator for QuantisedIter {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> { self.range.next().map(|i| self.space.point(i)) }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.range.size_hint() }
+
+    fn nth(&mut self, n: usize) -> Option<f64> { self.range.nth(n).map(|i| self.space.point(i)) }
+}
+
+impl ExactSizeIterator for QuantisedIter {}
+
+impl FusedIterator for QuantisedIter {}
+
+impl IntoIterator for Quantised {
+    type Item = f64;
+    type IntoIter = QuantisedIter;
+
+    fn into_iter(self) -> QuantisedIter {
+        QuantisedIter { space: self, range: 0..self.n_points(), }
+    }
+}
+
+impl Surjection<f64, f64> for Quantised {
+    fn map_onto(&self, val: f64) -> f64 { self.point(self.nearest_index(val)) }
+}
+
+impl cmp::PartialEq for Quantised {
+    fn eq(&self, other: &Quantised) -> bool {
+        self.lb.eq(&other.lb) && self.ub.eq(&other.ub) && self.step.eq(&other.step)
+    }
+}
+
+impl fmt::Display for Quantised {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}, {}, ..., {}}}", self.lb, self.lb + self.step, self.sup().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::for_all;
+    use super::*;
+
+    #[test]
+    fn test_card() {
+        assert_eq!(Quantised::new(0.0, 1.0, 0.1).card(), Card::Finite(11));
+        assert_eq!(Quantised::new(0.0, 1.0, 0.05).card(), Card::Finite(21));
+        assert_eq!(Quantised::new(0.0, 1.0, 0.3).card(), Card::Finite(4));
+        assert_eq!(Quantised::new(0.0, 0.3, 0.1).card(), Card::Finite(4));
+        assert_eq!(Quantised::new(0.7, 1.0, 0.1).card(), Card::Finite(4));
+        assert_eq!(Quantised::new(-1.0, 1.0, 0.2).card(), Card::Finite(11));
+        assert_eq!(Quantised::new(1.0, 1.0, 0.5).card(), Card::Finite(1));
+    }
+
+    #[test]
+    fn test_accumulation() {
+        let d = Quantised::new(0.0, 1.0, 0.1);
+        let accumulated: Vec<f64> = (0..11).scan(0.0, |acc, _| {
+            let v = *acc;
+            *acc += 0.1;
+            Some(v)
+        }).collect();
+
+        assert_ne!(accumulated[10], 1.0);
+        assert_eq!(d.iter().last(), Some(1.0));
+
+        for (v, a) in d.iter().zip(accumulated) {
+            assert!(d.contains(v));
+            assert!(d.contains(a));
+            assert_eq!(d.map_onto(a), v);
+        }
+
+    }
+
+    #[test]
+    fn test_bounds() {
+        let d = Quantised::new(0.0, 1.0, 0.3);
+
+        assert_eq!(d.inf(), Some(0.0));
+        assert_eq!(d.sup(), Some(0.8999999999999999));
+
+        assert!(d.contains(0.3));
+        assert!(d.contains(0.9));
+        assert!(!d.contains(0.15));
+        assert!(!d.contains(1.0));
+        assert!(!d.contains(-0.3));
+    }
+
+    #[test]
+    fn test_surjection() {
+        let d = Quantised::new(0.0, 1.0, 0.1);
+
+        assert_eq!(d.map_onto(-5.0), 0.0);
+        assert_eq!(d.map_onto(0.04), 0.0);
+        assert_eq!(d.map_onto(0.06), 0.1);
+        assert_eq!(d.map_onto(0.99), 1.0);
+        assert_eq!(d.map_onto(5.0), 1.0);
+    }
+
+    #[test]
+    fn test_surjection_ties() {
+        let d = Quantised::new(0.0, 2.0, 0.5);
+
+        assert_eq!(d.map_onto(0.25), 0.0);
+        assert_eq!(d.map_onto(0.75), 0.5);
+        assert_eq!(d.map_onto(1.75), 1.5);
+    }
+
+    #[test]
+    fn test_partitions_tile() {
+        let d = Quantised::new(0.0, 1.0, 0.3);
+        let parts: Vec<(f64, f64)> = d.partitions().collect();
+
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0].0, 0.0);
+        assert_eq!(parts[3].1, 1.0);
+        assert!(parts.windows(2).all(|w| w[0].1 == w[1].0));
+        assert_eq!(d.bucket_bounds(4), None);
+
+        for (i, &(lo, hi)) in parts.iter().enumerate() {
+            assert!(lo <= d.point(i) && d.point(i) <= hi);
+            assert_eq!(d.nearest_index(hi), i);
+        }
+    }
+
+    #[test]
+    fn test_refine() {
+        let d = Quantised::new(-1.0, 1.0, 0.2);
+        let r = d.refine(4);
+
+        assert_eq!(r.n_points(), 41);
+        assert_eq!((r.inf(), r.sup()), (d.inf(), d.sup()));
+
+        assert_eq!(for_all(&d, |&x| r.contains(x)), Ok(()));
+        assert_eq!(for_all(&d, |&x| r.nearest_index(x) == 4 * d.nearest_index(x)), Ok(()));
+    }
+
+    #[test]
+    fn test_iter_size_hint() {
+        let d = Quantised::new(0.0, 1.0, 0.25);
+        let mut it = d.iter();
+
+        for remaining in (0..=5).rev() {
+            assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(it.len(), remaining);
+
+            it.next();
+        }
+
+        assert_eq!(it.next(), None);
+        assert_eq!(it.len(), 0);
+
+        let mut it = d.iter();
+
+        assert_eq!(it.nth(1), Some(0.25));
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.clone().collect::<Vec<_>>(), vec![0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_index() {
+        let d = Quantised::new(0.0, 1.0, 0.1);
+
+        assert_eq!(
+            for_all(&d, |v| d.to_index(v).and_then(|i| d.from_index(i)) == Some(*v)),
+            Ok(())
+        );
+        assert!((0..d.len()).all(|i| d.from_index(i).and_then(|v| d.to_index(&v)) == Some(i)));
+
+        assert_eq!(d.to_index(&0.3), Some(3));
+        assert_eq!(d.to_index(&0.35), None);
+        assert_eq!(d.from_index(11), None);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let d = Quantised::new(0.0, 1.0, 0.05);
+
+        for _ in 0..100 {
+            let v = d.sample(&mut rng);
+
+            assert!(d.contains(v));
+            assert_eq!(d.map_onto(v), v);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_step() {
+        Quantised::new(0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Quantised::try_new(0.0, 1.0, 0.1).is_ok());
+        assert_eq!(
+            Quantised::try_new(0.0, 1.0, -0.1),
+            Err(QuantisedError::InvalidStep { step: -0.1 })
+        );
+        assert_eq!(
+            Quantised::try_new(1.0, 0.0, 0.1),
+            Err(QuantisedError::InvalidBounds { lb: 1.0, ub: 0.0 })
+        );
+        assert_eq!(
+            Quantised::try_new(0.0, 1e300, 1e-300),
+            Err(QuantisedError::TooManyPoints { lb: 0.0, ub: 1e300, step: 1e-300 })
+        );
+        assert!(Quantised::try_new(-f64::MAX, f64::MAX, 1.0).is_err());
+
+        // The largest grid that is accepted can still be counted and indexed.
+        let d = Quantised::try_new(0.0, 2f64.powi(63), 1.0).unwrap();
+
+        assert_eq!(d.card(), Card::Finite((1 << 63) + 1));
+        assert_eq!(d.nearest_index(f64::MAX), 1 << 63);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let parse = |json: &str| serde_json::from_str::<Quantised>(json);
+
+        assert_eq!(parse(r#"{"lb":0.0,"ub":1.0,"step":0.5}"#).unwrap().n_points(), 3);
+        assert!(parse(r#"{"lb":0.0,"ub":1.0,"step":0.0}"#).is_err());
+        assert!(parse(r#"{"lb":1.0,"ub":0.0,"step":0.5}"#).is_err());
+        assert!(parse(r#"{"lb":0.0,"ub":1e300,"step":1e-300}"#).is_err());
+    }
+
+    #[test]
+    fn test_laws() {
+        for &(lb, ub, step) in &[(0.0, 1.0, 0.1), (-1.0, 1.0, 0.3)] {
+            let d = Quantised::new(lb, ub, step);
+
+            assert_space_laws!(d; sample, bounds, shape, finite, fmt, serde);
+        }
+    }
+}
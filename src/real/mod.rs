@@ -0,0 +1,6 @@
+//! Real spaces module.
+
+pub type Interval = crate::Interval<f64>;
+
+import_all!(reals);
+import_all!(real_space);
@@ -0,0 +1,216 @@
+use crate::{prelude::*, ProductSpace, Quantised, Reward, TruncatedNormal};
+use super::{Interval, NonNegativeReals, PositiveReals, Reals};
+
+/// Trait for scalar spaces over the real line, with (possibly unbounded) extremal values.
+///
+/// This allows discretisers and normalisers to be written generically over any real space,
+/// rather than against `Interval` specifically.
+/// ```
+/// use spaces::{real::{Interval, RealSpace, Reals}, Quantised};
+///
+/// fn centre<S: RealSpace>(space: &S) -> f64 { space.midpoint().unwrap_or(0.0) }
+///
+/// assert_eq!(centre(&Interval::bounded(1.0, 3.0)), 2.0);
+/// assert_eq!(centre(&Quantised::new(0.0, 1.0, 0.25)), 0.5);
+/// assert_eq!(centre(&Reals), 0.0);
+/// ```
+pub trait RealSpace: Space<Value = f64> {
+    /// Returns the infimum of the space, or `None` if it is unbounded below.
+    fn lo(&self) -> Option<f64>;
+
+    /// Returns the supremum of the space, or `None` if it is unbounded above.
+    fn hi(&self) -> Option<f64>;
+
+    /// Returns the length of the space, which is infinite if it is unbounded.
+    fn width(&self) -> f64 {
+        match (self.lo(), self.hi()) {
+            (Some(lo), Some(hi)) => hi - lo,
+            _ => f64::INFINITY,
+        }
+    }
+
+    /// Returns the centre of the space, or `None` if it is unbounded.
+    fn midpoint(&self) -> Option<f64> { Some(self.lo()? + (self.hi()? - self.lo()?) / 2.0) }
+}
+
+impl RealSpace for Interval {
+    fn lo(&self) -> Option<f64> { self.lb }
+
+    fn hi(&self) -> Option<f64> { self.ub }
+}
+
+impl RealSpace for Reals {
+    fn lo(&self) -> Option<f64> { None }
+
+    fn hi(&self) -> Option<f64> { None }
+}
+
+impl RealSpace for NonNegativeReals {
+    fn lo(&self) -> Option<f64> { Some(0.0) }
+
+    fn hi(&self) -> Option<f64> { None }
+}
+
+impl RealSpace for PositiveReals {
+    fn lo(&self) -> Option<f64> { Some(0.0) }
+
+    fn hi(&self) -> Option<f64> { None }
+}
+
+/// The bounds are those of the grid points, so `hi` may fall short of the upper bound passed to
+/// `Quantised::new`.
+impl RealSpace for Quantised {
+    fn lo(&self) -> Option<f64> { self.inf() }
+
+    fn hi(&self) -> Option<f64> { self.sup() }
+}
+
+impl RealSpace for Reward {
+    fn lo(&self) -> Option<f64> { self.interval().lo() }
+
+    fn hi(&self) -> Option<f64> { self.interval().hi() }
+}
+
+impl<S: RealSpace> RealSpace for TruncatedNormal<S> {
+    fn lo(&self) -> Option<f64> { self.base().lo() }
+
+    fn hi(&self) -> Option<f64> { self.base().hi() }
+}
+
+/// Trait for vector-valued spaces over boxes in real coordinate space, the counterpart of
+/// `RealSpace`.
+/// ```
+/// use spaces::{real::{Interval, RealVectorSpace}, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 2.0), Interval::left_bounded(1.0)]);
+///
+/// assert_eq!(space.lo(), vec![Some(0.0), Some(1.0)]);
+/// assert_eq!(space.width(), vec![2.0, f64::INFINITY]);
+/// assert_eq!(space.midpoint(), None);
+/// ```
+pub trait RealVectorSpace: Space<Value = Vec<f64>> {
+    /// Returns the infimum of each coordinate, with `None` for coordinates unbounded below.
+    fn lo(&self) -> Vec<Option<f64>>;
+
+    /// Returns the supremum of each coordinate, with `None` for coordinates unbounded above.
+    fn hi(&self) -> Vec<Option<f64>>;
+
+    /// Returns the length of each coordinate, which is infinite where it is unbounded.
+    fn width(&self) -> Vec<f64> {
+        self.lo().into_iter().zip(self.hi()).map(|b| match b {
+            (Some(lo), Some(hi)) => hi - lo,
+            _ => f64::INFINITY,
+        }).collect()
+    }
+
+    /// Returns the centre of the space, or `None` if any coordinate is unbounded.
+    fn midpoint(&self) -> Option<Vec<f64>> {
+        self.lo().into_iter().zip(self.hi()).map(|b| Some(b.0? + (b.1? - b.0?) / 2.0)).collect()
+    }
+}
+
+impl<D: RealSpace> RealVectorSpace for ProductSpace<D> {
+    fn lo(&self) -> Vec<Option<f64>> { self.iter().map(|d| d.lo()).collect() }
+
+    fn hi(&self) -> Vec<Option<f64>> { self.iter().map(|d| d.hi()).collect() }
+
+    fn width(&self) -> Vec<f64> { self.iter().map(|d| d.width()).collect() }
+
+    fn midpoint(&self) -> Option<Vec<f64>> { self.iter().map(|d| d.midpoint()).collect() }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    fn within(lo: Option<f64>, hi: Option<f64>, x: f64) -> bool {
+        lo.map_or(true, |lo| lo <= x) && hi.map_or(true, |hi| x <= hi)
+    }
+
+    fn check_bounds<S: RealSpace>(space: &S) {
+        let (lo, hi) = (space.lo(), space.hi());
+
+        if let (Some(lo), Some(hi)) = (lo, hi) {
+            assert!(lo <= hi);
+            assert_eq!(space.width(), hi - lo);
+        } else {
+            assert_eq!(space.width(), f64::INFINITY);
+        }
+
+        match space.midpoint() {
+            Some(m) => assert!(within(lo, hi, m)),
+            None => assert!(lo.is_none() || hi.is_none()),
+        }
+    }
+
+    fn check_samples<S: RealSpace + Sample>(space: &S) {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..1000 {
+            match space.try_sample(&mut rng) {
+                Ok(x) => assert!(within(space.lo(), space.hi(), x)),
+                Err(_) => assert!(space.midpoint().is_none()),
+            }
+        }
+    }
+
+    // Generates a module of tests enforcing the shared invariants of `RealSpace` for a single
+    // impl; pass `sample` for spaces that also implement `Sample`.
+    macro_rules! real_space_tests {
+        ($name:ident, $space:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_bounds() { check_bounds(&$space); }
+            }
+        };
+        ($name:ident, $space:expr, sample) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_bounds() { check_bounds(&$space); }
+
+                #[test]
+                fn test_samples() { check_samples(&$space); }
+            }
+        };
+    }
+
+    real_space_tests!(interval_bounded, Interval::bounded(-1.0, 2.0), sample);
+    real_space_tests!(interval_open, Interval::open(0.0, 1.0), sample);
+    real_space_tests!(interval_left_bounded, Interval::left_bounded(1.0), sample);
+    real_space_tests!(interval_unbounded, Interval::unbounded(), sample);
+    real_space_tests!(reals, Reals, sample);
+    real_space_tests!(non_negative_reals, NonNegativeReals, sample);
+    real_space_tests!(positive_reals, PositiveReals, sample);
+    real_space_tests!(quantised, Quantised::new(-1.0, 1.1, 0.25), sample);
+    real_space_tests!(reward, Reward::new(Interval::symmetric_unit()));
+    real_space_tests!(
+        truncated_normal,
+        TruncatedNormal::new(Interval::bounded(0.0, 1.0), 0.5, 2.0),
+        sample
+    );
+
+    #[test]
+    fn test_vector() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::unit()]);
+
+        assert_eq!(space.lo(), vec![Some(-1.0), Some(0.0)]);
+        assert_eq!(space.hi(), vec![Some(1.0), Some(1.0)]);
+        assert_eq!(space.width(), vec![2.0, 1.0]);
+        assert_eq!(space.midpoint(), Some(vec![0.0, 0.5]));
+
+        for _ in 0..1000 {
+            let x = space.sample(&mut rng);
+
+            assert!(x.iter().zip(space.iter()).all(|(&x, d)| within(d.lo(), d.hi(), x)));
+        }
+
+        assert_eq!(ProductSpace::<Reals>::empty().midpoint(), Some(vec![]));
+        assert_eq!(ProductSpace::new(vec![Reals; 2]).midpoint(), None);
+    }
+}
@@ -1,11 +1,9 @@
-//! Real spaces module.
 use crate::prelude::*;
+use super::Interval;
 use std::fmt;
 
-pub type Interval = crate::Interval<f64>;
-
 /// Type representing the set of all real numbers.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Reals;
 
@@ -27,6 +25,8 @@ impl Space for Reals {
 
 impl_union_intersect!(Reals, Reals);
 
+impl_unbounded_sample!(Reals);
+
 impl Surjection<f64, f64> for Reals {
     fn map_onto(&self, val: f64) -> f64 { val }
 }
@@ -38,7 +38,7 @@ impl fmt::Display for Reals {
 }
 
 /// Type representing the set of non-negative real numbers, R(≥0).
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct NonNegativeReals;
 
@@ -60,6 +60,8 @@ impl BoundedSpace for NonNegativeReals {
 
 impl_union_intersect!(NonNegativeReals, NonNegativeReals);
 
+impl_unbounded_sample!(NonNegativeReals);
+
 impl Surjection<f64, f64> for NonNegativeReals {
     fn map_onto(&self, val: f64) -> f64 { val.max(0.0) }
 }
@@ -71,7 +73,7 @@ impl fmt::Display for NonNegativeReals {
 }
 
 /// Type representing the set of strictly positive real numbers, R(>0).
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct PositiveReals;
 
@@ -93,6 +95,8 @@ impl BoundedSpace for PositiveReals {
 
 impl_union_intersect!(PositiveReals, PositiveReals);
 
+impl_unbounded_sample!(PositiveReals);
+
 impl Surjection<f64, f64> for PositiveReals {
     fn map_onto(&self, val: f64) -> f64 { val.max(1e-7) }
 }
@@ -105,8 +109,24 @@ impl fmt::Display for PositiveReals {
 
 #[cfg(test)]
 mod tests {
+    use crate::SampleError;
     use super::*;
 
+    #[test]
+    fn test_try_sample() {
+        let mut rng = ::rand::thread_rng();
+
+        assert_eq!(Reals.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(NonNegativeReals.try_sample(&mut rng), Err(SampleError::Unbounded));
+        assert_eq!(PositiveReals.try_sample(&mut rng), Err(SampleError::Unbounded));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample() {
+        Reals.sample(&mut ::rand::thread_rng());
+    }
+
     #[cfg(feature = "serialize")]
     extern crate serde_test;
     #[cfg(feature = "serialize")]
@@ -144,4 +164,11 @@ mod tests {
 
         assert_tokens(&d, &[Token::UnitStruct { name: "Reals" }]);
     }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Reals; shape, fmt, serde);
+        assert_space_laws!(NonNegativeReals; sample, bounds, shape, fmt, serde);
+        assert_space_laws!(PositiveReals; sample, bounds, shape, fmt, serde);
+    }
 }
@@ -0,0 +1,134 @@
+//! Registry of named, user-defined spaces for the parser.
+//!
+//! Crates that define their own spaces can register a constructor under a name, after which the
+//! name may be used wherever the grammar of the `parse` module expects a space, optionally
+//! followed by a parenthesised list of numeric arguments. Registered spaces are lowered onto a
+//! `SpaceDescription` by their constructor, and names not recognised by the parser itself are
+//! looked up here before an `UnknownSpace` error is raised.
+//! ```
+//! use spaces::{registry, ScalarKind, SpaceDescription};
+//!
+//! fn unit_cube(args: &[f64]) -> Option<SpaceDescription> {
+//!     let n = match args {
+//!         [n] if n.fract() == 0.0 && *n >= 1.0 => *n as usize,
+//!         _ => return None,
+//!     };
+//!
+//!     Some(SpaceDescription::Product(vec![
+//!         SpaceDescription::Scalar(ScalarKind::real(Some(0.0), Some(1.0)));
+//!         n
+//!     ]))
+//! }
+//!
+//! registry::register_space("unit_cube", unit_cube).unwrap();
+//!
+//! let s: SpaceDescription = "unit_cube(3)".parse().unwrap();
+//!
+//! assert_eq!(s, unit_cube(&[3.0]).unwrap());
+//! ```
+use crate::SpaceDescription;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::{OnceLock, RwLock},
+};
+
+/// Constructor for a registered space, taking the numeric arguments given in the source.
+///
+/// Returning `None` indicates that the arguments are invalid for the space.
+pub type SpaceCtor = fn(&[f64]) -> Option<SpaceDescription>;
+
+/// Names that are part of the grammar itself, and so cannot be registered.
+const RESERVED: &[&str] = &["binary", "discrete", "interval", "box", "inf", "x"];
+
+/// Error type for failed registrations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegistryError {
+    /// A space has already been registered under the name.
+    Duplicate(String),
+    /// The name is reserved by the grammar.
+    Reserved(String),
+    /// The name is not a valid identifier; names must be non-empty and consist of ASCII letters
+    /// and underscores.
+    InvalidName(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::Duplicate(name) => write!(f, "space \"{}\" is already registered", name),
+            RegistryError::Reserved(name) => write!(f, "space name \"{}\" is reserved", name),
+            RegistryError::InvalidName(name) => write!(f, "invalid space name \"{}\"", name),
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+fn registry() -> &'static RwLock<HashMap<String, SpaceCtor>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, SpaceCtor>>> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+pub(crate) fn is_ident_char(c: char) -> bool { c.is_ascii_alphabetic() || c == '_' }
+
+/// Register `ctor` under `name`, making it available to the parser process-wide.
+pub fn register_space(name: &str, ctor: SpaceCtor) -> Result<(), RegistryError> {
+    if name.is_empty() || !name.chars().all(is_ident_char) {
+        return Err(RegistryError::InvalidName(name.to_owned()));
+    }
+
+    if RESERVED.contains(&name) { return Err(RegistryError::Reserved(name.to_owned())); }
+
+    let mut spaces = registry().write().unwrap_or_else(|e| e.into_inner());
+
+    if spaces.contains_key(name) { return Err(RegistryError::Duplicate(name.to_owned())); }
+
+    spaces.insert(name.to_owned(), ctor);
+
+    Ok(())
+}
+
+/// Returns the constructor registered under `name`, if any.
+pub fn lookup(name: &str) -> Option<SpaceCtor> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ScalarKind;
+    use super::*;
+
+    fn coin(args: &[f64]) -> Option<SpaceDescription> {
+        if args.is_empty() { Some(SpaceDescription::Scalar(ScalarKind::Binary)) } else { None }
+    }
+
+    #[test]
+    fn test_register() {
+        assert_eq!(register_space("coin_a", coin), Ok(()));
+        assert_eq!(lookup("coin_a").and_then(|c| c(&[])), coin(&[]));
+        assert!(lookup("coin_b").is_none());
+    }
+
+    #[test]
+    fn test_duplicate() {
+        assert_eq!(register_space("coin_c", coin), Ok(()));
+        assert_eq!(
+            register_space("coin_c", coin),
+            Err(RegistryError::Duplicate("coin_c".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert_eq!(register_space("box", coin), Err(RegistryError::Reserved("box".to_owned())));
+        assert_eq!(register_space("", coin), Err(RegistryError::InvalidName("".to_owned())));
+        assert_eq!(
+            register_space("coin(2)", coin),
+            Err(RegistryError::InvalidName("coin(2)".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{prelude::*, SampleError};
+use std::{error::Error, fmt};
+
+/// Error returned when a `Repeated` space would not repeat its values at least once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroRepeatsError;
+
+impl fmt::Display for ZeroRepeatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a repeated space must repeat each value at least once")
+    }
+}
+
+impl Error for ZeroRepeatsError {}
+
+/// Whether a `Repeated` space admits a single repeat count, or any count up to a maximum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum RepeatMode {
+    /// Every value is repeated exactly `times` times.
+    Fixed,
+    /// Every value is repeated between one and `times` times.
+    Variable,
+}
+
+/// Space of values from a base space paired with the number of times they are repeated, e.g. an
+/// action under a frame-skip wrapper.
+///
+/// The repeat count of a value must equal `times` in fixed mode (`Repeated::new`), and lie in
+/// `1..=times` in variable mode (`Repeated::variable`).
+/// ```
+/// use spaces::{discrete::Ordinal, BoundedSpace, Card, Repeated, Space, Surjection};
+///
+/// let space = Repeated::new(Ordinal::new(3), 4);
+///
+/// assert_eq!(space.card(), Card::Finite(3));
+/// assert_eq!(space.map_onto(2), (2, 4));
+/// assert!(!space.contains((2, 3)));
+///
+/// let space = Repeated::variable(Ordinal::new(3), 4);
+///
+/// assert_eq!(space.card(), Card::Finite(12));
+/// assert!(space.contains((2, 3)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "RepeatedRepr<S>"))]
+pub struct Repeated<S> {
+    base: S,
+    times: usize,
+    mode: RepeatMode,
+}
+
+// Deserialized spaces are checked as by `Repeated::try_with_mode`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Repeated")]
+struct RepeatedRepr<S> {
+    base: S,
+    times: usize,
+    mode: RepeatMode,
+}
+
+#[cfg(feature = "serialize")]
+impl<S> std::convert::TryFrom<RepeatedRepr<S>> for Repeated<S> {
+    type Error = ZeroRepeatsError;
+
+    fn try_from(repr: RepeatedRepr<S>) -> Result<Repeated<S>, ZeroRepeatsError> {
+        Repeated::try_with_mode(repr.base, repr.times, repr.mode)
+    }
+}
+
+impl<S> Repeated<S> {
+    /// Construct a space whose values are repeated exactly `times` times.
+    pub fn new(base: S, times: usize) -> Repeated<S> {
+        Repeated::with_mode(base, times, RepeatMode::Fixed)
+    }
+
+    /// Construct a space whose values are repeated between one and `times` times.
+    pub fn variable(base: S, times: usize) -> Repeated<S> {
+        Repeated::with_mode(base, times, RepeatMode::Variable)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `times` is zero; see `try_with_mode`.
+    pub fn with_mode(base: S, times: usize, mode: RepeatMode) -> Repeated<S> {
+        Repeated::try_with_mode(base, times, mode)
+            .unwrap_or_else(|e| panic!("Invalid repeated space: {}.", e))
+    }
+
+    /// Construct a space with the given repeat mode, or return an error if `times` is zero.
+    pub fn try_with_mode(
+        base: S,
+        times: usize,
+        mode: RepeatMode,
+    ) -> Result<Repeated<S>, ZeroRepeatsError> {
+        if times == 0 { return Err(ZeroRepeatsError); }
+
+        Ok(Repeated { base, times, mode, })
+    }
+
+    #[inline]
+    pub fn base(&self) -> &S { &self.base }
+
+    /// Returns the (maximum) number of times each value is repeated.
+    #[inline]
+    pub fn times(&self) -> usize { self.times }
+
+    #[inline]
+    pub fn mode(&self) -> RepeatMode { self.mode }
+
+    /// Returns the smallest legal repeat count.
+    pub fn min_times(&self) -> usize {
+        match self.mode {
+            RepeatMode::Fixed => self.times,
+            RepeatMode::Variable => 1,
+        }
+    }
+
+    /// Returns the number of legal repeat counts.
+    pub fn n_repeats(&self) -> usize { self.times - self.min_times() + 1 }
+
+    /// Returns true iff `times` is a legal repeat count.
+    pub fn contains_times(&self, times: usize) -> bool {
+        (self.min_times()..=self.times).contains(&times)
+    }
+
+    fn sample_times<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        rng.gen_range(self.min_times()..=self.times)
+    }
+}
+
+impl<S: Space> Space for Repeated<S> {
+    type Value = (S::Value, usize);
+
+    fn dim(&self) -> Dim { self.base.dim() + Dim::one() }
+
+    fn shape(&self) -> Shape { Shape::vector(self.dim().into()) }
+
+    fn card(&self) -> Card { Card::product(vec![self.base.card(), Card::Finite(self.n_repeats())]) }
+}
+
+/// In variable mode, the repeat count is drawn uniformly from `1..=times`.
+impl<S: Sample> Sample for Repeated<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value {
+        (self.base.sample(rng), self.sample_times(rng))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        Ok((self.base.try_sample(rng)?, self.sample_times(rng)))
+    }
+}
+
+impl<S: BoundedSpace> BoundedSpace for Repeated<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<Self::Value> { Some((self.base.inf()?, self.min_times())) }
+
+    fn sup(&self) -> Option<Self::Value> { Some((self.base.sup()?, self.times)) }
+
+    fn contains(&self, val: Self::Value) -> bool {
+        self.contains_times(val.1) && self.base.contains(val.0)
+    }
+}
+
+/// Pairs a value with the default repeat count, `times`.
+impl<S: Space> Surjection<S::Value, (S::Value, usize)> for Repeated<S> {
+    fn map_onto(&self, val: S::Value) -> (S::Value, usize) { (val, self.times) }
+}
+
+impl<S: fmt::Display> fmt::Display for Repeated<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            RepeatMode::Fixed => write!(f, "{} \u{00d7} {}", self.base, self.times),
+            RepeatMode::Variable => write!(f, "{} \u{00d7} [1, {}]", self.base, self.times),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval, ProductSpace};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_fixed() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = Repeated::new(Ordinal::new(5), 4);
+
+        assert_eq!(space.min_times(), 4);
+        assert_eq!(space.n_repeats(), 1);
+        assert_eq!(space.map_onto(3), (3, 4));
+        assert!(space.contains((3, 4)));
+        assert!(!space.contains((3, 1)));
+        assert!(!space.contains((3, 5)));
+        assert!(!space.contains((5, 4)));
+        assert_eq!(space.inf(), Some((0, 4)));
+        assert_eq!(space.sup(), Some((4, 4)));
+
+        for _ in 0..100 {
+            let (v, times) = space.sample(&mut rng);
+
+            assert!(v < 5);
+            assert_eq!(times, 4);
+        }
+    }
+
+    #[test]
+    fn test_variable() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = Repeated::variable(Interval::<f64>::unit(), 3);
+        let mut seen = [false; 3];
+
+        assert_eq!(space.min_times(), 1);
+        assert_eq!(space.n_repeats(), 3);
+        assert_eq!(space.map_onto(0.5), (0.5, 3));
+        assert!(space.contains((0.5, 1)));
+        assert!(space.contains((0.5, 3)));
+        assert!(!space.contains((0.5, 0)));
+        assert!(!space.contains((0.5, 4)));
+        assert!(!space.contains((1.5, 2)));
+
+        for _ in 0..1000 {
+            let val = space.sample(&mut rng);
+
+            assert!(space.contains(val));
+            seen[val.1 - 1] = true;
+        }
+
+        assert_eq!(seen, [true; 3]);
+        assert_eq!(
+            Repeated::variable(Interval::left_bounded(0.0), 3).try_sample(&mut rng),
+            Err(SampleError::Unbounded)
+        );
+    }
+
+    #[test]
+    fn test_dim_card() {
+        let base = ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]);
+
+        assert_eq!(Repeated::new(base.clone(), 4).dim(), Dim::Finite(3));
+        assert_eq!(Repeated::new(base.clone(), 4).card(), Card::Finite(6));
+        assert_eq!(Repeated::variable(base, 4).card(), Card::Finite(24));
+        assert_eq!(Repeated::variable(Ordinal::new(0), 4).card(), Card::Finite(0));
+        assert_eq!(Repeated::variable(Ordinal::new(usize::MAX), 2).card(), Card::Infinite);
+        assert_eq!(Repeated::new(Interval::<f64>::unit(), 4).card(), Card::Infinite);
+    }
+
+    #[test]
+    fn test_display() {
+        let d = Ordinal::new(3);
+
+        assert_eq!(Repeated::new(d, 4).to_string(), format!("{} \u{00d7} 4", d));
+        assert_eq!(Repeated::variable(d, 4).to_string(), format!("{} \u{00d7} [1, 4]", d));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_times() {
+        Repeated::new(Ordinal::new(5), 0);
+    }
+
+    #[test]
+    fn test_try_with_mode() {
+        assert!(Repeated::try_with_mode(Ordinal::new(5), 1, RepeatMode::Fixed).is_ok());
+        assert_eq!(
+            Repeated::try_with_mode(Ordinal::new(5), 0, RepeatMode::Variable),
+            Err(ZeroRepeatsError)
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let parse = |json: &str| serde_json::from_str::<Repeated<Ordinal>>(json);
+
+        assert_eq!(
+            parse(r#"{"base":5,"times":2,"mode":"Variable"}"#).unwrap(),
+            Repeated::variable(Ordinal::new(5), 2)
+        );
+        assert!(parse(r#"{"base":5,"times":0,"mode":"Fixed"}"#).is_err());
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Repeated::new(Ordinal::new(5), 4); sample, bounds, shape, fmt, serde);
+        assert_space_laws!(Repeated::variable(Interval::<f64>::unit(), 3));
+    }
+}
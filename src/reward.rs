@@ -0,0 +1,198 @@
+use crate::{prelude::*, Interval};
+use std::fmt;
+
+/// Floor on the variance of returns used by `Reward::normalise`, to avoid division by zero.
+const VARIANCE_EPSILON: f64 = 1e-8;
+
+/// Space of scalar rewards, with a running normaliser over the observed returns.
+///
+/// Rewards are clipped onto the interval of the space with `clip`. Independently, `update`
+/// records the discounted return of each step (e.g. `ret = gamma * ret + r`, reset at episode
+/// boundaries), and `normalise` scales a reward by the running standard deviation of those
+/// returns. Rewards are only scaled, never shifted, since shifting would change the relative
+/// value of episodes of different lengths. The normaliser state is persisted alongside the
+/// interval when serialised.
+/// ```
+/// use spaces::{real::Interval, Reward};
+///
+/// let mut reward = Reward::new(Interval::symmetric_unit());
+///
+/// assert_eq!(reward.clip(5.0), 1.0);
+///
+/// for ret in [1.0, 3.0, 1.0, 3.0] {
+///     reward.update(ret);
+/// }
+///
+/// assert!((reward.normalise(2.0) - 2.0).abs() < 1e-6);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Reward {
+    interval: Interval,
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Reward {
+    /// Construct a reward space over `interval`, with an empty normaliser.
+    pub fn new(interval: Interval) -> Reward {
+        Reward { interval, count: 0, mean: 0.0, m2: 0.0, }
+    }
+
+    /// Construct a reward space over the whole real line, with an empty normaliser.
+    pub fn unbounded() -> Reward { Reward::new(Interval::unbounded()) }
+
+    #[inline]
+    pub fn interval(&self) -> &Interval { &self.interval }
+
+    /// Returns `r` clipped onto the interval of the space.
+    pub fn clip(&self, r: f64) -> f64 { self.interval.map_onto(r) }
+
+    /// Record the discounted return `ret` of a step in the running statistics.
+    ///
+    /// Non-finite returns are ignored.
+    pub fn update(&mut self, ret: f64) {
+        if !ret.is_finite() { return; }
+
+        // Welford's online algorithm.
+        self.count += 1;
+
+        let delta = ret - self.mean;
+
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (ret - self.mean);
+    }
+
+    /// Returns the number of returns recorded by `update`.
+    #[inline]
+    pub fn count(&self) -> u64 { self.count }
+
+    /// Returns the running mean of the recorded returns.
+    #[inline]
+    pub fn return_mean(&self) -> f64 { self.mean }
+
+    /// Returns the running (population) variance of the recorded returns, or zero if fewer than
+    /// two have been recorded.
+    pub fn return_variance(&self) -> f64 {
+        if self.count < 2 { 0.0 } else { self.m2 / self.count as f64 }
+    }
+
+    /// Returns `r` divided by the running standard deviation of the recorded returns.
+    ///
+    /// Rewards are passed through unchanged until at least two returns have been recorded.
+    pub fn normalise(&self, r: f64) -> f64 {
+        if self.count < 2 { return r; }
+
+        r / (self.return_variance() + VARIANCE_EPSILON).sqrt()
+    }
+
+    /// Discard the running statistics, keeping the interval.
+    pub fn reset(&mut self) { *self = Reward::new(self.interval); }
+}
+
+impl Default for Reward {
+    fn default() -> Reward { Reward::unbounded() }
+}
+
+impl Space for Reward {
+    type Value = f64;
+
+    fn dim(&self) -> Dim { self.interval.dim() }
+
+    fn shape(&self) -> Shape { self.interval.shape() }
+
+    fn card(&self) -> Card { self.interval.card() }
+}
+
+impl BoundedSpace for Reward {
+    fn inf(&self) -> Option<f64> { self.interval.inf() }
+
+    fn sup(&self) -> Option<f64> { self.interval.sup() }
+
+    fn contains(&self, val: f64) -> bool { self.interval.contains(val) }
+}
+
+impl Surjection<f64, f64> for Reward {
+    fn map_onto(&self, val: f64) -> f64 { self.clip(val) }
+}
+
+impl fmt::Display for Reward {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.interval) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TruncatedNormal;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_clip() {
+        let reward = Reward::new(Interval::symmetric_unit());
+
+        assert_eq!(reward.clip(-3.0), -1.0);
+        assert_eq!(reward.clip(-1.0), -1.0);
+        assert_eq!(reward.clip(0.25), 0.25);
+        assert_eq!(reward.clip(1.0), 1.0);
+        assert_eq!(reward.clip(3.0), 1.0);
+
+        assert_eq!(Reward::new(Interval::unit()).clip(-0.5), 0.0);
+        assert_eq!(Reward::unbounded().clip(1e9), 1e9);
+    }
+
+    #[test]
+    fn test_normaliser_convergence() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut reward = Reward::unbounded();
+        let noise = TruncatedNormal::new(Interval::<f64>::unbounded(), 0.0, 1.0);
+        let gamma = 0.9;
+        let mut ret = 0.0;
+
+        assert_eq!(reward.normalise(2.0), 2.0);
+
+        // With i.i.d. rewards of unit variance, the stationary variance of the discounted return
+        // is 1 / (1 - gamma^2).
+        for _ in 0..200_000 {
+            ret = gamma * ret + noise.sample(&mut rng);
+            reward.update(ret);
+        }
+
+        let expected = 1.0 / (1.0 - gamma * gamma);
+
+        assert_eq!(reward.count(), 200_000);
+        assert!(reward.return_mean().abs() < 0.1);
+        assert!((reward.return_variance() - expected).abs() / expected < 0.05);
+        assert!((reward.normalise(expected.sqrt()) - 1.0).abs() < 0.05);
+
+        reward.update(f64::NAN);
+        reward.reset();
+
+        assert_eq!(reward.count(), 0);
+        assert_eq!(reward.normalise(2.0), 2.0);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serialisation_round_trip() {
+        extern crate serde_json;
+
+        let mut reward = Reward::new(Interval::symmetric_unit());
+
+        for ret in [0.5, -1.5, 2.0] {
+            reward.update(ret);
+        }
+
+        let json = serde_json::to_string(&reward).unwrap();
+        let restored: Reward = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, reward);
+        assert_eq!(restored.normalise(1.0), reward.normalise(1.0));
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Reward::new(Interval::symmetric_unit()); bounds, shape, fmt, serde);
+        assert_space_laws!(Reward::new(Interval::left_bounded(0.0)); bounds, shape, fmt, serde);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{discrete::Ordinal, prelude::*, Quantised};
+
+/// Round `t` down or up at random, rounding up with probability equal to its fractional part.
+fn stochastic_round<R: Rng + ?Sized>(t: f64, rng: &mut R) -> f64 {
+    let lo = t.floor();
+
+    if rng.gen::<f64>() < t - lo { lo + 1.0 } else { lo }
+}
+
+/// Stochastic rounding of reals onto a discrete space.
+///
+/// Values are rounded up with probability equal to their fractional part, and down otherwise,
+/// such that the rounding is unbiased in expectation. Values outside of the space are clamped
+/// onto its nearest extreme.
+/// ```
+/// use spaces::{discrete::Ordinal, StochasticRound, StochasticSurjection};
+///
+/// let map = StochasticRound::new(Ordinal::new(5));
+/// let i: usize = map.map_with(2.3, &mut rand::thread_rng());
+///
+/// assert!(i == 2 || i == 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct StochasticRound<S>(S);
+
+impl<S> StochasticRound<S> {
+    pub fn new(space: S) -> StochasticRound<S> { StochasticRound(space) }
+
+    #[inline]
+    pub fn space(&self) -> &S { &self.0 }
+}
+
+/// # Panics
+///
+/// Panics if the value is `NaN`, or the space is empty.
+impl StochasticSurjection<f64, usize> for StochasticRound<Ordinal> {
+    fn map_with<R: Rng + ?Sized>(&self, val: f64, rng: &mut R) -> usize {
+        assert!(!val.is_nan(), "Cannot round a NaN value.");
+
+        let max = self.0.size().checked_sub(1).expect("Cannot round onto an empty space.");
+        let t = clip!(0.0f64, val, max as f64);
+
+        (stochastic_round(t, rng) as usize).min(max)
+    }
+}
+
+/// Rounds onto the neighbouring grid points of the space, in proportion to their proximity.
+///
+/// # Panics
+///
+/// Panics if the value is `NaN`.
+impl StochasticSurjection<f64, f64> for StochasticRound<Quantised> {
+    fn map_with<R: Rng + ?Sized>(&self, val: f64, rng: &mut R) -> f64 {
+        assert!(!val.is_nan(), "Cannot round a NaN value.");
+
+        let d = &self.0;
+        let t = (clip!(d.lb, val, d.ub) - d.lb) / d.step;
+
+        d.point((stochastic_round(t, rng) as usize).min(d.n_points() - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_ordinal_frequencies() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let map = StochasticRound::new(Ordinal::new(5));
+        let mut counts = [0usize; 5];
+
+        for _ in 0..10_000 {
+            counts[map.map_with(2.3, &mut rng)] += 1;
+        }
+
+        assert_eq!(counts[0] + counts[1] + counts[4], 0);
+        assert!((counts[3] as f64 / 10_000.0 - 0.3).abs() < 0.02);
+        assert!((counts[2] as f64 / 10_000.0 - 0.7).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_ordinal_clamping() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let map = StochasticRound::new(Ordinal::new(5));
+
+        for _ in 0..100 {
+            assert_eq!(map.map_with(-3.7, &mut rng), 0);
+            assert_eq!(map.map_with(12.5, &mut rng), 4);
+            assert_eq!(map.map_with(f64::INFINITY, &mut rng), 4);
+            assert_eq!(map.map_with(3.0, &mut rng), 3);
+        }
+    }
+
+    #[test]
+    fn test_quantised() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = Quantised::new(0.0, 1.0, 0.25);
+        let map = StochasticRound::new(space);
+
+        let n = 10_000;
+        let mean = (0..n).map(|_| {
+            let x = map.map_with(0.3, &mut rng);
+
+            assert!(x == 0.25 || x == 0.5);
+
+            x / n as f64
+        }).sum::<f64>();
+
+        assert!((mean - 0.3).abs() < 0.005);
+        assert_eq!(map.map_with(2.0, &mut rng), 1.0);
+        assert_eq!(map.map_with(-2.0, &mut rng), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nan() {
+        StochasticRound::new(Ordinal::new(5)).map_with(f64::NAN, &mut ::rand::thread_rng());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::prelude::*;
+use std::{error::Error, fmt};
+
+/// Error returned by row-wise batch mapping, identifying the first row of the wrong width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RowsError {
+    /// Position of the offending row in the batch.
+    pub row: usize,
+    /// Width that the row was required to have.
+    pub expected: usize,
+    /// Width that the row actually had.
+    pub found: usize,
+}
+
+impl fmt::Display for RowsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "row {} has width {}, but a width of {} was expected",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl Error for RowsError {}
+
+/// Memory layout of a flat matrix of values with one sample per row, e.g. for
+/// `ProductSpace::contains_all_matrix`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// The values of each sample are contiguous, as in C.
+    #[default]
+    RowMajor,
+    /// The values of each dimension are contiguous, as in Fortran and BLAS.
+    ColumnMajor,
+}
+
+impl Layout {
+    /// Returns the position, in an `n_rows` by `n_cols` matrix, of the value at row `i` and
+    /// column `j`.
+    #[inline]
+    pub fn offset(self, n_rows: usize, n_cols: usize, i: usize, j: usize) -> usize {
+        match self {
+            Layout::RowMajor => i * n_cols + j,
+            Layout::ColumnMajor => j * n_rows + i,
+        }
+    }
+}
+
+pub(crate) fn check_input(data: &[f64], n_cols: usize) -> Result<usize, RowsError> {
+    // Rows of zero width cannot hold any data, so any data at all overflows the first row.
+    if n_cols == 0 {
+        return if data.is_empty() {
+            Ok(0)
+        } else {
+            Err(RowsError { row: 0, expected: 0, found: data.len(), })
+        };
+    }
+
+    let n_rows = data.len() / n_cols;
+    let rem = data.len() % n_cols;
+
+    if rem != 0 {
+        Err(RowsError { row: n_rows, expected: n_cols, found: rem, })
+    } else {
+        Ok(n_rows)
+    }
+}
+
+/// Apply a surjection to each row of a row-major batch of values, `n_cols` values per row.
+///
+/// The output is stored row-major in a single, preallocated buffer. Its width is taken from the
+/// dimensionality of the space when finite, and from the first mapped row otherwise; every
+/// mapped row must then have that same width.
+/// ```
+/// use spaces::{map_rows, real::Interval, ProductSpace};
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]);
+/// let data = [-1.0, 0.5, 0.25, 2.0];
+///
+/// assert_eq!(map_rows(&space, &data, 2), Ok(vec![0.0, 0.5, 0.25, 1.0]));
+/// ```
+pub fn map_rows<S>(space: &S, data: &[f64], n_cols: usize) -> Result<Vec<f64>, RowsError>
+where S: Space + Surjection<Vec<f64>, Vec<f64>>
+{
+    let n_rows = check_input(data, n_cols)?;
+
+    if n_rows == 0 { return Ok(vec![]); }
+
+    let mut width = match space.dim() {
+        Dim::Finite(d) => Some(d),
+        Dim::Infinite => None,
+    };
+    let mut out = Vec::with_capacity(n_rows * width.unwrap_or(n_cols));
+
+    for (i, row) in data.chunks(n_cols).enumerate() {
+        let mapped = space.map_onto(row.to_vec());
+        let expected = *width.get_or_insert(mapped.len());
+
+        if mapped.len() != expected {
+            return Err(RowsError { row: i, expected, found: mapped.len(), });
+        }
+
+        out.extend(mapped);
+    }
+
+    Ok(out)
+}
+
+/// As `map_rows`, but overwrites each row of `data` with its image under the surjection.
+///
+/// Every mapped row must have the same width, `n_cols`, as the input rows. Rows preceding the
+/// offending one will already have been mapped when an error is returned.
+pub fn map_rows_in_place<S>(space: &S, data: &mut [f64], n_cols: usize) -> Result<(), RowsError>
+where S: Surjection<Vec<f64>, Vec<f64>>
+{
+    check_input(data, n_cols)?;
+
+    if n_cols == 0 { return Ok(()); }
+
+    for (i, row) in data.chunks_mut(n_cols).enumerate() {
+        let mapped = space.map_onto(row.to_vec());
+
+        if mapped.len() != n_cols {
+            return Err(RowsError { row: i, expected: n_cols, found: mapped.len(), });
+        }
+
+        row.copy_from_slice(&mapped);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, ProductSpace};
+    use super::*;
+
+    fn clamping_box() -> ProductSpace<Interval> {
+        ProductSpace::new(vec![
+            Interval::bounded(0.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::left_bounded(0.0),
+        ])
+    }
+
+    // Surjection that drops the trailing value of each row.
+    struct Truncate;
+
+    impl Surjection<Vec<f64>, Vec<f64>> for Truncate {
+        fn map_onto(&self, mut val: Vec<f64>) -> Vec<f64> {
+            val.pop();
+            val
+        }
+    }
+
+    // Surjection whose output width varies with the input.
+    struct Ragged;
+
+    impl Space for Ragged {
+        type Value = Vec<f64>;
+
+        fn dim(&self) -> Dim { Dim::Infinite }
+
+        fn card(&self) -> Card { Card::Infinite }
+    }
+
+    impl Surjection<Vec<f64>, Vec<f64>> for Ragged {
+        fn map_onto(&self, val: Vec<f64>) -> Vec<f64> { vec![0.0; val[0] as usize] }
+    }
+
+    #[test]
+    fn test_map_rows() {
+        let space = clamping_box();
+        let data = [2.0, -2.0, -5.0, 0.5, 0.5, 10.0];
+        let naive: Vec<f64> = data
+            .chunks(3)
+            .flat_map(|row| space.map_onto(row.to_vec()))
+            .collect();
+
+        assert_eq!(map_rows(&space, &data, 3), Ok(vec![1.0, -1.0, 0.0, 0.5, 0.5, 10.0]));
+        assert_eq!(map_rows(&space, &data, 3), Ok(naive));
+        assert_eq!(map_rows(&space, &[], 3), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_map_rows_in_place() {
+        let mut data = [2.0, -2.0, -5.0, 0.5, 0.5, 10.0];
+
+        assert_eq!(map_rows_in_place(&clamping_box(), &mut data, 3), Ok(()));
+        assert_eq!(data, [1.0, -1.0, 0.0, 0.5, 0.5, 10.0]);
+    }
+
+    #[test]
+    fn test_layout_offset() {
+        let data = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_eq!(Layout::default(), Layout::RowMajor);
+        assert_eq!(data[Layout::RowMajor.offset(2, 3, 1, 0)], 3.0);
+        assert_eq!(data[Layout::ColumnMajor.offset(2, 3, 1, 0)], 1.0);
+        assert_eq!(data[Layout::ColumnMajor.offset(2, 3, 0, 2)], 4.0);
+    }
+
+    #[test]
+    fn test_width_mismatch() {
+        let space = clamping_box();
+
+        assert_eq!(
+            map_rows(&space, &[0.0, 0.0, 0.0, 1.0], 3),
+            Err(RowsError { row: 1, expected: 3, found: 1, })
+        );
+        assert_eq!(
+            map_rows(&space, &[0.0, 0.0, 0.0, 0.0], 2),
+            Err(RowsError { row: 0, expected: 3, found: 2, })
+        );
+        assert_eq!(
+            map_rows(&Ragged, &[2.0, 2.0, 1.0], 1),
+            Err(RowsError { row: 2, expected: 2, found: 1, })
+        );
+        assert_eq!(
+            map_rows_in_place(&Truncate, &mut [0.0, 1.0], 2),
+            Err(RowsError { row: 0, expected: 2, found: 1, })
+        );
+        assert_eq!(
+            map_rows(&space, &[0.0, 1.0], 0),
+            Err(RowsError { row: 0, expected: 0, found: 2, })
+        );
+        assert_eq!(map_rows(&space, &[], 0), Ok(vec![]));
+    }
+}
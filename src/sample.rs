@@ -0,0 +1,30 @@
+use std::{error::Error, fmt};
+
+/// Error returned when a value cannot be drawn uniformly at random from a space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SampleError {
+    /// The space has at least one missing bound.
+    Unbounded,
+    /// The space contains no values.
+    Empty,
+    /// The space is bounded and non-empty, but admits no uniform distribution (e.g. due to
+    /// non-finite bounds).
+    NoDistribution,
+    /// No acceptable value was drawn within the given number of attempts, e.g. by `SampleWith`.
+    Exhausted { attempts: usize },
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SampleError::Unbounded => write!(f, "space is unbounded"),
+            SampleError::Empty => write!(f, "space is empty"),
+            SampleError::NoDistribution => write!(f, "space admits no uniform distribution"),
+            SampleError::Exhausted { attempts } =>
+                write!(f, "no value in the space was drawn in {} attempts", attempts),
+        }
+    }
+}
+
+impl Error for SampleError {}
@@ -0,0 +1,240 @@
+use crate::{prelude::*, SampleError};
+use rand::distributions::Distribution;
+use std::fmt;
+
+/// Default number of draws made by `SampleWith` before giving up on finding a contained value.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 1000;
+
+/// Space wrapper that samples from a user-chosen distribution, rather than uniformly.
+///
+/// This allows sampling from spaces for which no uniform distribution exists, such as `Naturals`
+/// and `Integers`, or with a non-uniform distribution over a bounded space. Draws that fall
+/// outside of the base space are rejected and redrawn, at most `max_attempts` times in total;
+/// `try_sample` returns `SampleError::Exhausted` if no draw was accepted. Everything other than
+/// sampling is that of the base space.
+/// ```
+/// extern crate rand;
+///
+/// use rand::distributions::Distribution;
+/// use spaces::{discrete::Naturals, BoundedSpace, Sample, SampleWith};
+///
+/// struct Geometric;
+///
+/// impl Distribution<u64> for Geometric {
+///     fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+///         (0..).take_while(|_| rng.gen_bool(0.5)).count() as u64
+///     }
+/// }
+///
+/// let space = SampleWith::new(Naturals, Geometric);
+/// let mut rng = rand::thread_rng();
+///
+/// assert!(Naturals.try_sample(&mut rng).is_err());
+/// assert!(space.contains(space.sample(&mut rng)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SampleWith<S, D> {
+    base: S,
+    dist: D,
+    max_attempts: usize,
+}
+
+impl<S, D> SampleWith<S, D> {
+    /// Construct a wrapper drawing from `dist`, with `DEFAULT_MAX_ATTEMPTS` attempts per sample.
+    pub fn new(base: S, dist: D) -> SampleWith<S, D> {
+        SampleWith::with_max_attempts(base, dist, DEFAULT_MAX_ATTEMPTS)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is zero.
+    pub fn with_max_attempts(base: S, dist: D, max_attempts: usize) -> SampleWith<S, D> {
+        if max_attempts == 0 {
+            panic!("A sampler must be allowed at least one attempt.")
+        }
+
+        SampleWith { base, dist, max_attempts, }
+    }
+
+    #[inline]
+    pub fn base(&self) -> &S { &self.base }
+
+    #[inline]
+    pub fn distribution(&self) -> &D { &self.dist }
+
+    #[inline]
+    pub fn max_attempts(&self) -> usize { self.max_attempts }
+}
+
+impl<S: Space, D> Space for SampleWith<S, D> {
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.base.dim() }
+
+    fn shape(&self) -> Shape { self.base.shape() }
+
+    fn card(&self) -> Card { self.base.card() }
+}
+
+impl<S: BoundedSpace, D> BoundedSpace for SampleWith<S, D>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.base.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.base.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { self.base.contains(val) }
+}
+
+/// Values are drawn from the distribution by rejection, discarding any outside of the base space.
+impl<S, D> Sample for SampleWith<S, D>
+where
+    S: BoundedSpace,
+    S::Value: PartialOrd,
+    D: Distribution<S::Value>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from space: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+        for _ in 0..self.max_attempts {
+            let val = self.dist.sample(rng);
+
+            if self.base.contains(val.clone()) { return Ok(val); }
+        }
+
+        Err(SampleError::Exhausted { attempts: self.max_attempts, })
+    }
+}
+
+impl<X, S: Surjection<X, Y>, D, Y> Surjection<X, Y> for SampleWith<S, D> {
+    fn map_onto(&self, val: X) -> Y { self.base.map_onto(val) }
+}
+
+impl<S: fmt::Display, D> fmt::Display for SampleWith<S, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.base) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discrete::{Naturals, Ordinal};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    // Poisson distribution with the given rate, by Knuth's multiplication method.
+    struct Poisson(f64);
+
+    impl Distribution<u64> for Poisson {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+            let threshold = (-self.0).exp();
+            let (mut k, mut p) = (0, rng.gen::<f64>());
+
+            while p > threshold {
+                k += 1;
+                p *= rng.gen::<f64>();
+            }
+
+            k
+        }
+    }
+
+    // Zipf distribution over `0..n` with exponent `s`, by inversion of the cumulative weights.
+    struct Zipf(Vec<f64>);
+
+    impl Zipf {
+        fn new(n: usize, s: f64) -> Zipf {
+            Zipf((1..=n).scan(0.0, |acc, k| {
+                *acc += (k as f64).powf(-s);
+
+                Some(*acc)
+            }).collect())
+        }
+    }
+
+    impl Distribution<usize> for Zipf {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+            let u = rng.gen::<f64>() * self.0[self.0.len() - 1];
+
+            self.0.iter().position(|&c| u < c).unwrap_or(self.0.len() - 1)
+        }
+    }
+
+    // Distribution that only ever produces zero.
+    struct Zero;
+
+    impl Distribution<u64> for Zero {
+        fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> u64 { 0 }
+    }
+
+    #[test]
+    fn test_poisson_naturals() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = SampleWith::new(Naturals, Poisson(3.0));
+        let n = 20_000;
+        let mut total = 0;
+
+        for _ in 0..n {
+            let x = space.sample(&mut rng);
+
+            assert!(x > 0);
+            total += x;
+        }
+
+        // The mean of a Poisson distribution conditioned on a positive outcome.
+        let expected = 3.0 / (1.0 - (-3.0f64).exp());
+        let mean = total as f64 / n as f64;
+
+        assert!((mean - expected).abs() < 0.05, "mean {} != {}", mean, expected);
+    }
+
+    #[test]
+    fn test_zipf_ordinal() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = SampleWith::new(Ordinal::new(10), Zipf::new(1000, 2.0));
+        let mut counts = [0usize; 10];
+
+        for _ in 0..20_000 {
+            counts[space.sample(&mut rng)] += 1;
+        }
+
+        // Frequencies of successive ranks fall off as `(k + 1)^-2`.
+        let ratio = counts[0] as f64 / counts[1] as f64;
+
+        assert!((ratio - 4.0).abs() < 0.3, "ratio {} != 4", ratio);
+        assert!(counts.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(space.card(), Card::Finite(10));
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = SampleWith::with_max_attempts(Naturals, Zero, 25);
+
+        assert_eq!(space.try_sample(&mut rng), Err(SampleError::Exhausted { attempts: 25 }));
+        assert_eq!(
+            SampleWith::new(Naturals, Zero).try_sample(&mut rng),
+            Err(SampleError::Exhausted { attempts: DEFAULT_MAX_ATTEMPTS })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_exhausted() {
+        SampleWith::new(Naturals, Zero).sample(&mut SmallRng::seed_from_u64(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_attempts() {
+        SampleWith::with_max_attempts(Naturals, Zero, 0);
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(SampleWith::new(Naturals, Poisson(3.0)); sample, bounds, shape);
+        let zipf = SampleWith::new(Ordinal::new(10), Zipf::new(10, 2.0));
+
+        assert_space_laws!(zipf; sample, bounds, shape);
+    }
+}
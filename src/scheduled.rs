@@ -0,0 +1,344 @@
+use crate::{discrete::Ordinal, prelude::*, Interval, ProductSpace, SampleError};
+use std::{error::Error, fmt};
+
+/// Error type for pairs of spaces that cannot be interpolated between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScheduleError {
+    /// The products have a different number of components.
+    LengthMismatch { start: usize, end: usize },
+    /// A bound is present in one interval but missing from the other, or the kinds of the bounds
+    /// differ.
+    BoundMismatch,
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::LengthMismatch { start, end } => write!(
+                f, "cannot interpolate from {} components to {} components", start, end
+            ),
+            ScheduleError::BoundMismatch =>
+                write!(f, "cannot interpolate between intervals with different kinds of bound"),
+        }
+    }
+}
+
+impl Error for ScheduleError {}
+
+/// Trait for spaces that can be interpolated between, as used by `Scheduled`.
+pub trait Interpolate: Sized {
+    /// Returns an error if `self` and `other` have incompatible shapes.
+    fn check_interpolate(&self, other: &Self) -> Result<(), ScheduleError>;
+
+    /// Returns the space at progress `t` from `self` (at zero) to `other` (at one).
+    ///
+    /// The spaces must be compatible according to `check_interpolate`.
+    fn interpolate(&self, other: &Self, t: f64) -> Self;
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 { a + t * (b - a) }
+
+/// Bounds are interpolated linearly; the kinds of bound must match.
+impl Interpolate for Interval<f64> {
+    fn check_interpolate(&self, other: &Interval<f64>) -> Result<(), ScheduleError> {
+        let same = self.lb.is_some() == other.lb.is_some()
+            && self.ub.is_some() == other.ub.is_some()
+            && self.lb_kind == other.lb_kind && self.ub_kind == other.ub_kind;
+
+        if same { Ok(()) } else { Err(ScheduleError::BoundMismatch) }
+    }
+
+    fn interpolate(&self, other: &Interval<f64>, t: f64) -> Interval<f64> {
+        let lerp_opt = |a: Option<f64>, b: Option<f64>| Some(lerp(a?, b?, t));
+
+        Interval::new(lerp_opt(self.lb, other.lb), lerp_opt(self.ub, other.ub))
+            .with_kinds(self.lb_kind, self.ub_kind)
+    }
+}
+
+/// The size is interpolated linearly and rounded to the nearest integer.
+impl Interpolate for Ordinal {
+    fn check_interpolate(&self, _: &Ordinal) -> Result<(), ScheduleError> { Ok(()) }
+
+    fn interpolate(&self, other: &Ordinal, t: f64) -> Ordinal {
+        Ordinal::new(lerp(self.size() as f64, other.size() as f64, t).round() as usize)
+    }
+}
+
+/// Components are interpolated pairwise, so boxes are interpolated bound by bound.
+impl<D: Interpolate> Interpolate for ProductSpace<D> {
+    fn check_interpolate(&self, other: &ProductSpace<D>) -> Result<(), ScheduleError> {
+        let (start, end) = (self.iter().len(), other.iter().len());
+
+        if start != end { return Err(ScheduleError::LengthMismatch { start, end, }); }
+
+        self.iter().zip(other.iter()).try_for_each(|(a, b)| a.check_interpolate(b))
+    }
+
+    fn interpolate(&self, other: &ProductSpace<D>, t: f64) -> ProductSpace<D> {
+        self.iter().zip(other.iter()).map(|(a, b)| a.interpolate(b, t)).collect()
+    }
+}
+
+/// Space that varies over the course of a schedule, from a start space to an end space, e.g. for
+/// curriculum learning.
+///
+/// The space at the current progress `t ∈ [0, 1]` is kept materialised, and `Space`, `Sample`
+/// and `BoundedSpace` all reflect it.
+/// ```
+/// use spaces::{real::Interval, BoundedSpace, Scheduled};
+///
+/// let mut space = Scheduled::new(Interval::bounded(-0.1, 0.1), Interval::bounded(-1.0, 1.0))
+///     .unwrap();
+///
+/// assert!(!space.contains(0.5));
+///
+/// space.set_progress(0.5);
+///
+/// assert_eq!(space.current(), Interval::bounded(-0.55, 0.55));
+/// assert!(space.contains(0.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(
+    try_from = "ScheduledRepr<S>",
+    bound(deserialize = "S: Interpolate + Clone + serde::Deserialize<'de>")
+))]
+pub struct Scheduled<S> {
+    start: S,
+    end: S,
+    progress: f64,
+    current: S,
+}
+
+// Deserialized schedules are checked as by `Scheduled::new`, and the current space is then
+// recomputed from the progress, as by `Scheduled::set_progress`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "Scheduled")]
+struct ScheduledRepr<S> {
+    start: S,
+    end: S,
+    progress: f64,
+}
+
+#[cfg(feature = "serialize")]
+impl<S: Interpolate + Clone> std::convert::TryFrom<ScheduledRepr<S>> for Scheduled<S> {
+    type Error = ScheduleError;
+
+    fn try_from(repr: ScheduledRepr<S>) -> Result<Scheduled<S>, ScheduleError> {
+        let mut space = Scheduled::new(repr.start, repr.end)?;
+
+        space.set_progress(repr.progress);
+
+        Ok(space)
+    }
+}
+
+impl<S: Interpolate + Clone> Scheduled<S> {
+    /// Construct a schedule from `start` to `end`, at zero progress.
+    pub fn new(start: S, end: S) -> Result<Scheduled<S>, ScheduleError> {
+        start.check_interpolate(&end)?;
+
+        Ok(Scheduled { current: start.clone(), start, end, progress: 0.0, })
+    }
+
+    #[inline]
+    pub fn start(&self) -> &S { &self.start }
+
+    #[inline]
+    pub fn end(&self) -> &S { &self.end }
+
+    /// Returns the current progress, in `[0, 1]`.
+    #[inline]
+    pub fn progress(&self) -> f64 { self.progress }
+
+    /// Set the current progress, clamped onto `[0, 1]`; `NaN` is treated as zero.
+    pub fn set_progress(&mut self, t: f64) {
+        self.progress = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        self.current = self.start.interpolate(&self.end, self.progress);
+    }
+
+    /// Returns the space at the current progress.
+    pub fn current(&self) -> S { self.current.clone() }
+}
+
+impl<S: Space> Space for Scheduled<S> {
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.current.dim() }
+
+    fn shape(&self) -> Shape { self.current.shape() }
+
+    fn card(&self) -> Card { self.current.card() }
+}
+
+impl<S: Sample> Sample for Scheduled<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value { self.current.sample(rng) }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+        self.current.try_sample(rng)
+    }
+}
+
+impl<S: BoundedSpace> BoundedSpace for Scheduled<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.current.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.current.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { self.current.contains(val) }
+}
+
+impl<S: Surjection<X, Y>, X, Y> Surjection<X, Y> for Scheduled<S> {
+    fn map_onto(&self, val: X) -> Y { self.current.map_onto(val) }
+}
+
+impl<S: fmt::Display> fmt::Display for Scheduled<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.current) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BoundKind;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut space =
+            Scheduled::new(Interval::bounded(0.0, 1.0), Interval::bounded(-4.0, 5.0)).unwrap();
+
+        assert_eq!(space.inf(), Some(0.0));
+        assert_eq!(space.sup(), Some(1.0));
+
+        space.set_progress(0.5);
+
+        assert_eq!(space.progress(), 0.5);
+        assert_eq!(space.current(), Interval::bounded(-2.0, 3.0));
+
+        for _ in 0..1000 {
+            let x = space.sample(&mut rng);
+
+            assert!((-2.0..=3.0).contains(&x));
+        }
+
+        space.set_progress(1.0);
+
+        assert_eq!(space.current(), Interval::bounded(-4.0, 5.0));
+
+        space.set_progress(7.0);
+
+        assert_eq!(space.progress(), 1.0);
+
+        space.set_progress(f64::NAN);
+
+        assert_eq!(space.progress(), 0.0);
+        assert_eq!(space.current(), Interval::bounded(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_box() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut space = Scheduled::new(
+            ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::left_bounded(0.0)]),
+            ProductSpace::new(vec![Interval::bounded(0.0, 3.0), Interval::left_bounded(2.0)]),
+        ).unwrap();
+
+        space.set_progress(0.5);
+
+        assert_eq!(
+            space.current(),
+            ProductSpace::new(vec![Interval::bounded(0.0, 2.0), Interval::left_bounded(1.0)])
+        );
+        assert!(space.try_sample(&mut rng).is_err());
+
+        let mut space = Scheduled::new(
+            ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]),
+            ProductSpace::new(vec![Interval::bounded(-1.0, 3.0); 2]),
+        ).unwrap();
+
+        space.set_progress(0.5);
+
+        for _ in 0..1000 {
+            let x = space.sample(&mut rng);
+
+            assert!(x.iter().all(|x| (-0.5..=2.0).contains(x)));
+        }
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let mut space = Scheduled::new(Ordinal::new(2), Ordinal::new(10)).unwrap();
+
+        assert_eq!(space.card(), Card::Finite(2));
+
+        space.set_progress(0.5);
+
+        assert_eq!(space.current(), Ordinal::new(6));
+        assert!(space.contains(5) && !space.contains(6));
+
+        space.set_progress(0.3);
+
+        assert_eq!(space.current(), Ordinal::new(4));
+
+        space.set_progress(1.0);
+
+        assert_eq!(space.card(), Card::Finite(10));
+    }
+
+    #[test]
+    fn test_incompatible() {
+        assert_eq!(
+            Scheduled::new(Interval::bounded(0.0, 1.0), Interval::left_bounded(0.0)),
+            Err(ScheduleError::BoundMismatch)
+        );
+        assert_eq!(
+            Scheduled::new(Interval::bounded(0.0, 1.0), Interval::open(0.0, 1.0)),
+            Err(ScheduleError::BoundMismatch)
+        );
+        assert_eq!(
+            Scheduled::new(
+                ProductSpace::new(vec![Interval::unit(); 2]),
+                ProductSpace::new(vec![Interval::unit(); 3]),
+            ),
+            Err(ScheduleError::LengthMismatch { start: 2, end: 3 })
+        );
+        assert!(Scheduled::new(
+            Interval::open(0.0, 1.0),
+            Interval::bounded(0.0, 2.0).with_kinds(BoundKind::Open, BoundKind::Open),
+        ).is_ok());
+    }
+
+    #[test]
+    fn test_laws() {
+        let mut space =
+            Scheduled::new(Interval::bounded(0.0, 1.0), Interval::bounded(-4.0, 5.0)).unwrap();
+
+        space.set_progress(0.3);
+
+        assert_space_laws!(space; sample, bounds, shape, fmt, serde);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let space =
+            Scheduled::new(Interval::bounded(0.0, 1.0), Interval::bounded(-4.0, 5.0)).unwrap();
+        let mut json = serde_json::to_value(&space).unwrap();
+
+        // The current space is recomputed from the progress, rather than trusted.
+        json["progress"] = 0.5.into();
+
+        let parsed: Scheduled<Interval> = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(parsed.current(), Interval::bounded(-2.0, 3.0));
+
+        json["end"] = serde_json::to_value(Interval::left_bounded(0.0)).unwrap();
+
+        assert!(serde_json::from_value::<Scheduled<Interval>>(json).is_err());
+    }
+}
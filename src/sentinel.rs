@@ -0,0 +1,191 @@
+use crate::{prelude::*, SampleError};
+
+/// Space wrapper that reserves one designated value of its base space as "not a member".
+///
+/// This is useful for protocols that encode a null value in-band, e.g. `u64::MAX` standing for
+/// "no action" alongside `Naturals`. The sentinel is excluded from `contains` and from the
+/// cardinality of the space, is never sampled, and is replaced by `default` wherever a
+/// surjection would otherwise produce it. The bounds of the space are those of the base space.
+/// ```
+/// use spaces::{discrete::Naturals, BoundedSpace, Surjection, WithSentinel};
+///
+/// let space = WithSentinel::new(Naturals, u64::MAX, 1);
+///
+/// assert!(space.contains(5));
+/// assert!(!space.contains(u64::MAX));
+/// assert_eq!(space.map_onto(u64::MAX), 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct WithSentinel<S: Space> {
+    base: S,
+    sentinel: S::Value,
+    default: S::Value,
+}
+
+impl<S: BoundedSpace> WithSentinel<S>
+where S::Value: PartialOrd
+{
+    /// # Panics
+    ///
+    /// Panics if `default` is equal to `sentinel`, or is not contained in `base`.
+    pub fn new(base: S, sentinel: S::Value, default: S::Value) -> WithSentinel<S> {
+        if default == sentinel {
+            panic!("The default value must differ from the sentinel.")
+        }
+
+        if !base.contains(default.clone()) {
+            panic!("The default value must be contained in the base space.")
+        }
+
+        WithSentinel { base, sentinel, default, }
+    }
+}
+
+impl<S: Space> WithSentinel<S>
+where S::Value: PartialEq
+{
+    #[inline]
+    pub fn base(&self) -> &S { &self.base }
+
+    #[inline]
+    pub fn sentinel(&self) -> &S::Value { &self.sentinel }
+
+    #[inline]
+    pub fn default_value(&self) -> &S::Value { &self.default }
+
+    /// Returns true iff `val` is the sentinel.
+    #[inline]
+    pub fn is_sentinel(&self, val: &S::Value) -> bool { *val == self.sentinel }
+}
+
+impl<S: BoundedSpace> Space for WithSentinel<S>
+where S::Value: PartialOrd
+{
+    type Value = S::Value;
+
+    fn dim(&self) -> Dim { self.base.dim() }
+
+    fn shape(&self) -> Shape { self.base.shape() }
+
+    fn card(&self) -> Card {
+        match self.base.card() {
+            Card::Finite(n) if n > 0 && self.base.contains(self.sentinel.clone()) =>
+                Card::Finite(n - 1),
+            card => card,
+        }
+    }
+}
+
+impl<S: BoundedSpace> BoundedSpace for WithSentinel<S>
+where S::Value: PartialOrd
+{
+    fn inf(&self) -> Option<S::Value> { self.base.inf() }
+
+    fn sup(&self) -> Option<S::Value> { self.base.sup() }
+
+    fn contains(&self, val: S::Value) -> bool { val != self.sentinel && self.base.contains(val) }
+}
+
+/// Samples are drawn from the base space by rejection, discarding the sentinel.
+impl<S: BoundedSpace + Sample> Sample for WithSentinel<S>
+where S::Value: PartialOrd
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S::Value {
+        self.try_sample(rng).unwrap_or_else(|e| panic!("Cannot sample from space: {}.", e))
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<S::Value, SampleError> {
+        if self.card() == Card::Finite(0) { return Err(SampleError::Empty); }
+
+        loop {
+            let val = self.base.try_sample(rng)?;
+
+            if val != self.sentinel { return Ok(val); }
+        }
+    }
+}
+
+impl<X, S> Surjection<X, S::Value> for WithSentinel<S>
+where
+    S: Space + Surjection<X, S::Value>,
+    S::Value: PartialEq,
+{
+    fn map_onto(&self, val: X) -> S::Value {
+        let val = self.base.map_onto(val);
+
+        if val == self.sentinel { self.default.clone() } else { val }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discrete::{Binary, Naturals, Ordinal};
+    use rand::{rngs::SmallRng, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let space = WithSentinel::new(Naturals, u64::MAX, 1);
+
+        assert!(space.contains(1));
+        assert!(space.contains(u64::MAX - 1));
+        assert!(!space.contains(u64::MAX));
+        assert!(!space.contains(0));
+        assert_eq!(space.card(), Card::Infinite);
+
+        let space = WithSentinel::new(Ordinal::new(4), 3, 0);
+
+        assert!(space.contains(2));
+        assert!(!space.contains(3));
+        assert_eq!(space.card(), Card::Finite(3));
+
+        // Sentinels outside of the base space do not affect its cardinality.
+        assert_eq!(WithSentinel::new(Ordinal::new(4), 10, 0).card(), Card::Finite(4));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let space = WithSentinel::new(Ordinal::new(3), 1, 0);
+        let mut counts = [0usize; 3];
+
+        for _ in 0..10_000 {
+            counts[space.sample(&mut rng)] += 1;
+        }
+
+        assert_eq!(counts[1], 0);
+        assert!(counts[0] > 4_500 && counts[2] > 4_500);
+
+        let space = WithSentinel::new(Binary, true, false);
+
+        assert!((0..1_000).all(|_| !space.sample(&mut rng)));
+    }
+
+    #[test]
+    fn test_surjection() {
+        let space = WithSentinel::new(Naturals, u64::MAX, 7);
+
+        assert_eq!(space.map_onto(u64::MAX), 7);
+        assert_eq!(space.map_onto(0), 1);
+        assert_eq!(space.map_onto(10), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_default_is_sentinel() {
+        WithSentinel::new(Ordinal::new(3), 1, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_default_not_contained() {
+        WithSentinel::new(Ordinal::new(3), 1, 3);
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(WithSentinel::new(Ordinal::new(4), 3, 0); sample, bounds, shape, serde);
+        assert_space_laws!(WithSentinel::new(Naturals, u64::MAX, 1); sample, bounds, shape, serde);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Dim;
+use std::fmt;
+
+/// Multi-dimensional extents of the values of a space, e.g. `(H, W, C)` for an image.
+///
+/// The number of components of a value, `len`, is the product of the extents; scalars have no
+/// extents at all, and therefore a single component.
+/// ```
+/// use spaces::Shape;
+///
+/// let shape = Shape::new(vec![3, 84, 84]);
+///
+/// assert_eq!(shape.ndim(), 3);
+/// assert_eq!(shape.len(), 21168);
+/// assert_eq!(shape.to_string(), "3\u{00d7}84\u{00d7}84");
+/// assert_eq!(Shape::scalar().len(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Shape(Vec<usize>);
+
+impl Shape {
+    pub fn new(extents: Vec<usize>) -> Shape { Shape(extents) }
+
+    /// Returns the shape of a single value, with no extents.
+    pub fn scalar() -> Shape { Shape(vec![]) }
+
+    /// Returns the shape of a flat vector of `n` values.
+    pub fn vector(n: usize) -> Shape { Shape(vec![n]) }
+
+    /// Returns the shape of scalars for a dimensionality of one, and of vectors otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is infinite.
+    pub fn from_dim(dim: Dim) -> Shape {
+        match dim {
+            Dim::Finite(1) => Shape::scalar(),
+            Dim::Finite(n) => Shape::vector(n),
+            Dim::Infinite => panic!("A space of infinite dimensionality has no shape."),
+        }
+    }
+
+    /// Returns this shape with a leading extent of `n`, e.g. for a window of `n` values.
+    pub fn prepend(&self, n: usize) -> Shape {
+        Shape(Some(n).into_iter().chain(self.0.iter().cloned()).collect())
+    }
+
+    #[inline]
+    pub fn extents(&self) -> &[usize] { &self.0 }
+
+    /// Returns the number of extents.
+    #[inline]
+    pub fn ndim(&self) -> usize { self.0.len() }
+
+    /// Returns the number of components, i.e. the product of the extents.
+    pub fn len(&self) -> usize { self.0.iter().product() }
+
+    /// Returns true iff the shape has no components, i.e. some extent is zero.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    #[inline]
+    pub fn is_scalar(&self) -> bool { self.0.is_empty() }
+}
+
+impl From<Vec<usize>> for Shape {
+    fn from(extents: Vec<usize>) -> Shape { Shape(extents) }
+}
+
+/// Extents are separated by `×`, and scalars are written as `()`.
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() { return write!(f, "()"); }
+
+        for (i, n) in self.0.iter().enumerate() {
+            if i > 0 { write!(f, "\u{00d7}")?; }
+
+            write!(f, "{}", n)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_len() {
+        assert_eq!(Shape::scalar().len(), 1);
+        assert_eq!(Shape::scalar().ndim(), 0);
+        assert_eq!(Shape::vector(0).len(), 0);
+        assert!(Shape::vector(0).is_empty());
+        assert_eq!(Shape::new(vec![2, 3, 4]).len(), 24);
+        assert_eq!(Shape::new(vec![2, 0, 4]).len(), 0);
+    }
+
+    #[test]
+    fn test_from_dim() {
+        assert_eq!(Shape::from_dim(Dim::one()), Shape::scalar());
+        assert_eq!(Shape::from_dim(Dim::Finite(0)), Shape::vector(0));
+        assert_eq!(Shape::from_dim(Dim::Finite(5)), Shape::vector(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_infinite_dim() {
+        Shape::from_dim(Dim::Infinite);
+    }
+
+    #[test]
+    fn test_prepend() {
+        assert_eq!(Shape::scalar().prepend(4), Shape::vector(4));
+        assert_eq!(Shape::new(vec![84, 84]).prepend(3), Shape::new(vec![3, 84, 84]));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Shape::scalar().to_string(), "()");
+        assert_eq!(Shape::vector(7).to_string(), "7");
+        assert_eq!(Shape::new(vec![3, 84, 84]).to_string(), "3\u{00d7}84\u{00d7}84");
+    }
+}
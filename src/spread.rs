@@ -0,0 +1,83 @@
+use std::iter::FusedIterator;
+
+/// Iterator over `k` indices spread evenly across `0..n`, in increasing order.
+///
+/// The `i`th index is `floor(i * n / k)`, such that the first index is always `0` and successive
+/// indices are at most one apart in their spacing. If `k >= n`, every index is yielded exactly
+/// once.
+/// ```
+/// use spaces::Spread;
+///
+/// assert_eq!(Spread::new(10, 4).collect::<Vec<_>>(), vec![0, 2, 5, 7]);
+/// assert_eq!(Spread::new(3, 5).collect::<Vec<_>>(), vec![0, 1, 2]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spread {
+    n: usize,
+    k: usize,
+    i: usize,
+}
+
+impl Spread {
+    pub fn new(n: usize, k: usize) -> Spread { Spread { n, k: k.min(n), i: 0, } }
+}
+
+impl Iterator for Spread {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.i == self.k { return None; }
+
+        // Widened, such that the product cannot overflow.
+        let idx = (self.i as u128 * self.n as u128 / self.k as u128) as usize;
+
+        self.i += 1;
+
+        Some(idx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rem = self.k - self.i;
+
+        (rem, Some(rem))
+    }
+}
+
+impl ExactSizeIterator for Spread {}
+
+impl FusedIterator for Spread {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indices() {
+        assert_eq!(Spread::new(10, 1).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(Spread::new(10, 3).collect::<Vec<_>>(), vec![0, 3, 6]);
+        assert_eq!(Spread::new(10, 5).collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+        assert_eq!(Spread::new(4, 4).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(Spread::new(4, 100).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(Spread::new(0, 3).count(), 0);
+        assert_eq!(Spread::new(5, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_extreme() {
+        let spread: Vec<usize> = Spread::new(usize::MAX, 4).collect();
+
+        assert_eq!(spread, vec![0, usize::MAX / 4, usize::MAX / 2, usize::MAX / 4 * 3 + 2]);
+        assert!(spread.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_exact_size() {
+        let mut spread = Spread::new(100, 7);
+
+        assert_eq!(spread.len(), 7);
+        spread.next();
+        assert_eq!(spread.len(), 6);
+        assert_eq!(spread.by_ref().count(), 6);
+        assert_eq!(spread.next(), None);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    codec::{CodecError, ValueCodec},
+    prelude::*,
+    Describe,
+    SampleError,
+    SpaceDescription,
+};
+use std::{collections::VecDeque, fmt};
+
+/// Space of fixed-length windows of `k` consecutive values from a base space.
+///
+/// This is the space-level counterpart of frame stacking: values are ordered from oldest (at the
+/// front) to newest (at the back), and encodings concatenate the values in that same order.
+/// ```
+/// use spaces::{discrete::Ordinal, Dim, Space, Stacked};
+/// use std::collections::VecDeque;
+///
+/// let space = Stacked::new(Ordinal::new(5), 3);
+/// let mut window: VecDeque<usize> = vec![0, 1, 2].into();
+///
+/// space.push(&mut window, 4);
+///
+/// assert_eq!(space.dim(), Dim::Finite(3));
+/// assert_eq!(window, vec![1, 2, 4]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Stacked<S> {
+    base: S,
+    k: usize,
+}
+
+impl<S> Stacked<S> {
+    pub fn new(base: S, k: usize) -> Stacked<S> {
+        if k == 0 {
+            panic!("A stacked space must have a window of at least one value.")
+        }
+
+        Stacked { base, k, }
+    }
+
+    #[inline]
+    pub fn base(&self) -> &S { &self.base }
+
+    /// Returns the number of values in each window.
+    #[inline]
+    pub fn k(&self) -> usize { self.k }
+}
+
+impl<S: Space> Stacked<S> {
+    /// Append `new` to the back of `window`, dropping the oldest values such that the window
+    /// holds at most `k` values.
+    pub fn push(&self, window: &mut VecDeque<S::Value>, new: S::Value) {
+        window.push_back(new);
+
+        while window.len() > self.k {
+            window.pop_front();
+        }
+    }
+}
+
+impl<S: BoundedSpace> Stacked<S>
+where S::Value: PartialOrd
+{
+    /// Returns true iff `window` holds exactly `k` values, each contained in the base space.
+    pub fn contains(&self, window: &VecDeque<S::Value>) -> bool {
+        window.len() == self.k && window.iter().all(|v| self.base.contains(v.clone()))
+    }
+}
+
+impl<S: Space> Space for Stacked<S> {
+    type Value = VecDeque<S::Value>;
+
+    fn dim(&self) -> Dim {
+        match self.base.dim() {
+            Dim::Finite(d) => Dim::Finite(d * self.k),
+            Dim::Infinite => Dim::Infinite,
+        }
+    }
+
+    fn card(&self) -> Card { Card::product((0..self.k).map(|_| self.base.card())) }
+
+    /// Windows have a leading extent of `k`, followed by the shape of the base space.
+    fn shape(&self) -> Shape { self.base.shape().prepend(self.k) }
+}
+
+impl<S: Sample> Sample for Stacked<S> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Value {
+        (0..self.k).map(|_| self.base.sample(rng)).collect()
+    }
+
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Self::Value, SampleError> {
+        (0..self.k).map(|_| self.base.try_sample(rng)).collect()
+    }
+}
+
+impl<S: ValueCodec> ValueCodec for Stacked<S> {
+    fn encode(&self, val: &VecDeque<S::Value>, out: &mut Vec<u8>) {
+        for v in val.iter() {
+            self.base.encode(v, out);
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(Self::Value, usize), CodecError> {
+        let mut offset = 0;
+        let mut vals = VecDeque::with_capacity(self.k);
+
+        for _ in 0..self.k {
+            let (v, n) = self.base.decode(&bytes[offset..]).map_err(|e| match e {
+                CodecError::InvalidValue(o) => CodecError::InvalidValue(o + offset),
+                e => e,
+            })?;
+
+            offset += n;
+            vals.push_back(v);
+        }
+
+        Ok((vals, offset))
+    }
+}
+
+impl<S: Describe> Describe for Stacked<S> {
+    fn describe(&self) -> SpaceDescription {
+        SpaceDescription::Product(vec![self.base.describe(); self.k])
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Stacked<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}^{}", self.base, self.k) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{discrete::Ordinal, Interval, ProductSpace};
+    use super::*;
+
+    #[test]
+    fn test_window() {
+        let space = Stacked::new(Interval::bounded(0.0, 1.0), 3);
+        let mut window = VecDeque::new();
+
+        for (i, &v) in [0.1, 0.2, 0.3, 0.4, 0.5].iter().enumerate() {
+            space.push(&mut window, v);
+
+            assert_eq!(window.len(), (i + 1).min(3));
+            assert_eq!(space.contains(&window), i >= 2);
+        }
+
+        assert_eq!(window, vec![0.3, 0.4, 0.5]);
+
+        space.push(&mut window, 2.0);
+
+        assert!(!space.contains(&window));
+    }
+
+    #[test]
+    fn test_dim_card() {
+        let space = Stacked::new(ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]), 4);
+
+        assert_eq!(space.dim(), Dim::Finite(8));
+        assert_eq!(space.card(), Card::Finite(1296));
+        assert_eq!(Stacked::new(Interval::<f64>::unit(), 4).dim(), Dim::Finite(4));
+        assert_eq!(Stacked::new(Interval::<f64>::unit(), 4).card(), Card::Infinite);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = ::rand::thread_rng();
+        let space = Stacked::new(Ordinal::new(5), 4);
+
+        for _ in 0..10 {
+            assert!(space.contains(&space.sample(&mut rng)));
+        }
+
+        assert_eq!(
+            Stacked::new(Interval::left_bounded(0.0), 2).try_sample(&mut rng),
+            Err(SampleError::Unbounded)
+        );
+    }
+
+    #[test]
+    fn test_encoding_order() {
+        let space = Stacked::new(Ordinal::new(200), 3);
+        let mut window = VecDeque::new();
+
+        for v in [1, 2, 3, 150] {
+            space.push(&mut window, v);
+        }
+
+        let mut buf = vec![];
+
+        space.encode(&window, &mut buf);
+
+        assert_eq!(buf, vec![2, 3, 150, 1]);
+        assert_eq!(space.decode(&buf), Ok((window, 4)));
+        assert_eq!(space.decode(&[2, 3, 201, 1]), Err(CodecError::InvalidValue(2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_window() {
+        Stacked::new(Ordinal::new(5), 0);
+    }
+
+    #[test]
+    fn test_laws() {
+        assert_space_laws!(Stacked::new(Ordinal::new(5), 4); shape, fmt, serde);
+    }
+}
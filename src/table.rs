@@ -0,0 +1,244 @@
+use crate::prelude::*;
+use std::{error::Error, fmt};
+
+/// Error type for invalid lookup tables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TableMapError {
+    /// The table does not have exactly one entry per element of the domain.
+    LengthMismatch { expected: usize, found: usize },
+    /// The entry at `index` names a target outside the codomain.
+    OutOfRange { index: usize, target: usize },
+    /// The listed targets are not reached by any entry of the table.
+    NotSurjective(Vec<usize>),
+}
+
+impl fmt::Display for TableMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableMapError::LengthMismatch { expected, found } =>
+                write!(f, "expected a table of length {}, found {}", expected, found),
+            TableMapError::OutOfRange { index, target } =>
+                write!(f, "table entry {} points to target {} outside the codomain", index, target),
+            TableMapError::NotSurjective(targets) =>
+                write!(f, "table does not reach targets {:?}", targets),
+        }
+    }
+}
+
+impl Error for TableMapError {}
+
+/// Mapping between two finite spaces defined by an explicit lookup table.
+///
+/// Entry `i` of the table holds the index (see `FiniteSpace::to_index`) of the value in `B` onto
+/// which the `i`th value of `A` is mapped.
+/// ```
+/// use spaces::{discrete::Ordinal, Surjection, TableMap};
+///
+/// let map = TableMap::new(Ordinal::new(4), Ordinal::new(2), vec![0, 1, 1, 0]).unwrap();
+///
+/// assert_eq!(map.map_onto(2), 1);
+/// assert_eq!(map.preimage(&0), vec![0, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(
+    try_from = "TableMapRepr<A, B>",
+    bound(deserialize = "A: FiniteSpace + serde::Deserialize<'de>, \
+                         B: FiniteSpace + serde::Deserialize<'de>")
+))]
+pub struct TableMap<A, B> {
+    domain: A,
+    codomain: B,
+    table: Vec<usize>,
+}
+
+// Deserialized tables are checked as by `TableMap::new`.
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+#[serde(rename = "TableMap")]
+struct TableMapRepr<A, B> {
+    domain: A,
+    codomain: B,
+    table: Vec<usize>,
+}
+
+#[cfg(feature = "serialize")]
+impl<A, B> std::convert::TryFrom<TableMapRepr<A, B>> for TableMap<A, B>
+where
+    A: FiniteSpace,
+    B: FiniteSpace,
+{
+    type Error = TableMapError;
+
+    fn try_from(repr: TableMapRepr<A, B>) -> Result<TableMap<A, B>, TableMapError> {
+        TableMap::new(repr.domain, repr.codomain, repr.table)
+    }
+}
+
+impl<A: FiniteSpace, B: FiniteSpace> TableMap<A, B> {
+    pub fn new(domain: A, codomain: B, table: Vec<usize>) -> Result<Self, TableMapError> {
+        let expected = domain.card_finite();
+
+        if table.len() != expected {
+            return Err(TableMapError::LengthMismatch { expected, found: table.len(), });
+        }
+
+        let n_targets = codomain.card_finite();
+
+        if let Some((index, &target)) = table.iter().enumerate().find(|(_, &t)| t >= n_targets) {
+            return Err(TableMapError::OutOfRange { index, target, });
+        }
+
+        Ok(TableMap { domain, codomain, table, })
+    }
+
+    /// As `new`, but additionally checks that every element of the codomain is reached.
+    pub fn new_surjective(domain: A, codomain: B, table: Vec<usize>) -> Result<Self, TableMapError> {
+        let map = TableMap::new(domain, codomain, table)?;
+        let unreached = map.unreached();
+
+        if unreached.is_empty() { Ok(map) } else { Err(TableMapError::NotSurjective(unreached)) }
+    }
+
+    /// Returns the indices of all elements of the codomain that are not reached by the table.
+    pub fn unreached(&self) -> Vec<usize> {
+        let mut reached = vec![false; self.codomain.card_finite()];
+
+        for &t in self.table.iter() {
+            reached[t] = true;
+        }
+
+        reached.into_iter().enumerate().filter(|(_, r)| !r).map(|(i, _)| i).collect()
+    }
+
+    /// Compose this map with `other`, applying `other` to the output of `self`.
+    pub fn then<C>(self, other: TableMap<B, C>) -> Result<TableMap<A, C>, TableMapError>
+    where C: FiniteSpace {
+        let expected = self.codomain.card_finite();
+
+        if other.table.len() != expected {
+            return Err(TableMapError::LengthMismatch { expected, found: other.table.len(), });
+        }
+
+        let table = self.table.iter().map(|&t| other.table[t]).collect();
+
+        Ok(TableMap { domain: self.domain, codomain: other.codomain, table, })
+    }
+
+    pub fn domain(&self) -> &A { &self.domain }
+
+    pub fn codomain(&self) -> &B { &self.codomain }
+
+    pub fn table(&self) -> &[usize] { &self.table }
+
+    /// Returns all values of the domain that map onto `val`, in enumeration order.
+    pub fn preimage(&self, val: &B::Value) -> Vec<A::Value>
+    where B::Value: PartialEq {
+        match self.codomain.to_index(val) {
+            Some(target) => self
+                .domain
+                .iter()
+                .zip(self.table.iter())
+                .filter(|(_, &t)| t == target)
+                .map(|(v, _)| v)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl<A, B> Surjection<A::Value, B::Value> for TableMap<A, B>
+where
+    A: FiniteSpace,
+    B: FiniteSpace,
+    A::Value: PartialEq,
+{
+    fn map_onto(&self, val: A::Value) -> B::Value {
+        let idx = self.domain.to_index(&val).expect("Value is not contained in the domain.");
+
+        self.codomain.from_index(self.table[idx]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discrete::{Binary, Ordinal};
+    use super::*;
+
+    #[test]
+    fn test_mapping() {
+        let map = TableMap::new(Ordinal::new(6), Binary, vec![0, 1, 0, 1, 0, 1]).unwrap();
+
+        for i in 0..6 {
+            assert_eq!(map.map_onto(i), i % 2 == 1);
+        }
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert_eq!(
+            TableMap::new(Ordinal::new(3), Ordinal::new(2), vec![0, 1]),
+            Err(TableMapError::LengthMismatch { expected: 3, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_invalid_entry() {
+        assert_eq!(
+            TableMap::new(Ordinal::new(3), Ordinal::new(2), vec![0, 2, 1]),
+            Err(TableMapError::OutOfRange { index: 1, target: 2 })
+        );
+    }
+
+    #[test]
+    fn test_surjectivity() {
+        let table = vec![0, 0, 2, 2, 4, 0];
+
+        assert!(TableMap::new(Ordinal::new(6), Ordinal::new(6), table.clone()).is_ok());
+        assert_eq!(
+            TableMap::new_surjective(Ordinal::new(6), Ordinal::new(6), table),
+            Err(TableMapError::NotSurjective(vec![1, 3, 5]))
+        );
+        assert!(TableMap::new_surjective(Ordinal::new(3), Ordinal::new(2), vec![1, 0, 1]).is_ok());
+    }
+
+    #[test]
+    fn test_then() {
+        let halve = TableMap::new(Ordinal::new(4), Ordinal::new(2), vec![0, 0, 1, 1]).unwrap();
+        let parity = TableMap::new(Ordinal::new(2), Binary, vec![1, 0]).unwrap();
+        let map = halve.clone().then(parity).unwrap();
+
+        assert_eq!(map.table(), &[1, 1, 0, 0]);
+        assert_eq!(
+            halve.then(TableMap::new(Ordinal::new(3), Binary, vec![0, 1, 0]).unwrap()),
+            Err(TableMapError::LengthMismatch { expected: 2, found: 3 })
+        );
+    }
+
+    #[test]
+    fn test_preimage() {
+        let map = TableMap::new(Ordinal::new(18), Ordinal::new(6), (0..18).map(|i| i / 3).collect())
+            .unwrap();
+
+        for t in 0..6 {
+            assert_eq!(map.preimage(&t), vec![3 * t, 3 * t + 1, 3 * t + 2]);
+        }
+
+        assert!(map.preimage(&6).is_empty());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_deserialize() {
+        let map: TableMap<Ordinal, Ordinal> =
+            serde_json::from_str(r#"{"domain":2,"codomain":2,"table":[1,0]}"#).unwrap();
+
+        assert_eq!(map.map_onto(0), 1);
+
+        let err = |json: &str| serde_json::from_str::<TableMap<Ordinal, Ordinal>>(json).is_err();
+
+        assert!(err(r#"{"domain":2,"codomain":2,"table":[0,5]}"#));
+        assert!(err(r#"{"domain":2,"codomain":2,"table":[0]}"#));
+    }
+}
@@ -0,0 +1,355 @@
+//! Helpers for testing spaces, and properties over every value of a finite space.
+//!
+//! These are intended for use in the tests of downstream crates, as much as in those of `spaces`
+//! itself:
+//! ```
+//! use spaces::{discrete::Ordinal, testing::for_all, BoundedSpace};
+//!
+//! let space = Ordinal::new(10);
+//!
+//! assert!(for_all(&space, |&v| space.contains(v)).is_ok());
+//! assert_eq!(for_all(&space, |&v| v < 7).unwrap_err().value, 7);
+//! ```
+//!
+//! The `check_*` functions each assert one of the laws that every space should satisfy, and are
+//! most easily run together through the `assert_space_laws!` macro. The `check_empty_*`
+//! functions instead assert the laws of spaces without values, and are run together through the
+//! `empty_space_laws!` macro.
+use crate::{BoundedSpace, Card, Dim, FiniteSpace, Sample, SampleError, Space};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::{error::Error, fmt};
+
+/// Number of values drawn by `check_samples` in `assert_space_laws!`.
+pub const LAW_SAMPLES: usize = 1000;
+
+/// Maximum number of values enumerated by `check_enumeration`.
+pub const LAW_ENUMERATION_LIMIT: usize = 1 << 16;
+
+/// Maximum number of pairs checked exhaustively by `for_all_pairs`.
+pub const PAIR_LIMIT: usize = 1 << 20;
+
+/// Number of pairs drawn by `for_all_pairs` from spaces with more than `PAIR_LIMIT` pairs.
+pub const PAIR_SAMPLES: usize = 1 << 16;
+
+/// Value for which a property does not hold, together with its position in the enumeration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CounterExample<V> {
+    /// Index of the value in the enumeration order of the space, or of the pair in the
+    /// lexicographic order of the square of the space; see `for_all_pairs`.
+    pub index: usize,
+    pub value: V,
+}
+
+impl<V: fmt::Debug> fmt::Display for CounterExample<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "property does not hold for {:?} (at index {})", self.value, self.index)
+    }
+}
+
+impl<V: fmt::Debug> Error for CounterExample<V> {}
+
+/// Pair of values for which a property does not hold; see `for_all_pairs`.
+pub type PairCounterExample<V> = CounterExample<(V, V)>;
+
+/// Checks that `prop` holds for every value of `space`, returning the first value for which it
+/// does not.
+///
+/// Values are streamed from `FiniteSpace::iter`, so nothing is materialised beyond the value
+/// currently being checked.
+pub fn for_all<S: FiniteSpace>(
+    space: &S,
+    prop: impl Fn(&S::Value) -> bool,
+) -> Result<(), CounterExample<S::Value>> {
+    match space.iter().enumerate().find(|(_, v)| !prop(v)) {
+        Some((index, value)) => Err(CounterExample { index, value, }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `prop` holds for every pair of values of `space`, returning the first pair for
+/// which it does not.
+///
+/// The pair `(a, b)`, where `a` and `b` are at positions `i` and `j` in the enumeration of a
+/// space of `n` values, has index `i * n + j`. If there are more than `PAIR_LIMIT` pairs, only
+/// `PAIR_SAMPLES` of them are drawn uniformly at random, from a fixed seed, such that a failing
+/// pair is reported reproducibly.
+/// ```
+/// use spaces::{discrete::Ordinal, testing::for_all_pairs};
+///
+/// assert!(for_all_pairs(&Ordinal::new(5), |a, b| a.max(b) < &5).is_ok());
+///
+/// let err = for_all_pairs(&Ordinal::new(5), |a, b| a + b < 6).unwrap_err();
+///
+/// assert_eq!((err.index, err.value), (14, (2, 4)));
+/// ```
+pub fn for_all_pairs<S: FiniteSpace>(
+    space: &S,
+    prop: impl Fn(&S::Value, &S::Value) -> bool,
+) -> Result<(), PairCounterExample<S::Value>> {
+    let n = space.len();
+    let check = |i: usize, a: S::Value, j: usize, b: S::Value| {
+        if prop(&a, &b) {
+            Ok(())
+        } else {
+            Err(CounterExample { index: i.saturating_mul(n).saturating_add(j), value: (a, b), })
+        }
+    };
+
+    if n.checked_mul(n).is_some_and(|n_pairs| n_pairs <= PAIR_LIMIT) {
+        space.iter().enumerate().try_for_each(|(i, a)| {
+            space.iter().enumerate().try_for_each(|(j, b)| check(i, a.clone(), j, b))
+        })
+    } else {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        (0..PAIR_SAMPLES).try_for_each(|_| {
+            let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let value = |k| space.from_index(k).expect("Index must lie within the space.");
+
+            check(i, value(i), j, value(j))
+        })
+    }
+}
+
+/// Asserts that `n` values drawn from `space`, from a fixed seed, are all contained in it.
+///
+/// Spaces that cannot be sampled from, as reported by `try_sample`, pass trivially.
+pub fn check_samples<S>(space: &S, n: usize)
+where S: Sample + BoundedSpace, S::Value: PartialOrd + fmt::Debug
+{
+    let mut rng = SmallRng::seed_from_u64(0);
+
+    for _ in 0..n {
+        match space.try_sample(&mut rng) {
+            Ok(val) => assert!(
+                space.contains(val.clone()),
+                "sampled value {:?} is not contained in the space", val
+            ),
+            Err(_) => return,
+        }
+    }
+}
+
+/// Asserts that the infimum of `space` is no greater than its supremum, where both exist and the
+/// space is not known to be empty.
+pub fn check_bounds<S>(space: &S)
+where S: BoundedSpace, S::Value: PartialOrd + fmt::Debug
+{
+    if space.card() == Card::Finite(0) { return; }
+
+    if let (Some(inf), Some(sup)) = (space.inf(), space.sup()) {
+        assert!(inf <= sup, "infimum {:?} is greater than supremum {:?}", inf, sup);
+    }
+}
+
+/// Asserts that the shape of `space` has as many components as its (finite) dimensionality.
+pub fn check_shape<S: Space>(space: &S) {
+    if let Dim::Finite(dim) = space.dim() {
+        let shape = space.shape();
+
+        assert_eq!(shape.len(), dim, "shape {} disagrees with dimensionality {}", shape, dim);
+    }
+}
+
+/// Asserts that enumerating `space` agrees with its cardinality, and that the index of every
+/// value round-trips.
+///
+/// Spaces of more than `LAW_ENUMERATION_LIMIT` values pass trivially.
+pub fn check_enumeration<S>(space: &S)
+where S: FiniteSpace, S::Value: PartialEq + fmt::Debug
+{
+    if space.len() > LAW_ENUMERATION_LIMIT { return; }
+
+    assert_eq!(Card::Finite(space.iter().count()), space.card());
+    assert_eq!(space.iter().count(), space.len());
+    assert_eq!(space.is_empty(), space.len() == 0);
+
+    if let Err(e) = for_all(space, |v| {
+        space.to_index(v).and_then(|i| space.from_index(i)).as_ref() == Some(v)
+    }) {
+        panic!("index of value {:?} does not round-trip (at index {})", e.value, e.index);
+    }
+}
+
+/// Asserts that formatting `space` with `Debug`, alternate `Debug` and `Display` succeeds.
+pub fn check_fmt<S: fmt::Debug + fmt::Display>(space: &S) {
+    let _ = format!("{:?}", space);
+    let _ = format!("{:#?}", space);
+    let _ = format!("{}", space);
+}
+
+/// Asserts that `round_trip`, e.g. through a serialisation format, reproduces `space` exactly.
+pub fn check_round_trip<S, F>(space: &S, round_trip: F)
+where S: PartialEq + fmt::Debug, F: FnOnce(&S) -> S
+{
+    assert_eq!(&round_trip(space), space, "space does not survive a round trip");
+}
+
+/// Asserts that `space` has a cardinality of zero, and that `try_sample` reports it as empty.
+pub fn check_empty_samples<S: Sample>(space: &S) {
+    assert_eq!(space.card(), Card::Finite(0), "space is not empty");
+    assert_eq!(
+        space.try_sample(&mut SmallRng::seed_from_u64(0)).err(),
+        Some(SampleError::Empty),
+        "sampling from an empty space does not report it as empty"
+    );
+}
+
+/// Asserts that the empty `space` has neither an infimum nor a supremum.
+pub fn check_empty_bounds<S>(space: &S)
+where S: BoundedSpace, S::Value: PartialOrd + fmt::Debug
+{
+    assert_eq!(space.inf(), None, "empty space has an infimum");
+    assert_eq!(space.sup(), None, "empty space has a supremum");
+}
+
+/// Asserts that enumerating the empty `space` yields nothing, by every means of enumeration, and
+/// that its coverage is zero, rather than `NaN`.
+pub fn check_empty_enumeration<S>(space: &S)
+where S: FiniteSpace, S::Value: PartialEq + fmt::Debug
+{
+    assert_eq!(space.len(), 0);
+    assert!(space.is_empty());
+    assert_eq!(space.iter().next(), None);
+    assert_eq!(space.from_index(0), None);
+    assert_eq!(space.iter_every(1).next(), None);
+    assert_eq!(space.iter_spread(4).next(), None);
+    assert_eq!(space.coverage(&[]), 0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        discrete::{Alphabet, Binary, Ordinal},
+        BoundedSpace,
+        Empty,
+        Equipartition,
+        FiniteOrderedSpace,
+        Interval,
+        LogPartition,
+        Mixture,
+        Nominal,
+        Ordered,
+        PairSpace,
+        ProductSpace,
+        Quantised,
+        Stacked,
+        TwoSpace,
+    };
+    use std::cell::Cell;
+    use super::*;
+
+    // Checks that the index of every value of `space` round-trips.
+    fn check_index<S: FiniteSpace>(space: &S) where S::Value: PartialEq + fmt::Debug {
+        assert_eq!(for_all(space, |v| {
+            space.to_index(v).and_then(|i| space.from_index(i)).as_ref() == Some(v)
+        }), Ok(()));
+    }
+
+    // Checks, for every value of `space`, that it is contained in the space and that its index
+    // round-trips.
+    fn check_invariants<S>(space: S)
+    where S: FiniteSpace + BoundedSpace, S::Value: PartialOrd + fmt::Debug
+    {
+        assert_eq!(for_all(&space, |v| space.contains(v.clone())), Ok(()));
+
+        check_index(&space);
+    }
+
+    // Checks that the range of `space` holds exactly its values, in order.
+    fn check_range<S>(space: S)
+    where
+        S: FiniteOrderedSpace,
+        S::Value: PartialOrd + fmt::Debug,
+        std::ops::RangeInclusive<S::Value>: Iterator<Item = S::Value>,
+    {
+        assert_eq!(space.range().collect::<Vec<_>>(), space.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_empty_laws() {
+        empty_space_laws!(Empty; bounds, finite);
+        empty_space_laws!(Ordinal::new(0); sample, bounds, finite);
+        empty_space_laws!(Ordinal::<u8>::try_new(0).unwrap(); sample, bounds, finite);
+        empty_space_laws!(Nominal::new(Ordinal::new(0)); sample, finite);
+        empty_space_laws!(Ordered::new(Ordinal::new(0)); sample, bounds, finite);
+        empty_space_laws!(Ordinal::new(0).enumerated().unwrap(); sample, bounds, finite);
+        empty_space_laws!(Interval::<i64>::bounded(3, 1));
+        empty_space_laws!(Interval::<f64>::bounded(1.0, 0.0));
+        empty_space_laws!(Interval::<f64>::open(0.5, 0.5));
+        empty_space_laws!(Mixture::uniform(vec![Ordinal::new(0); 2]).unwrap());
+
+        // Composites with an empty component are themselves empty.
+        empty_space_laws!(ProductSpace::new(vec![Ordinal::new(3), Ordinal::new(0)]); sample);
+        empty_space_laws!(PairSpace::new(Ordinal::new(3), Interval::<i64>::bounded(3, 1)); sample);
+        empty_space_laws!(TwoSpace::new([Ordinal::new(0), Ordinal::new(2)]); sample);
+        empty_space_laws!(Stacked::new(Ordinal::new(0), 3); sample);
+    }
+
+    #[test]
+    fn test_invariants() {
+        check_invariants(Binary);
+        check_invariants(Ordinal::new(0));
+        check_invariants(Ordinal::new(100));
+        check_invariants(Ordinal::<u8>::try_new(256).unwrap());
+        check_invariants(Equipartition::new(-1.0, 1.0, 7));
+        check_invariants(LogPartition::new(1e-3, 1e3, 6));
+        check_invariants(Quantised::new(0.0, 1.0, 0.1));
+        check_invariants(Quantised::new(-1.0, 1.0, 0.3));
+
+        check_range(Ordinal::new(0));
+        check_range(Ordinal::new(100));
+        check_range(Ordinal::<u8>::try_new(256).unwrap());
+        check_range(Equipartition::new(-1.0, 1.0, 7));
+        check_range(LogPartition::new(1e-3, 1e3, 6));
+
+        check_index(&Alphabet::new("abcß🦀"));
+        check_index(&Alphabet::printable_ascii());
+    }
+
+    #[test]
+    fn test_counter_example() {
+        let err = for_all(&Ordinal::new(10), |&v| v * v < 20).unwrap_err();
+
+        assert_eq!(err, CounterExample { index: 5, value: 5 });
+        assert_eq!(err.to_string(), "property does not hold for 5 (at index 5)");
+        assert_eq!(for_all(&Ordinal::new(0), |_| false), Ok(()));
+    }
+
+    #[test]
+    fn test_pairs_exhaustive() {
+        let calls = Cell::new(0);
+        let space = Ordinal::new(30);
+
+        assert_eq!(for_all_pairs(&space, |a, b| {
+            calls.set(calls.get() + 1);
+
+            (a + b) % 2 == (a ^ b) % 2
+        }), Ok(()));
+        assert_eq!(calls.get(), 900);
+
+        assert_eq!(
+            for_all_pairs(&Binary, |a, b| a == b),
+            Err(CounterExample { index: 1, value: (false, true) })
+        );
+    }
+
+    #[test]
+    fn test_pairs_sampled() {
+        let calls = Cell::new(0);
+        let space = Ordinal::new(10_000);
+
+        assert_eq!(for_all_pairs(&space, |a, b| {
+            calls.set(calls.get() + 1);
+
+            space.contains(*a) && space.contains(*b)
+        }), Ok(()));
+        assert_eq!(calls.get(), PAIR_SAMPLES);
+
+        let err = for_all_pairs(&space, |a, b| a.max(b) < &5_000).unwrap_err();
+
+        assert!(err.value.0 >= 5_000 || err.value.1 >= 5_000);
+        assert_eq!(err.index, err.value.0 * 10_000 + err.value.1);
+        assert_eq!(for_all_pairs(&space, |a, b| a.max(b) < &5_000), Err(err));
+    }
+}
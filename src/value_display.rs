@@ -0,0 +1,634 @@
+use crate::{discrete::{Binary, Ordinal}, prelude::*, Interval, PairSpace, ProductSpace, TwoSpace};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Kind of error encountered while parsing a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseValueErrorKind {
+    /// The input ended while more was expected.
+    UnexpectedEnd,
+    /// A token other than the one described was found.
+    Expected(&'static str),
+    /// A number could not be parsed.
+    InvalidNumber,
+    /// The input was well-formed, but the value is not contained in the space.
+    NotContained,
+    /// The input contained unexpected characters after a complete value.
+    TrailingInput,
+}
+
+/// Error type for values that could not be parsed, pointing at the offending byte offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseValueError {
+    pub offset: usize,
+    pub kind: ParseValueErrorKind,
+}
+
+impl ParseValueError {
+    fn new(offset: usize, kind: ParseValueErrorKind) -> ParseValueError {
+        ParseValueError { offset, kind, }
+    }
+
+    // Shifts the offset of the error by `n` bytes, for errors in a component of a value.
+    fn shifted(self, n: usize) -> ParseValueError {
+        ParseValueError { offset: self.offset + n, ..self }
+    }
+}
+
+impl fmt::Display for ParseValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseValueErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseValueErrorKind::Expected(what) => write!(f, "expected {}", what)?,
+            ParseValueErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ParseValueErrorKind::NotContained => write!(f, "value is not contained in the space")?,
+            ParseValueErrorKind::TrailingInput => write!(f, "unexpected trailing input")?,
+        }
+
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl Error for ParseValueError {}
+
+type Result<T> = ::std::result::Result<T, ParseValueError>;
+
+/// Trait for spaces whose values can be written as strings and parsed back, e.g. for logs and
+/// CSV export.
+///
+/// The formats are as follows, where whitespace is permitted between any two tokens on parsing:
+///
+/// - `Binary` values are written as `0` or `1`, and `true` or `false` are also accepted;
+/// - `Ordinal` values and integer intervals are written as integers;
+/// - real intervals are written in the shortest decimal form that parses back to the same value;
+/// - products, including `PairSpace` and `TwoSpace`, are written as their comma-separated
+///   components within brackets.
+///
+/// Parsing fails for values that are well-formed but not contained in the space.
+///
+/// Values may also be rendered for humans with `display_value`, which writes each component of a
+/// product on a line of its own; see `DisplayOptions`.
+/// ```
+/// use spaces::{discrete::Ordinal, real::Interval, ProductSpace, ValueDisplay};
+/// use spaces::ParseValueErrorKind;
+///
+/// let space = ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]);
+///
+/// assert_eq!(space.fmt_value(&vec![0.1, 1.0]), "[0.1, 1.0]");
+/// assert_eq!(space.parse_value("[0.1, 1]"), Ok(vec![0.1, 1.0]));
+///
+/// let err = space.parse_value("[0.1, 2]").unwrap_err();
+///
+/// assert_eq!((err.offset, err.kind), (6, ParseValueErrorKind::NotContained));
+/// assert_eq!(Ordinal::new(5).parse_value(" 4 "), Ok(4));
+/// ```
+pub trait ValueDisplay: Space {
+    /// Write `val` as a string.
+    fn fmt_value(&self, val: &Self::Value) -> String;
+
+    /// Parse a value from the start of `s`, after any whitespace, returning it together with the
+    /// number of bytes of `s` consumed.
+    fn parse_value_prefix(&self, s: &str) -> Result<(Self::Value, usize)>;
+
+    /// Parse a value from the whole of `s`, surrounding whitespace aside.
+    fn parse_value(&self, s: &str) -> Result<Self::Value> {
+        let (val, n) = self.parse_value_prefix(s)?;
+        let end = n + skip_whitespace(&s[n..]);
+
+        if end == s.len() {
+            Ok(val)
+        } else {
+            Err(ParseValueError::new(end, ParseValueErrorKind::TrailingInput))
+        }
+    }
+
+    /// Render `val` for humans with the default `DisplayOptions`; see `display_value_with`.
+    fn display_value(&self, val: &Self::Value) -> String {
+        self.display_value_with(val, &DisplayOptions::default())
+    }
+
+    /// Render `val` for humans.
+    ///
+    /// Scalars are written plainly. Products are written one scalar component per line, as
+    /// `label: value (bounds)`, where the label is the path of indices to the component (e.g.
+    /// `1/0`); labels are padded to a common width, and components that lie outside of their
+    /// bounds are flagged with `OUT_OF_BOUNDS`.
+    /// ```
+    /// use spaces::{discrete::Ordinal, Interval, PairSpace, ProductSpace, ValueDisplay};
+    ///
+    /// let space = PairSpace::new(
+    ///     ProductSpace::new(vec![Interval::bounded(0.0, 1.0); 2]),
+    ///     Ordinal::new(3),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     space.display_value(&(vec![0.25, 1.5], 2)),
+    ///     "0/0: 0.25 ([0, 1])\n0/1: 1.5 ([0, 1])  <- out of bounds\n1  : 2 ([0..2])"
+    /// );
+    /// assert_eq!(Interval::bounded(0.0, 1.0).display_value(&0.5), "0.5");
+    /// ```
+    fn display_value_with(&self, val: &Self::Value, options: &DisplayOptions) -> String {
+        let mut entries = vec![];
+
+        self.display_entries(val, String::new(), options, &mut entries);
+
+        match &entries[..] {
+            [(label, text)] if label.is_empty() => text.clone(),
+            _ => {
+                let width = entries.iter().map(|(l, _)| l.chars().count()).max().unwrap_or(0);
+
+                entries
+                    .iter()
+                    .map(|(l, text)| format!("{:w$}: {}", l, text, w = width))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+        }
+    }
+
+    /// Push the `(label, text)` entries rendering `val` onto `entries`, for `display_value`.
+    ///
+    /// Scalars push a single entry, whose text is the plain value if `label` is empty; products
+    /// push the entries of their components. By default, `val` is written with `fmt_value`.
+    #[doc(hidden)]
+    fn display_entries(
+        &self,
+        val: &Self::Value,
+        label: String,
+        _: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        entries.push((label, self.fmt_value(val)));
+    }
+}
+
+/// Marker appended by `ValueDisplay::display_value` to components outside of their bounds.
+pub const OUT_OF_BOUNDS: &str = "  <- out of bounds";
+
+/// Options controlling `ValueDisplay::display_value_with`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Number of decimal places of real values, or `None` for the shortest form that round-trips.
+    pub precision: Option<usize>,
+    /// Maximum number of components of a product that are written, or `None` for no limit; the
+    /// first and last components are kept, and those in between elided.
+    pub max_items: Option<usize>,
+}
+
+/// Values are written in full precision, with products of more than 16 components elided.
+impl Default for DisplayOptions {
+    fn default() -> DisplayOptions { DisplayOptions { precision: None, max_items: Some(16), } }
+}
+
+// Pushes the entry of a scalar component, with its bounds and whether it is contained.
+fn display_scalar<S: fmt::Display>(
+    space: &S,
+    text: String,
+    contained: bool,
+    label: String,
+    entries: &mut Vec<(String, String)>,
+) {
+    if label.is_empty() { return entries.push((label, text)); }
+
+    let flag = if contained { "" } else { OUT_OF_BOUNDS };
+
+    entries.push((label, format!("{} ({}){}", text, space, flag)));
+}
+
+// Pushes the entries of the components of a product, `display(i, label, entries)` pushing those
+// of the `i`th, eliding any beyond `options.max_items`.
+fn display_components<F>(
+    n: usize,
+    label: &str,
+    options: &DisplayOptions,
+    entries: &mut Vec<(String, String)>,
+    mut display: F,
+) where F: FnMut(usize, String, &mut Vec<(String, String)>)
+{
+    let child = |i: &dyn fmt::Display| {
+        if label.is_empty() { i.to_string() } else { format!("{}/{}", label, i) }
+    };
+    let k = options.max_items.unwrap_or(n).min(n);
+    let (head, tail) = (k - k / 2, n - k / 2);
+
+    for i in 0..head { display(i, child(&i), entries); }
+
+    if head < tail {
+        entries.push((child(&"..."), format!("{} components elided", tail - head)));
+    }
+
+    for i in tail.max(head)..n { display(i, child(&i), entries); }
+}
+
+// Returns the offset of the first non-whitespace character of `s`.
+fn skip_whitespace(s: &str) -> usize { s.len() - s.trim_start().len() }
+
+// Returns the offsets of the start and end of the token beginning after any whitespace, where a
+// token is a run of alphanumeric characters, signs and decimal points.
+fn token(s: &str) -> Result<(usize, usize)> {
+    let start = skip_whitespace(s);
+    let rest = &s[start..];
+    let n = rest.find(|c: char| !c.is_alphanumeric() && !"+-.".contains(c)).unwrap_or(rest.len());
+
+    if n > 0 {
+        Ok((start, start + n))
+    } else if rest.is_empty() {
+        Err(ParseValueError::new(start, ParseValueErrorKind::UnexpectedEnd))
+    } else {
+        Err(ParseValueError::new(start, ParseValueErrorKind::Expected("a value")))
+    }
+}
+
+// Parses a number from the start of `s`, checking that it is contained in `space`.
+fn number<S, T>(space: &S, s: &str) -> Result<(T, usize)>
+where S: BoundedSpace<Value = T>, T: FromStr + PartialOrd + Clone
+{
+    let (start, end) = token(s)?;
+    let val: T = s[start..end]
+        .parse()
+        .map_err(|_| ParseValueError::new(start, ParseValueErrorKind::InvalidNumber))?;
+
+    if space.contains(val.clone()) {
+        Ok((val, end))
+    } else {
+        Err(ParseValueError::new(start, ParseValueErrorKind::NotContained))
+    }
+}
+
+// Consumes `token` after any whitespace, returning the offset just past it.
+fn expect(s: &str, pos: usize, token: &'static str) -> Result<usize> {
+    let start = pos + skip_whitespace(&s[pos..]);
+
+    if s[start..].starts_with(token) {
+        Ok(start + token.len())
+    } else if start == s.len() {
+        Err(ParseValueError::new(start, ParseValueErrorKind::UnexpectedEnd))
+    } else {
+        Err(ParseValueError::new(start, ParseValueErrorKind::Expected(token)))
+    }
+}
+
+// Parses exactly `n` bracketed, comma-separated components, the `i`th with `parse(i, _)`.
+fn components<T, F>(s: &str, n: usize, mut parse: F) -> Result<(Vec<T>, usize)>
+where F: FnMut(usize, &str) -> Result<(T, usize)>
+{
+    let mut pos = expect(s, 0, "[")?;
+    let mut vals = Vec::with_capacity(n);
+
+    for i in 0..n {
+        if i > 0 { pos = expect(s, pos, ",")?; }
+
+        let (val, m) = parse(i, &s[pos..]).map_err(|e| e.shifted(pos))?;
+
+        vals.push(val);
+        pos += m;
+    }
+
+    expect(s, pos, "]").map(|pos| (vals, pos))
+}
+
+fn fmt_components<I: Iterator<Item = String>>(components: I) -> String {
+    format!("[{}]", components.collect::<Vec<_>>().join(", "))
+}
+
+const BINARY_TOKENS: &str = "0, 1, true or false";
+
+impl ValueDisplay for Binary {
+    fn fmt_value(&self, val: &bool) -> String { (if *val { "1" } else { "0" }).to_string() }
+
+    fn display_entries(
+        &self,
+        val: &bool,
+        label: String,
+        _: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_scalar(self, self.fmt_value(val), true, label, entries)
+    }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<(bool, usize)> {
+        let (start, end) = token(s)?;
+
+        match &s[start..end] {
+            "0" | "false" => Ok((false, end)),
+            "1" | "true" => Ok((true, end)),
+            _ => Err(ParseValueError::new(start, ParseValueErrorKind::Expected(BINARY_TOKENS))),
+        }
+    }
+}
+
+impl ValueDisplay for Ordinal {
+    fn fmt_value(&self, val: &usize) -> String { val.to_string() }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<(usize, usize)> { number(self, s) }
+
+    fn display_entries(
+        &self,
+        val: &usize,
+        label: String,
+        _: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_scalar(self, val.to_string(), self.contains(*val), label, entries)
+    }
+}
+
+impl ValueDisplay for Interval<i64> {
+    fn fmt_value(&self, val: &i64) -> String { val.to_string() }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<(i64, usize)> { number(self, s) }
+
+    fn display_entries(
+        &self,
+        val: &i64,
+        label: String,
+        _: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_scalar(self, val.to_string(), self.contains(*val), label, entries)
+    }
+}
+
+/// Values are written with `Debug`, which gives the shortest form that round-trips exactly.
+impl ValueDisplay for Interval<f64> {
+    fn fmt_value(&self, val: &f64) -> String { format!("{:?}", val) }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<(f64, usize)> { number(self, s) }
+
+    /// Values are written with `options.precision` decimal places, if given.
+    fn display_entries(
+        &self,
+        val: &f64,
+        label: String,
+        options: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        let text = match options.precision {
+            Some(p) => format!("{:.*}", p, val),
+            None => self.fmt_value(val),
+        };
+
+        display_scalar(self, text, self.contains(*val), label, entries)
+    }
+}
+
+impl<D: ValueDisplay> ValueDisplay for ProductSpace<D> {
+    fn fmt_value(&self, val: &Vec<D::Value>) -> String {
+        fmt_components(self.iter().zip(val.iter()).map(|(d, v)| d.fmt_value(v)))
+    }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<(Vec<D::Value>, usize)> {
+        components(s, self.iter().len(), |i, s| self[i].parse_value_prefix(s))
+    }
+    fn display_entries(
+        &self,
+        val: &Vec<D::Value>,
+        label: String,
+        options: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_components(val.len().min(self.iter().len()), &label, options, entries, |i, l, e| {
+            self[i].display_entries(&val[i], l, options, e)
+        })
+    }
+}
+
+impl<D: ValueDisplay> ValueDisplay for TwoSpace<D> {
+    fn fmt_value(&self, val: &[D::Value; 2]) -> String {
+        fmt_components(self.iter().zip(val.iter()).map(|(d, v)| d.fmt_value(v)))
+    }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<([D::Value; 2], usize)> {
+        let (mut vals, n) = components(s, 2, |i, s| self[i].parse_value_prefix(s))?;
+        let v1 = vals.pop().unwrap();
+        let v0 = vals.pop().unwrap();
+
+        Ok(([v0, v1], n))
+    }
+    fn display_entries(
+        &self,
+        val: &[D::Value; 2],
+        label: String,
+        options: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_components(2, &label, options, entries, |i, l, e| {
+            self[i].display_entries(&val[i], l, options, e)
+        })
+    }
+}
+
+impl<D1: ValueDisplay, D2: ValueDisplay> ValueDisplay for PairSpace<D1, D2> {
+    fn fmt_value(&self, val: &(D1::Value, D2::Value)) -> String {
+        fmt_components(vec![self.0.fmt_value(&val.0), self.1.fmt_value(&val.1)].into_iter())
+    }
+
+    fn parse_value_prefix(&self, s: &str) -> Result<((D1::Value, D2::Value), usize)> {
+        let pos = expect(s, 0, "[")?;
+        let (v0, n) = self.0.parse_value_prefix(&s[pos..]).map_err(|e| e.shifted(pos))?;
+        let pos = expect(s, pos + n, ",")?;
+        let (v1, n) = self.1.parse_value_prefix(&s[pos..]).map_err(|e| e.shifted(pos))?;
+
+        expect(s, pos + n, "]").map(|pos| ((v0, v1), pos))
+    }
+    fn display_entries(
+        &self,
+        val: &(D1::Value, D2::Value),
+        label: String,
+        options: &DisplayOptions,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        display_components(2, &label, options, entries, |i, l, e| match i {
+            0 => self.0.display_entries(&val.0, l, options, e),
+            _ => self.1.display_entries(&val.1, l, options, e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+    use super::*;
+
+    use self::ParseValueErrorKind::*;
+
+    // Checks that every value round-trips through its string form.
+    fn check_round_trip<S: ValueDisplay + Sample>(space: &S)
+    where S::Value: PartialEq + fmt::Debug
+    {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let val = space.sample(&mut rng);
+            let s = space.fmt_value(&val);
+
+            assert_eq!(space.parse_value(&s), Ok(val), "{} does not round-trip", s);
+        }
+    }
+
+    fn err(offset: usize, kind: ParseValueErrorKind) -> ParseValueError {
+        ParseValueError::new(offset, kind)
+    }
+
+    #[test]
+    fn test_binary() {
+        check_round_trip(&Binary);
+
+        assert_eq!(Binary.fmt_value(&true), "1");
+        assert_eq!(Binary.parse_value("true"), Ok(true));
+        assert_eq!(Binary.parse_value(" false "), Ok(false));
+        assert_eq!(Binary.parse_value("0"), Ok(false));
+        assert_eq!(Binary.parse_value("2"), Err(err(0, Expected("0, 1, true or false"))));
+        assert_eq!(Binary.parse_value("yes"), Err(err(0, Expected("0, 1, true or false"))));
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let d = Ordinal::new(10);
+
+        check_round_trip(&d);
+
+        assert_eq!(d.fmt_value(&7), "7");
+        assert_eq!(d.parse_value("  9"), Ok(9));
+        assert_eq!(d.parse_value("  10"), Err(err(2, NotContained)));
+        assert_eq!(d.parse_value("-1"), Err(err(0, InvalidNumber)));
+        assert_eq!(d.parse_value("1.5"), Err(err(0, InvalidNumber)));
+        assert_eq!(d.parse_value("1 2"), Err(err(2, TrailingInput)));
+        assert_eq!(d.parse_value(""), Err(err(0, UnexpectedEnd)));
+        assert_eq!(d.parse_value(" "), Err(err(1, UnexpectedEnd)));
+    }
+
+    #[test]
+    fn test_intervals() {
+        let d = Interval::bounded(-1.0, 1.0);
+
+        check_round_trip(&d);
+        check_round_trip(&Interval::bounded(-1e-300, 1e300));
+        check_round_trip(&Interval::bounded(i64::MIN, i64::MAX));
+
+        assert_eq!(d.fmt_value(&0.1), "0.1");
+        assert_eq!(d.fmt_value(&-1.0), "-1.0");
+        assert_eq!(d.parse_value("1e-3"), Ok(0.001));
+        assert_eq!(d.parse_value("1.5"), Err(err(0, NotContained)));
+        assert_eq!(d.parse_value("NaN"), Err(err(0, NotContained)));
+        assert_eq!(d.parse_value("0.5.5"), Err(err(0, InvalidNumber)));
+        assert_eq!(d.parse_value("*"), Err(err(0, Expected("a value"))));
+        assert_eq!(Interval::open(0.0, 1.0).parse_value("0"), Err(err(0, NotContained)));
+        assert_eq!(Interval::bounded(0i64, 5).parse_value("6"), Err(err(0, NotContained)));
+        assert_eq!(Interval::bounded(0i64, 5).parse_value("5.0"), Err(err(0, InvalidNumber)));
+    }
+
+    #[test]
+    fn test_products() {
+        let d = ProductSpace::new(vec![Ordinal::new(3); 3]);
+
+        check_round_trip(&d);
+        check_round_trip(&TwoSpace::new([Interval::bounded(0.0, 1.0); 2]));
+        check_round_trip(&PairSpace::new(Binary, Interval::bounded(-5i64, 5)));
+
+        assert_eq!(d.fmt_value(&vec![0, 1, 2]), "[0, 1, 2]");
+        assert_eq!(d.parse_value(" [ 0 ,1,  2 ] "), Ok(vec![0, 1, 2]));
+        assert_eq!(ProductSpace::<Ordinal>::empty().fmt_value(&vec![]), "[]");
+        assert_eq!(ProductSpace::<Ordinal>::empty().parse_value("[ ]"), Ok(vec![]));
+
+        assert_eq!(d.parse_value("0, 1, 2"), Err(err(0, Expected("["))));
+        assert_eq!(d.parse_value("[0, 1, 3]"), Err(err(7, NotContained)));
+        assert_eq!(d.parse_value("[0, 1]"), Err(err(5, Expected(","))));
+        assert_eq!(d.parse_value("[0, 1, 2, 0]"), Err(err(8, Expected("]"))));
+        assert_eq!(d.parse_value("[0, 1,"), Err(err(6, UnexpectedEnd)));
+        assert_eq!(d.parse_value("[0, 1, 2]]"), Err(err(9, TrailingInput)));
+
+        let nested = ProductSpace::new(vec![PairSpace::new(Binary, Ordinal::new(2)); 2]);
+
+        assert_eq!(nested.fmt_value(&vec![(true, 0), (false, 1)]), "[[1, 0], [0, 1]]");
+        assert_eq!(nested.parse_value("[[1, 0], [0, 1]]"), Ok(vec![(true, 0), (false, 1)]));
+        assert_eq!(nested.parse_value("[[1, 0], [0, 2]]"), Err(err(13, NotContained)));
+    }
+
+    #[test]
+    fn test_malformed() {
+        // Random strings over the characters of the format must be rejected or parsed into a
+        // contained value, and never panic or point past the end of the input.
+        let mut rng = SmallRng::seed_from_u64(0);
+        let d = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0), Interval::bounded(0.0, 2.0)]);
+        let chars: Vec<char> = "[], .-+0123e1 é".chars().collect();
+
+        for _ in 0..10_000 {
+            let len = rng.gen_range(0..12);
+            let s: String = (0..len).map(|_| *chars.choose(&mut rng).unwrap()).collect();
+
+            match d.parse_value(&s) {
+                Ok(val) => assert!(d.contains(&val), "{:?} parsed from {:?}", val, s),
+                Err(e) => {
+                    assert!(e.offset <= s.len(), "offset {} out of {:?}", e.offset, s);
+                    assert!(s.is_char_boundary(e.offset));
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_value() {
+        let space = PairSpace::new(
+            ProductSpace::new(vec![
+                Interval::bounded(-1.0, 1.0),
+                Interval::bounded(0.0, 10.0),
+                Interval::left_bounded(0.0),
+            ]),
+            PairSpace::new(Ordinal::new(4), PairSpace::new(Binary, Interval::bounded(-5i64, 5))),
+        );
+        let val = (vec![0.5, 12.25, 1e9], (3, (true, -2)));
+
+        assert_eq!(space.display_value(&val), [
+            "0/0  : 0.5 ([-1, 1])",
+            "0/1  : 12.25 ([0, 10])  <- out of bounds",
+            "0/2  : 1000000000.0 ([0, inf])",
+            "1/0  : 3 ([0..3])",
+            "1/1/0: 1 ({0, 1})",
+            "1/1/1: -2 ([-5, 5])",
+        ].join("\n"));
+
+        let options = DisplayOptions { precision: Some(2), max_items: None, };
+
+        assert_eq!(
+            space.display_value_with(&val, &options).lines().nth(2),
+            Some("0/2  : 1000000000.00 ([0, inf])")
+        );
+
+        assert_eq!(Interval::bounded(0.0, 1.0).display_value(&2.0), "2.0");
+        assert_eq!(Ordinal::new(3).display_value(&1), "1");
+        assert_eq!(Binary.display_value(&false), "0");
+        assert_eq!(Interval::<f64>::unit().display_value_with(&(1.0 / 3.0), &options), "0.33");
+    }
+
+    #[test]
+    fn test_display_elided() {
+        let space = ProductSpace::new(vec![Ordinal::new(100); 100]);
+        let val: Vec<usize> = (0..100).collect();
+        let options = DisplayOptions { precision: None, max_items: Some(5), };
+
+        assert_eq!(space.display_value_with(&val, &options), [
+            "0  : 0 ([0..99])",
+            "1  : 1 ([0..99])",
+            "2  : 2 ([0..99])",
+            "...: 95 components elided",
+            "98 : 98 ([0..99])",
+            "99 : 99 ([0..99])",
+        ].join("\n"));
+        assert_eq!(space.display_value(&val).lines().count(), 17);
+        assert_eq!(
+            space.display_value_with(&val, &DisplayOptions { max_items: Some(0), ..options }),
+            "...: 100 components elided"
+        );
+        assert_eq!(ProductSpace::<Ordinal>::empty().display_value(&vec![]), "");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            err(3, NotContained).to_string(),
+            "value is not contained in the space at offset 3"
+        );
+        assert_eq!(err(0, Expected("[")).to_string(), "expected [ at offset 0");
+    }
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "serialize")]
+extern crate bincode;
+extern crate serde;
+extern crate serde_json;
+extern crate spaces;
+
+use serde::{de::DeserializeOwned, Serialize};
+use spaces::{
+    basis::{Fourier, Polynomial, Tile},
+    codec::SinglePrecision,
+    discrete::{
+        Alphabet,
+        Binary,
+        Integers,
+        Naturals,
+        NonNegativeIntegers,
+        NonZeroIntegers,
+        Ordinal,
+        Rescale,
+    },
+    real::{Interval, NonNegativeReals, PositiveReals, Reals},
+    AffineMap,
+    BoundKind,
+    Card,
+    Describe,
+    Dim,
+    Empty,
+    EnvSpec,
+    Equipartition,
+    LogPartition,
+    PairSpace,
+    Pmf,
+    ProductSpace,
+    Quantised,
+    Repeated,
+    Reward,
+    Scheduled,
+    Stacked,
+    StochasticRound,
+    TableMap,
+    TruncatedNormal,
+    TwoSpace,
+    WithSentinel,
+};
+use std::fmt::Debug;
+
+// Round-trips `val` through bincode, which is not self-describing, and through JSON, which is.
+//
+// Not every space implements `PartialEq`, so the round trip is checked by re-encoding the decoded
+// value and comparing the bytes.
+fn check<T: Serialize + DeserializeOwned>(val: &T) {
+    let bytes = bincode::serialize(val).unwrap();
+    let decoded: T = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(bincode::serialize(&decoded).unwrap(), bytes);
+
+    let json = serde_json::to_string(val).unwrap();
+    let decoded: T = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+}
+
+fn check_eq<T: Serialize + DeserializeOwned + PartialEq + Debug>(val: &T) {
+    check(val);
+
+    assert_eq!(&bincode::deserialize::<T>(&bincode::serialize(val).unwrap()).unwrap(), val);
+}
+
+#[test]
+fn test_scalar_spaces() {
+    check_eq(&Empty);
+    check_eq(&Binary);
+    check_eq(&Ordinal::new(7));
+    check_eq(&Ordinal::<u8>::try_new(200).unwrap());
+    check(&Integers);
+    check(&NonZeroIntegers);
+    check(&NonNegativeIntegers);
+    check_eq(&Naturals);
+    check_eq(&Reals);
+    check_eq(&NonNegativeReals);
+    check_eq(&PositiveReals);
+    check_eq(&Alphabet::new("abc"));
+    check_eq(&Equipartition::new(-1.0, 1.0, 5));
+    check_eq(&LogPartition::new(1.0, 100.0, 4));
+    check_eq(&Quantised::new(0.0, 1.0, 0.25));
+    check_eq(&Reward::new(Interval::symmetric_unit()));
+}
+
+#[test]
+fn test_intervals() {
+    // Missing bounds and closed kinds are skipped in human-readable formats only; the binary
+    // layout must keep every field in place.
+    for d in [
+        Interval::bounded(0.0, 1.0),
+        Interval::left_bounded(-2.5),
+        Interval::right_bounded(3.0),
+        Interval::unbounded(),
+        Interval::open(0.0, 1.0),
+        Interval::bounded(0.0, 1.0).with_kinds(BoundKind::Closed, BoundKind::Open),
+    ] {
+        check_eq(&d);
+    }
+
+    check_eq(&spaces::Interval::bounded(-3i64, 3));
+    check_eq(&spaces::Interval::left_bounded(0i64).with_kinds(BoundKind::Open, BoundKind::Closed));
+}
+
+#[test]
+fn test_composite_spaces() {
+    let bx = ProductSpace::new(vec![Interval::unit(), Interval::left_bounded(0.0)]);
+
+    check_eq(&bx);
+    check_eq(&ProductSpace::new(vec![Ordinal::new(2), Ordinal::new(3)]));
+    check(&PairSpace::new(Binary, Interval::open(0.0, 1.0)));
+    check(&TwoSpace::new([Ordinal::new(2), Ordinal::new(5)]));
+    check_eq(&Stacked::new(Ordinal::new(4), 3));
+    check_eq(&Repeated::variable(Interval::unit(), 4));
+    check_eq(&Scheduled::new(Interval::unit(), Interval::symmetric_unit()).unwrap());
+    check_eq(&TruncatedNormal::new(Interval::unit(), 0.5, 0.1));
+    check_eq(&EnvSpec::new(bx, Ordinal::new(3)));
+    check(&SinglePrecision(Interval::unit()));
+    check(&StochasticRound::new(Ordinal::new(3)));
+    check(&WithSentinel::new(Ordinal::new(3), 2, 0));
+}
+
+#[test]
+fn test_maps() {
+    let bx = ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]);
+
+    check(&AffineMap::new(2.0, -1.0));
+    check(&Rescale::new(Ordinal::new(3), Ordinal::new(6)));
+    check(&Ordinal::new(3).embed_into(&Ordinal::new(5)).unwrap());
+    check(&TableMap::new(Ordinal::new(3), Ordinal::new(2), vec![0, 1, 1]).unwrap());
+    check_eq(&Pmf::new(Ordinal::new(2), vec![0.25, 0.75]).unwrap());
+    check(&Polynomial::new(&bx, 2).unwrap());
+    check(&Fourier::new(&bx, 2).unwrap());
+    check(&Tile::new(&bx, 2, 3).unwrap());
+}
+
+#[test]
+fn test_descriptions() {
+    let space = PairSpace::new(
+        ProductSpace::new(vec![Interval::bounded(0.0, 1.0), Interval::unbounded()]),
+        PairSpace::new(Ordinal::new(3), Quantised::new(0.0, 1.0, 0.5)),
+    );
+
+    check_eq(&space.describe());
+    check_eq(&Equipartition::new(0.0, 1.0, 4).describe());
+    check_eq(&Binary.describe());
+    check_eq(&Empty.describe());
+    check_eq(&spaces::Interval::left_bounded(0i64).describe());
+    check_eq(&PositiveReals.describe());
+    check_eq(&NonZeroIntegers.describe());
+    check_eq(&spaces::Interval::bounded(0i64, 3).with_kinds(BoundKind::Closed, BoundKind::Open)
+        .describe());
+    check_eq(&Card::Finite(3));
+    check_eq(&Card::Infinite);
+    check_eq(&Dim::Finite(2));
+    check_eq(&Dim::Infinite);
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "derive")]
+extern crate rand;
+extern crate spaces;
+
+use rand::{rngs::SmallRng, SeedableRng};
+use spaces::{
+    discrete::{Binary, Ordinal},
+    real::Interval,
+    BoundedSpace,
+    Card,
+    Dim,
+    Sample,
+    Shape,
+    SampleError,
+    Space,
+};
+
+#[derive(Space)]
+pub struct Obs {
+    position: Interval,
+    velocity: Interval,
+    on_ground: Binary,
+}
+
+#[derive(Space)]
+pub struct Robot {
+    pub obs: Obs,
+    pub gear: Ordinal,
+    #[space(flatten)]
+    pub joints: Vec<Interval>,
+    #[space(skip)]
+    pub name: String,
+}
+
+fn obs() -> Obs {
+    Obs {
+        position: Interval::bounded(-1.0, 1.0),
+        velocity: Interval::bounded(-2.0, 2.0),
+        on_ground: Binary,
+    }
+}
+
+fn robot() -> Robot {
+    Robot {
+        obs: obs(),
+        gear: Ordinal::new(3),
+        joints: vec![Interval::unit(); 4],
+        name: "walker".to_owned(),
+    }
+}
+
+#[test]
+fn test_dim_card() {
+    assert_eq!(obs().dim(), Dim::Finite(3));
+    assert_eq!(obs().card(), Card::Infinite);
+    assert_eq!(robot().dim(), Dim::Finite(8));
+    assert_eq!(robot().shape(), Shape::vector(8));
+
+    #[derive(Space)]
+    struct Switches {
+        a: Binary,
+        #[space(flatten)]
+        b: [Ordinal; 2],
+    }
+
+    let switches = Switches { a: Binary, b: [Ordinal::new(3), Ordinal::new(5)] };
+
+    assert_eq!(switches.dim(), Dim::Finite(3));
+    assert_eq!(switches.card(), Card::Finite(30));
+
+    let empty = Switches { a: Binary, b: [Ordinal::new(3), Ordinal::new(0)] };
+
+    assert_eq!(empty.card(), Card::Finite(0));
+
+    let gearless = Robot { gear: Ordinal::new(0), ..robot() };
+
+    assert_eq!(gearless.card(), Card::Finite(0));
+}
+
+#[test]
+fn test_sample() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let robot = robot();
+
+    for _ in 0..1000 {
+        let val: RobotValue = robot.sample(&mut rng);
+
+        assert!(val.obs.position >= -1.0 && val.obs.position <= 1.0);
+        assert!(val.obs.velocity >= -2.0 && val.obs.velocity <= 2.0);
+        assert!(val.gear < 3);
+        assert_eq!(val.joints.len(), 4);
+        assert!(val.joints.iter().all(|&j| (0.0..=1.0).contains(&j)));
+        assert!(robot.contains(val));
+    }
+
+    #[derive(Space)]
+    struct Unbounded {
+        x: Interval,
+        y: Interval,
+    }
+
+    let space = Unbounded { x: Interval::unit(), y: Interval::left_bounded(0.0) };
+
+    assert_eq!(space.try_sample(&mut rng).err(), Some(SampleError::Unbounded));
+}
+
+#[test]
+fn test_contains() {
+    let obs = obs();
+
+    assert!(obs.contains(ObsValue { position: 0.5, velocity: -2.0, on_ground: true }));
+    assert!(!obs.contains(ObsValue { position: 1.5, velocity: 0.0, on_ground: false }));
+    assert!(!obs.contains(ObsValue { position: 0.0, velocity: 2.5, on_ground: false }));
+
+    let robot = robot();
+    let val = RobotValue {
+        obs: ObsValue { position: 0.0, velocity: 0.0, on_ground: true },
+        gear: 2,
+        joints: vec![0.5; 4],
+    };
+
+    assert!(robot.contains(val.clone()));
+    assert!(!robot.contains(RobotValue { joints: vec![0.5; 3], ..val.clone() }));
+    assert!(!robot.contains(RobotValue { gear: 3, ..val }));
+}
+
+#[test]
+fn test_bounds() {
+    let obs = obs();
+
+    assert_eq!(
+        obs.inf(),
+        Some(ObsValue { position: -1.0, velocity: -2.0, on_ground: false })
+    );
+    assert_eq!(obs.sup(), Some(ObsValue { position: 1.0, velocity: 2.0, on_ground: true }));
+}
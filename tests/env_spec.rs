@@ -0,0 +1,44 @@
+#![cfg(feature = "serialize")]
+extern crate serde_json;
+extern crate spaces;
+
+use spaces::{discrete::Ordinal, real::Interval, EnvSpec, EnvSpecDyn, ProductSpace, SpecError};
+
+const CARTPOLE: &str = include_str!("fixtures/cartpole.json");
+
+type CartPole = EnvSpec<ProductSpace<Interval>, Ordinal>;
+
+#[test]
+fn test_typed_spec() {
+    let spec: CartPole = serde_json::from_str(CARTPOLE).unwrap();
+
+    assert_eq!(spec.action, Ordinal::new(2));
+    assert!(spec.observation.contains(&[0.0, 0.0, 0.1, -100.0]));
+    assert_eq!(serde_json::to_string(&spec).unwrap(), CARTPOLE);
+}
+
+#[test]
+fn test_transitions() {
+    let spec: CartPole = serde_json::from_str(CARTPOLE).unwrap();
+    let actions = EnvSpec::new(spec.observation[0], spec.action);
+
+    assert_eq!(actions.validate_transition(&1.0, &1), Ok(()));
+    assert_eq!(
+        actions.validate_transition(&5.0, &1),
+        Err(SpecError::InvalidObservation("5.0".to_owned()))
+    );
+    assert_eq!(
+        actions.validate_transition(&1.0, &2),
+        Err(SpecError::InvalidAction("2".to_owned()))
+    );
+}
+
+#[test]
+fn test_dynamic_spec() {
+    let spec: CartPole = serde_json::from_str(CARTPOLE).unwrap();
+    let dynamic = EnvSpecDyn::describe(&spec);
+    let json = serde_json::to_string(&dynamic).unwrap();
+
+    assert_eq!(serde_json::from_str::<EnvSpecDyn>(&json).unwrap(), dynamic);
+    assert_eq!(dynamic.to_typed(), Some(spec));
+}
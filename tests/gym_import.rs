@@ -0,0 +1,150 @@
+#![cfg(feature = "serialize")]
+extern crate serde_json;
+extern crate spaces;
+
+use serde_json::json;
+use spaces::{
+    discrete::Ordinal,
+    gym::{from_gym_json, import_gym_json, ImportError, ImportErrorKind, ImportMode},
+    EnvSpec,
+    Interval,
+    ProductSpace,
+};
+
+// Hand-written in the layouts of gym's `Box` and `Discrete` spaces, and of the untagged spaces
+// saved alongside stable-baselines models, with infinities written as strings.
+const GYM_CARTPOLE: &str = include_str!("fixtures/gym_cartpole.json");
+const SB3_PENDULUM: &str = include_str!("fixtures/sb3_pendulum.json");
+
+type CartPole = EnvSpec<ProductSpace<Interval>, Ordinal>;
+type Pendulum = EnvSpec<ProductSpace<Interval>, ProductSpace<Interval>>;
+
+fn kind(result: Result<serde_json::Value, ImportError>) -> ImportErrorKind {
+    result.unwrap_err().kind
+}
+
+#[test]
+fn test_cartpole() {
+    let spec: CartPole = from_gym_json(GYM_CARTPOLE, ImportMode::Lenient).unwrap();
+
+    assert_eq!(spec, EnvSpec::new(
+        ProductSpace::new(vec![
+            Interval::bounded(-4.8, 4.8),
+            Interval::unbounded(),
+            Interval::bounded(-0.41887903, 0.41887903),
+            Interval::unbounded(),
+        ]),
+        Ordinal::new(2),
+    ));
+
+    let err = from_gym_json::<CartPole>(GYM_CARTPOLE, ImportMode::Strict).unwrap_err();
+
+    assert_eq!(err.path, "$.observation_space.bounded_above");
+    assert_eq!(err.kind, ImportErrorKind::UnknownKey("bounded_above".to_owned()));
+}
+
+#[test]
+fn test_pendulum() {
+    let spec: Pendulum = from_gym_json(SB3_PENDULUM, ImportMode::Lenient).unwrap();
+
+    assert_eq!(spec, EnvSpec::new(
+        ProductSpace::new(vec![
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(-1.0, 1.0),
+            Interval::bounded(-8.0, 8.0),
+        ]),
+        ProductSpace::new(vec![Interval::bounded(-2.0, 2.0)]),
+    ));
+
+    assert_eq!(
+        from_gym_json::<Pendulum>(SB3_PENDULUM, ImportMode::Strict).unwrap_err().kind,
+        ImportErrorKind::UnknownKey("_np_random".to_owned())
+    );
+}
+
+#[test]
+fn test_bounds() {
+    let import = |v| import_gym_json(v, ImportMode::Lenient);
+
+    assert_eq!(import(json!({"low": "-Infinity", "high": 3})), Ok(json!({"ub": 3})));
+    assert_eq!(import(json!({"low": " +INF ", "high": null})), Ok(json!({})));
+    assert_eq!(import(json!({"lo": 0, "right": 1.5e1})), Ok(json!({"lb": 0, "ub": 15.0})));
+    assert_eq!(
+        import(json!({"type": "Box", "low": 0, "high": [1, "inf"]})),
+        Ok(json!([{"lb": 0, "ub": 1}, {"lb": 0}]))
+    );
+    assert_eq!(
+        import(json!({"type": "Box", "low": -1, "high": 1, "shape": [2, 1]})),
+        Ok(json!([{"lb": -1, "ub": 1}, {"lb": -1, "ub": 1}]))
+    );
+    assert_eq!(
+        import(json!({"lb": 0, "ub_kind": "Open"})),
+        Ok(json!({"lb": 0, "ub_kind": "Open"}))
+    );
+
+    assert_eq!(
+        kind(import(json!({"low": "infinite"}))),
+        ImportErrorKind::InvalidBound("\"infinite\"".to_owned())
+    );
+    assert_eq!(
+        kind(import(json!({"low": [true]}))),
+        ImportErrorKind::InvalidBound("true".to_owned())
+    );
+    assert_eq!(
+        kind(import(json!({"low": [0, 0], "high": [1, 1], "shape": [3]}))),
+        ImportErrorKind::ShapeMismatch
+    );
+    assert!(matches!(kind(import(json!({"low": 0, "lb": 1}))), ImportErrorKind::Invalid(_)));
+
+    let err = import_gym_json(json!({"low": [0, "-inf"]}), ImportMode::Strict).unwrap_err();
+
+    assert_eq!(err.path, "$.low[1]");
+    assert_eq!(err.kind, ImportErrorKind::StringBound("-inf".to_owned()));
+    assert_eq!(
+        err.to_string(),
+        "at $.low[1]: infinite bound given as the string \"-inf\" in strict mode"
+    );
+}
+
+#[test]
+fn test_discrete() {
+    let import = |v| import_gym_json(v, ImportMode::Strict);
+
+    assert_eq!(import(json!({"type": "Discrete", "n": 3, "start": 0})), Ok(json!(3)));
+    assert_eq!(import(json!({"size": 4, "dtype": "int64"})), Ok(json!(4)));
+    assert_eq!(import(json!({"type": "MultiDiscrete", "nvec": [3, 4]})), Ok(json!([3, 4])));
+    assert_eq!(import(json!({"type": "MultiBinary", "n": 2})), Ok(json!([2, 2])));
+    assert_eq!(import(json!({"type": "MultiBinary", "n": [2, 2]})), Ok(json!([2, 2, 2, 2])));
+    assert_eq!(
+        import(json!({"type": "Tuple", "spaces": [{"type": "Discrete", "n": 2}, {"low": 0}]})),
+        Ok(json!([2, {"lb": 0}]))
+    );
+
+    assert!(matches!(
+        kind(import(json!({"type": "Discrete", "n": 3, "start": 1}))),
+        ImportErrorKind::Unsupported(_)
+    ));
+    assert_eq!(
+        kind(import(json!({"type": "Dict", "spaces": {}}))),
+        ImportErrorKind::Unsupported("Dict".to_owned())
+    );
+    assert!(matches!(
+        kind(import(json!({"type": "Discrete", "n": -1}))),
+        ImportErrorKind::Invalid(_)
+    ));
+}
+
+#[test]
+fn test_passthrough() {
+    let desc = json!({"product": [{"scalar": {"type": "discrete", "n": 3}}]});
+
+    assert_eq!(import_gym_json(desc.clone(), ImportMode::Strict), Ok(desc));
+    assert_eq!(
+        import_gym_json(json!({"a": [{"n": 2}], "b": 1}), ImportMode::Strict),
+        Ok(json!({"a": [2], "b": 1}))
+    );
+    assert!(matches!(
+        from_gym_json::<CartPole>("{", ImportMode::Lenient).unwrap_err().kind,
+        ImportErrorKind::Invalid(_)
+    ));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "serialize")]
+extern crate bincode;
+extern crate serde_json;
+extern crate spaces;
+
+use serde_json::{json, Value};
+use spaces::{compat::upgrade_json, discrete::Ordinal, EnvSpec, Interval, ProductSpace};
+
+const CARTPOLE: &str = include_str!("fixtures/cartpole.json");
+const LEGACY_CARTPOLE: &str = include_str!("fixtures/legacy_cartpole.json");
+
+type CartPole = EnvSpec<ProductSpace<Interval>, Ordinal>;
+
+#[test]
+fn test_aliases() {
+    let current: CartPole = serde_json::from_str(CARTPOLE).unwrap();
+    let legacy: CartPole = serde_json::from_str(LEGACY_CARTPOLE).unwrap();
+
+    assert_eq!(legacy, current);
+    assert_eq!(serde_json::to_string(&legacy).unwrap(), CARTPOLE);
+
+    assert_eq!(serde_json::from_str::<Ordinal>(r#"{"size": 4}"#).unwrap(), Ordinal::new(4));
+    assert_eq!(
+        serde_json::from_str::<Interval<i64>>(r#"{"left": 0, "ub": 3}"#).unwrap(),
+        Interval::bounded(0, 3)
+    );
+    assert!(serde_json::from_str::<Interval>(r#"{"lb": 0.0, "lo": 1.0}"#).is_err());
+    assert!(serde_json::from_str::<Ordinal>(r#"{"n": 4, "size": 4}"#).is_err());
+}
+
+#[test]
+fn test_upgrade_json() {
+    let current: Value = serde_json::from_str(CARTPOLE).unwrap();
+    let legacy: Value = serde_json::from_str(LEGACY_CARTPOLE).unwrap();
+
+    assert_eq!(upgrade_json(legacy), current);
+    assert_eq!(upgrade_json(current.clone()), current);
+
+    // Keys are only renamed within intervals, and sizes only replace single-entry maps.
+    let other = json!({"lo": 1, "name": "x", "n": 2, "nested": [{"size": 3, "lo": 0}]});
+
+    assert_eq!(upgrade_json(other.clone()), other);
+    assert_eq!(upgrade_json(json!([{"n": 5}, {"n": -1}])), json!([5, {"n": -1}]));
+}
+
+#[test]
+fn test_upgrade_bincode() {
+    let legacy: Value = serde_json::from_str(LEGACY_CARTPOLE).unwrap();
+    let spec: CartPole = serde_json::from_value(upgrade_json(legacy)).unwrap();
+    let bytes = bincode::serialize(&spec).unwrap();
+
+    assert_eq!(bincode::deserialize::<CartPole>(&bytes).unwrap(), spec);
+    assert_eq!(spec, serde_json::from_str::<CartPole>(CARTPOLE).unwrap());
+}
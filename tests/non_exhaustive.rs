@@ -0,0 +1,82 @@
+extern crate spaces;
+
+use spaces::{
+    discrete::Ordinal,
+    parse::ParseErrorKind,
+    real::Interval,
+    Card,
+    Dim,
+    Sample,
+    SampleError,
+    ScalarKind,
+    Space,
+    SpaceDescription,
+};
+
+// Downstream code sees the public enums as non-exhaustive, so every match below requires its
+// wildcard arm to compile. The exhaustive counterpart is checked by a compile_fail doctest on
+// `Card`.
+fn describe_card(card: Card) -> String {
+    match card {
+        Card::Finite(n) => format!("{} values", n),
+        Card::Infinite => "infinitely many values".to_owned(),
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn dim_or_zero(dim: Dim) -> usize {
+    match dim {
+        Dim::Finite(n) => n,
+        _ => 0,
+    }
+}
+
+fn is_bounded_scalar(desc: &SpaceDescription) -> bool {
+    match desc {
+        SpaceDescription::Scalar(ScalarKind::Real { lb: Some(_), ub: Some(_), .. }) => true,
+        SpaceDescription::Scalar(ScalarKind::Binary) => true,
+        SpaceDescription::Scalar(_) => false,
+        _ => false,
+    }
+}
+
+fn sample_error_name(err: SampleError) -> &'static str {
+    match err {
+        SampleError::Unbounded => "unbounded",
+        SampleError::Empty => "empty",
+        _ => "other",
+    }
+}
+
+fn unknown_space(kind: ParseErrorKind) -> Option<String> {
+    match kind {
+        ParseErrorKind::UnknownSpace(name) => Some(name),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_constructors() {
+    assert_eq!(Card::finite(3), Card::Finite(3));
+    assert_eq!(Card::infinite(), Card::Infinite);
+    assert_eq!(Dim::finite(2), Dim::Finite(2));
+    assert_eq!(Dim::infinite(), Dim::Infinite);
+}
+
+#[test]
+fn test_downstream_matching() {
+    assert_eq!(describe_card(Ordinal::new(4).card()), "4 values");
+    assert_eq!(describe_card(Interval::unbounded().card()), "infinitely many values");
+    assert_eq!(dim_or_zero(Ordinal::new(4).dim()), 1);
+
+    assert!(is_bounded_scalar(&"interval(0, 1)".parse().unwrap()));
+    assert!(!is_bounded_scalar(&"interval(0, inf)".parse().unwrap()));
+
+    let err = Interval::left_bounded(0.0).try_sample(&mut rand::thread_rng()).unwrap_err();
+
+    assert_eq!(sample_error_name(err), "unbounded");
+
+    let err = "nonsense".parse::<SpaceDescription>().unwrap_err();
+
+    assert_eq!(unknown_space(err.kind), Some("nonsense".to_owned()));
+}
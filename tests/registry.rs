@@ -0,0 +1,79 @@
+extern crate spaces;
+
+use spaces::{
+    parse::{ParseError, ParseErrorKind},
+    real::Interval,
+    registry::{self, RegistryError},
+    ProductSpace,
+    ScalarKind,
+    SpaceDescription,
+};
+use std::thread;
+
+// Toy plugin space: the hypercube [lb, ub]^n, written "cube(n)" or "cube(n, lb, ub)".
+fn cube(args: &[f64]) -> Option<SpaceDescription> {
+    let (n, lb, ub) = match *args {
+        [n] => (n, 0.0, 1.0),
+        [n, lb, ub] if lb <= ub => (n, lb, ub),
+        _ => return None,
+    };
+
+    if n < 1.0 || n.fract() != 0.0 { return None; }
+
+    Some(SpaceDescription::Product(vec![
+        SpaceDescription::Scalar(ScalarKind::real(Some(lb), Some(ub)));
+        n as usize
+    ]))
+}
+
+#[test]
+fn test_plugin_space() {
+    registry::register_space("cube", cube).unwrap();
+
+    assert_eq!(
+        registry::register_space("cube", cube),
+        Err(RegistryError::Duplicate("cube".to_owned()))
+    );
+
+    // Parsed into the concrete box type, and back through its displayed form.
+    let space: ProductSpace<Interval> = "cube(2, -1, 1)".parse().unwrap();
+
+    assert_eq!(space, ProductSpace::new(vec![Interval::bounded(-1.0, 1.0); 2]));
+    assert_eq!(space.to_string().parse::<ProductSpace<Interval>>(), Ok(space));
+
+    // Composed with built-in spaces.
+    let desc: SpaceDescription = "binary x cube(1)".parse().unwrap();
+
+    assert_eq!(desc, SpaceDescription::Product(vec![
+        SpaceDescription::Scalar(ScalarKind::Binary),
+        cube(&[1.0]).unwrap(),
+    ]));
+
+    assert_eq!(
+        "binary x cube(0)".parse::<SpaceDescription>(),
+        Err(ParseError {
+            offset: 9,
+            kind: ParseErrorKind::InvalidArguments("cube".to_owned()),
+        })
+    );
+
+    // Lookups are safe to perform concurrently.
+    let handles: Vec<_> = (1..=8)
+        .map(|n| thread::spawn(move || format!("cube({})", n).parse::<SpaceDescription>()))
+        .collect();
+
+    for (n, h) in handles.into_iter().enumerate() {
+        assert_eq!(h.join().unwrap(), Ok(cube(&[n as f64 + 1.0]).unwrap()));
+    }
+}
+
+#[test]
+fn test_unregistered() {
+    assert_eq!(
+        "tetrahedron(3)".parse::<SpaceDescription>(),
+        Err(ParseError {
+            offset: 0,
+            kind: ParseErrorKind::UnknownSpace("tetrahedron".to_owned()),
+        })
+    );
+}
@@ -0,0 +1,99 @@
+extern crate spaces;
+
+use spaces::{
+    codec::SinglePrecision,
+    discrete::{
+        Alphabet,
+        Binary,
+        Integers,
+        Naturals,
+        NonNegativeIntegers,
+        NonZeroIntegers,
+        Ordinal,
+    },
+    real::{Interval, NonNegativeReals, PositiveReals, Reals},
+    Dim,
+    Empty,
+    Equipartition,
+    LogPartition,
+    PairSpace,
+    ProductSpace,
+    Quantised,
+    Repeated,
+    Reward,
+    Scheduled,
+    Shape,
+    Space,
+    Stacked,
+    TruncatedNormal,
+    TwoSpace,
+    WithSentinel,
+};
+use std::{rc::Rc, sync::Arc};
+
+// Checks that the shape of `space` agrees with its dimensionality, and returns the shape.
+fn check<S: Space>(space: S) -> Shape {
+    let shape = space.shape();
+
+    assert_eq!(Dim::Finite(shape.len()), space.dim(), "shape {} disagrees with dim", shape);
+
+    shape
+}
+
+#[test]
+fn test_scalar_spaces() {
+    assert!(check(Binary).is_scalar());
+    assert!(check(Ordinal::new(5)).is_scalar());
+    assert!(check(Alphabet::new("abc")).is_scalar());
+    assert!(check(Integers).is_scalar());
+    assert!(check(NonZeroIntegers).is_scalar());
+    assert!(check(NonNegativeIntegers).is_scalar());
+    assert!(check(Naturals).is_scalar());
+    assert!(check(Reals).is_scalar());
+    assert!(check(NonNegativeReals).is_scalar());
+    assert!(check(PositiveReals).is_scalar());
+    assert!(check(Interval::unit()).is_scalar());
+    assert!(check(spaces::Interval::bounded(0i64, 3)).is_scalar());
+    assert!(check(Equipartition::new(0.0, 1.0, 4)).is_scalar());
+    assert!(check(LogPartition::new(1.0, 10.0, 4)).is_scalar());
+    assert!(check(Quantised::new(0.0, 1.0, 0.5)).is_scalar());
+    assert!(check(Reward::new(Interval::unit())).is_scalar());
+    assert!(check(TruncatedNormal::new(Interval::unit(), 0.5, 0.1)).is_scalar());
+    assert!(check(SinglePrecision(Interval::unit())).is_scalar());
+    assert!(check(WithSentinel::new(Ordinal::new(3), 2, 0)).is_scalar());
+}
+
+#[test]
+fn test_composite_spaces() {
+    let bx = ProductSpace::new(vec![Interval::unit(); 3]);
+
+    assert_eq!(check(Empty), Shape::vector(0));
+    assert_eq!(check(ProductSpace::<Interval>::empty()), Shape::vector(0));
+    assert_eq!(check(ProductSpace::new(vec![Interval::unit()])), Shape::vector(1));
+    assert_eq!(check(bx.clone()), Shape::vector(3));
+    assert_eq!(check(ProductSpace::new(vec![bx.clone(), bx.clone()])), Shape::vector(6));
+    assert_eq!(check(PairSpace::new(Binary, bx.clone())), Shape::vector(4));
+    assert_eq!(check(TwoSpace::new([Ordinal::new(2), Ordinal::new(3)])), Shape::vector(2));
+    assert_eq!(check(Repeated::new(Ordinal::new(3), 4)), Shape::vector(2));
+    assert_eq!(check(Scheduled::new(bx.clone(), bx).unwrap()), Shape::vector(3));
+}
+
+#[test]
+fn test_stacked_spaces() {
+    let bx = ProductSpace::new(vec![Interval::unit(); 3]);
+
+    assert_eq!(check(Stacked::new(Ordinal::new(5), 4)), Shape::vector(4));
+    assert_eq!(check(Stacked::new(bx.clone(), 4)), Shape::new(vec![4, 3]));
+    assert_eq!(check(Stacked::new(Stacked::new(bx, 4), 2)), Shape::new(vec![2, 4, 3]));
+    assert_eq!(check(Stacked::new(Empty, 2)), Shape::new(vec![2, 0]));
+}
+
+#[test]
+fn test_pointers() {
+    let stacked = Stacked::new(ProductSpace::new(vec![Interval::unit(); 3]), 4);
+
+    assert_eq!(check(&stacked), stacked.shape());
+    assert_eq!(check(Box::new(stacked.clone())), stacked.shape());
+    assert_eq!(check(Rc::new(stacked.clone())), stacked.shape());
+    assert_eq!(check(Arc::new(stacked.clone())), stacked.shape());
+}
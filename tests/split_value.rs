@@ -0,0 +1,63 @@
+//! Checks that splitting flat values into component views does not allocate.
+extern crate spaces;
+
+use spaces::{Interval, PairSpace, ProductSpace};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// Counts the allocations of the current thread, such that tests running concurrently on other
+// threads do not interfere.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) { System.dealloc(ptr, layout) }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(|n| n.get());
+
+    f();
+
+    ALLOCATIONS.with(|n| n.get()) - before
+}
+
+#[test]
+fn test_zero_allocation() {
+    let unit = Interval::bounded(0.0, 1.0);
+    let space = ProductSpace::new(vec![ProductSpace::new(vec![unit; 4]); 8]);
+    let pair = PairSpace::new(unit, ProductSpace::new(vec![unit; 31]));
+    let mut x = vec![0.5; 32];
+
+    let n = allocations(|| {
+        for _ in 0..100 {
+            let total: f64 = space.split_value(&x).unwrap().map(|v| v[0]).sum();
+
+            for view in space.split_value_mut(&mut x).unwrap() {
+                view[1] = total;
+            }
+
+            let (head, tail) = pair.split_value_mut(&mut x).unwrap();
+
+            head[0] = tail.len() as f64;
+        }
+    });
+
+    assert_eq!(n, 0);
+    assert_eq!(x[0], 31.0);
+    assert_eq!((x[1], x[29]), (34.5, 34.5));
+}
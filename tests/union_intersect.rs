@@ -0,0 +1,61 @@
+#[macro_use]
+extern crate spaces;
+
+use spaces::{discrete::Ordinal, Card, Dim, Intersection, Space, Union};
+use std::marker::PhantomData;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Colour;
+
+impl Space for Colour {
+    type Value = (u8, u8, u8);
+
+    fn dim(&self) -> Dim { Dim::Finite(3) }
+
+    fn card(&self) -> Card { Card::Finite(256 * 256 * 256) }
+}
+
+impl_union_intersect!(Colour, Colour);
+
+#[derive(Debug, PartialEq)]
+struct Anything<T>(PhantomData<T>);
+
+impl<T: Clone> Space for Anything<T> {
+    type Value = T;
+
+    fn dim(&self) -> Dim { Dim::one() }
+
+    fn card(&self) -> Card { Card::Infinite }
+}
+
+impl_union_intersect!(impl<T: Clone> Anything<T>, Anything<T>);
+impl_union_intersect!(impl<T: Clone> Anything<T>, Ordinal);
+
+#[test]
+fn test_self_union() {
+    assert_eq!(Colour.union(&Colour), Colour);
+    assert_eq!(Colour.intersect(&Colour), Colour);
+    assert_eq!(Colour.union_many(&[Colour, Colour]), Colour);
+}
+
+#[test]
+fn test_generic_union() {
+    let s: Anything<f64> = Anything(PhantomData);
+
+    assert_eq!(s.union(&Anything(PhantomData)), Anything(PhantomData));
+    assert_eq!(
+        Anything::<f64>(PhantomData).intersect(&Anything(PhantomData)),
+        Anything(PhantomData)
+    );
+}
+
+#[test]
+fn test_cross_type_union() {
+    let s: Anything<usize> = Anything(PhantomData);
+
+    assert_eq!(s.union(&Ordinal::new(5)), Anything(PhantomData));
+    assert_eq!(
+        Anything::<usize>(PhantomData).intersect_many(&[Ordinal::new(2), Ordinal::new(3)]),
+        Anything(PhantomData)
+    );
+}